@@ -8,7 +8,7 @@ use crate::components::features::clipboard::ClipboardToast;
 use crate::components::layout::AppLayout;
 use crate::components::features::navigation::KeyboardHandler;
 use crate::components::features::modals::ModalManager;
-use crate::hooks::{use_app_state, use_word_meaning_effect, use_clipboard_monitor};
+use crate::hooks::{use_app_state, use_word_meaning_effect, use_clipboard_monitor, use_shutdown_flush};
 
 #[component]
 pub fn App() -> Element {
@@ -17,10 +17,15 @@ pub fn App() -> Element {
     // Global image cache context
     let image_cache = use_signal(std::collections::HashMap::<String, crate::services::ImageFetchState>::new);
     use_context_provider(|| image_cache);
+    let image_fetch_epoch = use_signal(crate::utils::fetch_epoch::FetchEpoch::new);
+    use_context_provider(|| image_fetch_epoch);
     
     // Handle word meaning effects
     use_word_meaning_effect(&mut app_state);
     
+    // Persist reading state when the shutdown handler asks for it
+    use_shutdown_flush(app_state.reading_state);
+    
     // Initialize clipboard monitoring
     let current_text = app_state.reading_state.read().current_sentence().unwrap_or_default();
     use_clipboard_monitor(
@@ -28,6 +33,7 @@ pub fn App() -> Element {
         app_state.current_clipboard_text.clone(),
         app_state.show_clipboard_toast.clone(),
         current_text,
+        app_state.clipboard_guard,
     );
     
     
@@ -79,7 +85,7 @@ pub fn App() -> Element {
                     sentence_to_fetch: app_state.sentence_to_fetch,
                     word_to_fetch: app_state.word_to_fetch,
                     encounter_tracked_sentences: app_state.encounter_tracked_sentences,
-                    promotion_notification: app_state.promotion_notification,
+                    promotion_notifier: app_state.promotion_notifier,
                     theme: app_state.theme.clone(),
                 }
             }
@@ -39,12 +39,21 @@ fn user_friendly_error(error: &AppError) -> String {
         AppError::HttpError { status: 500..=599, .. } => {
             "Server error. Please try again later.".to_string()
         },
+        AppError::QuotaExceededError { .. } => {
+            "The AI service quota has been used up. Please check your billing settings.".to_string()
+        },
         AppError::ParseError { .. } => {
             "The AI response couldn't be processed. Please try again.".to_string()
         },
         AppError::NetworkError { .. } => {
             "Network connection issue. Please check your internet connection.".to_string()
         },
+        AppError::TimeoutError { .. } => {
+            "The service took too long to respond. Please try again.".to_string()
+        },
+        AppError::ContentRefused { .. } => {
+            "The AI declined to process this sentence. Try the next one.".to_string()
+        },
         AppError::InvalidResponseContent => {
             "The AI service returned an unexpected response. Please try again.".to_string()
         },
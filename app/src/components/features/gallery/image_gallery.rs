@@ -68,7 +68,7 @@ fn ImageThumbnail(image: ImageResult) -> Element {
             class: "image-item",
             
             img {
-                src: "{image.thumbnail_url}",
+                src: "{image.primary_url()}",
                 alt: "{image.title}",
                 loading: "lazy",
             }
@@ -9,6 +9,8 @@ pub fn modal_manager(app_state: AppState) -> Element {
     let mut input_close_state = app_state.clone();
     let mut known_modal_close_state = app_state.clone();
     let mut known_modal_remove_state = app_state.clone();
+    let mut known_modal_undo_state = app_state.clone();
+    let undo_word = app_state.last_removed_word.read().as_ref().map(|removed| removed.word.clone());
     
     rsx! {
         if *app_state.show_input_modal.read() {
@@ -40,16 +42,10 @@ pub fn modal_manager(app_state: AppState) -> Element {
                 theme: app_state.theme.clone(),
                 on_close: move |_| known_modal_close_state.hide_known_words_modal(),
                 on_remove_word: move |word: String| {
-                    if let Err(e) = known_modal_remove_state.vocabulary_state.write().remove_known_word(&word) {
-                        tracing::error!(
-                            event = "remove_known_word_failed",
-                            component = "modal_manager",
-                            word = %word,
-                            error = %e,
-                            "Failed to remove known word from modal"
-                        );
-                    }
-                }
+                    known_modal_remove_state.remove_known_word(&word);
+                },
+                undo_word: undo_word,
+                on_undo: move |_| known_modal_undo_state.undo_remove_known_word()
             }
         }
     }
@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
 use glossia_shared::WordMeaning;
 use crate::theme::Theme;
+use crate::utils::DEFAULT_MIN_CLICKABLE_LEN;
 use crate::components::TextRenderer;
 use crate::components::features::reading::SimplifiedTextSkeleton;
 
@@ -11,7 +12,10 @@ pub fn ReadingContainer(
     is_loading: bool,
     words: Option<Vec<WordMeaning>>,
     theme: Theme,
-    on_word_click: EventHandler<String>
+    on_word_click: EventHandler<String>,
+    /// Words shorter than this many characters render as plain text
+    #[props(default = DEFAULT_MIN_CLICKABLE_LEN)]
+    min_clickable_len: usize,
 ) -> Element {
 
     rsx! {
@@ -27,7 +31,8 @@ pub fn ReadingContainer(
                         text: text.clone(),
                         word_meanings: words.clone(),
                         theme: theme.clone(),
-                        on_word_click: on_word_click
+                        on_word_click: on_word_click,
+                        min_clickable_len
                     }
                 } else {
                     "Paste text to start reading."
@@ -48,7 +53,8 @@ pub fn ReadingContainer(
                             text: text.clone(),
                             word_meanings: words.clone(),
                             theme: theme.clone(),
-                            on_word_click: on_word_click
+                            on_word_click: on_word_click,
+                            min_clickable_len
                         }
                     }
                 }
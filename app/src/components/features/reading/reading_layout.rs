@@ -3,6 +3,7 @@ use glossia_shared::WordMeaning;
 use crate::theme::Theme;
 use crate::components::{ReadingContainer, WordMeanings, NavigationControls};
 use crate::components::features::reading::WordMeaningSkeleton;
+use crate::utils::MeaningRevealMode;
 
 #[component]
 pub fn ReadingLayout(
@@ -17,6 +18,7 @@ pub fn ReadingLayout(
     on_word_click: EventHandler<String>,
     on_expand_word: EventHandler<String>,
 ) -> Element {
+    let reveal_mode = use_hook(MeaningRevealMode::from_env);
     let responsive_styles = format!(
         "
         /* Custom scrollbar styles for reading areas */
@@ -143,7 +145,8 @@ pub fn ReadingLayout(
                             reading_state: reading_state,
                             current_sentence: original.clone().unwrap_or_default(),
                             theme: theme.clone(),
-                            on_expand_word: on_expand_word
+                            on_expand_word: on_expand_word,
+                            reveal_mode,
                         }
                     } else {
                         WordMeaningSkeleton {
@@ -1,6 +1,6 @@
 use dioxus::prelude::*;
 use std::collections::HashSet;
-use crate::hooks::{VocabularyState, use_word_tracking};
+use crate::hooks::{PromotionNotifier, VocabularyState, use_word_tracking};
 use glossia_shared::SimplificationResponse;
 
 /// Handles sentence processing and word tracking
@@ -10,7 +10,7 @@ pub fn SentenceProcessor(
     cached_result: Option<SimplificationResponse>,
     encounter_tracked_sentences: Signal<HashSet<String>>,
     vocabulary_state: Signal<VocabularyState>,
-    promotion_notification: Signal<Option<String>>,
+    promotion_notifier: PromotionNotifier,
 ) -> Element {
     // Track word encounters when we have a cached result
     if let Some(ref result) = cached_result {
@@ -19,7 +19,7 @@ pub fn SentenceProcessor(
             &result.words,
             encounter_tracked_sentences,
             vocabulary_state,
-            promotion_notification,
+            promotion_notifier,
         );
     }
     
@@ -1,7 +1,10 @@
 use dioxus::prelude::*;
 use glossia_shared::WordMeaning;
+use glossia_text_parser::detect_text_direction;
+use std::cell::RefCell;
+use std::rc::Rc;
 use crate::theme::Theme;
-use crate::utils::{tokenize_text_for_clicks, is_word_token, generate_word_color_themed, find_phrase_matches};
+use crate::utils::{generate_word_color_themed, HighlightCache, RenderSegment, DEFAULT_MIN_CLICKABLE_LEN};
 use crate::components::ClickableWord;
 
 #[component]
@@ -10,120 +13,51 @@ pub fn TextRenderer(
     word_meanings: Option<Vec<WordMeaning>>,
     theme: Theme,
     on_word_click: EventHandler<String>,
+    /// Words shorter than this many characters are not clickable
+    #[props(default = DEFAULT_MIN_CLICKABLE_LEN)]
+    min_clickable_len: usize,
 ) -> Element {
-    let tokens = tokenize_text_for_clicks(&text);
-    let empty_vec = Vec::new();
-    let word_meanings = word_meanings.as_ref().unwrap_or(&empty_vec);
-    
-    // Find all highlight spans (both phrases and words)
-    let highlight_spans = find_phrase_matches(&tokens, word_meanings);
-    
-    rsx! {
-        {
-            let mut current_index = 0;
-            let mut elements = Vec::new();
-            
-            for span in &highlight_spans {
-                // Render non-highlighted tokens before this span
-                while current_index < span.start_index {
-                    let token = &tokens[current_index];
-                    if is_word_token(token) {
-                        elements.push(rsx! {
-                            ClickableWord {
-                                key: "unhighlighted_{current_index}",
-                                text: token.clone(),
-                                index: current_index,
-                                is_clickable: true,
-                                style: "".to_string(),
-                                on_click: on_word_click
-                            }
-                        });
-                    } else {
-                        elements.push(rsx! {
-                            ClickableWord {
-                                key: "non_word_{current_index}",
-                                text: token.clone(),
-                                index: current_index,
-                                is_clickable: false,
-                                style: "".to_string(),
-                                on_click: on_word_click
-                            }
-                        });
-                    }
-                    current_index += 1;
-                }
-                
-                // Render the highlighted span
-                let span_text = if span.is_phrase && span.end_index < tokens.len() {
-                    // For phrases, use the original phrase text for color generation
-                    word_meanings.iter()
-                        .find(|wm| wm.is_phrase && 
-                            wm.word.split_whitespace()
-                                .zip(tokens[span.start_index..=span.end_index].iter().filter(|t| is_word_token(t)))
-                                .all(|(phrase_word, token)| phrase_word.to_lowercase() == token.to_lowercase()))
-                        .map(|wm| wm.word.clone())
-                        .unwrap_or_else(|| span.text.clone())
-                } else {
-                    span.text.clone()
-                };
-                
-                let color = generate_word_color_themed(&span_text, &theme);
-                let style = format!("color: {color}; font-weight: 600;");
-                
-                // Render the entire span as one highlighted unit
-                let span_tokens = if span.end_index < tokens.len() {
-                    &tokens[span.start_index..=span.end_index]
-                } else {
-                    &tokens[span.start_index..]
-                };
-                let span_display_text = span_tokens.iter().map(|token| token.as_str()).collect::<String>();
-                
-                elements.push(rsx! {
-                    span {
-                        key: "highlighted_{span.start_index}_{span.end_index}",
-                        style: "{style}; cursor: pointer; user-select: none; -webkit-user-select: none; -moz-user-select: none; -ms-user-select: none;",
-                        ondoubleclick: {
-                            let span_text_clone = span_text.clone();
-                            let on_word_click_clone = on_word_click.clone();
-                            move |_| on_word_click_clone.call(span_text_clone.clone())
-                        },
-                        "{span_display_text}"
-                    }
-                });
-                
-                current_index = span.end_index + 1;
+    // Reuse the previous segments when text and words are unchanged
+    let highlight_cache = use_hook(|| Rc::new(RefCell::new(HighlightCache::default())));
+    let word_meanings = word_meanings.unwrap_or_default();
+    let segments = {
+        let mut cache = highlight_cache.borrow_mut();
+        cache.set_min_clickable_len(min_clickable_len);
+        cache.get(&text, &word_meanings)
+    };
+
+    // Right-to-left scripts (Hebrew, Arabic) need `dir` so punctuation lands on the correct side
+    let direction = detect_text_direction(&text).as_html_dir();
+
+    let elements = segments.iter().map(|segment| match segment {
+        RenderSegment::Plain { index, text, is_word } => rsx! {
+            ClickableWord {
+                key: "token_{index}",
+                text: text.clone(),
+                index: *index,
+                is_clickable: *is_word,
+                style: "".to_string(),
+                on_click: on_word_click
             }
-            
-            // Render remaining non-highlighted tokens
-            while current_index < tokens.len() {
-                let token = &tokens[current_index];
-                if is_word_token(token) {
-                    elements.push(rsx! {
-                        ClickableWord {
-                            key: "final_unhighlighted_{current_index}",
-                            text: token.clone(),
-                            index: current_index,
-                            is_clickable: true,
-                            style: "".to_string(),
-                            on_click: on_word_click
-                        }
-                    });
-                } else {
-                    elements.push(rsx! {
-                        ClickableWord {
-                            key: "final_non_word_{current_index}",
-                            text: token.clone(),
-                            index: current_index,
-                            is_clickable: false,
-                            style: "".to_string(),
-                            on_click: on_word_click
-                        }
-                    });
+        },
+        RenderSegment::Highlight { start_index, end_index, display, label } => {
+            let color = generate_word_color_themed(label, &theme);
+            let label = label.clone();
+            rsx! {
+                span {
+                    key: "highlighted_{start_index}_{end_index}",
+                    style: "color: {color}; font-weight: 600; cursor: pointer; user-select: none; -webkit-user-select: none; -moz-user-select: none; -ms-user-select: none;",
+                    ondoubleclick: move |_| on_word_click.call(label.clone()),
+                    "{display}"
                 }
-                current_index += 1;
             }
-            
-            elements.into_iter()
+        }
+    });
+
+    rsx! {
+        span {
+            dir: "{direction}",
+            {elements}
         }
     }
 }
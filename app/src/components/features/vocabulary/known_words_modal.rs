@@ -4,69 +4,129 @@ use crate::components::common::modals::Modal;
 use super::{ModalHeader, SearchBar, WordGrid, EmptyState};
 use super::known_words_modal_styles::KnownWordsModalStyles;
 
+/// Default number of known words rendered per page
+pub const DEFAULT_PAGE_SIZE: usize = 200;
+
 #[component]
 pub fn KnownWordsModal(
     words: Vec<String>,
     theme: Theme,
     on_close: EventHandler<()>,
     on_remove_word: EventHandler<String>,
+    #[props(default = DEFAULT_PAGE_SIZE)]
+    page_size: usize,
+    /// Most recently removed word, shown with an undo action
+    #[props(default)]
+    undo_word: Option<String>,
+    #[props(default)]
+    on_undo: Option<EventHandler<()>>,
 ) -> Element {
     let search_query = use_signal(|| String::new());
+    let mut page = use_signal(|| 0usize);
     let words_clone = words.clone();
     let styles = KnownWordsModalStyles::new(&theme);
-    
+
     let filtered_words = use_memo(move || {
-        let query = search_query.read().to_lowercase();
-        if query.is_empty() {
-            words_clone.clone()
-        } else {
-            words_clone.iter()
-                .filter(|word| word.to_lowercase().contains(&query))
-                .cloned()
-                .collect()
-        }
+        filter_words(&words_clone, &search_query.read())
     });
 
+    // Start from the first page whenever the search changes
+    use_effect(move || {
+        let _ = search_query.read();
+        page.set(0);
+    });
+
+    let total_filtered = filtered_words.read().len();
+    let total_pages = page_count(total_filtered, page_size);
+    let current_page = (*page.read()).min(total_pages.saturating_sub(1));
+    let page_words = page_slice(&filtered_words.read(), current_page, page_size).to_vec();
+    let showing = page_words.len();
+
     rsx! {
         Modal {
             theme: theme.clone(),
             on_close: Some(on_close.clone()),
             max_width: Some("600px".to_string()),
-            
+
             ModalHeader {
                 title: "Known Words".to_string(),
                 count: words.len(),
                 theme: theme.clone(),
                 on_close: on_close.clone(),
             }
-            
+
             SearchBar {
                 search_query: search_query,
                 theme: theme.clone(),
             }
-            
+
             div {
                 class: "modal-body",
                 style: "{styles.body()}",
-                
-                if filtered_words.read().is_empty() {
+
+                if page_words.is_empty() {
                     EmptyState {
                         is_searching: !search_query.read().is_empty(),
                         theme: theme.clone(),
                     }
                 } else {
                     WordGrid {
-                        words: filtered_words.read().clone(),
+                        words: page_words,
                         theme: theme.clone(),
                         on_remove_word: on_remove_word.clone(),
                     }
                 }
             }
-            
+
+            if let Some(removed) = undo_word {
+                div {
+                    class: "undo-toast",
+                    style: "{styles.undo_toast()}",
+
+                    span { "Removed \"{removed}\"" }
+
+                    button {
+                        style: "{styles.undo_button()}",
+                        onclick: move |_| {
+                            if let Some(handler) = &on_undo {
+                                handler.call(());
+                            }
+                        },
+                        "Undo"
+                    }
+                }
+            }
+
             div {
                 class: "modal-footer",
                 style: "{styles.footer()}",
-                
+
+                if total_pages > 1 {
+                    div {
+                        class: "pager",
+                        style: "{styles.pager()}",
+
+                        button {
+                            style: "{styles.pager_button(current_page == 0)}",
+                            disabled: current_page == 0,
+                            onclick: move |_| page.set(current_page.saturating_sub(1)),
+                            "‹ Prev"
+                        }
+
+                        span {
+                            style: "{styles.pager_text()}",
+                            "Showing {showing} of {total_filtered} (page {current_page + 1}/{total_pages})"
+                        }
+
+                        button {
+                            style: "{styles.pager_button(current_page + 1 >= total_pages)}",
+                            disabled: current_page + 1 >= total_pages,
+                            onclick: move |_| page.set((current_page + 1).min(total_pages - 1)),
+                            "Next ›"
+                        }
+                    }
+                }
+
                 button {
                     style: "{styles.action_button()}",
                     onclick: move |_| on_close.call(()),
@@ -75,4 +135,82 @@ pub fn KnownWordsModal(
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Case-insensitive substring filter over the full word list
+fn filter_words(words: &[String], query: &str) -> Vec<String> {
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        words.to_vec()
+    } else {
+        words.iter()
+            .filter(|word| word.to_lowercase().contains(&query))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Number of pages needed to show `total` words, at least one
+fn page_count(total: usize, page_size: usize) -> usize {
+    let page_size = page_size.max(1);
+    total.div_ceil(page_size).max(1)
+}
+
+/// Words on the given zero-based page; empty when the page is out of range
+fn page_slice(words: &[String], page: usize, page_size: usize) -> &[String] {
+    let page_size = page_size.max(1);
+    let start = page.saturating_mul(page_size).min(words.len());
+    let end = start.saturating_add(page_size).min(words.len());
+    &words[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("word{i}")).collect()
+    }
+
+    #[test]
+    fn test_page_slicing() {
+        let words = words(450);
+
+        assert_eq!(page_count(words.len(), 200), 3);
+        assert_eq!(page_slice(&words, 0, 200).len(), 200);
+        assert_eq!(page_slice(&words, 1, 200)[0], "word200");
+        assert_eq!(page_slice(&words, 2, 200).len(), 50);
+        assert!(page_slice(&words, 3, 200).is_empty());
+    }
+
+    #[test]
+    fn test_empty_list_has_one_page() {
+        assert_eq!(page_count(0, 200), 1);
+        assert!(page_slice(&[], 0, 200).is_empty());
+    }
+
+    #[test]
+    fn test_search_filters_full_set_before_paging() {
+        let mut words = words(300);
+        words.push("Serendipity".to_string());
+
+        // The match sits beyond the first page of the unfiltered list
+        assert!(!page_slice(&words, 0, 200).contains(&"Serendipity".to_string()));
+
+        let filtered = filter_words(&words, "SEREN");
+        assert_eq!(filtered, vec!["Serendipity".to_string()]);
+        assert_eq!(page_count(filtered.len(), 200), 1);
+        assert_eq!(page_slice(&filtered, 0, 200), ["Serendipity".to_string()]);
+    }
+
+    #[test]
+    fn test_search_results_are_paged() {
+        let words = words(450);
+        // word1, word10..word19, word100..word199 = 111 matches
+        let filtered = filter_words(&words, "word1");
+
+        assert_eq!(filtered.len(), 111);
+        assert_eq!(page_count(filtered.len(), 100), 2);
+        assert_eq!(page_slice(&filtered, 1, 100).len(), 11);
+    }
+}
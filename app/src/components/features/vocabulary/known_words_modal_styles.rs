@@ -138,6 +138,62 @@ impl<'a> KnownWordsModalStyles<'a> {
         )
     }
 
+    pub fn pager(&self) -> String {
+        "display: flex; \
+         align-items: center; \
+         gap: 10px; \
+         margin-right: auto;".to_string()
+    }
+
+    pub fn pager_button(&self, disabled: bool) -> String {
+        format!(
+            "background: none; \
+             border: 1px solid {}; \
+             color: {}; \
+             padding: 6px 12px; \
+             border-radius: 6px; \
+             cursor: {}; \
+             opacity: {};",
+            self.theme.border,
+            self.theme.text_primary,
+            if disabled { "default" } else { "pointer" },
+            if disabled { "0.4" } else { "1" }
+        )
+    }
+
+    pub fn pager_text(&self) -> String {
+        format!(
+            "color: {}; \
+             font-size: 0.9em;",
+            self.theme.text_secondary
+        )
+    }
+
+    pub fn undo_toast(&self) -> String {
+        format!(
+            "margin: 0 20px; \
+             padding: 10px 14px; \
+             border-radius: 6px; \
+             background: {}; \
+             color: {}; \
+             display: flex; \
+             justify-content: space-between; \
+             align-items: center;",
+            self.theme.text_primary, self.theme.background
+        )
+    }
+
+    pub fn undo_button(&self) -> String {
+        format!(
+            "background: none; \
+             border: none; \
+             color: {}; \
+             font-weight: 600; \
+             cursor: pointer;",
+            self.theme.accent
+        )
+    }
+
     pub fn action_button(&self) -> String {
         format!(
             "background: {}; \
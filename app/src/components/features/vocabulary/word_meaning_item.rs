@@ -2,6 +2,7 @@ use dioxus::prelude::*;
 use glossia_shared::WordMeaning;
 use crate::utils::generate_word_color_themed;
 use crate::theme::Theme;
+use crate::utils::MeaningRevealMode;
 use crate::components::ImageGallery;
 use crate::hooks::{use_image_cache, use_image_fetcher, use_vocabulary};
 use std::collections::HashSet;
@@ -12,12 +13,16 @@ pub fn WordMeaningItem(
     word_meaning: WordMeaning,
     is_last: bool,
     expanded_words: Signal<HashSet<String>>,
+    hovered_word: Signal<Option<String>>,
+    reveal_mode: MeaningRevealMode,
     on_expand_word: EventHandler<String>,
     reading_state: Signal<ReadingEngine>,
     current_sentence: String,
     theme: Theme,
 ) -> Element {
     let is_expanded = expanded_words.read().contains(&word_meaning.word);
+    let is_hovered = hovered_word.read().as_deref() == Some(word_meaning.word.as_str());
+    let meaning_visible = reveal_mode.is_visible(is_hovered, is_expanded);
     
     // Get vocabulary state for progress tracking
     let vocabulary_state = use_vocabulary();
@@ -37,7 +42,6 @@ pub fn WordMeaningItem(
         let sentence_context = current_sentence.clone();
         let mut expanded_words = expanded_words.clone();
         let on_expand_word = on_expand_word.clone();
-        let image_cache_check = image_cache.clone();
         
         move |_| {
             let is_currently_expanded = expanded_words.read().contains(&word);
@@ -50,13 +54,8 @@ pub fn WordMeaningItem(
                 expanded_words.write().insert(word.clone());
                 on_expand_word.call(word.clone());
                 
-                // Check cache state at the time of expansion
-                let has_cached_images = image_cache_check.read().contains_key(&word);
-                
-                // Only fetch images if not already cached
-                if !has_cached_images {
-                    fetch_images(word.clone(), word_meaning_text.clone(), sentence_context.clone());
-                }
+                // The fetcher skips cached words but still marks this one as current
+                fetch_images(word.clone(), word_meaning_text.clone(), sentence_context.clone());
             }
         }
     };
@@ -68,6 +67,15 @@ pub fn WordMeaningItem(
             div {
                 class: "word-header",
                 onclick: toggle_expansion,
+                onmouseenter: {
+                    let word = word_meaning.word.clone();
+                    let mut hovered_word = hovered_word.clone();
+                    move |_| hovered_word.set(Some(word.clone()))
+                },
+                onmouseleave: {
+                    let mut hovered_word = hovered_word.clone();
+                    move |_| hovered_word.set(None)
+                },
                 
                 div {
                     class: "word-label",
@@ -75,9 +83,17 @@ pub fn WordMeaningItem(
                     "{word_meaning.word}"
                 }
                 
-                div {
-                    class: "meaning-definition",
-                    "{word_meaning.meaning}"
+                if meaning_visible {
+                    div {
+                        class: "meaning-definition",
+                        "{word_meaning.meaning}"
+                    }
+                } else {
+                    div {
+                        class: "meaning-definition meaning-hidden",
+                        style: "opacity: 0.5; font-style: italic;",
+                        "{reveal_mode.hidden_hint()}"
+                    }
                 }
                 
                 // Progress indicator showing encounter count
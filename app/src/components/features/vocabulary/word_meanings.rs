@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
-use glossia_shared::WordMeaning;
+use glossia_shared::{classify_words, WordMeaning};
 use crate::components::WordMeaningItem;
+use crate::utils::MeaningRevealMode;
 use std::collections::HashSet;
 
 #[component] 
@@ -10,6 +11,9 @@ pub fn WordMeanings(
     reading_state: Signal<glossia_reading_engine::ReadingEngine>,
     current_sentence: String,
     theme: crate::theme::Theme,
+    /// When definitions are shown; always visible by default
+    #[props(default)]
+    reveal_mode: MeaningRevealMode,
 ) -> Element {
     if words.is_empty() {
         return None;
@@ -17,11 +21,22 @@ pub fn WordMeanings(
 
     // Track which words are expanded
     let expanded_words = use_signal(|| HashSet::<String>::new());
+    let hovered_word = use_signal(|| None::<String>);
+    let classification = classify_words(&words);
+    let summary = match (classification.phrase_count, classification.single_count) {
+        (0, singles) => format!("{} words", singles),
+        (phrases, singles) => format!("{} phrases, {} words", phrases, singles),
+    };
 
     rsx! {
         div {
             class: "word-meanings-container",
             
+            div {
+                class: "meanings-summary",
+                "{summary}"
+            }
+
             div {
                 class: "meanings-list",
                 
@@ -30,6 +45,8 @@ pub fn WordMeanings(
                         word_meaning: word_meaning.clone(),
                         is_last: index == words.len() - 1,
                         expanded_words,
+                        hovered_word,
+                        reveal_mode,
                         on_expand_word: on_expand_word.clone(),
                         reading_state: reading_state.clone(),
                         current_sentence: current_sentence.clone(),
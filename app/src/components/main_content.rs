@@ -1,8 +1,9 @@
 use dioxus::prelude::*;
 use crate::components::{ErrorDisplay};
 use crate::components::features::reading::{ContentDisplay, SentenceProcessor};
-use crate::hooks::{use_simplification, VocabularyState};
+use crate::hooks::{use_simplification, PromotionNotifier, VocabularyState};
 use crate::theme::Theme;
+use glossia_reading_engine::SimplificationStatus;
 use std::collections::HashSet;
 
 #[component]
@@ -12,7 +13,7 @@ pub fn MainContent(
     sentence_to_fetch: Signal<String>,
     word_to_fetch: Signal<String>,
     encounter_tracked_sentences: Signal<HashSet<String>>,
-    promotion_notification: Signal<Option<String>>,
+    promotion_notifier: PromotionNotifier,
     theme: Theme,
 ) -> Element {
     // Use the simplification hook
@@ -56,10 +57,16 @@ pub fn MainContent(
                     false
                 };
                 
-                let is_loading = !sentence_being_fetched.is_empty() && 
-                               sentence_being_fetched == current_sentence_str &&
-                               cached_result.is_none() &&
-                               !has_error;
+                // A requested fetch counts as loading until its resource marks it pending
+                let is_loading = match reading_state.read().simplification_status(&current_sentence_str) {
+                    SimplificationStatus::Cached => false,
+                    SimplificationStatus::Pending => true,
+                    SimplificationStatus::None => {
+                        !sentence_being_fetched.is_empty() &&
+                            sentence_being_fetched == current_sentence_str &&
+                            !has_error
+                    }
+                };
                 
                 rsx! {
                     // Process sentence for word tracking
@@ -68,7 +75,7 @@ pub fn MainContent(
                         cached_result: cached_result.clone(),
                         encounter_tracked_sentences: encounter_tracked_sentences,
                         vocabulary_state: vocabulary_state.clone(),
-                        promotion_notification: promotion_notification,
+                        promotion_notifier: promotion_notifier,
                     }
                     // Error state
                     if has_error {
@@ -83,7 +90,8 @@ pub fn MainContent(
                     // Content display directly (loading is handled within ReadingLayout now)
                     ContentDisplay {
                         original: current_sentence.clone(),
-                        simplified: cached_result.as_ref().map(|r| r.simplified.clone()),
+                        // Hide the simplified line when it would just repeat the original
+                        simplified: cached_result.as_ref().filter(|r| !r.was_already_simple).map(|r| r.simplified.clone()),
                         words: cached_result.as_ref().map(|r| r.words.clone()).unwrap_or_default(),
                         is_loading,
                         theme: theme.clone(),
@@ -7,6 +7,7 @@ pub mod use_app_state;
 pub mod use_word_tracking;
 pub mod use_word_meaning_effect;
 pub mod use_clipboard_monitor;
+pub mod use_shutdown_flush;
 
 pub use use_reading_state::*;
 pub use use_simplification::*;
@@ -17,3 +18,4 @@ pub use use_app_state::*;
 pub use use_word_tracking::*;
 pub use use_word_meaning_effect::*;
 pub use use_clipboard_monitor::*;
+pub use use_shutdown_flush::*;
@@ -1,8 +1,52 @@
 use dioxus::prelude::*;
 use crate::hooks::{use_reading_state, use_vocabulary, VocabularyState};
-use crate::theme::{use_theme, ThemeMode, Theme};
+use crate::theme::{use_theme, ColorPalette, ThemeMode, Theme};
+use crate::utils::clipboard_guard::ClipboardGuard;
+use crate::utils::promotion_batch::PromotionBatch;
+use glossia_vocabulary_manager::RemovedWord;
 use std::collections::HashSet;
-use tracing::{instrument, info, debug};
+use std::time::{Duration, Instant};
+use tracing::{instrument, info, debug, warn};
+
+/// Shows word promotion notifications, grouping bursts into one message
+/// The batch decides how long each notification stays visible
+#[derive(Clone, Copy, PartialEq)]
+pub struct PromotionNotifier {
+    pub notification: Signal<Option<String>>,
+    batch: Signal<PromotionBatch>,
+}
+
+impl PromotionNotifier {
+    pub fn new(notification: Signal<Option<String>>, batch: Signal<PromotionBatch>) -> Self {
+        Self { notification, batch }
+    }
+
+    pub fn display_duration(&self) -> Duration {
+        self.batch.read().display_duration()
+    }
+
+    /// Show a notification for newly promoted words, merged with any recent ones
+    /// The notification is hidden once no further promotions arrive for the display duration
+    pub fn notify(&mut self, promoted_words: &[String]) {
+        let now = Instant::now();
+        let Some(message) = self.batch.write().add(promoted_words, now) else {
+            return;
+        };
+        self.notification.set(Some(message));
+
+        let mut notification = self.notification;
+        let mut batch = self.batch;
+        let display_duration = self.display_duration();
+        spawn(async move {
+            tokio::time::sleep(display_duration).await;
+            // A newer promotion restarts the timer
+            if batch.read().last_promoted() == Some(now) {
+                batch.write().clear();
+                notification.set(None);
+            }
+        });
+    }
+}
 
 /// Centralized application state management
 #[derive(Clone)]
@@ -16,10 +60,13 @@ pub struct AppState {
     pub sentence_to_fetch: Signal<String>,
     pub word_to_fetch: Signal<String>,
     pub promotion_notification: Signal<Option<String>>,
+    pub promotion_notifier: PromotionNotifier,
     pub encounter_tracked_sentences: Signal<HashSet<String>>,
     pub last_clipboard_text: Signal<Option<String>>,
     pub current_clipboard_text: Signal<Option<String>>,
     pub show_clipboard_toast: Signal<bool>,
+    pub clipboard_guard: ClipboardGuard,
+    pub last_removed_word: Signal<Option<RemovedWord>>,
 }
 
 impl AppState {
@@ -92,6 +139,13 @@ impl AppState {
     pub fn load_text_from_clipboard(&mut self) {
         let text = self.current_clipboard_text.read().clone();
         if let Some(text) = text {
+            if !self.clipboard_guard.is_within_size(&text) {
+                warn!("Ignoring clipboard text of {} bytes: too large to load", text.len());
+                self.last_clipboard_text.set(Some(text));
+                self.show_clipboard_toast.set(false);
+                return;
+            }
+
             info!("Loading text from clipboard: {} chars", text.len());
             
             // Load the text
@@ -143,18 +197,63 @@ impl AppState {
         (current, total)
     }
 
+    /// Remove a known word, keeping it available for undo for a few seconds
+    pub fn remove_known_word(&mut self, word: &str) {
+        match self.vocabulary_state.write().remove_known_word(word) {
+            Ok(removed) => {
+                self.last_removed_word.set(Some(removed.clone()));
+
+                let mut last_removed = self.last_removed_word;
+                spawn(async move {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                    // Only clear if no newer removal replaced it
+                    if last_removed.read().as_ref() == Some(&removed) {
+                        last_removed.set(None);
+                    }
+                });
+            }
+            Err(e) => {
+                tracing::error!(
+                    event = "remove_known_word_failed",
+                    component = "app_state",
+                    word = %word,
+                    error = %e,
+                    "Failed to remove known word"
+                );
+            }
+        }
+    }
+
+    /// Restore the most recently removed known word
+    pub fn undo_remove_known_word(&mut self) {
+        let removed = self.last_removed_word.write().take();
+        if let Some(removed) = removed {
+            let word = removed.word.clone();
+            if let Err(e) = self.vocabulary_state.write().restore_known_word(removed) {
+                tracing::error!(
+                    event = "restore_known_word_failed",
+                    component = "app_state",
+                    word = %word,
+                    error = %e,
+                    "Failed to restore known word"
+                );
+            }
+        }
+    }
+
     /// Set promotion notification
     #[allow(dead_code)]
     pub fn set_promotion_notification(&mut self, message: Option<String>) {
         self.promotion_notification.set(message);
     }
 
-    /// Clear promotion notification after delay
+    /// Clear promotion notification after the configured display duration
     #[allow(dead_code)]
     pub fn clear_promotion_notification_after_delay(&mut self) {
-        let mut notification_clone = self.promotion_notification.clone();
+        let mut notification_clone = self.promotion_notification;
+        let display_duration = self.promotion_notifier.display_duration();
         spawn(async move {
-            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            tokio::time::sleep(display_duration).await;
             notification_clone.set(None);
         });
     }
@@ -165,7 +264,8 @@ pub fn use_app_state() -> AppState {
     let reading_state = use_reading_state();
     let vocabulary_state = use_vocabulary();
     let theme_mode = use_theme();
-    let theme = Theme::from_mode(*theme_mode.read());
+    let palette = use_hook(ColorPalette::from_env);
+    let theme = Theme::from_mode(*theme_mode.read()).with_palette(palette);
     
     // Don't show input modal by default - we'll check clipboard first
     let show_input_modal = use_signal(|| false);
@@ -173,10 +273,13 @@ pub fn use_app_state() -> AppState {
     let sentence_to_fetch = use_signal(String::new);
     let word_to_fetch = use_signal(String::new);
     let promotion_notification = use_signal(|| None::<String>);
+    let promotion_batch = use_signal(PromotionBatch::from_env);
+    let promotion_notifier = PromotionNotifier::new(promotion_notification, promotion_batch);
     let encounter_tracked_sentences = use_signal(|| HashSet::<String>::new());
     let last_clipboard_text = use_signal(|| None::<String>);
     let current_clipboard_text = use_signal(|| None::<String>);
     let show_clipboard_toast = use_signal(|| false);
+    let last_removed_word = use_signal(|| None::<RemovedWord>);
 
     AppState {
         reading_state,
@@ -188,9 +291,12 @@ pub fn use_app_state() -> AppState {
         sentence_to_fetch,
         word_to_fetch,
         promotion_notification,
+        promotion_notifier,
         encounter_tracked_sentences,
         last_clipboard_text,
         current_clipboard_text,
         show_clipboard_toast,
+        clipboard_guard: ClipboardGuard::from_env(),
+        last_removed_word,
     }
 }
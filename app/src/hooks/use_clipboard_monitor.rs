@@ -1,7 +1,8 @@
 use dioxus::prelude::*;
 use arboard::Clipboard;
-use std::time::Duration;
-use tracing::{debug, error, info};
+use crate::utils::clipboard_guard::{ClipboardCheck, ClipboardGuard};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, warn};
 
 /// Hook to monitor clipboard changes and detect new text
 pub fn use_clipboard_monitor(
@@ -9,6 +10,7 @@ pub fn use_clipboard_monitor(
     current_clipboard_text: Signal<Option<String>>,
     show_clipboard_toast: Signal<bool>,
     current_reading_text: String,
+    clipboard_guard: ClipboardGuard,
 ) {
     // Use a coroutine instead of thread for better integration with Dioxus
     use_coroutine(move |_: UnboundedReceiver<()>| {
@@ -16,6 +18,7 @@ pub fn use_clipboard_monitor(
         let mut current_clipboard = current_clipboard_text.clone();
         let mut show_toast = show_clipboard_toast.clone();
         let reading_text = current_reading_text.clone();
+        let mut guard = clipboard_guard;
         
         async move {
             info!("Starting clipboard monitoring");
@@ -43,7 +46,22 @@ pub fn use_clipboard_monitor(
                             && new_text != reading_text
                             && new_text.len() > 10; // Minimum length to avoid accidental triggers
                         
+                        // Skip oversized text, and hold back changes inside the debounce
+                        // window so they are re-checked on a later tick
                         if should_show {
+                            match guard.check(&new_text, Instant::now()) {
+                                ClipboardCheck::Accepted => {}
+                                ClipboardCheck::TooLarge => {
+                                    warn!("Ignoring clipboard text of {} bytes: too large", new_text.len());
+                                    last_clipboard.set(Some(new_text));
+                                    continue;
+                                }
+                                ClipboardCheck::Debounced => {
+                                    debug!("Clipboard change within debounce window, deferring");
+                                    continue;
+                                }
+                            }
+
                             debug!(
                                 "New clipboard text detected: {} chars", 
                                 new_text.len()
@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
 use std::collections::HashMap;
 use crate::services::{ImageService, ImageFetchState};
+use crate::utils::fetch_epoch::FetchEpoch;
 use glossia_reading_engine::ReadingEngine;
 
 /// Custom hook for managing image cache and fetching
@@ -9,15 +10,21 @@ pub fn use_image_cache() -> Signal<HashMap<String, ImageFetchState>> {
 }
 
 /// Custom hook for fetching images for a specific word
+///
+/// Each fetch starts a new epoch for its word, so a response that arrives after
+/// a newer fetch for the same word is dropped instead of overwriting it.
 pub fn use_image_fetcher(
     reading_state: Signal<ReadingEngine>,
     mut image_cache: Signal<HashMap<String, ImageFetchState>>,
 ) -> impl FnMut(String, String, String) + 'static {
+    let mut fetch_epoch = use_context::<Signal<FetchEpoch>>();
+
     move |word: String, word_meaning: String, sentence_context: String| {
         // Check if we already have images for this word
         if image_cache.read().contains_key(&word) {
             return;
         }
+        let ticket = fetch_epoch.write().begin(&word);
         
         // Mark as loading
         image_cache.write().insert(word.clone(), ImageFetchState::Loading);
@@ -30,12 +37,19 @@ pub fn use_image_fetcher(
         let mut reading_state_clone = reading_state.clone();
         
         spawn(async move {
-            match ImageService::fetch_images_for_word(
+            let result = ImageService::fetch_images_for_word(
                 &word_clone,
                 &word_meaning_clone,
                 &sentence_clone,
                 &mut reading_state_clone,
-            ).await {
+            ).await;
+
+            // A newer fetch for this word owns the cache entry now
+            if !fetch_epoch.read().is_current(&ticket) {
+                return;
+            }
+
+            match result {
                 Ok(images) => {
                     image_cache_clone.write().insert(word_clone, ImageFetchState::Loaded(images));
                 }
@@ -1,9 +1,40 @@
 use dioxus::prelude::*;
-use glossia_reading_engine::ReadingEngine;
+use glossia_image_client::ImageClientConfig;
+use glossia_reading_engine::{OfflineDictionary, ReadingEngine, DEFAULT_IMAGES_PER_WORD};
+use std::path::Path;
 
 /// Custom hook for managing reading state
+/// Vocabulary and caches are persisted to `~/.glossia` on shutdown
 pub fn use_reading_state() -> Signal<ReadingEngine> {
-    use_signal(|| ReadingEngine::new().expect("Failed to initialize reading engine"))
+    use_signal(|| {
+        let images_per_word = ImageClientConfig::from_env()
+            .map(|config| config.default_count)
+            .unwrap_or(DEFAULT_IMAGES_PER_WORD);
+        // A missing API key shouldn't take the whole app down: keep reading and
+        // vocabulary working and report the error on each LLM request instead
+        let engine = ReadingEngine::new().unwrap_or_else(|e| {
+            tracing::error!("Failed to initialize LLM client: {}", e);
+            ReadingEngine::with_unavailable_llm(e)
+        });
+        // `GLOSSIA_RANK_BY_NOVELTY` lists the least-encountered words first
+        let rank_by_novelty = std::env::var("GLOSSIA_RANK_BY_NOVELTY")
+            .map(|s| matches!(s.to_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+        let mut engine = engine
+            .with_images_per_word(images_per_word)
+            .with_rank_by_novelty(rank_by_novelty);
+        // Optional word→definition file consulted before the LLM
+        if let Ok(path) = std::env::var("GLOSSIA_OFFLINE_DICTIONARY") {
+            match OfflineDictionary::load_from_file(Path::new(&path)) {
+                Ok(dictionary) => engine.set_offline_dictionary(dictionary),
+                Err(e) => tracing::warn!("Failed to load offline dictionary from {}: {}", path, e),
+            }
+        }
+        match dirs::home_dir() {
+            Some(home_dir) => engine.with_data_dir(home_dir.join(".glossia")),
+            None => engine,
+        }
+    })
 }
 
 /// Custom hook for handling navigation - returns closures that can be converted to EventHandlers
@@ -0,0 +1,21 @@
+use dioxus::prelude::*;
+use glossia_reading_engine::ReadingEngine;
+use tracing::{error, info};
+use crate::services::take_flush_receiver;
+
+/// Hook that persists the reading engine when the shutdown handler requests a flush
+pub fn use_shutdown_flush(reading_state: Signal<ReadingEngine>) {
+    use_coroutine(move |_: UnboundedReceiver<()>| async move {
+        let Some(mut flush_requests) = take_flush_receiver() else {
+            return;
+        };
+
+        while let Some(ack) = flush_requests.recv().await {
+            info!("Flushing reading state for shutdown");
+            if let Err(e) = reading_state.read().shutdown() {
+                error!("Failed to persist reading state on shutdown: {}", e);
+            }
+            let _ = ack.send(());
+        }
+    });
+}
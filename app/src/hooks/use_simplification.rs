@@ -34,19 +34,16 @@ pub fn use_simplification(
                     
                     // Only fetch if not cached
                     if reading_state.read().get_cached_simplified(&next_sentence).is_none() {
+                        // The engine runs the request in a tracked task, so `abort_all` cancels it;
+                        // it dedups with a foreground fetch of the same sentence and waits for a
+                        // background permit so prefetch and cache warming share one concurrency cap
+                        let task = reading_state.read().spawn_background_simplification(&next_sentence);
                         let mut reading_state_for_proactive = reading_state.clone();
-                        let next_sentence_clone = next_sentence.clone();
                         spawn(async move {
-                            // Double-check cache (since some time may have passed)
-                            if reading_state_for_proactive.read().get_cached_simplified(&next_sentence_clone).is_some() {
-                                return; // Already cached by another operation
-                            }
-                            
-                            // Use static method to avoid holding any borrow across await
-                            let response = glossia_reading_engine::ReadingEngine::simplify_sentence_static(&next_sentence_clone).await;
-                            // Cache the result afterwards (borrow is dropped from above block)
-                            if let Ok(response) = response {
-                                reading_state_for_proactive.write().cache_simplification_result(next_sentence_clone, response);
+                            // Cache the result afterwards; a failed or cancelled task just clears the mark
+                            match task.await {
+                                Ok(Ok(response)) => reading_state_for_proactive.write().cache_simplification_result(next_sentence, response),
+                                _ => reading_state_for_proactive.read().clear_simplification_pending(&next_sentence),
                             }
                         });
                     }
@@ -56,12 +53,14 @@ pub fn use_simplification(
             }
 
             // Fetch from API without holding any borrow
+            reading_state.read().mark_simplification_pending(&sentence);
             let result: Result<SimplificationResponse, AppError> = 
-                glossia_reading_engine::ReadingEngine::simplify_sentence_static(&sentence).await;
+                glossia_reading_engine::ReadingEngine::simplify_sentence_shared(&sentence).await;
             
             // Cache the result if successful (separate mutable operation, borrow is dropped from above block)
-            if let Ok(ref response) = result {
-                reading_state.write().cache_simplification_result(sentence.clone(), response.clone());
+            match result {
+                Ok(ref response) => reading_state.write().cache_simplification_result(sentence.clone(), response.clone()),
+                Err(_) => reading_state.read().clear_simplification_pending(&sentence),
             }
             
             Some(result)
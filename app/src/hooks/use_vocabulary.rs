@@ -1,5 +1,5 @@
 use dioxus::prelude::*;
-use glossia_vocabulary_manager::VocabularyManager;
+use glossia_vocabulary_manager::{RemovedWord, VocabularyManager};
 use glossia_shared::WordMeaning;
 use anyhow::Result;
 
@@ -30,10 +30,15 @@ impl VocabularyState {
         Ok(true) // The new vocabulary manager doesn't return whether it was new
     }
 
-    pub fn remove_known_word(&mut self, word: &str) -> Result<bool, anyhow::Error> {
-        self.manager.remove_known_word(word).map_err(|e| anyhow::anyhow!("{:?}", e))?;
+    pub fn remove_known_word(&mut self, word: &str) -> Result<RemovedWord, anyhow::Error> {
+        let removed = self.manager.remove_known_word(word).map_err(|e| anyhow::anyhow!("{:?}", e))?;
         self.refresh()?;
-        Ok(true) // Simplified - assume it was removed
+        Ok(removed)
+    }
+
+    pub fn restore_known_word(&mut self, removed: RemovedWord) -> Result<(), anyhow::Error> {
+        self.manager.restore_known_word(removed).map_err(|e| anyhow::anyhow!("{:?}", e))?;
+        self.refresh()
     }
 
     pub fn add_word_encounter(&mut self, word: &str) -> Result<(u32, bool), anyhow::Error> {
@@ -44,6 +49,15 @@ impl VocabularyState {
         Ok((count as u32, promoted))
     }
 
+    /// Record an encounter of `word` in `sentence`
+    pub fn add_word_encounter_in(&mut self, word: &str, sentence: &str) -> Result<(u32, bool), anyhow::Error> {
+        let (count, promoted) = self.manager.add_word_encounter_in(word, sentence).map_err(|e| anyhow::anyhow!("{:?}", e))?;
+        if promoted {
+            self.refresh()?;
+        }
+        Ok((count as u32, promoted))
+    }
+
     pub fn filter_known_words(&self, words: &[WordMeaning]) -> Vec<WordMeaning> {
         self.manager.filter_known_words(words)
     }
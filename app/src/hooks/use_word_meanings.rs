@@ -18,8 +18,11 @@ pub fn use_word_meanings(
             
             info!("use_word_meanings: Starting fetch for word: '{}'", word);
             
+            // Meanings are cached per sentence so a word's definition fits where it appears
+            let sentence = reading_state.read().current_sentence().unwrap_or_default();
+
             // Check cache first (read-only operation)
-            let cached_result = reading_state.read().get_cached_word_meaning(&word);
+            let cached_result = reading_state.read().get_cached_word_meaning_in_context(&word, &sentence);
             
             if let Some(cached) = cached_result {
                 if !cached.trim().is_empty() {
@@ -32,8 +35,16 @@ pub fn use_word_meanings(
                 info!("use_word_meanings: Cache MISS for word '{}', will fetch from API", word);
             }
 
+            // Use the offline dictionary when it knows the word
+            let offline_meaning = reading_state.read().offline_word_meaning(&word);
+            if let Some(meaning) = offline_meaning {
+                info!("use_word_meanings: Offline dictionary HIT for word '{}'", word);
+                reading_state.write().cache_word_meaning_in_context(word.clone(), &sentence, meaning.clone());
+                return Some(Ok(meaning));
+            }
+
             // Get current sentence for context (read-only operation)
-            let context = reading_state.read().current_sentence().unwrap_or_default();
+            let context = reading_state.read().word_meaning_context(&sentence);
             debug!("use_word_meanings: Using context for '{}': {}", word, context);
 
             // Fetch from API without holding any borrow
@@ -46,7 +57,7 @@ pub fn use_word_meanings(
                 Ok(meaning) => {
                     info!("use_word_meanings: API SUCCESS for word '{}', meaning length: {} chars", word, meaning.len());
                     debug!("use_word_meanings: Caching meaning for '{}'", word);
-                    reading_state.write().cache_word_meaning_result(word.clone(), meaning.clone());
+                    reading_state.write().cache_word_meaning_in_context(word.clone(), &sentence, meaning.clone());
                     info!("use_word_meanings: Successfully cached meaning for '{}'", word);
                 }
                 Err(e) => {
@@ -24,25 +24,11 @@ fn main() {
     // Perform basic health checks
     tokio::runtime::Runtime::new().unwrap().block_on(perform_health_checks());
 
-    // Set up graceful shutdown handler
-    let app_name = "Glossia";
-    let app_name_clone = app_name.to_string();
-    
-    let shutdown_handler = move || {
-        glossia_logging::log_shutdown(&app_name_clone);
-    };
-    
     // Set up signal handlers for graceful shutdown
     #[cfg(unix)]
     {
-        use std::sync::Arc;
-        let shutdown_handler = Arc::new(std::sync::Mutex::new(shutdown_handler));
-        let handler_clone = shutdown_handler.clone();
-        
-        ctrlc::set_handler(move || {
-            if let Ok(handler) = handler_clone.lock() {
-                handler();
-            }
+        ctrlc::set_handler(|| {
+            shutdown();
             std::process::exit(0);
         }).expect("Error setting Ctrl-C handler");
     }
@@ -52,7 +38,13 @@ fn main() {
     dioxus_desktop::launch::launch(App, vec![], Default::default());
     
     // This will only be reached if the app exits normally
-    glossia_logging::log_shutdown(app_name);
+    shutdown();
+}
+
+/// Save pending data and log the shutdown, on both signal and normal exit
+fn shutdown() {
+    services::request_flush(std::time::Duration::from_secs(2));
+    glossia_logging::log_shutdown("Glossia");
 }
 
 /// Perform basic health checks on application startup
@@ -83,12 +75,33 @@ async fn perform_health_checks() {
     
     // Try to initialize core services to check for major configuration issues
     match glossia_llm_client::LLMClientFactory::new().create_client() {
-        Ok(_) => info!("LLM client initialization successful"),
+        Ok(client) => {
+            info!("LLM client initialization successful");
+            match client.ping().await {
+                Ok(()) => info!("LLM provider {} is reachable", client.provider_name()),
+                Err(e) => warn!("LLM provider {} ping failed: {}", client.provider_name(), e),
+            }
+        }
         Err(e) => warn!("LLM client initialization failed: {}. Features requiring LLM may not work.", e),
     }
     
-    match glossia_image_client::ImageClientFactory::new().create_client() {
-        Ok(_) => info!("Image client initialization successful"),
+    match glossia_image_client::ImageClientFactory::new().create_and_check().await {
+        Ok(report) => {
+            info!("Image client initialization successful");
+            match report.health {
+                Ok(()) => info!("Image provider {} is reachable", report.client.provider_name()),
+                Err(ref e) if report.is_timeout() => {
+                    warn!("Image provider {} health check timed out, continuing without it: {}", report.client.provider_name(), e)
+                }
+                Err(ref e) if report.is_auth_failure() => {
+                    error!("Image provider {} rejected the API key: {}. Image features will not work.", report.client.provider_name(), e)
+                }
+                Err(ref e) if report.is_retryable() => {
+                    warn!("Image provider {} health check failed, will retry on first search: {}", report.client.provider_name(), e)
+                }
+                Err(ref e) => warn!("Image provider {} health check failed: {}. Image features may not work.", report.client.provider_name(), e),
+            }
+        }
         Err(e) => warn!("Image client initialization failed: {}. Image features may not work.", e),
     }
     
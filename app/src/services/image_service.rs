@@ -1,10 +1,8 @@
 use glossia_shared::{ImageResult, ImageQueryOptimizationRequest};
-use glossia_reading_engine::ReadingEngine;
+use glossia_reading_engine::{optimized_query_key, ReadingEngine};
 use glossia_image_client::ImageClientFactory;
 use glossia_llm_client::LLMClientFactory;
 use dioxus::prelude::{Readable, Writable};
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
 use tracing::{instrument, info, debug, warn};
 
 #[derive(Clone, Debug)]
@@ -37,13 +35,14 @@ impl ImageService {
         let image_client = image_factory.create_client()?;
         
         // Extract required data with a short-lived borrow
-        let (cached_images, optimized_query_cached) = {
+        let (cached_images, optimized_query_cached, images_per_word) = {
             let state = reading_state.read();
-            let context_key = Self::generate_context_key(word, sentence_context);
+            let context_key = optimized_query_key(word, sentence_context);
             
             (
                 state.get_images(word),
-                state.get_optimized_query(&context_key)
+                state.get_optimized_query(&context_key),
+                state.images_per_word(),
             )
         };
         
@@ -55,41 +54,56 @@ impl ImageService {
         
         debug!("No cached images found for word: '{}'", word);
         
-        // Get or generate optimized query
-        let optimized_query = if let Some(cached_query) = optimized_query_cached {
-            cached_query
-        } else {
-            debug!("No cached optimized query found, generating new one");
-            // Try to optimize the query
-            match llm_client.optimize_image_query(ImageQueryOptimizationRequest { 
-                word: word.to_string(),
-                sentence_context: sentence_context.to_string(),
-                word_meaning: word_meaning.to_string(),
-            }).await {
-                Ok(optimization_response) => {
-                    let optimized_query = optimization_response.optimized_query;
-                    info!("Generated optimized query: '{}' for word: '{}'", optimized_query, word);
-                    
-                    // Cache the optimized query
-                    let context_key = Self::generate_context_key(word, sentence_context);
-                    reading_state.write().cache_optimized_query(context_key, optimized_query.clone());
-                    
-                    optimized_query
+        // Query optimization and the image search run in a task tracked by the
+        // engine's registry, so `ReadingEngine::abort_all` cancels them
+        let task = {
+            let word = word.to_string();
+            let word_meaning = word_meaning.to_string();
+            let sentence_context = sentence_context.to_string();
+            reading_state.read().task_registry().spawn(format!("images: {word}"), async move {
+                // Get or generate optimized query
+                let (optimized_query, newly_optimized) = if let Some(cached_query) = optimized_query_cached {
+                    (cached_query, false)
+                } else {
+                    debug!("No cached optimized query found, generating new one");
+                    // Try to optimize the query
+                    match llm_client.optimize_image_query(ImageQueryOptimizationRequest { 
+                        word: word.clone(),
+                        sentence_context,
+                        word_meaning,
+                    }).await {
+                        Ok(optimization_response) => {
+                            let optimized_query = optimization_response.optimized_query;
+                            info!("Generated optimized query: '{}' for word: '{}'", optimized_query, word);
+                            (optimized_query, true)
+                        }
+                        Err(e) => {
+                            warn!("Failed to optimize query for word '{}', using fallback: {}", word, e);
+                            (word.clone(), false) // Fallback to original word
+                        }
+                    }
+                };
+                
+                // Fetch images using the optimized query
+                info!("Fetching {} images with query: '{}'", images_per_word, optimized_query);
+                let start_time = std::time::Instant::now();
+                let images = image_client.search_gallery_with_fallback(&optimized_query, &word, images_per_word).await;
+                if let Ok(images) = &images {
+                    info!("Successfully fetched {} images for word '{}' in {:?}", images.len(), word, start_time.elapsed());
                 }
-                Err(e) => {
-                    warn!("Failed to optimize query for word '{}', using fallback: {}", word, e);
-                    word.to_string() // Fallback to original word
-                }
-            }
+                (newly_optimized.then_some(optimized_query), images)
+            })
         };
+        let (optimized_query, images) = task
+            .await
+            .map_err(|_| glossia_shared::AppError::api_error("Image fetch was cancelled"))?;
         
-        // Fetch images using the optimized query
-        info!("Fetching images with query: '{}'", optimized_query);
-        let start_time = std::time::Instant::now();
-        let images = image_client.search_images(&optimized_query, Some(5)).await?;
-        let fetch_duration = start_time.elapsed();
-        
-        info!("Successfully fetched {} images for word '{}' in {:?}", images.len(), word, fetch_duration);
+        // Cache the optimized query
+        if let Some(optimized_query) = optimized_query {
+            let context_key = optimized_query_key(word, sentence_context);
+            reading_state.write().cache_optimized_query(context_key, optimized_query);
+        }
+        let images = images?;
         
         // Cache the results
         reading_state.write().cache_images(word.to_string(), images.clone());
@@ -97,12 +111,4 @@ impl ImageService {
         
         Ok(images)
     }
-    
-    /// Generate a consistent context key for caching
-    fn generate_context_key(word: &str, sentence_context: &str) -> String {
-        let mut hasher = DefaultHasher::new();
-        word.hash(&mut hasher);
-        sentence_context.hash(&mut hasher);
-        format!("{}_{}_{:x}", word, sentence_context, hasher.finish())
-    }
 }
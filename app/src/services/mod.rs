@@ -1,3 +1,5 @@
 pub mod image_service;
+pub mod shutdown_service;
 
 pub use image_service::*;
+pub use shutdown_service::*;
@@ -0,0 +1,52 @@
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tracing::{info, warn};
+
+/// A flush request carrying the channel used to acknowledge completion
+pub type FlushRequest = mpsc::Sender<()>;
+
+struct ShutdownChannel {
+    sender: UnboundedSender<FlushRequest>,
+    receiver: Mutex<Option<UnboundedReceiver<FlushRequest>>>,
+}
+
+static SHUTDOWN_CHANNEL: OnceLock<ShutdownChannel> = OnceLock::new();
+
+fn channel() -> &'static ShutdownChannel {
+    SHUTDOWN_CHANNEL.get_or_init(|| {
+        let (sender, receiver) = unbounded_channel();
+        ShutdownChannel {
+            sender,
+            receiver: Mutex::new(Some(receiver)),
+        }
+    })
+}
+
+/// Take the receiving end of the flush channel
+/// Only the first caller gets the receiver; the UI owns it for the app's lifetime
+pub fn take_flush_receiver() -> Option<UnboundedReceiver<FlushRequest>> {
+    channel().receiver.lock().ok()?.take()
+}
+
+/// Ask the UI to persist state and wait for it to finish, up to `timeout`
+/// Safe to call from a signal handler thread; returns whether the flush completed
+pub fn request_flush(timeout: Duration) -> bool {
+    let (ack_sender, ack_receiver) = mpsc::channel();
+
+    if channel().sender.send(ack_sender).is_err() {
+        warn!("No shutdown listener registered, skipping flush");
+        return false;
+    }
+
+    match ack_receiver.recv_timeout(timeout) {
+        Ok(()) => {
+            info!("State flushed before shutdown");
+            true
+        }
+        Err(_) => {
+            warn!("Timed out waiting for state flush before shutdown");
+            false
+        }
+    }
+}
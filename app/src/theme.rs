@@ -28,6 +28,7 @@ pub struct Theme {
     pub hover_bg: &'static str,
     pub gallery_bg: &'static str,
     pub gallery_border: &'static str,
+    pub palette: ColorPalette,
 }
 
 impl Theme {
@@ -46,6 +47,7 @@ impl Theme {
             hover_bg: "#f8f9fa",
             gallery_bg: "transparent",
             gallery_border: "rgba(0,0,0,0.05)",
+            palette: ColorPalette::default(),
         }
     }
 
@@ -64,6 +66,7 @@ impl Theme {
             hover_bg: "#3a3a3a",
             gallery_bg: "transparent",
             gallery_border: "rgba(255,255,255,0.05)",
+            palette: ColorPalette::default(),
         }
     }
 
@@ -73,6 +76,107 @@ impl Theme {
             ThemeMode::Dark => Self::dark(),
         }
     }
+
+    /// Use a different set of word highlight colors
+    pub fn with_palette(mut self, palette: ColorPalette) -> Self {
+        self.palette = palette;
+        self
+    }
+}
+
+/// Word highlight colors, with a separate set for each theme mode
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorPalette {
+    light: Vec<String>,
+    dark: Vec<String>,
+}
+
+impl ColorPalette {
+    /// An empty set of colors falls back to the default set for that mode
+    pub fn new(light: &[&str], dark: &[&str]) -> Self {
+        let defaults = Self::standard();
+        Self {
+            light: if light.is_empty() { defaults.light } else { to_strings(light) },
+            dark: if dark.is_empty() { defaults.dark } else { to_strings(dark) },
+        }
+    }
+
+    /// Okabe-Ito colors, distinguishable with the common forms of color-vision deficiency
+    /// Yellow is only used on dark backgrounds and dark blue only on light ones
+    pub fn colorblind_safe() -> Self {
+        Self::new(
+            &["#e69f00", "#56b4e9", "#009e73", "#0072b2", "#d55e00", "#cc79a7"],
+            &["#e69f00", "#56b4e9", "#009e73", "#f0e442", "#d55e00", "#cc79a7"],
+        )
+    }
+
+    /// Palette named by `GLOSSIA_COLOR_PALETTE`: `colorblind`, a comma-separated
+    /// list of colors used for both modes, or the default when unset
+    pub fn from_env() -> Self {
+        std::env::var("GLOSSIA_COLOR_PALETTE")
+            .map(|setting| Self::from_setting(&setting))
+            .unwrap_or_default()
+    }
+
+    pub fn from_setting(setting: &str) -> Self {
+        if setting.trim().eq_ignore_ascii_case("colorblind") {
+            return Self::colorblind_safe();
+        }
+        let colors: Vec<&str> = setting.split(',').map(str::trim).filter(|color| !color.is_empty()).collect();
+        Self::new(&colors, &colors)
+    }
+
+    pub fn colors_for(&self, mode: ThemeMode) -> &[String] {
+        match mode {
+            ThemeMode::Light => &self.light,
+            ThemeMode::Dark => &self.dark,
+        }
+    }
+
+    fn standard() -> Self {
+        Self {
+            // Vibrant but not too bright, readable on white/light backgrounds
+            light: to_strings(&[
+                "#d63384", // Bright Pink - good contrast on light
+                "#fd7e14", // Vibrant Orange
+                "#ffc107", // Golden Yellow
+                "#20c997", // Teal Green
+                "#0dcaf0", // Cyan Blue
+                "#6f42c1", // Purple
+                "#dc3545", // Red
+                "#198754", // Forest Green
+                "#0d6efd", // Primary Blue
+                "#6610f2", // Indigo
+                "#d63384", // Magenta
+                "#495057", // Dark Gray
+            ]),
+            // Bright and saturated for visibility on dark backgrounds
+            dark: to_strings(&[
+                "#ff6b9d", // Bright Pink - excellent visibility on dark
+                "#ffa726", // Light Orange
+                "#ffeb3b", // Bright Yellow
+                "#4caf50", // Light Green
+                "#29b6f6", // Light Blue
+                "#ab47bc", // Light Purple
+                "#ef5350", // Light Red
+                "#66bb6a", // Mint Green
+                "#42a5f5", // Sky Blue
+                "#7e57c2", // Light Indigo
+                "#ec407a", // Light Magenta
+                "#bdbdbd", // Light Gray
+            ]),
+        }
+    }
+}
+
+impl Default for ColorPalette {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+fn to_strings(colors: &[&str]) -> Vec<String> {
+    colors.iter().map(|color| color.to_string()).collect()
 }
 
 pub fn use_theme() -> Signal<ThemeMode> {
@@ -0,0 +1,135 @@
+use std::time::{Duration, Instant};
+
+/// Default maximum clipboard size accepted as reading text (in bytes)
+pub const DEFAULT_MAX_CLIPBOARD_BYTES: usize = 200_000;
+
+/// Default window during which further clipboard changes are ignored
+pub const DEFAULT_CLIPBOARD_DEBOUNCE: Duration = Duration::from_millis(1500);
+
+/// Outcome of checking a clipboard change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardCheck {
+    Accepted,
+    TooLarge,
+    Debounced,
+}
+
+/// Size limit and debounce for clipboard text detection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipboardGuard {
+    max_bytes: usize,
+    debounce: Duration,
+    last_accepted: Option<Instant>,
+}
+
+impl ClipboardGuard {
+    pub fn new() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_CLIPBOARD_BYTES,
+            debounce: DEFAULT_CLIPBOARD_DEBOUNCE,
+            last_accepted: None,
+        }
+    }
+
+    /// Limits from `GLOSSIA_CLIPBOARD_MAX_BYTES` and `GLOSSIA_CLIPBOARD_DEBOUNCE_MS`,
+    /// keeping the defaults for unset or unparsable values
+    pub fn from_env() -> Self {
+        Self::from_settings(
+            std::env::var("GLOSSIA_CLIPBOARD_MAX_BYTES").ok().as_deref(),
+            std::env::var("GLOSSIA_CLIPBOARD_DEBOUNCE_MS").ok().as_deref(),
+        )
+    }
+
+    pub fn from_settings(max_bytes: Option<&str>, debounce_ms: Option<&str>) -> Self {
+        let mut guard = Self::new();
+        if let Some(max_bytes) = max_bytes.and_then(|value| value.trim().parse().ok()) {
+            guard = guard.with_max_bytes(max_bytes);
+        }
+        if let Some(debounce_ms) = debounce_ms.and_then(|value| value.trim().parse().ok()) {
+            guard = guard.with_debounce(Duration::from_millis(debounce_ms));
+        }
+        guard
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Whether the text is small enough to load as reading text
+    pub fn is_within_size(&self, text: &str) -> bool {
+        text.len() <= self.max_bytes
+    }
+
+    /// Check a clipboard change observed at `now`
+    /// Oversized text is always rejected; otherwise changes within the debounce
+    /// window of the last accepted one are suppressed
+    pub fn check(&mut self, text: &str, now: Instant) -> ClipboardCheck {
+        if !self.is_within_size(text) {
+            return ClipboardCheck::TooLarge;
+        }
+
+        if let Some(last) = self.last_accepted {
+            if now.saturating_duration_since(last) < self.debounce {
+                return ClipboardCheck::Debounced;
+            }
+        }
+
+        self.last_accepted = Some(now);
+        ClipboardCheck::Accepted
+    }
+}
+
+impl Default for ClipboardGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oversize_text_is_rejected() {
+        let mut guard = ClipboardGuard::new().with_max_bytes(16);
+        let now = Instant::now();
+
+        assert!(!guard.is_within_size(&"x".repeat(17)));
+        assert_eq!(guard.check(&"x".repeat(17), now), ClipboardCheck::TooLarge);
+        assert_eq!(guard.check("short enough", now), ClipboardCheck::Accepted);
+    }
+
+    #[test]
+    fn test_changes_within_debounce_are_suppressed() {
+        let mut guard = ClipboardGuard::new().with_debounce(Duration::from_secs(1));
+        let start = Instant::now();
+
+        assert_eq!(guard.check("first copy", start), ClipboardCheck::Accepted);
+        assert_eq!(guard.check("second copy", start + Duration::from_millis(300)), ClipboardCheck::Debounced);
+        assert_eq!(guard.check("third copy", start + Duration::from_millis(999)), ClipboardCheck::Debounced);
+        assert_eq!(guard.check("fourth copy", start + Duration::from_secs(1)), ClipboardCheck::Accepted);
+    }
+
+    #[test]
+    fn test_rejected_text_does_not_start_debounce() {
+        let mut guard = ClipboardGuard::new().with_max_bytes(8);
+        let start = Instant::now();
+
+        assert_eq!(guard.check("far too long for the limit", start), ClipboardCheck::TooLarge);
+        assert_eq!(guard.check("fits", start), ClipboardCheck::Accepted);
+    }
+
+    #[test]
+    fn test_settings_override_defaults() {
+        let guard = ClipboardGuard::from_settings(Some("1024"), Some(" 250 "));
+        assert_eq!(guard, ClipboardGuard::new().with_max_bytes(1024).with_debounce(Duration::from_millis(250)));
+
+        assert_eq!(ClipboardGuard::from_settings(None, Some("soon")), ClipboardGuard::new());
+    }
+}
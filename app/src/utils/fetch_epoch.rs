@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+/// Tracks the latest image gallery fetch for each word
+///
+/// Each fetch takes a ticket from `begin`; a response is only applied when its
+/// ticket is still the latest for that word, so a slow reply from an earlier
+/// fetch cannot overwrite a newer one. Fetches for different words never
+/// invalidate each other.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchEpoch {
+    current: HashMap<String, u64>,
+}
+
+/// Issued by `FetchEpoch::begin` and checked when the response arrives
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchTicket {
+    epoch: u64,
+    word: String,
+}
+
+impl FetchEpoch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new generation for `word`, invalidating its earlier tickets
+    pub fn begin(&mut self, word: &str) -> FetchTicket {
+        let epoch = self.current.entry(word.to_string()).or_default();
+        *epoch += 1;
+        FetchTicket {
+            epoch: *epoch,
+            word: word.to_string(),
+        }
+    }
+
+    /// Whether `ticket` belongs to the latest fetch for its word
+    pub fn is_current(&self, ticket: &FetchTicket) -> bool {
+        self.current.get(&ticket.word) == Some(&ticket.epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_other_words_do_not_invalidate_a_ticket() {
+        let mut epoch = FetchEpoch::new();
+        let apple = epoch.begin("apple");
+        let banana = epoch.begin("banana");
+
+        assert!(epoch.is_current(&apple));
+        assert!(epoch.is_current(&banana));
+    }
+
+    #[test]
+    fn test_refetching_a_word_rejects_its_earlier_ticket() {
+        let mut epoch = FetchEpoch::new();
+        let stale = epoch.begin("apple");
+        epoch.begin("banana");
+        let fresh = epoch.begin("apple");
+
+        assert!(!epoch.is_current(&stale));
+        assert!(epoch.is_current(&fresh));
+    }
+}
@@ -0,0 +1,73 @@
+/// When a word's definition is shown in the meanings panel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MeaningRevealMode {
+    /// Definitions are always shown
+    #[default]
+    AlwaysVisible,
+    /// Hidden until the pointer is over the word, or the word is expanded
+    OnHover,
+    /// Hidden until the word is clicked (expanded)
+    OnClick,
+}
+
+impl MeaningRevealMode {
+    /// Mode named by `GLOSSIA_MEANING_REVEAL` (`always`, `hover` or `click`),
+    /// or the default when unset or unrecognized
+    pub fn from_env() -> Self {
+        std::env::var("GLOSSIA_MEANING_REVEAL")
+            .ok()
+            .and_then(|setting| Self::from_setting(&setting))
+            .unwrap_or_default()
+    }
+
+    pub fn from_setting(setting: &str) -> Option<Self> {
+        match setting.trim().to_lowercase().as_str() {
+            "always" | "always_visible" => Some(Self::AlwaysVisible),
+            "hover" | "on_hover" => Some(Self::OnHover),
+            "click" | "on_click" => Some(Self::OnClick),
+            _ => None,
+        }
+    }
+
+    /// Whether a definition is shown given the word's interaction state
+    pub fn is_visible(self, is_hovered: bool, is_expanded: bool) -> bool {
+        match self {
+            Self::AlwaysVisible => true,
+            Self::OnHover => is_hovered || is_expanded,
+            Self::OnClick => is_expanded,
+        }
+    }
+
+    /// Text shown in place of a hidden definition
+    pub fn hidden_hint(self) -> &'static str {
+        match self {
+            Self::OnHover => "Hover to reveal",
+            _ => "Click to reveal",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATES: [(bool, bool); 4] = [(false, false), (true, false), (false, true), (true, true)];
+
+    fn visibility(mode: MeaningRevealMode) -> Vec<bool> {
+        STATES.iter().map(|&(hovered, expanded)| mode.is_visible(hovered, expanded)).collect()
+    }
+
+    #[test]
+    fn test_visibility_per_mode() {
+        assert_eq!(visibility(MeaningRevealMode::AlwaysVisible), vec![true, true, true, true]);
+        assert_eq!(visibility(MeaningRevealMode::OnHover), vec![false, true, true, true]);
+        assert_eq!(visibility(MeaningRevealMode::OnClick), vec![false, false, true, true]);
+    }
+
+    #[test]
+    fn test_default_keeps_definitions_visible() {
+        assert_eq!(MeaningRevealMode::default(), MeaningRevealMode::AlwaysVisible);
+        assert_eq!(MeaningRevealMode::from_setting(" Hover "), Some(MeaningRevealMode::OnHover));
+        assert_eq!(MeaningRevealMode::from_setting("sometimes"), None);
+    }
+}
@@ -1,4 +1,9 @@
 pub mod word_utils;
 pub mod text_utils;
+pub mod clipboard_guard;
+pub mod promotion_batch;
+pub mod fetch_epoch;
+pub mod meaning_reveal;
 
 pub use text_utils::*;
+pub use meaning_reveal::MeaningRevealMode;
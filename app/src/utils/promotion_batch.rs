@@ -0,0 +1,142 @@
+use crate::utils::word_utils::format_promotion_message;
+use std::time::{Duration, Instant};
+
+/// Default window in which promotions are grouped into one notification
+pub const DEFAULT_PROMOTION_GROUP_WINDOW: Duration = Duration::from_secs(2);
+
+/// Default time a promotion notification stays visible
+pub const DEFAULT_PROMOTION_DISPLAY_DURATION: Duration = Duration::from_secs(3);
+
+/// Groups word promotions that happen close together into a single notification
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromotionBatch {
+    words: Vec<String>,
+    last_promoted: Option<Instant>,
+    window: Duration,
+    display_duration: Duration,
+}
+
+impl PromotionBatch {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            words: Vec::new(),
+            last_promoted: None,
+            window,
+            display_duration: DEFAULT_PROMOTION_DISPLAY_DURATION,
+        }
+    }
+
+    /// Default batch showing notifications for `GLOSSIA_PROMOTION_DISPLAY_MS`
+    /// milliseconds, or the default duration when unset or unparsable
+    pub fn from_env() -> Self {
+        Self::from_setting(std::env::var("GLOSSIA_PROMOTION_DISPLAY_MS").ok().as_deref())
+    }
+
+    pub fn from_setting(display_ms: Option<&str>) -> Self {
+        let display_duration = display_ms
+            .and_then(|value| value.trim().parse().ok())
+            .map_or(DEFAULT_PROMOTION_DISPLAY_DURATION, Duration::from_millis);
+        Self::default().with_display_duration(display_duration)
+    }
+
+    pub fn with_display_duration(mut self, display_duration: Duration) -> Self {
+        self.display_duration = display_duration;
+        self
+    }
+
+    /// How long the notification for a group stays visible after its last promotion
+    pub fn display_duration(&self) -> Duration {
+        self.display_duration
+    }
+
+    /// Record words promoted at `now` and return the message for the current group
+    /// Promotions within the window of the previous one join its group; later ones start a new group
+    pub fn add(&mut self, promoted_words: &[String], now: Instant) -> Option<String> {
+        if promoted_words.is_empty() {
+            return None;
+        }
+
+        let continues_group = self.last_promoted
+            .is_some_and(|last| now.saturating_duration_since(last) < self.window);
+        if !continues_group {
+            self.words.clear();
+        }
+
+        for word in promoted_words {
+            if !self.words.contains(word) {
+                self.words.push(word.clone());
+            }
+        }
+        self.last_promoted = Some(now);
+
+        format_promotion_message(&self.words)
+    }
+
+    /// Time of the most recent promotion in the current group
+    pub fn last_promoted(&self) -> Option<Instant> {
+        self.last_promoted
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+        self.last_promoted = None;
+    }
+}
+
+impl Default for PromotionBatch {
+    fn default() -> Self {
+        Self::new(DEFAULT_PROMOTION_GROUP_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_promotions_within_window_are_grouped() {
+        let mut batch = PromotionBatch::new(Duration::from_secs(2));
+        let start = Instant::now();
+
+        assert_eq!(batch.add(&words(&["lucid"]), start), Some("'lucid' added to known words!".to_string()));
+        assert_eq!(
+            batch.add(&words(&["serene"]), start + Duration::from_millis(500)),
+            Some("2 words added to known words!".to_string())
+        );
+        assert_eq!(
+            batch.add(&words(&["ephemeral", "lucid"]), start + Duration::from_millis(1500)),
+            Some("3 words added to known words!".to_string())
+        );
+    }
+
+    #[test]
+    fn test_promotion_after_window_starts_new_group() {
+        let mut batch = PromotionBatch::new(Duration::from_secs(2));
+        let start = Instant::now();
+
+        batch.add(&words(&["lucid", "serene"]), start);
+        assert_eq!(
+            batch.add(&words(&["ephemeral"]), start + Duration::from_secs(3)),
+            Some("'ephemeral' added to known words!".to_string())
+        );
+    }
+
+    #[test]
+    fn test_empty_promotion_produces_no_message() {
+        let mut batch = PromotionBatch::default();
+        assert_eq!(batch.add(&[], Instant::now()), None);
+        assert_eq!(batch.last_promoted(), None);
+    }
+
+    #[test]
+    fn test_display_duration_is_configurable() {
+        assert_eq!(PromotionBatch::from_setting(Some("10000")).display_duration(), Duration::from_secs(10));
+        assert_eq!(PromotionBatch::from_setting(Some(" 500 ")).display_duration(), Duration::from_millis(500));
+        assert_eq!(PromotionBatch::from_setting(Some("later")).display_duration(), DEFAULT_PROMOTION_DISPLAY_DURATION);
+        assert_eq!(PromotionBatch::from_setting(None), PromotionBatch::default());
+    }
+}
@@ -1,6 +1,9 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use crate::theme::{Theme, ThemeMode};
+use std::rc::Rc;
+use crate::theme::Theme;
+use glossia_shared::types::WordMeaning;
+use glossia_text_parser::{DefaultTokenizer, Tokenizer};
 
 /// Generate a consistent color for a given word (legacy function for backwards compatibility)
 #[allow(dead_code)]
@@ -16,87 +19,33 @@ pub fn generate_word_color_themed(word: &str, theme: &Theme) -> String {
     word.to_lowercase().hash(&mut hasher);
     let hash = hasher.finish();
 
-    let colors = match theme.mode {
-        ThemeMode::Light => light_theme_colors(),
-        ThemeMode::Dark => dark_theme_colors(),
-    };
+    let colors = theme.palette.colors_for(theme.mode);
 
     // Select color based on hash
     let index = (hash as usize) % colors.len();
-    colors[index].to_string()
-}
-
-/// Color palette optimized for light theme
-/// Colors are vibrant but not too bright, ensuring good readability on white/light backgrounds
-fn light_theme_colors() -> [&'static str; 12] {
-    [
-        "#d63384", // Bright Pink - good contrast on light
-        "#fd7e14", // Vibrant Orange
-        "#ffc107", // Golden Yellow
-        "#20c997", // Teal Green
-        "#0dcaf0", // Cyan Blue
-        "#6f42c1", // Purple
-        "#dc3545", // Red
-        "#198754", // Forest Green
-        "#0d6efd", // Primary Blue
-        "#6610f2", // Indigo
-        "#d63384", // Magenta
-        "#495057", // Dark Gray
-    ]
-}
-
-/// Color palette optimized for dark theme
-/// Colors are bright and saturated to ensure good visibility on dark backgrounds
-fn dark_theme_colors() -> [&'static str; 12] {
-    [
-        "#ff6b9d", // Bright Pink - excellent visibility on dark
-        "#ffa726", // Light Orange
-        "#ffeb3b", // Bright Yellow
-        "#4caf50", // Light Green
-        "#29b6f6", // Light Blue
-        "#ab47bc", // Light Purple
-        "#ef5350", // Light Red
-        "#66bb6a", // Mint Green
-        "#42a5f5", // Sky Blue
-        "#7e57c2", // Light Indigo
-        "#ec407a", // Light Magenta
-        "#bdbdbd", // Light Gray
-    ]
+    colors[index].clone()
 }
 
 /// Tokenize text into word elements for click handling
+/// Uses the same tokenizer as word extraction so every extracted word is clickable
 pub fn tokenize_text_for_clicks(text: &str) -> Vec<String> {
-    // Split text into words and non-word characters (spaces, punctuation, etc.)
-    let mut tokens = Vec::new();
-    let mut current_token = String::new();
-    let mut is_word = false;
-
-    for ch in text.chars() {
-        let char_is_word = ch.is_alphabetic();
-
-        if char_is_word != is_word {
-            // Character type changed, push current token if not empty
-            if !current_token.is_empty() {
-                tokens.push(current_token.clone());
-                current_token.clear();
-            }
-            is_word = char_is_word;
-        }
-
-        current_token.push(ch);
-    }
-
-    // Push the last token if not empty
-    if !current_token.is_empty() {
-        tokens.push(current_token);
-    }
-
-    tokens
+    DefaultTokenizer.tokenize(text)
+        .into_iter()
+        .map(|token| token.text)
+        .collect()
 }
 
-/// Check if a token is a word (contains only alphabetic characters)
+/// Check if a token is a single word
 pub fn is_word_token(token: &str) -> bool {
-    !token.is_empty() && token.chars().all(|c| c.is_alphabetic())
+    DefaultTokenizer.is_word(token)
+}
+
+/// Default `min_clickable_len`: every word is clickable
+pub const DEFAULT_MIN_CLICKABLE_LEN: usize = 1;
+
+/// Check if a token is a word long enough to be clickable
+pub fn is_clickable_token(token: &str, min_clickable_len: usize) -> bool {
+    is_word_token(token) && token.chars().count() >= min_clickable_len
 }
 
 /// Represents a span of tokens that should be highlighted together
@@ -183,6 +132,10 @@ pub fn find_phrase_matches(tokens: &[String], word_meanings: &[glossia_shared::t
 
 /// Try to match a phrase starting at the given token index
 fn try_match_phrase_at(tokens: &[String], start_idx: usize, phrase_words: &[&str]) -> Option<usize> {
+    // A phrase starts on its first word, not on the space or punctuation before it
+    if !tokens.get(start_idx).is_some_and(|token| is_word_token(token)) {
+        return None;
+    }
     let mut token_idx = start_idx;
     let mut phrase_word_idx = 0;
 
@@ -208,10 +161,133 @@ fn try_match_phrase_at(tokens: &[String], start_idx: usize, phrase_words: &[&str
     }
 }
 
+/// A piece of rendered text: a single plain token or a highlighted word/phrase
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderSegment {
+    /// Unhighlighted token; clickable when it is a word
+    Plain {
+        index: usize,
+        text: String,
+        is_word: bool,
+    },
+    /// Highlighted span covering `start_index..=end_index`
+    /// `label` is the word or phrase used for coloring and clicks
+    Highlight {
+        start_index: usize,
+        end_index: usize,
+        display: String,
+        label: String,
+    },
+}
+
+/// Split text into plain and highlighted segments for rendering; plain words
+/// shorter than `min_clickable_len` characters are rendered as non-clickable text
+pub fn compute_highlights_with_min_len(
+    text: &str,
+    word_meanings: &[WordMeaning],
+    min_clickable_len: usize,
+) -> Vec<RenderSegment> {
+    let tokens = tokenize_text_for_clicks(text);
+    let spans = find_phrase_matches(&tokens, word_meanings);
+
+    let mut segments = Vec::with_capacity(tokens.len());
+    let mut current_index = 0;
+
+    let push_plain = |segments: &mut Vec<RenderSegment>, index: usize| {
+        let token = &tokens[index];
+        segments.push(RenderSegment::Plain {
+            index,
+            text: token.clone(),
+            is_word: is_clickable_token(token, min_clickable_len),
+        });
+    };
+
+    for span in &spans {
+        while current_index < span.start_index {
+            push_plain(&mut segments, current_index);
+            current_index += 1;
+        }
+
+        let end_index = span.end_index.min(tokens.len() - 1);
+        let span_tokens = &tokens[span.start_index..=end_index];
+
+        // Phrases use the original phrase text so colors match the meanings panel
+        let label = if span.is_phrase {
+            word_meanings.iter()
+                .find(|wm| wm.is_phrase &&
+                    wm.word.split_whitespace()
+                        .zip(span_tokens.iter().filter(|t| is_word_token(t)))
+                        .all(|(phrase_word, token)| phrase_word.to_lowercase() == token.to_lowercase()))
+                .map(|wm| wm.word.clone())
+                .unwrap_or_else(|| span.text.clone())
+        } else {
+            span.text.clone()
+        };
+
+        segments.push(RenderSegment::Highlight {
+            start_index: span.start_index,
+            end_index,
+            display: span_tokens.concat(),
+            label,
+        });
+
+        current_index = end_index + 1;
+    }
+
+    while current_index < tokens.len() {
+        push_plain(&mut segments, current_index);
+        current_index += 1;
+    }
+
+    segments
+}
+
+/// Remembers the last computed segments so re-renders with the same inputs reuse them
+pub struct HighlightCache {
+    key: Option<(String, Vec<WordMeaning>)>,
+    segments: Rc<Vec<RenderSegment>>,
+    min_clickable_len: usize,
+}
+
+impl Default for HighlightCache {
+    fn default() -> Self {
+        Self {
+            key: None,
+            segments: Rc::default(),
+            min_clickable_len: DEFAULT_MIN_CLICKABLE_LEN,
+        }
+    }
+}
+
+impl HighlightCache {
+    /// Change the shortest clickable word length, dropping segments computed with the old one
+    pub fn set_min_clickable_len(&mut self, min_clickable_len: usize) {
+        if self.min_clickable_len != min_clickable_len {
+            self.min_clickable_len = min_clickable_len;
+            self.key = None;
+        }
+    }
+
+    /// Segments for `(text, word_meanings)`, recomputed only when either input changed
+    pub fn get(&mut self, text: &str, word_meanings: &[WordMeaning]) -> Rc<Vec<RenderSegment>> {
+        let is_fresh = matches!(
+            &self.key,
+            Some((cached_text, cached_words)) if cached_text == text && cached_words.as_slice() == word_meanings
+        );
+
+        if !is_fresh {
+            self.segments = Rc::new(compute_highlights_with_min_len(text, word_meanings, self.min_clickable_len));
+            self.key = Some((text.to_string(), word_meanings.to_vec()));
+        }
+
+        self.segments.clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::theme::{Theme, ThemeMode};
+    use crate::theme::{ColorPalette, Theme, ThemeMode};
 
     #[test]
     fn test_theme_aware_colors() {
@@ -241,6 +317,43 @@ mod tests {
         assert_eq!(dark_color.len(), 7); // #RRGGBB format
     }
 
+    #[test]
+    fn test_custom_palette_changes_colors_deterministically() {
+        let palette = ColorPalette::new(&["#111111", "#222222"], &["#eeeeee"]);
+        let theme = Theme::light().with_palette(palette.clone());
+        let words = ["alpha", "beta", "gamma", "delta", "epsilon"];
+
+        for word in words {
+            let color = generate_word_color_themed(word, &theme);
+            assert!(color == "#111111" || color == "#222222");
+            assert_eq!(color, generate_word_color_themed(word, &Theme::light().with_palette(palette.clone())));
+            assert_eq!(generate_word_color_themed(word, &Theme::dark().with_palette(palette.clone())), "#eeeeee");
+        }
+    }
+
+    #[test]
+    fn test_colorblind_palette_is_stable_per_word() {
+        let theme = Theme::dark().with_palette(ColorPalette::colorblind_safe());
+        let allowed = ColorPalette::colorblind_safe().colors_for(ThemeMode::Dark).to_vec();
+
+        let color = generate_word_color_themed("Harbor", &theme);
+        assert!(allowed.contains(&color));
+        assert_eq!(color, generate_word_color_themed("harbor", &theme));
+    }
+
+    #[test]
+    fn test_empty_palette_falls_back_to_default() {
+        let palette = ColorPalette::new(&[], &["#eeeeee"]);
+        assert_eq!(palette.colors_for(ThemeMode::Light), ColorPalette::default().colors_for(ThemeMode::Light));
+    }
+
+    #[test]
+    fn test_palette_from_setting() {
+        assert_eq!(ColorPalette::from_setting(" Colorblind "), ColorPalette::colorblind_safe());
+        assert_eq!(ColorPalette::from_setting("#111111, #222222,").colors_for(ThemeMode::Dark), ["#111111", "#222222"]);
+        assert_eq!(ColorPalette::from_setting(""), ColorPalette::default());
+    }
+
     #[test]
     fn test_backwards_compatibility() {
         let test_word = "compatibility";
@@ -253,4 +366,148 @@ mod tests {
         
         assert_eq!(legacy_color, light_theme_color);
     }
+
+    fn plain(index: usize, text: &str, is_word: bool) -> RenderSegment {
+        RenderSegment::Plain { index, text: text.to_string(), is_word }
+    }
+
+    #[test]
+    fn test_compute_highlights_without_words_is_all_plain() {
+        let segments = compute_highlights_with_min_len("Hi, you.", &[], DEFAULT_MIN_CLICKABLE_LEN);
+        assert_eq!(segments, vec![
+            plain(0, "Hi", true),
+            plain(1, ", ", false),
+            plain(2, "you", true),
+            plain(3, ".", false),
+        ]);
+    }
+
+    #[test]
+    fn test_compute_highlights_words_and_phrases() {
+        let words = vec![
+            WordMeaning::new_word("cat".to_string(), "a pet".to_string()),
+            WordMeaning::new_phrase("Give Up".to_string(), "stop trying".to_string()),
+        ];
+        let segments = compute_highlights_with_min_len("The Cat will give up.", &words, DEFAULT_MIN_CLICKABLE_LEN);
+
+        assert_eq!(segments, vec![
+            plain(0, "The", true),
+            plain(1, " ", false),
+            RenderSegment::Highlight {
+                start_index: 2,
+                end_index: 2,
+                display: "Cat".to_string(),
+                label: "Cat".to_string(),
+            },
+            plain(3, " ", false),
+            plain(4, "will", true),
+            plain(5, " ", false),
+            RenderSegment::Highlight {
+                start_index: 6,
+                end_index: 8,
+                display: "give up".to_string(),
+                label: "Give Up".to_string(),
+            },
+            plain(9, ".", false),
+        ]);
+    }
+
+    #[test]
+    fn test_min_clickable_len_makes_short_words_plain_text() {
+        let segments = compute_highlights_with_min_len("I saw a cat.", &[], 2);
+        assert_eq!(segments, vec![
+            plain(0, "I", false),
+            plain(1, " ", false),
+            plain(2, "saw", true),
+            plain(3, " ", false),
+            plain(4, "a", false),
+            plain(5, " ", false),
+            plain(6, "cat", true),
+            plain(7, ".", false),
+        ]);
+
+        // The default keeps every word clickable
+        assert_eq!(compute_highlights_with_min_len("I saw a cat.", &[], DEFAULT_MIN_CLICKABLE_LEN)[0], plain(0, "I", true));
+    }
+
+    #[test]
+    fn test_highlight_cache_recomputes_when_min_len_changes() {
+        let mut cache = HighlightCache::default();
+        assert_eq!(cache.get("A cat.", &[])[0], plain(0, "A", true));
+
+        cache.set_min_clickable_len(2);
+        let segments = cache.get("A cat.", &[]);
+        assert_eq!(segments[0], plain(0, "A", false));
+        assert_eq!(segments[2], plain(2, "cat", true));
+    }
+
+    #[test]
+    fn test_highlight_cache_reuses_segments_for_same_inputs() {
+        let words = vec![WordMeaning::new_word("cat".to_string(), "a pet".to_string())];
+        let mut cache = HighlightCache::default();
+
+        let first = cache.get("The cat sat.", &words);
+        let second = cache.get("The cat sat.", &words);
+        assert!(Rc::ptr_eq(&first, &second));
+
+        let other_text = cache.get("The cat ran.", &words);
+        assert!(!Rc::ptr_eq(&first, &other_text));
+
+        let other_words = cache.get("The cat ran.", &[]);
+        assert!(!Rc::ptr_eq(&other_text, &other_words));
+        assert!(other_words.iter().all(|segment| matches!(segment, RenderSegment::Plain { .. })));
+    }
+
+    #[test]
+    fn test_compute_highlights_long_sentence() {
+        let vocabulary = ["river", "mountain", "forest", "valley", "meadow"];
+        let text = (0..2_000)
+            .map(|i| vocabulary[i % vocabulary.len()].to_string() + if i % 7 == 0 { " and" } else { "" })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut words: Vec<WordMeaning> = vocabulary.iter()
+            .map(|word| WordMeaning::new_word(word.to_string(), "meaning".to_string()))
+            .collect();
+        words.push(WordMeaning::new_phrase("meadow river".to_string(), "meaning".to_string()));
+
+        let segments = compute_highlights_with_min_len(&text, &words, DEFAULT_MIN_CLICKABLE_LEN);
+
+        // Every vocabulary word is highlighted; only the filler "and" stays plain
+        assert!(segments.iter().all(|segment| match segment {
+            RenderSegment::Plain { text, is_word, .. } => !is_word || text == "and",
+            RenderSegment::Highlight { .. } => true,
+        }));
+        // "meadow river" is matched as a phrase wherever no "and" separates the two
+        let expected_phrases = (0..1_999).filter(|i| i % 5 == 4 && i % 7 != 0).count();
+        let phrases = segments.iter()
+            .filter(|segment| matches!(segment, RenderSegment::Highlight { label, .. } if label == "meadow river"))
+            .count();
+        assert_eq!(phrases, expected_phrases);
+
+        // Repeated lookups share the first computation instead of recomputing
+        let mut cache = HighlightCache::default();
+        let first = cache.get(&text, &words);
+        assert_eq!(*first, segments);
+        for _ in 0..100 {
+            assert!(Rc::ptr_eq(&first, &cache.get(&text, &words)));
+        }
+    }
+
+    #[test]
+    fn test_click_tokens_agree_with_word_extraction() {
+        for text in [
+            "Don't you think it's great? I can\u{2019}t.",
+            "A well-known, self-made man - or not.",
+            "Über café naïve São Paulo, 東京 and Ελλάδα.",
+        ] {
+            let click_words: Vec<String> = tokenize_text_for_clicks(text)
+                .into_iter()
+                .filter(|token| is_word_token(token))
+                .map(|token| token.to_lowercase())
+                .collect();
+
+            assert_eq!(click_words, glossia_text_parser::extract_words(text), "text: {text}");
+            assert_eq!(tokenize_text_for_clicks(text).concat(), text);
+        }
+    }
 }
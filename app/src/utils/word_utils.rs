@@ -12,6 +12,7 @@ pub fn is_word_already_difficult(
 }
 
 /// Get combined and filtered words for display, sorted by timestamp (newest first)
+/// or, when novelty ranking is enabled, by encounter count (least familiar first)
 pub fn get_display_words(
     api_words: &[WordMeaning],
     reading_state: &glossia_reading_engine::ReadingEngine,
@@ -19,6 +20,10 @@ pub fn get_display_words(
 ) -> Vec<WordMeaning> {
     let combined_words = reading_state.get_combined_words_with_cache(api_words);
     let mut filtered_words = vocabulary_state.filter_known_words(&combined_words);
+
+    if reading_state.rank_by_novelty() {
+        return reading_state.rank_display_words(&filtered_words);
+    }
     
     // Sort by timestamp: words with timestamps (manual) come first, sorted newest to oldest
     // Words without timestamps (from API) come after
@@ -70,7 +75,7 @@ pub fn track_word_encounters(
     let mut promoted_words = Vec::new();
     
     for word_meaning in words {
-        if let Ok((_count, promoted)) = vocab_state.add_word_encounter(&word_meaning.word) {
+        if let Ok((_count, promoted)) = vocab_state.add_word_encounter_in(&word_meaning.word, sentence_key) {
             if promoted {
                 promoted_words.push(word_meaning.word.clone());
             }
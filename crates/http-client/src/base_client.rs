@@ -1,24 +1,107 @@
 use glossia_shared::AppError;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::de::DeserializeOwned;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::time::Duration;
 use tracing::{info, warn, error, debug, instrument};
-use uuid::Uuid;
+use crate::request_id::{RequestId, REQUEST_ID_HEADER};
+
+/// Largest response body read before giving up, unless configured otherwise
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 8 * 1024 * 1024;
+
+/// Whether request bodies are logged unless configured otherwise: only in debug
+/// builds, since bodies carry the user's text
+pub const DEFAULT_LOG_BODIES: bool = cfg!(debug_assertions);
+
+/// Idle connections kept open per host unless configured otherwise
+pub const DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 8;
+
+/// How long an idle pooled connection is kept unless configured otherwise
+pub const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Keep-alive connection pool settings applied to the underlying `reqwest::Client`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolConfig {
+    pub max_idle_per_host: usize,
+    /// `None` keeps idle connections until the server closes them
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: DEFAULT_POOL_MAX_IDLE_PER_HOST,
+            idle_timeout: Some(DEFAULT_POOL_IDLE_TIMEOUT),
+        }
+    }
+}
 
 /// Base HTTP client with configurable headers and timeouts
 pub struct BaseHttpClient {
     client: reqwest::Client,
+    max_response_bytes: usize,
+    log_bodies: bool,
+    pool: PoolConfig,
 }
 
 impl BaseHttpClient {
     pub fn new() -> Result<Self, AppError> {
+        Self::with_settings(HashMap::new(), Duration::from_secs(30))
+    }
+
+    /// Create a client with default headers and timeout applied together
+    pub fn with_settings(headers: HashMap<String, String>, timeout: Duration) -> Result<Self, AppError> {
+        Self::with_pool_settings(headers, timeout, PoolConfig::default())
+    }
+
+    /// Like `with_settings`, also configuring the connection pool
+    pub fn with_pool_settings(headers: HashMap<String, String>, timeout: Duration, pool: PoolConfig) -> Result<Self, AppError> {
+        let mut header_map = HeaderMap::new();
+
+        for (key, value) in headers {
+            if let (Ok(header_name), Ok(header_value)) = (
+                HeaderName::from_bytes(key.as_bytes()),
+                HeaderValue::from_str(&value)
+            ) {
+                header_map.insert(header_name, header_value);
+            }
+        }
+
         let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
+            .default_headers(header_map)
+            .timeout(timeout)
+            .pool_max_idle_per_host(pool.max_idle_per_host)
+            .pool_idle_timeout(pool.idle_timeout)
             .build()
             .map_err(|e| AppError::config_error(format!("Failed to create HTTP client: {e}")))?;
 
-        Ok(Self { client })
+        Ok(Self { client, max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES, log_bodies: DEFAULT_LOG_BODIES, pool })
+    }
+
+    pub fn pool_config(&self) -> PoolConfig {
+        self.pool
+    }
+
+    /// Abort reading any response body larger than `max_bytes`
+    pub fn with_max_response_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_response_bytes = max_bytes;
+        self
+    }
+
+    pub fn max_response_bytes(&self) -> usize {
+        self.max_response_bytes
+    }
+
+    /// Log full request bodies at debug level; when false only their size and hash are logged
+    pub fn with_log_bodies(mut self, log_bodies: bool) -> Self {
+        self.log_bodies = log_bodies;
+        self
+    }
+
+    pub fn log_bodies(&self) -> bool {
+        self.log_bodies
     }
 
     pub fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
@@ -56,18 +139,27 @@ impl BaseHttpClient {
         self
     }
 
-    #[instrument(skip(self), fields(request_id = %Uuid::new_v4()))]
     pub async fn get(&self, url: &str) -> Result<reqwest::Response, AppError> {
+        self.get_with_headers(url, &HashMap::new()).await
+    }
+
+    /// GET with `extra_headers` added over the client's default headers for this request only
+    #[instrument(skip(self, extra_headers), fields(request_id = tracing::field::Empty))]
+    pub async fn get_with_headers(&self, url: &str, extra_headers: &HashMap<String, String>) -> Result<reqwest::Response, AppError> {
         info!("Making GET request to: {}", url);
+        let extra_headers = extra_header_map(extra_headers)?;
+        let request_id = span_request_id();
         let start_time = std::time::Instant::now();
         
         let response = self.client
             .get(url)
+            .headers(extra_headers)
+            .header(REQUEST_ID_HEADER, request_id.as_str())
             .send()
             .await
             .map_err(|e| {
                 error!("GET request failed: {}", e);
-                AppError::NetworkError { message: e.to_string() }
+                AppError::from(e)
             })?;
 
         let status = response.status();
@@ -82,20 +174,40 @@ impl BaseHttpClient {
         self.handle_response_status(response).await
     }
 
-    #[instrument(skip(self, body), fields(request_id = %Uuid::new_v4(), body_size = body.to_string().len()))]
     pub async fn post(&self, url: &str, body: serde_json::Value) -> Result<reqwest::Response, AppError> {
+        self.post_with_timeout(url, body, None).await
+    }
+
+    /// POST with an optional timeout overriding the client-wide one for this request only
+    pub async fn post_with_timeout(&self, url: &str, body: serde_json::Value, timeout: Option<Duration>) -> Result<reqwest::Response, AppError> {
+        self.post_with_headers(url, body, &HashMap::new(), timeout).await
+    }
+
+    /// POST with `extra_headers` added over the client's default headers, and
+    /// optionally a different timeout, for this request only
+    #[instrument(skip(self, body, extra_headers), fields(request_id = tracing::field::Empty, body_size = body.to_string().len()))]
+    pub async fn post_with_headers(&self, url: &str, body: serde_json::Value, extra_headers: &HashMap<String, String>, timeout: Option<Duration>) -> Result<reqwest::Response, AppError> {
         info!("Making POST request to: {}", url);
-        debug!("POST body: {}", serde_json::to_string_pretty(&body).unwrap_or_else(|_| "Invalid JSON".to_string()));
+        debug!("POST body: {}", describe_body(&body, self.log_bodies));
+        let extra_headers = extra_header_map(extra_headers)?;
+        let request_id = span_request_id();
         let start_time = std::time::Instant::now();
         
-        let response = self.client
+        let mut request = self.client
             .post(url)
-            .json(&body)
+            .headers(extra_headers)
+            .header(REQUEST_ID_HEADER, request_id.as_str())
+            .json(&body);
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+
+        let response = request
             .send()
             .await
             .map_err(|e| {
                 error!("POST request failed: {}", e);
-                AppError::NetworkError { message: e.to_string() }
+                AppError::from(e)
             })?;
 
         let status = response.status();
@@ -110,19 +222,21 @@ impl BaseHttpClient {
         self.handle_response_status(response).await
     }
 
-    #[instrument(skip(self, body), fields(request_id = %Uuid::new_v4(), body_size = body.to_string().len()))]
+    #[instrument(skip(self, body), fields(request_id = tracing::field::Empty, body_size = body.to_string().len()))]
     pub async fn put(&self, url: &str, body: serde_json::Value) -> Result<reqwest::Response, AppError> {
         info!("Making PUT request to: {}", url);
-        debug!("PUT body: {}", serde_json::to_string_pretty(&body).unwrap_or_else(|_| "Invalid JSON".to_string()));
+        debug!("PUT body: {}", describe_body(&body, self.log_bodies));
+        let request_id = span_request_id();
         let start_time = std::time::Instant::now();
         let response = self.client
             .put(url)
+            .header(REQUEST_ID_HEADER, request_id.as_str())
             .json(&body)
             .send()
             .await
             .map_err(|e| {
                 error!("PUT request failed: {}", e);
-                AppError::NetworkError { message: e.to_string() }
+                AppError::from(e)
             })?;
 
         let status = response.status();
@@ -137,17 +251,19 @@ impl BaseHttpClient {
         self.handle_response_status(response).await
     }
 
-    #[instrument(skip(self), fields(request_id = %Uuid::new_v4()))]
+    #[instrument(skip(self), fields(request_id = tracing::field::Empty))]
     pub async fn delete(&self, url: &str) -> Result<reqwest::Response, AppError> {
         info!("Making DELETE request to: {}", url);
+        let request_id = span_request_id();
         let start_time = std::time::Instant::now();
         let response = self.client
             .delete(url)
+            .header(REQUEST_ID_HEADER, request_id.as_str())
             .send()
             .await
             .map_err(|e| {
                 error!("DELETE request failed: {}", e);
-                AppError::NetworkError { message: e.to_string() }
+                AppError::from(e)
             })?;
 
         let status = response.status();
@@ -167,11 +283,7 @@ impl BaseHttpClient {
         let content_length = response.content_length();
         debug!("Parsing JSON response, content length: {:?}", content_length);
         
-        let text = response.text().await
-            .map_err(|e| {
-                error!("Failed to read response text: {}", e);
-                AppError::NetworkError { message: e.to_string() }
-            })?;
+        let text = self.read_body(response).await?;
 
         debug!("Response text length: {} bytes", text.len());
         
@@ -184,6 +296,32 @@ impl BaseHttpClient {
             })
     }
 
+    /// Read the body chunk by chunk, failing as soon as it exceeds `max_response_bytes`
+    async fn read_body(&self, mut response: reqwest::Response) -> Result<String, AppError> {
+        let too_large = || AppError::api_error(format!(
+            "Response body exceeds the {} byte limit", self.max_response_bytes
+        ));
+
+        if response.content_length().is_some_and(|length| length > self.max_response_bytes as u64) {
+            error!("Response declared {:?} bytes, over the limit", response.content_length());
+            return Err(too_large());
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| {
+            error!("Failed to read response text: {}", e);
+            AppError::from(e)
+        })? {
+            if body.len() + chunk.len() > self.max_response_bytes {
+                error!("Response body exceeded {} bytes, aborting", self.max_response_bytes);
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
     async fn handle_response_status(&self, response: reqwest::Response) -> Result<reqwest::Response, AppError> {
         let status = response.status();
         
@@ -199,7 +337,7 @@ impl BaseHttpClient {
                 })
                 .collect();
 
-            let error_body = response.text().await
+            let error_body = self.read_body(response).await
                 .unwrap_or_else(|_| "Failed to read error response".to_string());
             
             // Try to parse OpenAI/API-specific error format
@@ -256,6 +394,11 @@ impl BaseHttpClient {
                         error_code,
                     ))
                 }
+                429 if error_code.as_deref() == Some("insufficient_quota")
+                    || error_type.as_deref() == Some("insufficient_quota") => {
+                    error!("Quota exceeded: {} (type: {:?}, code: {:?})", error_message, error_type, error_code);
+                    Err(AppError::quota_exceeded_error(error_message))
+                }
                 429 => {
                     let retry_after = headers.get("retry-after")
                         .and_then(|h| h.parse().ok());
@@ -289,8 +432,216 @@ impl BaseHttpClient {
     }
 }
 
+impl BaseHttpClient {
+    /// Fallible counterpart of `Default`, for callers that must not panic
+    pub fn try_default() -> Result<Self, AppError> {
+        Self::new()
+    }
+}
+
+/// Panics if the HTTP client can't be built; use `try_default` to handle the error
 impl Default for BaseHttpClient {
     fn default() -> Self {
         Self::new().expect("Failed to create BaseHttpClient")
     }
 }
+
+/// The operation's request ID (or a fresh one), recorded on the current span
+fn span_request_id() -> RequestId {
+    let request_id = RequestId::current_or_new();
+    request_id.record_in_current_span();
+    request_id
+}
+
+/// Body text for a debug log line: the pretty JSON, or just its size and hash when bodies are omitted
+fn describe_body(body: &serde_json::Value, log_bodies: bool) -> String {
+    if log_bodies {
+        return serde_json::to_string_pretty(body).unwrap_or_else(|_| "Invalid JSON".to_string());
+    }
+    let text = body.to_string();
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    format!("<omitted: {} bytes, hash {:016x}>", text.len(), hasher.finish())
+}
+
+/// Per-request headers; unlike the client-wide ones, invalid names or values are an error
+fn extra_header_map(headers: &HashMap<String, String>) -> Result<HeaderMap, AppError> {
+    let mut header_map = HeaderMap::new();
+    for (key, value) in headers {
+        let name = HeaderName::from_bytes(key.as_bytes())
+            .map_err(|_| AppError::config_error(format!("Invalid header name: {key}")))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|_| AppError::config_error(format!("Invalid value for header {key}")))?;
+        header_map.insert(name, value);
+    }
+    Ok(header_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serve `body` as a JSON response, optionally without a Content-Length header
+    async fn spawn_body_server(body: String, declare_length: bool) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let body = body.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let _ = socket.read(&mut buf).await;
+                    let length = if declare_length { format!("Content-Length: {}\r\n", body.len()) } else { String::new() };
+                    let head = format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n{length}Connection: close\r\n\r\n");
+                    let _ = socket.write_all(head.as_bytes()).await;
+                    let _ = socket.write_all(body.as_bytes()).await;
+                });
+            }
+        });
+
+        format!("http://{addr}")
+    }
+
+    fn json_of_size(bytes: usize) -> String {
+        format!("{{\"data\":\"{}\"}}", "x".repeat(bytes - 11))
+    }
+
+    #[tokio::test]
+    async fn test_response_under_limit_parses() {
+        let url = spawn_body_server(json_of_size(1000), true).await;
+        let client = BaseHttpClient::new().unwrap().with_max_response_bytes(1000);
+
+        let response = client.get(&url).await.unwrap();
+        let value: serde_json::Value = client.parse_json_response(response).await.unwrap();
+        assert_eq!(value["data"].as_str().unwrap().len(), 989);
+    }
+
+    #[tokio::test]
+    async fn test_response_over_limit_errors() {
+        let client = BaseHttpClient::new().unwrap().with_max_response_bytes(1000);
+
+        // Rejected up front from Content-Length, and while streaming when the length is unknown
+        for declare_length in [true, false] {
+            let url = spawn_body_server(json_of_size(50_000), declare_length).await;
+            let response = client.get(&url).await.unwrap();
+            let error = client.parse_json_response::<serde_json::Value>(response).await.unwrap_err();
+            assert!(error.to_string().contains("1000 byte limit"), "{error}");
+        }
+    }
+
+    fn rate_limit_body(error_type: &str, code: &str) -> serde_json::Value {
+        serde_json::json!({
+            "error": {
+                "message": "Request rejected",
+                "type": error_type,
+                "code": code
+            }
+        })
+    }
+
+    #[test]
+    fn test_429_rate_limit_is_retryable() {
+        let client = BaseHttpClient::new().unwrap();
+        let body = rate_limit_body("requests", "rate_limit_exceeded");
+
+        let error = client
+            .create_structured_error(429, &body, HashMap::new(), body.to_string())
+            .unwrap_err();
+
+        assert!(matches!(error, AppError::RateLimitError { .. }));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_429_insufficient_quota_is_not_retryable() {
+        let client = BaseHttpClient::new().unwrap();
+        let body = rate_limit_body("insufficient_quota", "insufficient_quota");
+
+        let error = client
+            .create_structured_error(429, &body, HashMap::new(), body.to_string())
+            .unwrap_err();
+
+        assert!(matches!(error, AppError::QuotaExceededError { .. }));
+        assert!(!error.is_retryable());
+        assert!(error.user_friendly_message().contains("billing"));
+    }
+
+    #[test]
+    fn test_omitted_body_logs_size_and_hash_only() {
+        let body = serde_json::json!({ "messages": [{ "content": "My private diary entry." }] });
+
+        let line = describe_body(&body, false);
+        assert!(line.contains(&format!("{} bytes", body.to_string().len())));
+        assert!(line.contains("hash "));
+        assert!(!line.contains("private diary"));
+        assert_eq!(line, describe_body(&body, false));
+
+        assert!(describe_body(&body, true).contains("My private diary entry."));
+    }
+
+    /// Answer with `{}` and pass the raw request text back, headers included
+    async fn spawn_echo_server() -> (String, tokio::sync::mpsc::UnboundedReceiver<String>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let read = socket.read(&mut buf).await.unwrap_or(0);
+                let _ = sender.send(String::from_utf8_lossy(&buf[..read]).into_owned());
+                let _ = socket
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}")
+                    .await;
+            }
+        });
+
+        (format!("http://{addr}"), receiver)
+    }
+
+    /// Collects every value recorded for a `request_id` span field
+    #[derive(Clone, Default)]
+    struct RequestIdFields(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl tracing::field::Visit for RequestIdFields {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "request_id" {
+                self.0.lock().unwrap().push(format!("{value:?}"));
+            }
+        }
+    }
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for RequestIdFields {
+        fn on_record(&self, _span: &tracing::span::Id, values: &tracing::span::Record<'_>, _ctx: tracing_subscriber::layer::Context<'_, S>) {
+            values.record(&mut self.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_id_is_sent_and_recorded_on_span() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let fields = RequestIdFields::default();
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(fields.clone()));
+        let (url, mut requests) = spawn_echo_server().await;
+        let client = BaseHttpClient::new().unwrap();
+
+        let request_id = RequestId::new();
+        request_id
+            .clone()
+            .scope(async {
+                client.post(&url, serde_json::json!({ "a": 1 })).await.unwrap();
+                client.get(&url).await.unwrap();
+            })
+            .await;
+
+        let header = format!("{}: {}", REQUEST_ID_HEADER.to_lowercase(), request_id);
+        for _ in 0..2 {
+            let request = requests.recv().await.unwrap().to_lowercase();
+            assert!(request.contains(&header), "{request}");
+        }
+        assert_eq!(*fields.0.lock().unwrap(), vec![request_id.to_string(); 2]);
+    }
+}
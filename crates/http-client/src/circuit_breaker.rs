@@ -1,11 +1,13 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use std::time::{Duration, Instant};
 use glossia_shared::AppError;
+use serde::Serialize;
 use tracing::{instrument, info, warn, debug};
 
 /// Circuit breaker states
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum CircuitState {
     Closed,
     Open,
@@ -106,8 +108,11 @@ impl CircuitBreaker {
             CircuitState::HalfOpen => {
                 let mut success_count = self.success_count.write().await;
                 *success_count += 1;
-                
-                if *success_count >= self.config.success_threshold {
+                let reached_threshold = *success_count >= self.config.success_threshold;
+                // transition_to_closed takes this lock again
+                drop(success_count);
+
+                if reached_threshold {
                     self.transition_to_closed().await;
                 }
             }
@@ -177,6 +182,80 @@ impl CircuitBreaker {
     pub async fn get_state(&self) -> CircuitState {
         self.state.read().await.clone()
     }
+
+    /// Close the breaker immediately and forget past failures, e.g. after the
+    /// user has fixed their credentials
+    pub async fn reset(&self) {
+        *self.last_failure_time.write().await = None;
+        self.transition_to_closed().await;
+    }
+}
+
+/// One circuit breaker per host, so failures against one provider don't block another
+#[derive(Debug)]
+pub struct PerHostCircuitBreaker {
+    config: CircuitBreakerConfig,
+    breakers: std::sync::Mutex<HashMap<String, Arc<CircuitBreaker>>>,
+}
+
+impl PerHostCircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            breakers: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Breaker for the host (and port) of `url`, created on first use
+    pub fn for_url(&self, url: &str) -> Arc<CircuitBreaker> {
+        let mut breakers = self.breakers.lock().unwrap();
+        breakers
+            .entry(host_key(url))
+            .or_insert_with(|| Arc::new(CircuitBreaker::new(self.config.clone())))
+            .clone()
+    }
+
+    /// State of the breaker for `url`'s host; hosts never called are closed
+    pub async fn state_for(&self, url: &str) -> CircuitState {
+        let breaker = self.breakers.lock().unwrap().get(&host_key(url)).cloned();
+        match breaker {
+            Some(breaker) => breaker.get_state().await,
+            None => CircuitState::Closed,
+        }
+    }
+
+    /// Reset the breakers of every host
+    pub async fn reset_all(&self) {
+        let breakers: Vec<Arc<CircuitBreaker>> = self.breakers.lock().unwrap().values().cloned().collect();
+        for breaker in breakers {
+            breaker.reset().await;
+        }
+    }
+
+    /// Worst state across all hosts: open if any host is open, then half-open, else closed
+    pub async fn overall_state(&self) -> CircuitState {
+        let breakers: Vec<Arc<CircuitBreaker>> = self.breakers.lock().unwrap().values().cloned().collect();
+        let mut overall = CircuitState::Closed;
+        for breaker in breakers {
+            match breaker.get_state().await {
+                CircuitState::Open => return CircuitState::Open,
+                CircuitState::HalfOpen => overall = CircuitState::HalfOpen,
+                CircuitState::Closed => {}
+            }
+        }
+        overall
+    }
+}
+
+fn host_key(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) => match (parsed.host_str(), parsed.port_or_known_default()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            _ => url.to_string(),
+        },
+        Err(_) => url.to_string(),
+    }
 }
 
 #[cfg(test)]
@@ -195,14 +274,14 @@ mod tests {
 
         // First failure
         let result = circuit_breaker.call(|| async {
-            Err(AppError::HttpError { status: 401, message: "Unauthorized".to_string() })
+            Err::<(), _>(AppError::HttpError { status: 401, message: "Unauthorized".to_string(), headers: None, body: None })
         }).await;
         assert!(result.is_err());
         assert_eq!(circuit_breaker.get_state().await, CircuitState::Closed);
 
         // Second failure - should open circuit
         let result = circuit_breaker.call(|| async {
-            Err(AppError::HttpError { status: 401, message: "Unauthorized".to_string() })
+            Err::<(), _>(AppError::HttpError { status: 401, message: "Unauthorized".to_string(), headers: None, body: None })
         }).await;
         assert!(result.is_err());
         assert_eq!(circuit_breaker.get_state().await, CircuitState::Open);
@@ -226,7 +305,7 @@ mod tests {
 
         // Trigger circuit breaker
         let _result = circuit_breaker.call(|| async {
-            Err(AppError::HttpError { status: 401, message: "Unauthorized".to_string() })
+            Err::<(), _>(AppError::HttpError { status: 401, message: "Unauthorized".to_string(), headers: None, body: None })
         }).await;
         assert_eq!(circuit_breaker.get_state().await, CircuitState::Open);
 
@@ -240,4 +319,46 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(circuit_breaker.get_state().await, CircuitState::Closed);
     }
+
+    #[tokio::test]
+    async fn test_reset_closes_open_breaker() {
+        let circuit_breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            recovery_timeout: Duration::from_secs(60),
+            success_threshold: 1,
+        });
+
+        let _result = circuit_breaker.call(|| async {
+            Err::<(), _>(AppError::HttpError { status: 401, message: "Unauthorized".to_string(), headers: None, body: None })
+        }).await;
+        assert_eq!(circuit_breaker.get_state().await, CircuitState::Open);
+
+        circuit_breaker.reset().await;
+        assert_eq!(circuit_breaker.get_state().await, CircuitState::Closed);
+
+        let result = circuit_breaker.call(|| async { Ok("proceeds") }).await;
+        assert_eq!(result.unwrap(), "proceeds");
+    }
+
+    #[tokio::test]
+    async fn test_structured_authentication_error_does_not_trip() {
+        let circuit_breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            recovery_timeout: Duration::from_secs(60),
+            success_threshold: 1,
+        });
+
+        let result = circuit_breaker.call(|| async {
+            Err::<(), _>(AppError::authentication_error("Invalid API key", Some(401), None, None))
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(circuit_breaker.get_state().await, CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_host_key_distinguishes_hosts_and_ports() {
+        assert_eq!(host_key("https://api.openai.com/v1/chat"), "api.openai.com:443");
+        assert_eq!(host_key("http://127.0.0.1:8080/a"), "127.0.0.1:8080");
+        assert_ne!(host_key("http://127.0.0.1:8080/a"), host_key("http://127.0.0.1:8081/a"));
+    }
 }
@@ -0,0 +1,142 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use glossia_shared::AppError;
+use tracing::{debug, info};
+
+use crate::{hash_request_body, HttpClient};
+
+/// A request that ended in an error, kept for debugging
+#[derive(Debug, Clone)]
+pub struct FailedRequest {
+    pub method: String,
+    pub url: String,
+    /// Serialized size of the request body in bytes; the body itself is not kept
+    pub body_size: Option<usize>,
+    /// `hash_request_body` of the request body, for matching failures to requests
+    pub body_hash: Option<String>,
+    pub error_category: &'static str,
+    pub error_message: String,
+    pub timestamp: SystemTime,
+}
+
+/// Bounded log of the most recent failed requests
+#[derive(Debug, Clone)]
+pub struct FailureLog {
+    failures: Arc<Mutex<VecDeque<FailedRequest>>>,
+    capacity: usize,
+}
+
+impl FailureLog {
+    pub fn new() -> Self {
+        Self::with_capacity(50)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            failures: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Record a failed request, evicting the oldest entry when full
+    pub fn record(&self, method: &str, url: &str, body: Option<&serde_json::Value>, error: &AppError) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut failures = self.failures.lock().unwrap();
+        if failures.len() == self.capacity {
+            failures.pop_front();
+        }
+
+        debug!("Recording failed request: {} {} ({})", method, url, error.category());
+
+        failures.push_back(FailedRequest {
+            method: method.to_string(),
+            url: url.to_string(),
+            body_size: body.map(|body| body.to_string().len()),
+            body_hash: body.map(hash_request_body),
+            error_category: error.category(),
+            error_message: error.to_string(),
+            timestamp: SystemTime::now(),
+        });
+    }
+
+    /// Get recorded failures, oldest first
+    pub fn recent_failures(&self) -> Vec<FailedRequest> {
+        self.failures.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Replay the most recent failed GET request using the given client
+    /// Returns `None` when no GET failure has been recorded
+    pub async fn replay_last<C: HttpClient + ?Sized>(&self, client: &C) -> Option<Result<reqwest::Response, AppError>> {
+        let url = {
+            let failures = self.failures.lock().unwrap();
+            failures
+                .iter()
+                .rev()
+                .find(|failure| failure.method == "GET")
+                .map(|failure| failure.url.clone())?
+        };
+
+        info!("Replaying failed GET request: {}", url);
+        Some(client.get(&url).await)
+    }
+
+    pub fn len(&self) -> usize {
+        self.failures.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clear all recorded failures
+    pub fn clear(&self) {
+        self.failures.lock().unwrap().clear();
+    }
+}
+
+impl Default for FailureLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_request_is_logged() {
+        let log = FailureLog::new();
+        let error = AppError::api_error("boom");
+
+        log.record("GET", "https://api.example.com/test", None, &error);
+
+        let failures = log.recent_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].method, "GET");
+        assert_eq!(failures[0].url, "https://api.example.com/test");
+        assert_eq!(failures[0].error_category, error.category());
+    }
+
+    #[test]
+    fn test_failure_log_caps_at_capacity() {
+        let log = FailureLog::with_capacity(2);
+        let error = AppError::api_error("boom");
+
+        log.record("GET", "https://api.example.com/1", None, &error);
+        log.record("GET", "https://api.example.com/2", None, &error);
+        log.record("POST", "https://api.example.com/3", Some(&serde_json::json!({"a": 1})), &error);
+
+        let failures = log.recent_failures();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].url, "https://api.example.com/2");
+        assert_eq!(failures[1].url, "https://api.example.com/3");
+        assert_eq!(failures[1].body_size, Some(7));
+        assert_eq!(failures[1].body_hash, Some(hash_request_body(&serde_json::json!({"a": 1}))));
+        assert_eq!(failures[0].body_hash, None);
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Latencies remembered per host; older samples roll off
+pub const DEFAULT_LATENCY_WINDOW: usize = 100;
+
+/// Fewest samples before `LatencyStats::recommended_timeout` is trusted
+const MIN_SAMPLES_FOR_RECOMMENDATION: usize = 5;
+/// Headroom applied to p95 when recommending a timeout
+const TIMEOUT_HEADROOM: u32 = 3;
+/// Recommended timeouts never go below this
+const MIN_RECOMMENDED_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Percentiles over the recent latencies of one host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub samples: usize,
+    pub p50: Duration,
+    pub p95: Duration,
+}
+
+impl LatencyStats {
+    /// Nearest-rank percentiles of `samples`, or `None` when there are none
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        Some(Self {
+            samples: sorted.len(),
+            p50: percentile(&sorted, 50),
+            p95: percentile(&sorted, 95),
+        })
+    }
+
+    /// Timeout with headroom over p95, once enough samples have been seen
+    pub fn recommended_timeout(&self) -> Option<Duration> {
+        (self.samples >= MIN_SAMPLES_FOR_RECOMMENDATION)
+            .then(|| (self.p95 * TIMEOUT_HEADROOM).max(MIN_RECOMMENDED_TIMEOUT))
+    }
+}
+
+fn percentile(sorted: &[Duration], pct: usize) -> Duration {
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Rolling window of request latencies per host
+#[derive(Debug)]
+pub struct LatencyTracker {
+    window: usize,
+    samples: Mutex<HashMap<String, VecDeque<Duration>>>,
+}
+
+impl LatencyTracker {
+    pub fn new() -> Self {
+        Self::with_window(DEFAULT_LATENCY_WINDOW)
+    }
+
+    pub fn with_window(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            samples: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record how long a request to `url` took
+    pub fn record(&self, url: &str, latency: Duration) {
+        let mut samples = self.samples.lock().unwrap();
+        let host_samples = samples.entry(host_name(url)).or_default();
+        if host_samples.len() == self.window {
+            host_samples.pop_front();
+        }
+        host_samples.push_back(latency);
+    }
+
+    /// Percentiles for `host`, which may also be given as a full URL
+    pub fn stats(&self, host: &str) -> Option<LatencyStats> {
+        let samples = self.samples.lock().unwrap();
+        let host_samples: Vec<Duration> = samples.get(&host_name(host))?.iter().copied().collect();
+        LatencyStats::from_samples(&host_samples)
+    }
+}
+
+impl Default for LatencyTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Host name of `url`, or the input itself when it is already a bare host
+pub(crate) fn host_name(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn test_percentiles_use_nearest_rank() {
+        let samples = millis(&(1..=100).collect::<Vec<_>>());
+        let stats = LatencyStats::from_samples(&samples).unwrap();
+        assert_eq!(stats.p50, Duration::from_millis(50));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+
+        let stats = LatencyStats::from_samples(&millis(&[300, 100, 200])).unwrap();
+        assert_eq!(stats.p50, Duration::from_millis(200));
+        assert_eq!(stats.p95, Duration::from_millis(300));
+        assert!(LatencyStats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_tracker_keys_by_host_and_rolls_window() {
+        let tracker = LatencyTracker::with_window(3);
+        for ms in [1000, 10, 20, 30] {
+            tracker.record("https://api.openai.com/v1/chat/completions", Duration::from_millis(ms));
+        }
+        tracker.record("https://api.search.brave.com/res/v1/images/search", Duration::from_millis(500));
+
+        let stats = tracker.stats("api.openai.com").unwrap();
+        assert_eq!(stats.samples, 3);
+        assert_eq!(stats.p95, Duration::from_millis(30));
+        assert_eq!(tracker.stats("https://api.search.brave.com/x").unwrap().p50, Duration::from_millis(500));
+        assert!(tracker.stats("example.com").is_none());
+    }
+
+    #[test]
+    fn test_recommended_timeout_needs_samples_and_has_a_floor() {
+        let few = LatencyStats::from_samples(&millis(&[4000, 4000])).unwrap();
+        assert_eq!(few.recommended_timeout(), None);
+
+        let slow = LatencyStats::from_samples(&millis(&[1000, 2000, 3000, 4000, 5000])).unwrap();
+        assert_eq!(slow.recommended_timeout(), Some(Duration::from_secs(15)));
+
+        let fast = LatencyStats::from_samples(&millis(&[10, 20, 30, 40, 50])).unwrap();
+        assert_eq!(fast.recommended_timeout(), Some(MIN_RECOMMENDED_TIMEOUT));
+    }
+}
@@ -1,25 +1,42 @@
 mod base_client;
 mod retry_service;
+mod retry_budget;
 mod rate_limiter;
 mod circuit_breaker;
 mod request_tracker;
+mod failure_log;
+mod latency_tracker;
+mod single_flight;
+mod request_id;
 
-pub use base_client::BaseHttpClient;
-pub use retry_service::{RetryService, RetryConfig};
-pub use rate_limiter::RateLimiter;
-pub use request_tracker::{RequestTracker, RequestTrackingResult, RequestStats, hash_request_body};
-pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+pub use base_client::{BaseHttpClient, PoolConfig, DEFAULT_LOG_BODIES, DEFAULT_MAX_RESPONSE_BYTES, DEFAULT_POOL_IDLE_TIMEOUT, DEFAULT_POOL_MAX_IDLE_PER_HOST};
+pub use retry_service::{RetryService, RetryConfig, BackoffStrategy, DEFAULT_JITTER_RANGE};
+pub use retry_budget::RetryBudget;
+pub use request_id::{RequestId, REQUEST_ID_HEADER};
+pub use rate_limiter::{RateLimiter, Priority, WaitStats};
+pub use request_tracker::{DuplicatePolicy, RequestTracker, RequestTrackingResult, RequestStats, hash_request_body, hash_request_body_ignoring};
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState, PerHostCircuitBreaker};
+pub use failure_log::{FailureLog, FailedRequest};
+pub use latency_tracker::{LatencyStats, LatencyTracker, DEFAULT_LATENCY_WINDOW};
 
 use glossia_shared::AppError;
+use latency_tracker::host_name;
+use single_flight::SingleFlight;
 use async_trait::async_trait;
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Trait for HTTP client implementations
 #[async_trait]
 pub trait HttpClient: Send + Sync {
     async fn get(&self, url: &str) -> Result<reqwest::Response, AppError>;
     async fn post(&self, url: &str, body: serde_json::Value) -> Result<reqwest::Response, AppError>;
+    /// GET with `extra_headers` merged over the default headers for this request only
+    async fn get_with_headers(&self, url: &str, extra_headers: &HashMap<String, String>) -> Result<reqwest::Response, AppError>;
+    /// POST with `extra_headers` (e.g. an idempotency key) merged over the default headers for this request only
+    async fn post_with_headers(&self, url: &str, body: serde_json::Value, extra_headers: &HashMap<String, String>) -> Result<reqwest::Response, AppError>;
     async fn put(&self, url: &str, body: serde_json::Value) -> Result<reqwest::Response, AppError>;
     async fn delete(&self, url: &str) -> Result<reqwest::Response, AppError>;
     
@@ -32,30 +49,45 @@ pub trait HttpClient: Send + Sync {
 pub struct EnhancedHttpClient {
     base_client: BaseHttpClient,
     retry_service: RetryService,
-    rate_limiter: RateLimiter,
+    rate_limiter: Arc<RateLimiter>,
+    /// Limiters set at runtime for single hosts, replacing `rate_limiter` for them
+    host_rate_limiters: std::sync::Mutex<HashMap<String, Arc<RateLimiter>>>,
     request_tracker: RequestTracker,
-    circuit_breaker: CircuitBreaker,
+    circuit_breaker: Arc<CircuitBreaker>,
+    per_host_circuit_breaker: Option<PerHostCircuitBreaker>,
+    failure_log: FailureLog,
+    latency_tracker: LatencyTracker,
+    duplicate_policy: DuplicatePolicy,
+    single_flight: SingleFlight,
+    /// Body fields left out when deciding whether two requests are duplicates
+    volatile_fields: Vec<String>,
 }
 
 impl EnhancedHttpClient {
     pub fn new() -> Result<Self, AppError> {
-        Ok(Self {
-            base_client: BaseHttpClient::new()?,
-            retry_service: RetryService::new(RetryConfig::default()),
-            rate_limiter: RateLimiter::new(10, std::time::Duration::from_secs(1)), // 10 requests per second
-            request_tracker: RequestTracker::new(),
-            circuit_breaker: CircuitBreaker::new(CircuitBreakerConfig::default()),
-        })
+        Self::builder().build()
     }
 
-    pub fn with_config(retry_config: RetryConfig, rate_limit: (usize, std::time::Duration)) -> Result<Self, AppError> {
-        Ok(Self {
-            base_client: BaseHttpClient::new()?,
-            retry_service: RetryService::new(retry_config),
-            rate_limiter: RateLimiter::new(rate_limit.0, rate_limit.1),
-            request_tracker: RequestTracker::new(),
-            circuit_breaker: CircuitBreaker::new(CircuitBreakerConfig::default()),
-        })
+    /// Connection pool settings the client was built with
+    pub fn pool_config(&self) -> PoolConfig {
+        self.base_client.pool_config()
+    }
+
+    pub fn with_config(retry_config: RetryConfig, rate_limit: (usize, Duration)) -> Result<Self, AppError> {
+        Self::builder()
+            .retry_config(retry_config)
+            .rate_limit(rate_limit.0, rate_limit.1)
+            .build()
+    }
+
+    /// Fallible counterpart of `Default`, for callers that must not panic
+    pub fn try_default() -> Result<Self, AppError> {
+        Self::new()
+    }
+
+    /// Start building a client with custom retry, rate limit, circuit breaker and transport settings
+    pub fn builder() -> EnhancedHttpClientBuilder {
+        EnhancedHttpClientBuilder::new()
     }
 
     pub fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
@@ -68,59 +100,286 @@ impl EnhancedHttpClient {
         self
     }
 
+    /// Keep a separate circuit breaker per host instead of one shared by all requests
+    pub fn with_per_host_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.per_host_circuit_breaker = Some(PerHostCircuitBreaker::new(config));
+        self
+    }
+
+    /// Get the current state of the circuit breaker
+    /// With per-host breakers this is the worst state across hosts
+    pub async fn circuit_state(&self) -> CircuitState {
+        match &self.per_host_circuit_breaker {
+            Some(per_host) => per_host.overall_state().await,
+            None => self.circuit_breaker.get_state().await,
+        }
+    }
+
+    /// Close the circuit breaker (every host's, when per-host) so requests proceed immediately
+    pub async fn reset_circuit(&self) {
+        self.circuit_breaker.reset().await;
+        if let Some(per_host) = &self.per_host_circuit_breaker {
+            per_host.reset_all().await;
+        }
+    }
+
+    /// State of the circuit breaker that guards requests to `url`
+    pub async fn circuit_state_for(&self, url: &str) -> CircuitState {
+        match &self.per_host_circuit_breaker {
+            Some(per_host) => per_host.state_for(url).await,
+            None => self.circuit_breaker.get_state().await,
+        }
+    }
+
+    fn circuit_breaker_for(&self, url: &str) -> Arc<CircuitBreaker> {
+        match &self.per_host_circuit_breaker {
+            Some(per_host) => per_host.for_url(url),
+            None => self.circuit_breaker.clone(),
+        }
+    }
+
     /// Get request statistics for analyzing API usage patterns
     pub fn get_request_stats(&self) -> RequestStats {
         self.request_tracker.get_stats()
     }
 
+    /// Request and circuit breaker metrics in Prometheus text exposition format
+    pub async fn prometheus_metrics(&self) -> String {
+        let mut out = self.get_request_stats().to_prometheus();
+        let circuit_open = if self.circuit_state().await == CircuitState::Open { 1.0 } else { 0.0 };
+        request_tracker::write_metric(&mut out, "glossia_circuit_open", "gauge", "Whether the circuit breaker is rejecting requests", circuit_open);
+        request_tracker::write_metric(&mut out, "glossia_recent_failures", "gauge", "Failed requests kept in the failure log", self.recent_failures().len() as f64);
+        out
+    }
+
+    /// p50/p95 of recent request latencies to `host` (a host name or any URL on it)
+    pub fn latency_stats(&self, host: &str) -> Option<LatencyStats> {
+        self.latency_tracker.stats(host)
+    }
+
     /// Clear request tracking history
     pub fn clear_request_history(&self) {
         self.request_tracker.clear();
     }
-}
 
-#[async_trait]
-impl HttpClient for EnhancedHttpClient {
-    async fn get(&self, url: &str) -> Result<reqwest::Response, AppError> {
+    /// Get the most recent failed requests, oldest first
+    pub fn recent_failures(&self) -> Vec<FailedRequest> {
+        self.failure_log.recent_failures()
+    }
+
+    /// Replay the most recent failed GET request
+    pub async fn replay_last_failure(&self) -> Option<Result<reqwest::Response, AppError>> {
+        self.failure_log.replay_last(self).await
+    }
+
+    /// Only successful attempts count, so timeouts and error responses don't skew the percentiles
+    fn record_latency(&self, url: &str, started: std::time::Instant, response: &Result<reqwest::Response, AppError>) {
+        if response.is_ok() {
+            self.latency_tracker.record(url, started.elapsed());
+        }
+    }
+
+    fn record_failure<T>(&self, method: &str, url: &str, body: Option<&serde_json::Value>, result: Result<T, AppError>) -> Result<T, AppError> {
+        if let Err(error) = &result {
+            self.failure_log.record(method, url, body, error);
+        }
+        result
+    }
+
+    /// Allow `permits` requests per `window` to `host` (a host name or any URL on it)
+    /// from now on, without restarting. Requests already sent are unaffected and
+    /// queued ones keep their place. Fails if `permits` is zero or `window` is under 1ms.
+    pub async fn set_rate_limit(&self, host: &str, permits: usize, window: Duration) -> Result<(), AppError> {
+        let existing = self.host_rate_limiters.lock().unwrap().get(&host_name(host)).cloned();
+        match existing {
+            Some(limiter) => limiter.reconfigure(permits, window).await,
+            None => {
+                rate_limiter::validate_limit(permits, window)?;
+                let limiter = Arc::new(RateLimiter::new(permits, window));
+                self.host_rate_limiters.lock().unwrap().insert(host_name(host), limiter);
+                Ok(())
+            }
+        }
+    }
+
+    /// Time spent waiting for rate-limit permits, across the default and per-host limiters
+    pub fn rate_limit_wait_stats(&self) -> WaitStats {
+        self.host_rate_limiters
+            .lock()
+            .unwrap()
+            .values()
+            .fold(self.rate_limiter.wait_stats(), |stats, limiter| stats.merge(limiter.wait_stats()))
+    }
+
+    /// Permits `url`'s host can use right now
+    pub async fn available_permits(&self, url: &str) -> usize {
+        self.rate_limiter_for(url).available_tokens().await
+    }
+
+    fn rate_limiter_for(&self, url: &str) -> Arc<RateLimiter> {
+        self.host_rate_limiters
+            .lock()
+            .unwrap()
+            .get(&host_name(url))
+            .cloned()
+            .unwrap_or_else(|| self.rate_limiter.clone())
+    }
+
+    /// GET request whose rate-limit permit is granted according to `priority`
+    pub async fn get_with_priority(&self, url: &str, priority: Priority) -> Result<reqwest::Response, AppError> {
+        self.get_with_options(url, priority, &HashMap::new()).await
+    }
+
+    async fn get_with_options(&self, url: &str, priority: Priority, extra_headers: &HashMap<String, String>) -> Result<reqwest::Response, AppError> {
         // Track the request for duplicate detection
         let _tracking_result = self.request_tracker.track_request("GET", url, None);
         
-        self.rate_limiter.wait_for_permit().await;
+        self.rate_limiter_for(url).wait_for_permit_with_priority(priority).await;
         
-        self.retry_service.execute(|| async {
-            self.base_client.get(url).await
+        let result = self.retry_service.execute(|| async {
+            let started = std::time::Instant::now();
+            let response = self.base_client.get_with_headers(url, extra_headers).await;
+            self.record_latency(url, started, &response);
+            response
+        }).await;
+        self.record_failure("GET", url, None, result)
+    }
+
+    /// POST request whose rate-limit permit is granted according to `priority`
+    pub async fn post_with_priority(&self, url: &str, body: serde_json::Value, priority: Priority) -> Result<reqwest::Response, AppError> {
+        self.post_with_options(url, body, priority, None, &RetryBudget::unlimited(), &HashMap::new()).await
+    }
+
+    /// POST request with a timeout for this request only, instead of the client-wide one
+    pub async fn post_json_with_timeout<T: DeserializeOwned>(&self, url: &str, body: serde_json::Value, timeout: Duration) -> Result<T, AppError> {
+        self.post_json_with_budget(url, body, timeout, &RetryBudget::unlimited()).await
+    }
+
+    /// Like `post_json_with_timeout`, but every attempt, retries included, is drawn from `budget`
+    pub async fn post_json_with_budget<T: DeserializeOwned>(&self, url: &str, body: serde_json::Value, timeout: Duration, budget: &RetryBudget) -> Result<T, AppError> {
+        if self.duplicate_policy == DuplicatePolicy::SingleFlight {
+            return self.post_json_single_flight(url, body, Some(timeout), budget).await;
+        }
+
+        let response = self.post_with_options(url, body, Priority::Normal, Some(timeout), budget, &HashMap::new()).await?;
+        self.base_client.parse_json_response(response).await
+    }
+
+    /// Like `post_json_with_budget`, also reporting whether the response was shared
+    /// from an identical request already in flight rather than fetched by this call,
+    /// e.g. so per-call accounting isn't repeated for coalesced callers
+    pub async fn post_json_with_budget_shared(&self, url: &str, body: serde_json::Value, timeout: Duration, budget: &RetryBudget) -> Result<(serde_json::Value, bool), AppError> {
+        if self.duplicate_policy == DuplicatePolicy::SingleFlight {
+            let (value, shared) = self.post_value_single_flight(url, body, Some(timeout), budget).await;
+            return value.map(|value| (value, shared));
+        }
+
+        let value = self.post_json_with_budget(url, body, timeout, budget).await?;
+        Ok((value, false))
+    }
+
+    /// POST whose identical in-flight duplicates wait for this call and share its response
+    async fn post_json_single_flight<T: DeserializeOwned>(&self, url: &str, body: serde_json::Value, timeout: Option<Duration>, budget: &RetryBudget) -> Result<T, AppError> {
+        let value = self.post_value_single_flight(url, body, timeout, budget).await.0?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    async fn post_value_single_flight(&self, url: &str, body: serde_json::Value, timeout: Option<Duration>, budget: &RetryBudget) -> (Result<serde_json::Value, AppError>, bool) {
+        let key = format!("POST {url} {}", self.body_hash(&body));
+        self.single_flight.run(key, || async {
+            let response = self.post_with_options(url, body, Priority::Normal, timeout, budget, &HashMap::new()).await?;
+            self.base_client.parse_json_response::<serde_json::Value>(response).await
         }).await
     }
 
-    async fn post(&self, url: &str, body: serde_json::Value) -> Result<reqwest::Response, AppError> {
+    fn body_hash(&self, body: &serde_json::Value) -> String {
+        hash_request_body_ignoring(body, &self.volatile_fields)
+    }
+
+    async fn post_with_options(&self, url: &str, body: serde_json::Value, priority: Priority, timeout: Option<Duration>, budget: &RetryBudget, extra_headers: &HashMap<String, String>) -> Result<reqwest::Response, AppError> {
+        if budget.is_exhausted() {
+            return Err(AppError::api_error("Retry budget exhausted for this operation"));
+        }
+
         // Track the request for duplicate detection
-        let body_hash = Some(hash_request_body(&body));
+        let body_hash = Some(self.body_hash(&body));
         let _tracking_result = self.request_tracker.track_request("POST", url, body_hash);
         
-        self.rate_limiter.wait_for_permit().await;
+        self.rate_limiter_for(url).wait_for_permit_with_priority(priority).await;
         
         // Use circuit breaker to prevent cascading failures
-        self.circuit_breaker.call(|| async {
-            self.retry_service.execute(|| async {
-                self.base_client.post(url, body.clone()).await
+        let result = self.circuit_breaker_for(url).call(|| async {
+            self.retry_service.execute_with_budget(budget, || async {
+                let started = std::time::Instant::now();
+                let response = self.base_client.post_with_headers(url, body.clone(), extra_headers, timeout).await;
+                self.record_latency(url, started, &response);
+                response
             }).await
-        }).await
+        }).await;
+        self.record_failure("POST", url, Some(&body), result)
+    }
+
+    pub async fn get_json_with_priority<T: DeserializeOwned>(&self, url: &str, priority: Priority) -> Result<T, AppError> {
+        let response = self.get_with_priority(url, priority).await?;
+        self.base_client.parse_json_response(response).await
+    }
+
+    pub async fn post_json_with_priority<T: DeserializeOwned>(&self, url: &str, body: serde_json::Value, priority: Priority) -> Result<T, AppError> {
+        let response = self.post_with_priority(url, body, priority).await?;
+        self.base_client.parse_json_response(response).await
+    }
+
+    /// GET request that bypasses the rate limiter and retries
+    /// Intended only for health checks, which should not queue behind user traffic
+    pub async fn get_json_unlimited<T: DeserializeOwned>(&self, url: &str) -> Result<T, AppError> {
+        let result = self.base_client.get(url).await;
+        let response = self.record_failure("GET", url, None, result)?;
+        self.base_client.parse_json_response(response).await
+    }
+
+    /// POST request that bypasses the rate limiter and retries
+    /// Intended only for health checks, which should not queue behind user traffic
+    pub async fn post_json_unlimited<T: DeserializeOwned>(&self, url: &str, body: serde_json::Value) -> Result<T, AppError> {
+        let result = self.base_client.post(url, body.clone()).await;
+        let response = self.record_failure("POST", url, Some(&body), result)?;
+        self.base_client.parse_json_response(response).await
+    }
+}
+
+#[async_trait]
+impl HttpClient for EnhancedHttpClient {
+    async fn get(&self, url: &str) -> Result<reqwest::Response, AppError> {
+        self.get_with_priority(url, Priority::Normal).await
+    }
+
+    async fn post(&self, url: &str, body: serde_json::Value) -> Result<reqwest::Response, AppError> {
+        self.post_with_priority(url, body, Priority::Normal).await
+    }
+
+    async fn get_with_headers(&self, url: &str, extra_headers: &HashMap<String, String>) -> Result<reqwest::Response, AppError> {
+        self.get_with_options(url, Priority::Normal, extra_headers).await
+    }
+
+    async fn post_with_headers(&self, url: &str, body: serde_json::Value, extra_headers: &HashMap<String, String>) -> Result<reqwest::Response, AppError> {
+        self.post_with_options(url, body, Priority::Normal, None, &RetryBudget::unlimited(), extra_headers).await
     }
 
     async fn put(&self, url: &str, body: serde_json::Value) -> Result<reqwest::Response, AppError> {
-        self.rate_limiter.wait_for_permit().await;
+        self.rate_limiter_for(url).wait_for_permit().await;
         
-        self.retry_service.execute(|| async {
+        let result = self.retry_service.execute(|| async {
             self.base_client.put(url, body.clone()).await
-        }).await
+        }).await;
+        self.record_failure("PUT", url, Some(&body), result)
     }
 
     async fn delete(&self, url: &str) -> Result<reqwest::Response, AppError> {
-        self.rate_limiter.wait_for_permit().await;
+        self.rate_limiter_for(url).wait_for_permit().await;
         
-        self.retry_service.execute(|| async {
+        let result = self.retry_service.execute(|| async {
             self.base_client.delete(url).await
-        }).await
+        }).await;
+        self.record_failure("DELETE", url, None, result)
     }
 
     async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, AppError> {
@@ -129,13 +388,559 @@ impl HttpClient for EnhancedHttpClient {
     }
 
     async fn post_json<T: DeserializeOwned>(&self, url: &str, body: serde_json::Value) -> Result<T, AppError> {
+        if self.duplicate_policy == DuplicatePolicy::SingleFlight {
+            return self.post_json_single_flight(url, body, None, &RetryBudget::unlimited()).await;
+        }
+
         let response = self.post(url, body).await?;
         self.base_client.parse_json_response(response).await
     }
 }
 
+/// Panics if the HTTP client can't be built; use `try_default` to handle the error
 impl Default for EnhancedHttpClient {
     fn default() -> Self {
         Self::new().expect("Failed to create EnhancedHttpClient")
     }
 }
+
+/// Builder for a fully configured `EnhancedHttpClient`
+#[derive(Clone)]
+pub struct EnhancedHttpClientBuilder {
+    retry_config: RetryConfig,
+    rate_limit: (usize, Duration),
+    circuit_breaker_config: CircuitBreakerConfig,
+    per_host_circuit_breaker: bool,
+    request_cache_duration: Option<Duration>,
+    duplicate_policy: DuplicatePolicy,
+    volatile_fields: Vec<String>,
+    headers: HashMap<String, String>,
+    timeout: Duration,
+    max_response_bytes: usize,
+    log_bodies: bool,
+    pool: PoolConfig,
+}
+
+impl EnhancedHttpClientBuilder {
+    pub fn new() -> Self {
+        Self {
+            retry_config: RetryConfig::default(),
+            rate_limit: (10, Duration::from_secs(1)), // 10 requests per second
+            circuit_breaker_config: CircuitBreakerConfig::default(),
+            per_host_circuit_breaker: false,
+            request_cache_duration: None,
+            duplicate_policy: DuplicatePolicy::default(),
+            volatile_fields: Vec::new(),
+            headers: HashMap::new(),
+            timeout: Duration::from_secs(30),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            log_bodies: DEFAULT_LOG_BODIES,
+            pool: PoolConfig::default(),
+        }
+    }
+
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// Allow `max_requests` requests per `window`
+    pub fn rate_limit(mut self, max_requests: usize, window: Duration) -> Self {
+        self.rate_limit = (max_requests, window);
+        self
+    }
+
+    pub fn circuit_breaker_config(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker_config = config;
+        self
+    }
+
+    /// Use a separate circuit breaker per host, each configured with `config`
+    pub fn per_host_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker_config = config;
+        self.per_host_circuit_breaker = true;
+        self
+    }
+
+    /// How long requests are remembered for duplicate detection
+    pub fn request_cache_duration(mut self, duration: Duration) -> Self {
+        self.request_cache_duration = Some(duration);
+        self
+    }
+
+    /// How identical in-flight JSON POSTs are handled; warn-only by default
+    pub fn duplicate_policy(mut self, policy: DuplicatePolicy) -> Self {
+        self.duplicate_policy = policy;
+        self
+    }
+
+    /// Body fields (at any depth) ignored when detecting duplicate requests, so
+    /// requests differing only in e.g. a timestamp count as identical
+    pub fn volatile_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.volatile_fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Fail requests whose response body is larger than `max_bytes`
+    pub fn max_response_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_response_bytes = max_bytes;
+        self
+    }
+
+    /// Log full request bodies at debug level; defaults to `DEFAULT_LOG_BODIES`
+    pub fn log_bodies(mut self, log_bodies: bool) -> Self {
+        self.log_bodies = log_bodies;
+        self
+    }
+
+    /// Idle keep-alive connections kept per host; defaults to `DEFAULT_POOL_MAX_IDLE_PER_HOST`
+    pub fn pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.pool.max_idle_per_host = max_idle;
+        self
+    }
+
+    /// How long idle connections stay pooled; `None` keeps them until the server
+    /// closes them. Defaults to `DEFAULT_POOL_IDLE_TIMEOUT`
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.pool.idle_timeout = timeout;
+        self
+    }
+
+    pub fn build(self) -> Result<EnhancedHttpClient, AppError> {
+        let mut request_tracker = RequestTracker::new();
+        if let Some(duration) = self.request_cache_duration {
+            request_tracker = request_tracker.with_cache_duration(duration);
+        }
+
+        Ok(EnhancedHttpClient {
+            base_client: BaseHttpClient::with_pool_settings(self.headers, self.timeout, self.pool)?
+                .with_max_response_bytes(self.max_response_bytes)
+                .with_log_bodies(self.log_bodies),
+            retry_service: RetryService::try_new(self.retry_config)?,
+            rate_limiter: Arc::new(RateLimiter::new(self.rate_limit.0, self.rate_limit.1)),
+            host_rate_limiters: std::sync::Mutex::new(HashMap::new()),
+            request_tracker,
+            per_host_circuit_breaker: self.per_host_circuit_breaker
+                .then(|| PerHostCircuitBreaker::new(self.circuit_breaker_config.clone())),
+            circuit_breaker: Arc::new(CircuitBreaker::new(self.circuit_breaker_config)),
+            failure_log: FailureLog::new(),
+            latency_tracker: LatencyTracker::new(),
+            duplicate_policy: self.duplicate_policy,
+            single_flight: SingleFlight::new(),
+            volatile_fields: self.volatile_fields,
+        })
+    }
+}
+
+impl Default for EnhancedHttpClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serve a bare `401 Unauthorized` (no structured error body) to every
+    /// connection and return the base URL
+    async fn spawn_unauthorized_server() -> String {
+        spawn_json_server("401 Unauthorized", "Unauthorized").await
+    }
+
+    /// Serve the given status and JSON body to every connection and return the base URL
+    async fn spawn_json_server(status: &'static str, body: &'static str) -> String {
+        spawn_delayed_json_server(Duration::ZERO, status, body).await
+    }
+
+    /// Like `spawn_json_server`, but wait `delay` before responding
+    async fn spawn_delayed_json_server(delay: Duration, status: &'static str, body: &'static str) -> String {
+        spawn_recording_json_server(delay, status, body).await.0
+    }
+
+    /// Like `spawn_delayed_json_server`, also collecting the raw text of each request
+    async fn spawn_recording_json_server(
+        delay: Duration,
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    recorded.lock().unwrap().push(String::from_utf8_lossy(&buf[..n]).into_owned());
+                    tokio::time::sleep(delay).await;
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        (format!("http://{addr}"), requests)
+    }
+
+    #[tokio::test]
+    async fn test_builder_applies_circuit_breaker_threshold() {
+        let base_url = spawn_unauthorized_server().await;
+        let client = EnhancedHttpClient::builder()
+            .retry_config(RetryConfig { max_retries: 0, ..Default::default() })
+            .circuit_breaker_config(CircuitBreakerConfig {
+                failure_threshold: 2,
+                ..Default::default()
+            })
+            .request_cache_duration(Duration::from_secs(1))
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+
+        let url = format!("{base_url}/chat/completions");
+
+        assert!(client.post(&url, serde_json::json!({})).await.is_err());
+        assert_eq!(client.circuit_state().await, CircuitState::Closed);
+
+        assert!(client.post(&url, serde_json::json!({})).await.is_err());
+        assert_eq!(client.circuit_state().await, CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_per_host_circuit_breaker_isolates_hosts() {
+        let failing_url = format!("{}/chat/completions", spawn_unauthorized_server().await);
+        let healthy_url = format!("{}/res/v1/images/search", spawn_json_server("200 OK", r#"{"results":[]}"#).await);
+        let client = EnhancedHttpClient::builder()
+            .retry_config(RetryConfig { max_retries: 0, ..Default::default() })
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap()
+            .with_per_host_circuit_breaker(CircuitBreakerConfig {
+                failure_threshold: 2,
+                ..Default::default()
+            });
+
+        for _ in 0..2 {
+            assert!(client.post(&failing_url, serde_json::json!({})).await.is_err());
+        }
+        assert_eq!(client.circuit_state_for(&failing_url).await, CircuitState::Open);
+        let rejected = client.post(&failing_url, serde_json::json!({})).await.unwrap_err();
+        assert!(rejected.to_string().contains("Circuit breaker is open"));
+
+        let response: serde_json::Value = client.post_json(&healthy_url, serde_json::json!({"q": "cat"})).await.unwrap();
+        assert_eq!(response["results"], serde_json::json!([]));
+        assert_eq!(client.circuit_state_for(&healthy_url).await, CircuitState::Closed);
+        assert_eq!(client.circuit_state().await, CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_reset_circuit_lets_requests_through_again() {
+        let base_url = spawn_unauthorized_server().await;
+        let client = EnhancedHttpClient::builder()
+            .retry_config(RetryConfig { max_retries: 0, ..Default::default() })
+            .circuit_breaker_config(CircuitBreakerConfig { failure_threshold: 1, ..Default::default() })
+            .build()
+            .unwrap();
+        let url = format!("{base_url}/chat/completions");
+
+        assert!(client.post(&url, serde_json::json!({})).await.is_err());
+        assert_eq!(client.circuit_state().await, CircuitState::Open);
+
+        client.reset_circuit().await;
+        assert_eq!(client.circuit_state().await, CircuitState::Closed);
+
+        // The request reaches the server again instead of being rejected by the breaker
+        let error = client.post(&url, serde_json::json!({})).await.unwrap_err();
+        assert!(!error.to_string().contains("Circuit breaker is open"));
+    }
+
+    #[tokio::test]
+    async fn test_default_circuit_breaker_stays_closed_below_threshold() {
+        let base_url = spawn_unauthorized_server().await;
+        let client = EnhancedHttpClient::with_config(
+            RetryConfig { max_retries: 0, ..Default::default() },
+            (10, Duration::from_secs(1)),
+        ).unwrap();
+
+        let url = format!("{base_url}/chat/completions");
+        for _ in 0..2 {
+            assert!(client.post(&url, serde_json::json!({})).await.is_err());
+        }
+        assert_eq!(client.circuit_state().await, CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_request_proceeds_when_rate_limiter_is_saturated() {
+        let base_url = spawn_json_server("200 OK", r#"{"data":[{"id":"gpt-4o-mini"}]}"#).await;
+        let client = EnhancedHttpClient::builder()
+            .rate_limit(1, Duration::from_secs(60))
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+
+        let url = format!("{base_url}/models");
+        let _: serde_json::Value = client.get_json(&url).await.unwrap();
+
+        // The only permit is spent, so a normal request stays queued while the health check goes through
+        let (queued, health) = tokio::join!(
+            tokio::time::timeout(Duration::from_millis(500), client.get_json::<serde_json::Value>(&url)),
+            tokio::time::timeout(Duration::from_secs(2), client.get_json_unlimited::<serde_json::Value>(&url)),
+        );
+
+        assert!(queued.is_err());
+        assert_eq!(health.unwrap().unwrap()["data"][0]["id"], "gpt-4o-mini");
+    }
+
+    #[tokio::test]
+    async fn test_extra_headers_apply_to_one_request_only() {
+        let (base_url, requests) = spawn_recording_json_server(Duration::ZERO, "200 OK", r#"{"ok":true}"#).await;
+        let client = EnhancedHttpClient::builder()
+            .headers(HashMap::from([("X-Client".to_string(), "glossia".to_string())]))
+            .build()
+            .unwrap();
+        let extra = HashMap::from([
+            ("Idempotency-Key".to_string(), "key-123".to_string()),
+            ("X-Client".to_string(), "override".to_string()),
+        ]);
+
+        let url = format!("{base_url}/items");
+        client.post_with_headers(&url, serde_json::json!({}), &extra).await.unwrap();
+        client.post(&url, serde_json::json!({})).await.unwrap();
+        client.get_with_headers(&url, &extra).await.unwrap();
+
+        let requests: Vec<String> = requests.lock().unwrap().iter().map(|r| r.to_ascii_lowercase()).collect();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].contains("idempotency-key: key-123"));
+        assert!(requests[0].contains("x-client: override"));
+        assert!(!requests[0].contains("x-client: glossia"));
+        assert!(!requests[1].contains("idempotency-key"));
+        assert!(requests[1].contains("x-client: glossia"));
+        assert!(requests[2].starts_with("get ") && requests[2].contains("idempotency-key: key-123"));
+
+        let invalid = HashMap::from([("Bad Header".to_string(), "x".to_string())]);
+        assert!(client.get_with_headers(&url, &invalid).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_wait_stats_cover_queued_requests() {
+        let base_url = spawn_json_server("200 OK", r#"{"ok":true}"#).await;
+        let client = EnhancedHttpClient::builder()
+            .rate_limit(1, Duration::from_millis(100))
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+
+        let url = format!("{base_url}/status");
+        for _ in 0..2 {
+            let _: serde_json::Value = client.get_json(&url).await.unwrap();
+        }
+
+        let stats = client.rate_limit_wait_stats();
+        assert_eq!(stats.permits, 2);
+        assert!(stats.total_wait >= Duration::from_millis(50), "waited {:?}", stats.total_wait);
+    }
+
+    #[tokio::test]
+    async fn test_per_request_timeout_overrides_client_timeout() {
+        let base_url = spawn_delayed_json_server(Duration::from_millis(300), "200 OK", r#"{"ok":true}"#).await;
+        let client = EnhancedHttpClient::builder()
+            .retry_config(RetryConfig { max_retries: 0, ..Default::default() })
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+
+        let url = format!("{base_url}/chat/completions");
+
+        let slow = client
+            .post_json_with_timeout::<serde_json::Value>(&url, serde_json::json!({"n": 1}), Duration::from_millis(50))
+            .await;
+        assert!(slow.is_err());
+
+        let ok: serde_json::Value = client
+            .post_json_with_timeout(&url, serde_json::json!({"n": 2}), Duration::from_secs(2))
+            .await
+            .unwrap();
+        assert_eq!(ok["ok"], true);
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_bounds_stacked_layers() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/chat/completions", listener.local_addr().unwrap());
+        let hits = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let server_hits = hits.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                server_hits.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let _ = socket.read(&mut buf).await;
+                    let body = r#"{"error":{"message":"Overloaded"}}"#;
+                    let response = format!(
+                        "HTTP/1.1 503 Service Unavailable\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        // Up to 6 attempts per call inside the circuit breaker, called twice by an outer layer
+        let client = EnhancedHttpClient::builder()
+            .retry_config(RetryConfig { max_retries: 5, base_delay: Duration::from_millis(1), ..Default::default() })
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let budget = RetryBudget::new(3);
+
+        for n in 0..2 {
+            let result = client
+                .post_json_with_budget::<serde_json::Value>(&url, serde_json::json!({"n": n}), Duration::from_secs(5), &budget)
+                .await;
+            assert!(result.is_err());
+        }
+
+        assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 3);
+        assert!(budget.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_collapses_concurrent_identical_posts() {
+        let (base_url, requests) = spawn_recording_json_server(Duration::from_millis(100), "200 OK", r#"{"ok":true}"#).await;
+        let url = format!("{base_url}/chat/completions");
+
+        let client = EnhancedHttpClient::builder()
+            .duplicate_policy(DuplicatePolicy::SingleFlight)
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let body = serde_json::json!({"messages": ["same"]});
+
+        let (first, second) = tokio::join!(
+            client.post_json::<serde_json::Value>(&url, body.clone()),
+            client.post_json::<serde_json::Value>(&url, body.clone()),
+        );
+
+        assert_eq!(first.unwrap()["ok"], true);
+        assert_eq!(second.unwrap()["ok"], true);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_client_prometheus_metrics() {
+        let base_url = spawn_unauthorized_server().await;
+        let client = EnhancedHttpClient::builder()
+            .retry_config(RetryConfig { max_retries: 0, ..Default::default() })
+            .circuit_breaker_config(CircuitBreakerConfig { failure_threshold: 1, ..Default::default() })
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+
+        assert!(client.post(&format!("{base_url}/chat/completions"), serde_json::json!({})).await.is_err());
+        let metrics = client.prometheus_metrics().await;
+
+        assert!(metrics.contains("# TYPE glossia_tracked_requests gauge\nglossia_tracked_requests 1\n"));
+        assert!(metrics.contains("# TYPE glossia_duplicate_ratio gauge\nglossia_duplicate_ratio 0\n"));
+        assert!(metrics.contains("# TYPE glossia_circuit_open gauge\nglossia_circuit_open 1\n"));
+        assert!(metrics.contains("glossia_recent_failures 1\n"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_request_is_recorded_without_its_body() {
+        let base_url = spawn_unauthorized_server().await;
+        let client = EnhancedHttpClient::builder()
+            .retry_config(RetryConfig { max_retries: 0, ..Default::default() })
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let url = format!("{base_url}/chat/completions");
+        let body = serde_json::json!({"messages": ["secret text"]});
+
+        assert!(client.post(&url, body.clone()).await.is_err());
+
+        let failures = client.recent_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].method, "POST");
+        assert_eq!(failures[0].url, url);
+        assert_eq!(failures[0].body_size, Some(body.to_string().len()));
+        assert_eq!(failures[0].body_hash, Some(hash_request_body(&body)));
+        assert!(!format!("{:?}", failures[0]).contains("secret text"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_can_be_lowered_and_raised_at_runtime() {
+        let base_url = spawn_json_server("200 OK", r#"{"ok":true}"#).await;
+        let client = EnhancedHttpClient::builder()
+            .rate_limit(5, Duration::from_secs(60))
+            .build()
+            .unwrap();
+        let url = format!("{base_url}/models");
+
+        client.set_rate_limit(&base_url, 2, Duration::from_secs(60)).await.unwrap();
+        assert_eq!(client.available_permits(&url).await, 2);
+        let _: serde_json::Value = client.get_json(&url).await.unwrap();
+        assert_eq!(client.available_permits(&url).await, 1);
+
+        client.set_rate_limit(&base_url, 10, Duration::from_secs(60)).await.unwrap();
+        assert_eq!(client.available_permits(&url).await, 9);
+        let _: serde_json::Value = client.get_json(&url).await.unwrap();
+        assert_eq!(client.available_permits(&url).await, 8);
+
+        // Other hosts keep the client-wide limit
+        assert_eq!(client.available_permits("https://other.example.com/v1").await, 5);
+    }
+
+    #[tokio::test]
+    async fn test_set_rate_limit_rejects_zero_permits_and_sub_millisecond_windows() {
+        let client = EnhancedHttpClient::builder()
+            .rate_limit(5, Duration::from_secs(60))
+            .build()
+            .unwrap();
+        let url = "https://api.example.com/v1/models";
+
+        assert!(client.set_rate_limit(url, 0, Duration::from_secs(60)).await.is_err());
+        assert!(client.set_rate_limit(url, 3, Duration::ZERO).await.is_err());
+        assert_eq!(client.available_permits(url).await, 5);
+
+        client.set_rate_limit(url, 3, Duration::from_secs(60)).await.unwrap();
+        assert!(client.set_rate_limit(url, 0, Duration::from_secs(60)).await.is_err());
+        assert_eq!(client.available_permits(url).await, 3);
+    }
+
+    #[test]
+    fn test_custom_pool_settings_are_applied() {
+        let client = EnhancedHttpClient::builder()
+            .pool_max_idle_per_host(2)
+            .pool_idle_timeout(Some(Duration::from_secs(15)))
+            .build()
+            .unwrap();
+        assert_eq!(client.pool_config(), PoolConfig { max_idle_per_host: 2, idle_timeout: Some(Duration::from_secs(15)) });
+
+        let default_client = EnhancedHttpClient::new().unwrap();
+        assert_eq!(default_client.pool_config(), PoolConfig::default());
+    }
+}
@@ -1,10 +1,89 @@
+use glossia_shared::AppError;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
+/// Priority lane for a rate-limited request
+///
+/// When permits are scarce, waiters in a higher lane are served before
+/// any waiter in a lower lane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Background work such as prefetching and image optimization
+    Low,
+    #[default]
+    Normal,
+    /// User-initiated requests
+    High,
+}
+
+impl Priority {
+    const ALL: [Priority; 3] = [Priority::Low, Priority::Normal, Priority::High];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Time requests spent in `RateLimiter::wait_for_permit` before getting a permit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WaitStats {
+    pub permits: usize,
+    pub total_wait: Duration,
+    pub max_wait: Duration,
+}
+
+impl WaitStats {
+    /// Mean wait per permit, zero when no permits were granted
+    pub fn average_wait(&self) -> Duration {
+        match u32::try_from(self.permits) {
+            Ok(0) => Duration::ZERO,
+            Ok(permits) => self.total_wait / permits,
+            Err(_) => Duration::from_secs_f64(self.total_wait.as_secs_f64() / self.permits as f64),
+        }
+    }
+
+    /// Combine the stats of two limiters
+    pub fn merge(self, other: WaitStats) -> WaitStats {
+        WaitStats {
+            permits: self.permits + other.permits,
+            total_wait: self.total_wait + other.total_wait,
+            max_wait: self.max_wait.max(other.max_wait),
+        }
+    }
+
+    fn record(&mut self, wait: Duration) {
+        self.permits += 1;
+        self.total_wait += wait;
+        self.max_wait = self.max_wait.max(wait);
+    }
+}
+
 /// Token bucket rate limiter
 pub struct RateLimiter {
     bucket: Arc<Mutex<TokenBucket>>,
+    waiting: Arc<[AtomicUsize; 3]>,
+    wait_stats: std::sync::Mutex<WaitStats>,
+}
+
+/// Keeps a waiter registered in its priority lane until it is dropped
+struct WaiterGuard<'a> {
+    waiting: &'a [AtomicUsize; 3],
+    priority: Priority,
+}
+
+impl<'a> WaiterGuard<'a> {
+    fn register(waiting: &'a [AtomicUsize; 3], priority: Priority) -> Self {
+        waiting[priority.index()].fetch_add(1, Ordering::SeqCst);
+        Self { waiting, priority }
+    }
+}
+
+impl Drop for WaiterGuard<'_> {
+    fn drop(&mut self) {
+        self.waiting[self.priority.index()].fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 struct TokenBucket {
@@ -30,18 +109,29 @@ impl RateLimiter {
 
         Self {
             bucket: Arc::new(Mutex::new(bucket)),
+            waiting: Arc::new([AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)]),
+            wait_stats: std::sync::Mutex::new(WaitStats::default()),
         }
     }
 
     /// Wait for a permit to make a request
     pub async fn wait_for_permit(&self) {
+        self.wait_for_permit_with_priority(Priority::Normal).await
+    }
+
+    /// Wait for a permit, yielding to any queued waiters of higher priority
+    pub async fn wait_for_permit_with_priority(&self, priority: Priority) {
+        let _guard = WaiterGuard::register(&self.waiting, priority);
+        let started = Instant::now();
+
         loop {
-            {
+            if !self.has_higher_priority_waiters(priority) {
                 let mut bucket = self.bucket.lock().await;
                 bucket.refill_tokens();
                 
                 if bucket.tokens > 0 {
                     bucket.tokens -= 1;
+                    self.wait_stats.lock().unwrap().record(started.elapsed());
                     return;
                 }
             }
@@ -64,6 +154,39 @@ impl RateLimiter {
         }
     }
 
+    /// How long `wait_for_permit` calls have waited so far
+    pub fn wait_stats(&self) -> WaitStats {
+        *self.wait_stats.lock().unwrap()
+    }
+
+    /// Get the number of requests currently queued in a priority lane
+    pub fn waiting_count(&self, priority: Priority) -> usize {
+        self.waiting[priority.index()].load(Ordering::SeqCst)
+    }
+
+    fn has_higher_priority_waiters(&self, priority: Priority) -> bool {
+        Priority::ALL
+            .iter()
+            .filter(|lane| **lane > priority)
+            .any(|lane| self.waiting_count(*lane) > 0)
+    }
+
+    /// Change the limit in place; queued waiters stay queued and see the new limit.
+    /// Lowering caps the tokens left, raising grants the extra capacity right away.
+    /// A limit of zero permits or a window under 1ms is rejected.
+    pub async fn reconfigure(&self, max_requests: usize, window: Duration) -> Result<(), AppError> {
+        validate_limit(max_requests, window)?;
+        let mut bucket = self.bucket.lock().await;
+        bucket.refill_tokens();
+
+        let extra = max_requests.saturating_sub(bucket.max_tokens);
+        bucket.tokens = (bucket.tokens + extra).min(max_requests);
+        bucket.max_tokens = max_requests;
+        bucket.refill_rate = max_requests;
+        bucket.refill_interval = window;
+        Ok(())
+    }
+
     /// Get current number of available tokens
     pub async fn available_tokens(&self) -> usize {
         let mut bucket = self.bucket.lock().await;
@@ -72,6 +195,17 @@ impl RateLimiter {
     }
 }
 
+/// Check that a limit can refill: at least one permit per window of at least 1ms
+pub(crate) fn validate_limit(max_requests: usize, window: Duration) -> Result<(), AppError> {
+    if max_requests == 0 {
+        return Err(AppError::config_error("Rate limit must allow at least one request"));
+    }
+    if window.as_millis() == 0 {
+        return Err(AppError::config_error(format!("Rate limit window must be at least 1ms, got {window:?}")));
+    }
+    Ok(())
+}
+
 impl TokenBucket {
     fn refill_tokens(&mut self) {
         let now = Instant::now();
@@ -124,6 +258,32 @@ mod tests {
         assert!(rate_limiter.try_acquire().await);
     }
 
+    #[tokio::test]
+    async fn test_reconfigure_lowers_and_raises_capacity() {
+        let rate_limiter = RateLimiter::new(5, Duration::from_secs(60));
+        assert!(rate_limiter.try_acquire().await);
+
+        rate_limiter.reconfigure(2, Duration::from_secs(60)).await.unwrap();
+        assert_eq!(rate_limiter.available_tokens().await, 2);
+        assert!(rate_limiter.try_acquire().await);
+
+        rate_limiter.reconfigure(6, Duration::from_secs(60)).await.unwrap();
+        assert_eq!(rate_limiter.available_tokens().await, 5);
+    }
+
+    #[tokio::test]
+    async fn test_reconfigure_rejects_limits_that_cannot_refill() {
+        let rate_limiter = RateLimiter::new(5, Duration::from_secs(60));
+
+        assert!(rate_limiter.reconfigure(0, Duration::from_secs(60)).await.is_err());
+        assert!(rate_limiter.reconfigure(5, Duration::from_micros(500)).await.is_err());
+
+        // The previous limit stays in place
+        assert_eq!(rate_limiter.available_tokens().await, 5);
+        rate_limiter.reconfigure(1, Duration::from_millis(1)).await.unwrap();
+        assert_eq!(rate_limiter.available_tokens().await, 1);
+    }
+
     #[tokio::test]
     async fn test_wait_for_permit() {
         let rate_limiter = RateLimiter::new(1, Duration::from_millis(50));
@@ -139,4 +299,62 @@ mod tests {
         // Should have waited at least 50ms for token refill
         assert!(elapsed >= Duration::from_millis(40)); // Allow some tolerance
     }
+
+    #[tokio::test]
+    async fn test_wait_stats_record_time_spent_waiting() {
+        let unsaturated = RateLimiter::new(10, Duration::from_secs(60));
+        for _ in 0..3 {
+            unsaturated.wait_for_permit().await;
+        }
+        let stats = unsaturated.wait_stats();
+        assert_eq!(stats.permits, 3);
+        assert!(stats.max_wait < Duration::from_millis(10), "waited {:?}", stats.max_wait);
+
+        let saturated = RateLimiter::new(1, Duration::from_millis(50));
+        saturated.wait_for_permit().await;
+        saturated.wait_for_permit().await;
+        let stats = saturated.wait_stats();
+        assert_eq!(stats.permits, 2);
+        assert!(stats.max_wait >= Duration::from_millis(40), "waited {:?}", stats.max_wait);
+        assert_eq!(stats.average_wait(), stats.total_wait / 2);
+        assert_eq!(WaitStats::default().average_wait(), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_served_before_queued_low_priority() {
+        let rate_limiter = Arc::new(RateLimiter::new(1, Duration::from_millis(50)));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        // Saturate the limiter
+        rate_limiter.wait_for_permit().await;
+
+        let low = {
+            let rate_limiter = rate_limiter.clone();
+            let order = order.clone();
+            tokio::spawn(async move {
+                rate_limiter.wait_for_permit_with_priority(Priority::Low).await;
+                order.lock().await.push(Priority::Low);
+            })
+        };
+
+        // Make sure the low-priority request is queued first
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(rate_limiter.waiting_count(Priority::Low), 1);
+
+        let high = {
+            let rate_limiter = rate_limiter.clone();
+            let order = order.clone();
+            tokio::spawn(async move {
+                rate_limiter.wait_for_permit_with_priority(Priority::High).await;
+                order.lock().await.push(Priority::High);
+            })
+        };
+
+        high.await.unwrap();
+        low.await.unwrap();
+
+        assert_eq!(*order.lock().await, vec![Priority::High, Priority::Low]);
+        assert_eq!(rate_limiter.waiting_count(Priority::Low), 0);
+        assert_eq!(rate_limiter.waiting_count(Priority::High), 0);
+    }
 }
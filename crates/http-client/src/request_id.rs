@@ -0,0 +1,76 @@
+use std::fmt;
+use std::future::Future;
+use uuid::Uuid;
+
+/// Header carrying the request ID to the provider
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+tokio::task_local! {
+    static CURRENT: RequestId;
+}
+
+/// Identifier shared by every log line and HTTP request of one logical operation
+///
+/// A provider creates one per operation (e.g. a simplification) and runs the
+/// operation inside `scope`; `BaseHttpClient` picks it up for the
+/// `X-Request-Id` header and its `request_id` span field, retries included.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(String);
+
+impl RequestId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The ID of the operation the current task is running, if any
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(Self::clone).ok()
+    }
+
+    /// The current operation's ID, or a fresh one for a standalone request
+    pub fn current_or_new() -> Self {
+        Self::current().unwrap_or_default()
+    }
+
+    /// Fill the `request_id` field of the current span, if it declares one
+    pub fn record_in_current_span(&self) {
+        tracing::Span::current().record("request_id", tracing::field::display(self));
+    }
+
+    /// Run `operation` with this ID as the current one
+    pub async fn scope<F: Future>(self, operation: F) -> F::Output {
+        CURRENT.scope(self, operation).await
+    }
+}
+
+impl Default for RequestId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scope_sets_current_id() {
+        assert_eq!(RequestId::current(), None);
+
+        let id = RequestId::new();
+        let seen = id.clone().scope(async { RequestId::current() }).await;
+        assert_eq!(seen, Some(id));
+        assert_eq!(RequestId::current(), None);
+        assert_ne!(RequestId::current_or_new(), RequestId::current_or_new());
+    }
+}
@@ -1,9 +1,20 @@
+use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tracing::{warn, info, debug};
 use uuid::Uuid;
 
+/// What `EnhancedHttpClient` does with an identical request while one is still in flight
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Send it anyway and log a warning
+    #[default]
+    Warn,
+    /// Wait for the in-flight request and share its JSON response
+    SingleFlight,
+}
+
 /// Tracks API requests to detect and prevent duplicate requests
 #[derive(Debug, Clone)]
 pub struct RequestTracker {
@@ -152,7 +163,7 @@ pub struct RequestTrackingResult {
     pub original_request_id: Option<Uuid>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct RequestStats {
     pub total_unique_requests: usize,
     pub total_requests: u32,
@@ -160,20 +171,74 @@ pub struct RequestStats {
     pub duplicate_percentage: f64,
 }
 
+impl RequestStats {
+    /// Fraction of requests that repeated an earlier one, from 0.0 to 1.0
+    pub fn duplicate_ratio(&self) -> f64 {
+        self.duplicate_percentage / 100.0
+    }
+
+    /// Prometheus text exposition format, ready to serve from a `/metrics` endpoint
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        // Requests age out of the tracking window, so these can fall and are gauges, not counters
+        write_metric(&mut out, "glossia_tracked_requests", "gauge", "Requests sent within the tracking window, including duplicates", self.total_requests as f64);
+        write_metric(&mut out, "glossia_duplicate_requests", "gauge", "Requests within the tracking window identical to an earlier one", self.duplicate_requests as f64);
+        write_metric(&mut out, "glossia_unique_requests", "gauge", "Distinct requests currently tracked", self.total_unique_requests as f64);
+        write_metric(&mut out, "glossia_duplicate_ratio", "gauge", "Fraction of requests that were duplicates", self.duplicate_ratio());
+        out
+    }
+}
+
+/// Append one metric with its `# HELP` and `# TYPE` lines
+pub(crate) fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, value: f64) {
+    out.push_str(&format!("# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n"));
+}
+
 /// Create a hash of request body for deduplication
 pub fn hash_request_body(body: &serde_json::Value) -> String {
+    hash_request_body_ignoring(body, &[])
+}
+
+/// Like `hash_request_body`, but object keys named in `volatile_fields` (e.g. a
+/// timestamp or nonce) are removed at any depth first, so bodies differing only
+/// in those fields hash equal
+pub fn hash_request_body_ignoring(body: &serde_json::Value, volatile_fields: &[String]) -> String {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
-    
-    let body_str = body.to_string();
+
+    let body_str = if volatile_fields.is_empty() {
+        body.to_string()
+    } else {
+        let mut normalized = body.clone();
+        strip_fields(&mut normalized, volatile_fields);
+        normalized.to_string()
+    };
     let mut hasher = DefaultHasher::new();
     body_str.hash(&mut hasher);
     format!("{:x}", hasher.finish())
 }
 
+fn strip_fields(value: &mut serde_json::Value, fields: &[String]) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|key, _| !fields.contains(key));
+            for nested in map.values_mut() {
+                strip_fields(nested, fields);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                strip_fields(item, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
     use std::thread;
 
     #[test]
@@ -236,4 +301,57 @@ mod tests {
         assert_eq!(hash_request_body(&body1), hash_request_body(&body2));
         assert_ne!(hash_request_body(&body1), hash_request_body(&body3));
     }
+
+    #[test]
+    fn test_hash_ignores_volatile_fields() {
+        let volatile = vec!["timestamp".to_string()];
+        let body1 = serde_json::json!({"sentence": "The cat sat.", "timestamp": 1, "meta": {"timestamp": 10}});
+        let body2 = serde_json::json!({"sentence": "The cat sat.", "timestamp": 2, "meta": {"timestamp": 20}});
+        let body3 = serde_json::json!({"sentence": "The dog sat.", "timestamp": 1, "meta": {"timestamp": 10}});
+
+        assert_eq!(hash_request_body_ignoring(&body1, &volatile), hash_request_body_ignoring(&body2, &volatile));
+        assert_ne!(hash_request_body_ignoring(&body1, &volatile), hash_request_body_ignoring(&body3, &volatile));
+        assert_ne!(hash_request_body(&body1), hash_request_body(&body2));
+    }
+
+    /// Parse exposition text into metric values, checking every sample is preceded by its HELP and TYPE lines
+    fn parse_prometheus(text: &str) -> HashMap<String, f64> {
+        let mut described = HashSet::new();
+        let mut samples = HashMap::new();
+        for line in text.lines() {
+            if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let mut parts = rest.split_whitespace();
+                let name = parts.next().unwrap();
+                assert!(matches!(parts.next(), Some("counter" | "gauge")), "bad TYPE line: {line}");
+                described.insert(name.to_string());
+            } else if line.starts_with("# HELP ") {
+                continue;
+            } else {
+                let (name, value) = line.rsplit_once(' ').unwrap();
+                let metric = name.split('{').next().unwrap();
+                assert!(described.contains(metric), "{metric} has no TYPE line");
+                samples.insert(name.to_string(), value.parse::<f64>().unwrap());
+            }
+        }
+        samples
+    }
+
+    #[test]
+    fn test_stats_to_prometheus() {
+        let tracker = RequestTracker::new();
+        tracker.track_request("POST", "https://api.example.com/chat", Some("a".to_string()));
+        tracker.track_request("POST", "https://api.example.com/chat", Some("a".to_string()));
+        tracker.track_request("POST", "https://api.example.com/chat", Some("b".to_string()));
+        tracker.track_request("GET", "https://api.example.com/models", None);
+
+        let text = tracker.get_stats().to_prometheus();
+        let samples = parse_prometheus(&text);
+
+        assert!(text.contains("# TYPE glossia_tracked_requests gauge\n"));
+        assert!(!text.contains("counter"));
+        assert_eq!(samples["glossia_tracked_requests"], 4.0);
+        assert_eq!(samples["glossia_duplicate_requests"], 1.0);
+        assert_eq!(samples["glossia_unique_requests"], 3.0);
+        assert_eq!(samples["glossia_duplicate_ratio"], 0.25);
+    }
 }
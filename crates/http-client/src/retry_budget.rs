@@ -0,0 +1,80 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Cap on the total number of attempts one user-level operation may make
+///
+/// Clones share the same counter, so passing a budget down through nested
+/// retry layers (retry service, JSON re-requests) bounds their combined
+/// attempts instead of letting them multiply.
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    max_attempts: usize,
+    used: Arc<AtomicUsize>,
+}
+
+impl RetryBudget {
+    pub fn new(max_attempts: usize) -> Self {
+        Self {
+            max_attempts,
+            used: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Budget that never runs out, leaving each layer to its own limits
+    pub fn unlimited() -> Self {
+        Self::new(usize::MAX)
+    }
+
+    /// Claim one attempt, returning false once the budget is spent
+    pub fn try_acquire(&self) -> bool {
+        self.used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+                (used < self.max_attempts).then_some(used + 1)
+            })
+            .is_ok()
+    }
+
+    pub fn attempts_used(&self) -> usize {
+        self.used.load(Ordering::SeqCst)
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.max_attempts.saturating_sub(self.attempts_used())
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining() == 0
+    }
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_the_budget() {
+        let budget = RetryBudget::new(2);
+        let nested = budget.clone();
+
+        assert!(budget.try_acquire());
+        assert!(nested.try_acquire());
+        assert!(!budget.try_acquire());
+        assert!(nested.is_exhausted());
+        assert_eq!(budget.attempts_used(), 2);
+    }
+
+    #[test]
+    fn test_unlimited_budget_never_runs_out() {
+        let budget = RetryBudget::unlimited();
+        for _ in 0..100 {
+            assert!(budget.try_acquire());
+        }
+        assert!(!budget.is_exhausted());
+    }
+}
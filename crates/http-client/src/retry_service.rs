@@ -2,6 +2,24 @@ use glossia_shared::AppError;
 use std::future::Future;
 use std::time::Duration;
 use rand::Rng;
+use crate::RetryBudget;
+
+/// How the delay grows between retry attempts
+#[derive(Clone, Debug, PartialEq)]
+pub enum BackoffStrategy {
+    /// `base_delay * multiplier^attempt`
+    Exponential { multiplier: f64 },
+    /// `base_delay + step * attempt`
+    Linear { step: Duration },
+    /// Always wait `base_delay`
+    Fixed,
+}
+
+impl Default for BackoffStrategy {
+    fn default() -> Self {
+        Self::Exponential { multiplier: 2.0 }
+    }
+}
 
 /// Configuration for retry behavior
 #[derive(Clone)]
@@ -9,8 +27,27 @@ pub struct RetryConfig {
     pub max_retries: usize,
     pub base_delay: Duration,
     pub max_delay: Duration,
-    pub backoff_multiplier: f64,
+    pub backoff: BackoffStrategy,
     pub jitter: bool,
+    /// Bounds of the random factor applied to each delay when `jitter` is on;
+    /// both must be positive with low < high
+    pub jitter_range: (f64, f64),
+}
+
+/// Jitter bounds used unless configured otherwise
+pub const DEFAULT_JITTER_RANGE: (f64, f64) = (0.8, 1.2);
+
+impl RetryConfig {
+    /// Check the jitter bounds, which `calculate_delay` samples between
+    pub fn validate(&self) -> Result<(), AppError> {
+        let (low, high) = self.jitter_range;
+        if !(low > 0.0 && high.is_finite() && low < high) {
+            return Err(AppError::config_error(format!(
+                "Invalid jitter range ({low}, {high}): both bounds must be positive and low < high"
+            )));
+        }
+        Ok(())
+    }
 }
 
 impl Default for RetryConfig {
@@ -19,8 +56,9 @@ impl Default for RetryConfig {
             max_retries: 3,
             base_delay: Duration::from_millis(100),
             max_delay: Duration::from_secs(5),
-            backoff_multiplier: 2.0,
+            backoff: BackoffStrategy::default(),
             jitter: true,
+            jitter_range: DEFAULT_JITTER_RANGE,
         }
     }
 }
@@ -31,12 +69,33 @@ pub struct RetryService {
 }
 
 impl RetryService {
-    pub fn new(config: RetryConfig) -> Self {
+    /// Service for `config`; an invalid jitter range falls back to `DEFAULT_JITTER_RANGE`
+    pub fn new(mut config: RetryConfig) -> Self {
+        if let Err(e) = config.validate() {
+            tracing::warn!("{}, using the default range", e);
+            config.jitter_range = DEFAULT_JITTER_RANGE;
+        }
         Self { config }
     }
 
+    /// Service for `config`, rejecting an invalid jitter range
+    pub fn try_new(config: RetryConfig) -> Result<Self, AppError> {
+        config.validate()?;
+        Ok(Self { config })
+    }
+
     /// Execute a function with retry logic
-    pub async fn execute<F, Fut, T>(&self, mut operation: F) -> Result<T, AppError>
+    pub async fn execute<F, Fut, T>(&self, operation: F) -> Result<T, AppError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        self.execute_with_budget(&RetryBudget::unlimited(), operation).await
+    }
+
+    /// Execute with retry logic, drawing every attempt from `budget`
+    /// Stops early once the budget is spent, even if retries remain
+    pub async fn execute_with_budget<F, Fut, T>(&self, budget: &RetryBudget, mut operation: F) -> Result<T, AppError>
     where
         F: FnMut() -> Fut,
         Fut: Future<Output = Result<T, AppError>>,
@@ -44,6 +103,10 @@ impl RetryService {
         let mut last_error = None;
 
         for attempt in 0..=self.config.max_retries {
+            if !budget.try_acquire() {
+                break;
+            }
+
             match operation().await {
                 Ok(result) => return Ok(result),
                 Err(error) => {
@@ -55,7 +118,7 @@ impl RetryService {
                     }
 
                     // Don't delay after the last attempt
-                    if attempt < self.config.max_retries {
+                    if attempt < self.config.max_retries && !budget.is_exhausted() {
                         let delay = self.calculate_delay(attempt);
                         tokio::time::sleep(delay).await;
                     }
@@ -64,39 +127,47 @@ impl RetryService {
         }
 
         // Return the last error if all retries failed
-        Err(last_error.unwrap_or_else(|| AppError::api_error("Retry service failed without error")))
+        Err(last_error.unwrap_or_else(|| AppError::api_error("Retry budget exhausted before the request was attempted")))
     }
 
     /// Determine if an error should be retried
     fn should_retry(&self, error: &AppError) -> bool {
         match error {
             AppError::NetworkError { .. } => true,
+            AppError::TimeoutError { .. } => true,
             AppError::HttpError { status, .. } => {
                 // Retry on server errors (5xx) and rate limiting (429)
                 *status >= 500 || *status == 429
             },
             AppError::RateLimitError { .. } => true, // Always retry rate limit errors with backoff
+            AppError::QuotaExceededError { .. } => false, // Billing problems won't resolve on retry
             AppError::AuthenticationError { .. } => false, // Don't retry auth errors
             AppError::BadRequestError { .. } => false, // Don't retry bad requests
             AppError::ApiError { .. } => false, // Usually don't retry API errors
             AppError::ParseError { .. } => false, // Don't retry parse errors
             AppError::ConfigError { .. } => false, // Don't retry config errors
             AppError::InvalidResponseContent => false,
+            AppError::ContentRefused { .. } => false, // The model gives the same answer again
             AppError::EmptyBook => false,
         }
     }
 
-    /// Calculate delay with exponential backoff and optional jitter
+    /// Calculate delay using the configured backoff strategy and optional jitter
     fn calculate_delay(&self, attempt: usize) -> Duration {
         let base_delay_ms = self.config.base_delay.as_millis() as f64;
-        let exponential_delay = base_delay_ms * self.config.backoff_multiplier.powi(attempt as i32);
+        let backoff_delay = match &self.config.backoff {
+            BackoffStrategy::Exponential { multiplier } => base_delay_ms * multiplier.powi(attempt as i32),
+            BackoffStrategy::Linear { step } => base_delay_ms + step.as_millis() as f64 * attempt as f64,
+            BackoffStrategy::Fixed => base_delay_ms,
+        };
         
-        let mut delay_ms = exponential_delay.min(self.config.max_delay.as_millis() as f64);
+        let mut delay_ms = backoff_delay.min(self.config.max_delay.as_millis() as f64);
 
         // Add jitter to prevent thundering herd
         if self.config.jitter {
             let mut rng = rand::thread_rng();
-            let jitter_factor: f64 = rng.gen_range(0.8..1.2);
+            let (low, high) = self.config.jitter_range;
+            let jitter_factor: f64 = rng.gen_range(low..high);
             delay_ms *= jitter_factor;
         }
 
@@ -180,4 +251,127 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(attempt_count.load(std::sync::atomic::Ordering::SeqCst), 1); // Should not retry parse errors
     }
+
+    fn delays_for(backoff: BackoffStrategy, max_delay: Duration) -> Vec<Duration> {
+        let retry_service = RetryService::new(RetryConfig {
+            base_delay: Duration::from_millis(100),
+            max_delay,
+            backoff,
+            jitter: false,
+            ..Default::default()
+        });
+        (0..3).map(|attempt| retry_service.calculate_delay(attempt)).collect()
+    }
+
+    #[test]
+    fn test_exponential_backoff_delays() {
+        let delays = delays_for(BackoffStrategy::Exponential { multiplier: 2.0 }, Duration::from_secs(5));
+        assert_eq!(delays, vec![
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            Duration::from_millis(400),
+        ]);
+    }
+
+    #[test]
+    fn test_linear_backoff_delays() {
+        let delays = delays_for(BackoffStrategy::Linear { step: Duration::from_millis(50) }, Duration::from_secs(5));
+        assert_eq!(delays, vec![
+            Duration::from_millis(100),
+            Duration::from_millis(150),
+            Duration::from_millis(200),
+        ]);
+    }
+
+    #[test]
+    fn test_fixed_backoff_delays() {
+        let delays = delays_for(BackoffStrategy::Fixed, Duration::from_secs(5));
+        assert_eq!(delays, vec![Duration::from_millis(100); 3]);
+    }
+
+    #[test]
+    fn test_backoff_respects_max_delay() {
+        let max_delay = Duration::from_millis(150);
+
+        let exponential = delays_for(BackoffStrategy::Exponential { multiplier: 3.0 }, max_delay);
+        assert_eq!(exponential[2], max_delay);
+
+        let linear = delays_for(BackoffStrategy::Linear { step: Duration::from_millis(100) }, max_delay);
+        assert_eq!(linear[2], max_delay);
+
+        let fixed = delays_for(BackoffStrategy::Fixed, Duration::from_millis(50));
+        assert!(fixed.iter().all(|delay| *delay == Duration::from_millis(50)));
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_for_quota_exceeded() {
+        let config = RetryConfig {
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        };
+        let retry_service = RetryService::new(config);
+
+        let attempt_count = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let attempt_count_clone = attempt_count.clone();
+        
+        let result: Result<String, AppError> = retry_service.execute(move || {
+            let count = attempt_count_clone.clone();
+            async move {
+                count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Err(AppError::quota_exceeded_error("You exceeded your current quota"))
+            }
+        }).await;
+
+        assert!(result.is_err());
+        assert_eq!(attempt_count.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_budget_caps_attempts_below_max_retries() {
+        let retry_service = RetryService::new(RetryConfig {
+            max_retries: 5,
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        });
+        let budget = RetryBudget::new(2);
+        let attempt_count = std::sync::atomic::AtomicUsize::new(0);
+
+        let result: Result<(), AppError> = retry_service.execute_with_budget(&budget, || async {
+            attempt_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(AppError::NetworkError { message: "Test error".to_string() })
+        }).await;
+
+        assert!(matches!(result, Err(AppError::NetworkError { .. })));
+        assert_eq!(attempt_count.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // A spent budget makes no further attempts
+        let again: Result<(), AppError> = retry_service.execute_with_budget(&budget, || async { Ok(()) }).await;
+        assert!(again.is_err());
+    }
+
+    #[test]
+    fn test_delays_stay_within_configured_jitter_range() {
+        let retry_service = RetryService::try_new(RetryConfig {
+            base_delay: Duration::from_millis(1000),
+            backoff: BackoffStrategy::Fixed,
+            jitter_range: (0.5, 2.0),
+            max_delay: Duration::from_secs(10),
+            ..Default::default()
+        })
+        .unwrap();
+
+        for _ in 0..200 {
+            let delay = retry_service.calculate_delay(0);
+            assert!(delay >= Duration::from_millis(500) && delay < Duration::from_millis(2000), "{delay:?}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_jitter_range_is_rejected() {
+        for jitter_range in [(1.2, 0.8), (1.0, 1.0), (-0.5, 1.0), (0.0, 1.0)] {
+            let config = RetryConfig { jitter_range, ..Default::default() };
+            assert!(RetryService::try_new(config).is_err(), "{jitter_range:?}");
+        }
+        assert!(RetryService::try_new(RetryConfig::default()).is_ok());
+    }
 }
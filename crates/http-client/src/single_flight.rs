@@ -0,0 +1,86 @@
+use glossia_shared::AppError;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// The shared result slot for one in-flight request
+type InFlight = Arc<OnceCell<Result<Value, AppError>>>;
+
+/// Collapses concurrent identical requests into one call whose result every caller shares
+///
+/// Entries only live while the request is in flight, so a later identical
+/// request goes to the network again.
+#[derive(Default)]
+pub(crate) struct SingleFlight {
+    in_flight: Mutex<HashMap<String, InFlight>>,
+}
+
+impl SingleFlight {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `fetch` for `key`, or wait for the identical call already in flight
+    /// The flag is true when the result was shared from another caller's fetch
+    pub async fn run<F, Fut>(&self, key: String, fetch: F) -> (Result<Value, AppError>, bool)
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Value, AppError>>,
+    {
+        let cell = self.in_flight.lock().unwrap().entry(key.clone()).or_default().clone();
+        let mut fetched = false;
+        let result = cell.get_or_init(|| {
+            fetched = true;
+            fetch()
+        }).await.clone();
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight.get(&key).is_some_and(|current| Arc::ptr_eq(current, &cell)) {
+            in_flight.remove(&key);
+        }
+        (result, !fetched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    async fn counted(calls: &AtomicUsize) -> Result<Value, AppError> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        Ok(serde_json::json!({"ok": true}))
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_calls_share_one_fetch() {
+        let flight = SingleFlight::new();
+        let calls = AtomicUsize::new(0);
+
+        let ((first, first_shared), (second, second_shared)) = tokio::join!(
+            flight.run("key".to_string(), || counted(&calls)),
+            flight.run("key".to_string(), || counted(&calls)),
+        );
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first.unwrap(), second.unwrap());
+        assert!(!first_shared);
+        assert!(second_shared);
+    }
+
+    #[tokio::test]
+    async fn test_finished_call_is_not_reused() {
+        let flight = SingleFlight::new();
+        let calls = AtomicUsize::new(0);
+
+        flight.run("key".to_string(), || counted(&calls)).await.0.unwrap();
+        let (_, shared) = flight.run("key".to_string(), || counted(&calls)).await;
+        assert!(!shared);
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -16,28 +16,35 @@ impl BraveProvider {
     pub fn new(config: ImageClientConfig) -> Result<Self, AppError> {
         config.validate()?;
 
-        let mut client = EnhancedHttpClient::new()?
-            .with_timeout(config.timeout);
-
         // Add API key as header if provided
+        let mut headers = std::collections::HashMap::new();
         if let Some(ref api_key) = config.api_key {
-            let mut headers = std::collections::HashMap::new();
             headers.insert("X-Subscription-Token".to_string(), api_key.clone());
-            client = client.with_headers(headers);
         }
 
+        let client = EnhancedHttpClient::builder()
+            .timeout(config.timeout)
+            .headers(headers)
+            .build()?;
+
         Ok(Self {
             client,
             config,
         })
     }
 
+    fn get_base_url(&self) -> String {
+        self.config.base_url.clone()
+            .unwrap_or_else(|| "https://api.search.brave.com/res/v1".to_string())
+    }
+
     fn build_search_url(&self, query: &str, count: usize) -> String {
         let encoded_query = encode(query);
         let clamped_count = self.config.clamp_count(Some(count));
         
         format!(
-            "https://api.search.brave.com/res/v1/images/search?q={encoded_query}&count={clamped_count}"
+            "{}/images/search?q={encoded_query}&count={clamped_count}",
+            self.get_base_url()
         )
     }
 
@@ -65,7 +72,9 @@ impl BraveProvider {
                     thumbnail_url,
                     width: None,
                     height: None,
-                })
+                    display_url: None,
+                }
+                .with_display_preference(self.config.prefer_thumbnails))
             })
             .collect();
 
@@ -92,7 +101,11 @@ impl ImageClient for BraveProvider {
         let response: Value = self.client.get_json(&url).await?;
         let results = self.parse_brave_response(response)?;
         
-        info!("Found {} images for query: '{}'", results.len(), query);
+        if results.is_empty() {
+            warn!("Brave found no images for query: '{}'", query);
+        } else {
+            info!("Found {} images for query: '{}'", results.len(), query);
+        }
         Ok(results)
     }
 
@@ -100,9 +113,35 @@ impl ImageClient for BraveProvider {
         "Brave"
     }
 
+    fn clamp_count(&self, requested: usize) -> usize {
+        self.config.clamp_count(Some(requested))
+    }
+
+    fn min_results(&self) -> usize {
+        self.config.min_results
+    }
+
+    fn empty_result_retries(&self) -> usize {
+        self.config.empty_result_retries
+    }
+
+    fn fallback_image_url(&self) -> Option<&str> {
+        self.config.fallback_image_url.as_deref()
+    }
+
     async fn health_check(&self) -> Result<(), AppError> {
-        // Do a minimal search to test if the API is working
-        let test_results = self.search_images("test", Some(1)).await?;
+        // A single minimal search, bypassing retries and the rate limiter, with a short timeout
+        let url = self.build_search_url("test", 1);
+        let timeout = self.config.health_check_timeout;
+        let response: Value = match tokio::time::timeout(timeout, self.client.get_json_unlimited(&url)).await {
+            Ok(result) => result?,
+            Err(_) => {
+                return Err(AppError::TimeoutError {
+                    message: format!("Brave health check timed out after {:?}", timeout),
+                })
+            }
+        };
+        let test_results = self.parse_brave_response(response)?;
         
         if test_results.is_empty() {
             Err(AppError::api_error("Brave Search API returned no results for test query"))
@@ -135,6 +174,125 @@ mod tests {
         assert!(url.contains("count=5"));
     }
 
+    #[test]
+    fn test_gallery_count_clamped_to_config_max() {
+        let config = ImageClientConfig::new(crate::ImageProvider::Brave)
+            .with_api_key("test_key".to_string());
+        let max_count = config.max_count;
+        let provider = BraveProvider::new(config).unwrap();
+
+        assert_eq!(provider.clamp_count(max_count + 10), max_count);
+        assert_eq!(provider.clamp_count(0), 1);
+        assert_eq!(provider.clamp_count(7), 7);
+    }
+
+    #[test]
+    fn test_build_search_url_with_custom_base_url() {
+        for base_url in ["https://proxy.example.com/brave/", "https://proxy.example.com/brave"] {
+            let config = ImageClientConfig::new(crate::ImageProvider::Brave)
+                .with_api_key("test_key".to_string())
+                .with_base_url(base_url.to_string());
+            let provider = BraveProvider::new(config).unwrap();
+
+            let url = provider.build_search_url("cat", 3);
+            assert!(url.starts_with("https://proxy.example.com/brave/images/search?"));
+        }
+    }
+
+    /// Answer every request with `status_line` and an empty JSON body after `delay`
+    async fn spawn_brave_server(status_line: &'static str, delay: std::time::Duration) -> String {
+        spawn_brave_server_with_body(status_line, delay, r#"{"results": []}"#).await.0
+    }
+
+    /// Like `spawn_brave_server` with a custom body, also counting the requests served
+    async fn spawn_brave_server_with_body(
+        status_line: &'static str,
+        delay: std::time::Duration,
+        body: &'static str,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let served = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                served.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let _ = socket.read(&mut buf).await;
+                    tokio::time::sleep(delay).await;
+                    let response = format!(
+                        "HTTP/1.1 {status_line}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        (format!("http://{addr}"), requests)
+    }
+
+    fn provider_for(base_url: String, empty_result_retries: usize) -> BraveProvider {
+        let config = ImageClientConfig::new(crate::ImageProvider::Brave)
+            .with_api_key("test_key".to_string())
+            .with_base_url(base_url)
+            .with_empty_result_retries(empty_result_retries);
+        BraveProvider::new(config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_zero_results_are_reported_as_empty() {
+        let (base_url, requests) = spawn_brave_server_with_body("200 OK", std::time::Duration::ZERO, r#"{"results": []}"#).await;
+        let provider = provider_for(base_url, 2);
+
+        let outcome = provider.search_outcome("qwertyuiop", Some(3)).await.unwrap();
+        assert!(outcome.was_empty);
+        assert!(outcome.results.is_empty());
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_populated_results_are_not_empty() {
+        let body = r#"{"results": [{"title": "A cat", "properties": {"url": "https://img.example.com/cat.jpg"}}]}"#;
+        let (base_url, requests) = spawn_brave_server_with_body("200 OK", std::time::Duration::ZERO, body).await;
+        let provider = provider_for(base_url, 2);
+
+        let outcome = provider.search_outcome("cat", Some(3)).await.unwrap();
+        assert!(!outcome.was_empty);
+        assert_eq!(outcome.results[0].url, "https://img.example.com/cat.jpg");
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_slow_health_check_is_a_timeout() {
+        let base_url = spawn_brave_server("200 OK", std::time::Duration::from_secs(5)).await;
+        let config = ImageClientConfig::new(crate::ImageProvider::Brave)
+            .with_api_key("test_key".to_string())
+            .with_base_url(base_url)
+            .with_health_check_timeout(std::time::Duration::from_millis(50));
+        let provider = BraveProvider::new(config).unwrap();
+
+        let error = provider.health_check().await.unwrap_err();
+        assert!(error.is_timeout());
+        assert!(!error.is_authentication_error());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_key_is_an_auth_error() {
+        let base_url = spawn_brave_server("401 Unauthorized", std::time::Duration::ZERO).await;
+        let config = ImageClientConfig::new(crate::ImageProvider::Brave)
+            .with_api_key("bad_key".to_string())
+            .with_base_url(base_url);
+        let provider = BraveProvider::new(config).unwrap();
+
+        let error = provider.health_check().await.unwrap_err();
+        assert!(error.is_authentication_error());
+        assert!(!error.is_timeout());
+    }
+
     #[test]
     fn test_parse_brave_response() {
         let config = ImageClientConfig::new(crate::ImageProvider::Brave)
@@ -195,4 +353,28 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].thumbnail_url, "https://example.com/image1.jpg"); // Should fallback to main URL
     }
+
+    #[test]
+    fn test_display_url_follows_thumbnail_preference() {
+        let mock_response = serde_json::json!({
+            "results": [
+                { "url": "https://example.com/full1.jpg", "title": "With thumb", "thumbnail": { "src": "https://example.com/thumb1.jpg" } },
+                { "url": "https://example.com/full2.jpg", "title": "No thumb" }
+            ]
+        });
+
+        let config = ImageClientConfig::new(crate::ImageProvider::Brave)
+            .with_api_key("test_key".to_string())
+            .with_prefer_thumbnails(true);
+        let results = BraveProvider::new(config).unwrap().parse_brave_response(mock_response.clone()).unwrap();
+        assert_eq!(results[0].primary_url(), "https://example.com/thumb1.jpg");
+        assert_eq!(results[0].url, "https://example.com/full1.jpg");
+        assert_eq!(results[1].primary_url(), "https://example.com/full2.jpg");
+
+        let config = ImageClientConfig::new(crate::ImageProvider::Brave)
+            .with_api_key("test_key".to_string())
+            .with_prefer_thumbnails(false);
+        let results = BraveProvider::new(config).unwrap().parse_brave_response(mock_response).unwrap();
+        assert_eq!(results[0].primary_url(), "https://example.com/full1.jpg");
+    }
 }
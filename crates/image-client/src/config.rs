@@ -1,6 +1,9 @@
 use glossia_shared::AppError;
 use std::time::Duration;
 
+/// Time allowed for a health check before the provider is reported as timed out
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Supported image search providers
 #[derive(Debug, Clone, PartialEq)]
 pub enum ImageProvider {
@@ -25,10 +28,23 @@ impl std::str::FromStr for ImageProvider {
 pub struct ImageClientConfig {
     pub provider: ImageProvider,
     pub api_key: Option<String>,
+    pub base_url: Option<String>,
     pub timeout: Duration,
     pub max_retries: usize,
     pub default_count: usize,
     pub max_count: usize,
+    /// Single-attempt limit for `health_check`, kept short so startup isn't held up
+    pub health_check_timeout: Duration,
+    /// Display thumbnails in the gallery instead of full-resolution images
+    pub prefer_thumbnails: bool,
+    /// Fewest images a gallery should show; smaller results trigger a second,
+    /// broader search. 0 disables the fallback.
+    pub min_results: usize,
+    /// Extra attempts when a search succeeds with zero results
+    pub empty_result_retries: usize,
+    /// Image a gallery shows, labeled "no results", when a search finds nothing;
+    /// without one the gallery stays empty
+    pub fallback_image_url: Option<String>,
 }
 
 impl Default for ImageClientConfig {
@@ -36,10 +52,16 @@ impl Default for ImageClientConfig {
         Self {
             provider: ImageProvider::Brave,
             api_key: None,
+            base_url: None,
             timeout: Duration::from_secs(10),
             max_retries: 3,
             default_count: 5,
             max_count: 20,
+            health_check_timeout: DEFAULT_HEALTH_CHECK_TIMEOUT,
+            prefer_thumbnails: true,
+            min_results: 0,
+            empty_result_retries: 0,
+            fallback_image_url: None,
         }
     }
 }
@@ -63,6 +85,12 @@ impl ImageClientConfig {
             ImageProvider::Mock => None,
         };
 
+        let base_url = match provider {
+            ImageProvider::Brave => std::env::var("BRAVE_BASE_URL").ok(),
+            ImageProvider::Mock => None,
+        }
+        .map(|url| normalize_base_url(&url));
+
         let timeout = std::env::var("IMAGE_TIMEOUT")
             .ok()
             .and_then(|s| s.parse().ok())
@@ -84,13 +112,44 @@ impl ImageClientConfig {
             .and_then(|s| s.parse().ok())
             .unwrap_or(20);
 
+        let health_check_timeout = std::env::var("IMAGE_HEALTH_CHECK_TIMEOUT")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_HEALTH_CHECK_TIMEOUT);
+
+        let prefer_thumbnails = std::env::var("IMAGE_PREFER_THUMBNAILS")
+            .map(|s| !matches!(s.to_lowercase().as_str(), "0" | "false" | "no"))
+            .unwrap_or(true);
+
+        let min_results = std::env::var("IMAGE_MIN_RESULTS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+
+        let empty_result_retries = std::env::var("IMAGE_EMPTY_RESULT_RETRIES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+
+        let fallback_image_url = std::env::var("IMAGE_FALLBACK_URL")
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+
         Ok(Self {
             provider,
             api_key,
+            base_url,
             timeout,
             max_retries,
             default_count,
             max_count,
+            health_check_timeout,
+            prefer_thumbnails,
+            min_results,
+            empty_result_retries,
+            fallback_image_url,
         })
     }
 
@@ -99,6 +158,31 @@ impl ImageClientConfig {
         self
     }
 
+    pub fn with_health_check_timeout(mut self, timeout: Duration) -> Self {
+        self.health_check_timeout = timeout;
+        self
+    }
+
+    pub fn with_prefer_thumbnails(mut self, prefer: bool) -> Self {
+        self.prefer_thumbnails = prefer;
+        self
+    }
+
+    pub fn with_min_results(mut self, min_results: usize) -> Self {
+        self.min_results = min_results;
+        self
+    }
+
+    pub fn with_empty_result_retries(mut self, retries: usize) -> Self {
+        self.empty_result_retries = retries;
+        self
+    }
+
+    pub fn with_fallback_image_url(mut self, url: impl Into<String>) -> Self {
+        self.fallback_image_url = Some(url.into());
+        self
+    }
+
     pub fn with_max_retries(mut self, max_retries: usize) -> Self {
         self.max_retries = max_retries;
         self
@@ -119,6 +203,11 @@ impl ImageClientConfig {
         self
     }
 
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = Some(normalize_base_url(&base_url));
+        self
+    }
+
     /// Validate the configuration
     pub fn validate(&self) -> Result<(), AppError> {
         match self.provider {
@@ -132,6 +221,14 @@ impl ImageClientConfig {
             }
         }
 
+        if let Some(ref base_url) = self.base_url {
+            if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+                return Err(AppError::config_error(format!(
+                    "Invalid base URL '{base_url}'. The URL must start with http:// or https://"
+                )));
+            }
+        }
+
         if self.default_count == 0 {
             return Err(AppError::config_error("Default count must be greater than 0"));
         }
@@ -155,3 +252,29 @@ impl ImageClientConfig {
         }
     }
 }
+
+/// Trim surrounding whitespace and trailing slashes so endpoint paths can be appended safely
+fn normalize_base_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url_trailing_slash_is_stripped() {
+        let config = ImageClientConfig::new(ImageProvider::Brave)
+            .with_base_url("https://api.search.brave.com/res/v1/".to_string());
+        assert_eq!(config.base_url.as_deref(), Some("https://api.search.brave.com/res/v1"));
+    }
+
+    #[test]
+    fn test_invalid_base_url_scheme_rejected() {
+        let config = ImageClientConfig::new(ImageProvider::Mock)
+            .with_base_url("api.search.brave.com/res/v1".to_string());
+
+        let result = config.validate();
+        assert!(matches!(result, Err(AppError::ConfigError { .. })));
+    }
+}
@@ -1,5 +1,34 @@
 use crate::{ImageClient, ImageClientConfig, ImageProvider, BraveProvider, MockImageClient};
 use glossia_shared::AppError;
+use std::time::Duration;
+
+/// A freshly created client together with the outcome of its health check
+pub struct ImageClientHealth {
+    pub client: Box<dyn ImageClient>,
+    pub health: Result<(), AppError>,
+}
+
+impl ImageClientHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.health.is_ok()
+    }
+
+    /// Whether the failure looks transient (network, rate limit, 5xx) rather than
+    /// a configuration problem such as a bad API key
+    pub fn is_retryable(&self) -> bool {
+        self.health.as_ref().err().is_some_and(AppError::is_retryable)
+    }
+
+    /// Whether the check gave up waiting, which is worth a warning but not fatal
+    pub fn is_timeout(&self) -> bool {
+        self.health.as_ref().err().is_some_and(AppError::is_timeout)
+    }
+
+    /// Whether the provider rejected the configured credentials
+    pub fn is_auth_failure(&self) -> bool {
+        self.health.as_ref().err().is_some_and(AppError::is_authentication_error)
+    }
+}
 
 /// Factory for creating image search clients based on configuration
 pub struct ImageClientFactory;
@@ -23,6 +52,27 @@ impl ImageClientFactory {
         Self::from_env()
     }
 
+    /// Create an image client from the environment and run its health check
+    /// within the configured `health_check_timeout`, so bad credentials show up at startup
+    pub async fn create_and_check(&self) -> Result<ImageClientHealth, AppError> {
+        let config = ImageClientConfig::from_env()?;
+        let timeout = config.health_check_timeout;
+        let client = Self::create(config)?;
+        Ok(Self::check_client(client, timeout).await)
+    }
+
+    /// Run `health_check` on an existing client, failing with a timeout error if it exceeds `timeout`
+    pub async fn check_client(client: Box<dyn ImageClient>, timeout: Duration) -> ImageClientHealth {
+        let health = match tokio::time::timeout(timeout, client.health_check()).await {
+            Ok(result) => result,
+            Err(_) => Err(AppError::TimeoutError {
+                message: format!("{} health check timed out after {:?}", client.provider_name(), timeout),
+            }),
+        };
+
+        ImageClientHealth { client, health }
+    }
+
     /// Create an image client based on the provided configuration
     pub fn create(config: ImageClientConfig) -> Result<Box<dyn ImageClient>, AppError> {
         match config.provider {
@@ -31,7 +81,13 @@ impl ImageClientFactory {
                 Ok(Box::new(provider))
             }
             ImageProvider::Mock => {
-                let mock_client = MockImageClient::new();
+                let mock_client = MockImageClient::new()
+                    .with_prefer_thumbnails(config.prefer_thumbnails)
+                    .with_min_results(config.min_results);
+                let mock_client = match config.fallback_image_url {
+                    Some(url) => mock_client.with_fallback_image_url(url),
+                    None => mock_client,
+                };
                 Ok(Box::new(mock_client))
             }
         }
@@ -119,6 +175,49 @@ mod tests {
         assert!(available.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_check_client_reports_healthy_mock() {
+        let report = ImageClientFactory::check_client(Box::new(MockImageClient::new()), Duration::from_secs(1)).await;
+        assert!(report.is_healthy());
+        assert!(!report.is_retryable());
+        assert_eq!(report.client.provider_name(), "Mock");
+    }
+
+    #[tokio::test]
+    async fn test_check_client_reports_failing_mock() {
+        let report = ImageClientFactory::check_client(Box::new(MockImageClient::new().with_failure()), Duration::from_secs(1)).await;
+        assert!(!report.is_healthy());
+        assert!(!report.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_check_client_times_out_as_retryable() {
+        // The mock health check does not sleep, so wrap a client whose check never finishes in time
+        struct StalledClient;
+
+        #[async_trait::async_trait]
+        impl ImageClient for StalledClient {
+            async fn search_images(&self, _query: &str, _count: Option<usize>) -> Result<Vec<glossia_shared::ImageResult>, AppError> {
+                Ok(Vec::new())
+            }
+
+            fn provider_name(&self) -> &str {
+                "Stalled"
+            }
+
+            async fn health_check(&self) -> Result<(), AppError> {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(())
+            }
+        }
+
+        let report = ImageClientFactory::check_client(Box::new(StalledClient), Duration::from_millis(20)).await;
+        assert!(!report.is_healthy());
+        assert!(report.is_retryable());
+        assert!(report.is_timeout());
+        assert!(!report.is_auth_failure());
+    }
+
     #[test]
     fn test_create_mock_with_config() {
         let client = ImageClientFactory::create_mock_with_config(true, Some(100));
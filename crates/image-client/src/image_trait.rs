@@ -1,6 +1,24 @@
 use async_trait::async_trait;
 use glossia_shared::{AppError, ImageResult};
 
+/// Title of the placeholder image shown for a search without results
+pub const NO_RESULTS_TITLE: &str = "No results";
+
+/// Result of a search that reached the provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchOutcome {
+    pub results: Vec<ImageResult>,
+    /// The provider answered but found nothing, as opposed to failing
+    pub was_empty: bool,
+}
+
+impl SearchOutcome {
+    pub fn new(results: Vec<ImageResult>) -> Self {
+        let was_empty = results.is_empty();
+        Self { results, was_empty }
+    }
+}
+
 /// Trait for image search clients
 #[async_trait]
 pub trait ImageClient: Send + Sync {
@@ -12,6 +30,104 @@ pub trait ImageClient: Send + Sync {
     
     /// Check if the client is properly configured
     async fn health_check(&self) -> Result<(), AppError>;
+
+    /// Clamp a requested image count to what the provider supports
+    fn clamp_count(&self, requested: usize) -> usize {
+        requested.max(1)
+    }
+
+    /// Extra attempts `search_outcome` makes when a search finds nothing
+    fn empty_result_retries(&self) -> usize {
+        0
+    }
+
+    /// Search, retrying up to `empty_result_retries` times while the provider
+    /// answers with zero results
+    async fn search_outcome(&self, query: &str, count: Option<usize>) -> Result<SearchOutcome, AppError> {
+        let mut results = self.search_images(query, count).await?;
+        for _ in 0..self.empty_result_retries() {
+            if !results.is_empty() {
+                break;
+            }
+            results = self.search_images(query, count).await?;
+        }
+        Ok(SearchOutcome::new(results))
+    }
+
+    /// Fewest images a gallery should show before `search_gallery_with_fallback` broadens the query
+    fn min_results(&self) -> usize {
+        0
+    }
+
+    /// URL of the image a gallery shows when a search finds nothing; `None` leaves it empty
+    fn fallback_image_url(&self) -> Option<&str> {
+        None
+    }
+
+    /// Fetch a word's gallery with the configured number of images, clamped to provider limits
+    /// When nothing is found, the gallery holds just the `fallback_image_url` placeholder, if set
+    async fn search_gallery(&self, query: &str, images_per_word: usize) -> Result<Vec<ImageResult>, AppError> {
+        let count = self.clamp_count(images_per_word);
+        let images = self.search_outcome(query, Some(count)).await?.results;
+        Ok(or_placeholder(images, self.fallback_image_url()))
+    }
+
+    /// Like `search_gallery`, but when fewer than `min_results` images come back the
+    /// search is repeated with just `base_word` and new images are appended
+    async fn search_gallery_with_fallback(
+        &self,
+        query: &str,
+        base_word: &str,
+        images_per_word: usize,
+    ) -> Result<Vec<ImageResult>, AppError> {
+        let count = self.clamp_count(images_per_word);
+        let mut images = self.search_outcome(query, Some(count)).await?.results;
+        let min_results = self.min_results().min(self.clamp_count(images_per_word));
+        if images.len() >= min_results {
+            return Ok(or_placeholder(images, self.fallback_image_url()));
+        }
+
+        // The first results are already good, so a failed fallback keeps them
+        if let Some(broadened) = broaden_query(query, base_word) {
+            if let Ok(more) = self.search_outcome(&broadened, Some(count)).await {
+                merge_images(&mut images, more.results, count);
+            }
+        }
+        Ok(or_placeholder(images, self.fallback_image_url()))
+    }
+}
+
+/// `images`, or a single "no results" placeholder at `fallback_url` when there are none
+fn or_placeholder(images: Vec<ImageResult>, fallback_url: Option<&str>) -> Vec<ImageResult> {
+    match fallback_url {
+        Some(url) if images.is_empty() => vec![ImageResult {
+            url: url.to_string(),
+            thumbnail_url: url.to_string(),
+            title: NO_RESULTS_TITLE.to_string(),
+            width: None,
+            height: None,
+            display_url: Some(url.to_string()),
+        }],
+        _ => images,
+    }
+}
+
+/// Just the base word, unless the query already is that
+fn broaden_query(query: &str, base_word: &str) -> Option<String> {
+    let base_word = base_word.trim();
+    (!base_word.is_empty() && !query.trim().eq_ignore_ascii_case(base_word)).then(|| base_word.to_string())
+}
+
+/// Append images whose URL isn't already present, up to `limit` in total
+fn merge_images(images: &mut Vec<ImageResult>, more: Vec<ImageResult>, limit: usize) {
+    for image in more {
+        if images.len() >= limit {
+            break;
+        }
+        if !images.iter().any(|existing| existing.url == image.url) {
+            images.push(image);
+        }
+    }
 }
 
 /// Mock implementation for testing
@@ -20,6 +136,12 @@ pub struct MockImageClient {
     pub should_fail: bool,
     pub delay_ms: Option<u64>,
     pub custom_results: std::collections::HashMap<String, Vec<ImageResult>>,
+    pub max_count: Option<usize>,
+    pub prefer_thumbnails: bool,
+    pub min_results: usize,
+    pub fallback_image_url: Option<String>,
+    requested_counts: std::sync::Arc<std::sync::Mutex<Vec<Option<usize>>>>,
+    requested_queries: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
 }
 
 impl Default for MockImageClient {
@@ -34,6 +156,12 @@ impl MockImageClient {
             should_fail: false,
             delay_ms: None,
             custom_results: std::collections::HashMap::new(),
+            max_count: None,
+            prefer_thumbnails: true,
+            min_results: 0,
+            fallback_image_url: None,
+            requested_counts: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
+            requested_queries: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
         }
     }
 
@@ -52,6 +180,36 @@ impl MockImageClient {
         self
     }
 
+    pub fn with_max_count(mut self, max_count: usize) -> Self {
+        self.max_count = Some(max_count);
+        self
+    }
+
+    pub fn with_prefer_thumbnails(mut self, prefer: bool) -> Self {
+        self.prefer_thumbnails = prefer;
+        self
+    }
+
+    pub fn with_min_results(mut self, min_results: usize) -> Self {
+        self.min_results = min_results;
+        self
+    }
+
+    pub fn with_fallback_image_url(mut self, url: impl Into<String>) -> Self {
+        self.fallback_image_url = Some(url.into());
+        self
+    }
+
+    /// Counts passed to `search_images`, in call order
+    pub fn requested_counts(&self) -> Vec<Option<usize>> {
+        self.requested_counts.lock().unwrap().clone()
+    }
+
+    /// Queries passed to `search_images`, in call order
+    pub fn requested_queries(&self) -> Vec<String> {
+        self.requested_queries.lock().unwrap().clone()
+    }
+
     fn generate_mock_results(&self, query: &str, count: usize) -> Vec<ImageResult> {
         (0..count)
             .map(|i| ImageResult {
@@ -60,6 +218,7 @@ impl MockImageClient {
                 thumbnail_url: format!("https://example.com/{}_thumb_{}.jpg", query.replace(" ", "_"), i),
                 width: Some(800),
                 height: Some(600),
+                display_url: None,
             })
             .collect()
     }
@@ -72,6 +231,9 @@ impl ImageClient for MockImageClient {
             tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
         }
 
+        self.requested_counts.lock().unwrap().push(count);
+        self.requested_queries.lock().unwrap().push(query.to_string());
+
         if self.should_fail {
             return Err(AppError::api_error("Mock image client configured to fail"));
         }
@@ -79,17 +241,35 @@ impl ImageClient for MockImageClient {
         let count = count.unwrap_or(5);
 
         // Return custom results if available
-        if let Some(custom_results) = self.custom_results.get(query) {
-            Ok(custom_results.clone())
-        } else {
-            Ok(self.generate_mock_results(query, count))
-        }
+        let results = match self.custom_results.get(query) {
+            Some(custom_results) => custom_results.clone(),
+            None => self.generate_mock_results(query, count),
+        };
+        Ok(results
+            .into_iter()
+            .map(|image| image.with_display_preference(self.prefer_thumbnails))
+            .collect())
     }
 
     fn provider_name(&self) -> &str {
         "Mock"
     }
 
+    fn clamp_count(&self, requested: usize) -> usize {
+        match self.max_count {
+            Some(max_count) => requested.min(max_count).max(1),
+            None => requested.max(1),
+        }
+    }
+
+    fn min_results(&self) -> usize {
+        self.min_results
+    }
+
+    fn fallback_image_url(&self) -> Option<&str> {
+        self.fallback_image_url.as_deref()
+    }
+
     async fn health_check(&self) -> Result<(), AppError> {
         if self.should_fail {
             Err(AppError::api_error("Mock image client health check failed"))
@@ -130,6 +310,7 @@ mod tests {
                 thumbnail_url: "https://custom.com/thumb1.jpg".to_string(),
                 width: Some(800),
                 height: Some(600),
+                display_url: None,
             }
         ];
 
@@ -141,6 +322,25 @@ mod tests {
         assert_eq!(results[0].title, "Custom Image 1");
     }
 
+    #[tokio::test]
+    async fn test_mock_client_falls_back_to_full_url_without_thumbnail() {
+        let image = ImageResult {
+            url: "https://custom.com/full.jpg".to_string(),
+            title: "No thumbnail".to_string(),
+            thumbnail_url: String::new(),
+            width: None,
+            height: None,
+            display_url: None,
+        };
+        let client = MockImageClient::new().with_custom_results("custom".to_string(), vec![image]);
+
+        let results = client.search_images("custom", None).await.unwrap();
+        assert_eq!(results[0].primary_url(), "https://custom.com/full.jpg");
+
+        let results = MockImageClient::new().search_images("cat", Some(1)).await.unwrap();
+        assert_eq!(results[0].primary_url(), results[0].thumbnail_url);
+    }
+
     #[tokio::test]
     async fn test_mock_client_delay() {
         let client = MockImageClient::new().with_delay(50);
@@ -151,4 +351,78 @@ mod tests {
         
         assert!(elapsed >= std::time::Duration::from_millis(40)); // Allow some tolerance
     }
+
+    #[tokio::test]
+    async fn test_gallery_requests_configured_count() {
+        let client = MockImageClient::new();
+        let images = client.search_gallery("cat", 8).await.unwrap();
+
+        assert_eq!(client.requested_counts(), vec![Some(8)]);
+        assert_eq!(images.len(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_gallery_count_is_clamped_to_provider_limits() {
+        let client = MockImageClient::new().with_max_count(10);
+
+        client.search_gallery("cat", 50).await.unwrap();
+        client.search_gallery("cat", 0).await.unwrap();
+
+        assert_eq!(client.requested_counts(), vec![Some(10), Some(1)]);
+    }
+
+    fn custom_image(name: &str) -> ImageResult {
+        ImageResult {
+            url: format!("https://custom.com/{name}.jpg"),
+            title: name.to_string(),
+            thumbnail_url: format!("https://custom.com/{name}_thumb.jpg"),
+            width: None,
+            height: None,
+            display_url: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_under_count_triggers_broadened_search() {
+        let client = MockImageClient::new()
+            .with_min_results(3)
+            .with_custom_results("bank river erosion".to_string(), vec![custom_image("a")])
+            .with_custom_results("bank".to_string(), vec![custom_image("a"), custom_image("b"), custom_image("c")]);
+
+        let images = client.search_gallery_with_fallback("bank river erosion", "bank", 5).await.unwrap();
+
+        assert_eq!(client.requested_queries(), vec!["bank river erosion", "bank"]);
+        let titles: Vec<&str> = images.iter().map(|image| image.title.as_str()).collect();
+        assert_eq!(titles, vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_enough_results_skip_fallback() {
+        let client = MockImageClient::new().with_min_results(3);
+        let images = client.search_gallery_with_fallback("bank river", "bank", 5).await.unwrap();
+        assert_eq!(images.len(), 5);
+
+        let client = MockImageClient::new()
+            .with_min_results(3)
+            .with_custom_results("bank".to_string(), vec![custom_image("a")]);
+        client.search_gallery_with_fallback("Bank", "bank", 5).await.unwrap();
+
+        // Nothing broader to try when the query already is the base word
+        assert_eq!(client.requested_queries(), vec!["Bank"]);
+    }
+
+    #[tokio::test]
+    async fn test_empty_gallery_shows_configured_fallback() {
+        let client = MockImageClient::new().with_custom_results("zyxw".to_string(), vec![]);
+        assert!(client.search_gallery("zyxw", 5).await.unwrap().is_empty());
+
+        let client = client.with_fallback_image_url("https://example.com/no-results.png");
+        let images = client.search_gallery("zyxw", 5).await.unwrap();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].title, NO_RESULTS_TITLE);
+        assert_eq!(images[0].primary_url(), "https://example.com/no-results.png");
+
+        let images = client.search_gallery("cat", 2).await.unwrap();
+        assert!(images.iter().all(|image| image.title != NO_RESULTS_TITLE));
+    }
 }
@@ -4,9 +4,9 @@ mod config;
 mod factory;
 
 pub use brave_provider::BraveProvider;
-pub use image_trait::{ImageClient, MockImageClient};
-pub use config::{ImageClientConfig, ImageProvider};
-pub use factory::ImageClientFactory;
+pub use image_trait::{ImageClient, MockImageClient, SearchOutcome, NO_RESULTS_TITLE};
+pub use config::{ImageClientConfig, ImageProvider, DEFAULT_HEALTH_CHECK_TIMEOUT};
+pub use factory::{ImageClientFactory, ImageClientHealth};
 
 // Re-export commonly used types
 pub use glossia_shared::{ImageResult, AppError};
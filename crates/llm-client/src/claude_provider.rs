@@ -1,14 +1,24 @@
 use async_trait::async_trait;
-use glossia_shared::{AppError, SimplificationRequest, SimplificationResponse, ImageQueryOptimizationRequest, ImageQueryOptimizationResponse, WordMeaning};
-use glossia_http_client::{EnhancedHttpClient, HttpClient};
-use crate::{LLMClient, LLMConfig};
+use glossia_shared::{strip_code_fences, AppError, SimplificationRequest, SimplificationResponse, ImageQueryOptimizationRequest, ImageQueryOptimizationResponse, WordMeaning};
+use glossia_http_client::{CircuitState, EnhancedHttpClient, LatencyTracker, RequestId, RequestStats, RetryBudget};
+use crate::{LLMClient, LLMConfig, LLMOperation, PhraseImageQueries};
+use crate::prompt_sanitizer::{fence_user_text, USER_TEXT_NOTICE};
+use crate::image_query::{is_phrase, phrase_image_query_prompt, unoptimized_query, validate_optimized_query};
+use crate::json_retry::{parse_or_retry, STRICT_JSON_INSTRUCTION};
+use crate::meaning_retry::{meaning_or_retry, non_circular_instruction, non_circular_meaning_or_retry, truncate_meaning};
 use serde_json::{json, Value};
 use std::collections::HashMap;
+use tracing::instrument;
+
+/// Completion tokens when no `max_tokens` is configured
+const DEFAULT_MAX_TOKENS: u32 = 1024;
 
 /// Claude provider implementation (Anthropic)
 pub struct ClaudeProvider {
     client: EnhancedHttpClient,
     config: LLMConfig,
+    /// Completion latency keyed by `LLMOperation`, so each operation gets its own recommended timeout
+    operation_latency: LatencyTracker,
 }
 
 impl ClaudeProvider {
@@ -23,13 +33,15 @@ impl ClaudeProvider {
         headers.insert("Content-Type".to_string(), "application/json".to_string());
         headers.insert("anthropic-version".to_string(), "2023-06-01".to_string());
 
-        let client = EnhancedHttpClient::new()?
-            .with_headers(headers)
-            .with_timeout(config.timeout);
+        let client = EnhancedHttpClient::builder()
+            .headers(headers)
+            .timeout(config.timeout)
+            .build()?;
 
         Ok(Self {
             client,
             config,
+            operation_latency: LatencyTracker::new(),
         })
     }
 
@@ -39,16 +51,60 @@ impl ClaudeProvider {
     }
 
     fn get_model(&self) -> String {
-        self.config.model.clone()
-            .unwrap_or_else(|| "claude-3-haiku-20240307".to_string())
+        self.config.model_or_default()
+    }
+
+    fn max_tokens(&self) -> u32 {
+        self.config.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS)
+    }
+
+    async fn make_completion_request(&self, prompt: &str, operation: LLMOperation, budget: &RetryBudget) -> Result<String, AppError> {
+        self.make_completion_request_with_system(None, prompt, operation, budget).await
+    }
+
+    /// Completion request with optional system instructions kept apart from user content
+    async fn make_completion_request_with_system(&self, system: Option<&str>, prompt: &str, operation: LLMOperation, budget: &RetryBudget) -> Result<String, AppError> {
+        self.make_completion_request_with_limit(system, prompt, self.max_tokens(), operation, budget).await
     }
 
-    async fn make_completion_request(&self, prompt: &str) -> Result<String, AppError> {
+    /// Like `make_completion_request_with_system`, allowing up to `max_tokens` completion tokens
+    async fn make_completion_request_with_limit(&self, system: Option<&str>, prompt: &str, max_tokens: u32, operation: LLMOperation, budget: &RetryBudget) -> Result<String, AppError> {
         let url = format!("{}/messages", self.get_base_url());
-        
+        let request_body = self.build_completion_body(system, prompt, max_tokens);
+
+        let timeout = self.config.effective_timeout(operation, self.recommended_timeout(operation));
+        let started = std::time::Instant::now();
+        let response: Value = self.client
+            .post_json_with_budget(&url, request_body, timeout, budget)
+            .await?;
+        self.operation_latency.record(operation.as_str(), started.elapsed());
+
+        // Extract the response content
+        let content = response["content"][0]["text"]
+            .as_str()
+            .ok_or_else(|| AppError::api_error("Invalid response format from Claude"))?;
+
+        Ok(content.to_string())
+    }
+
+    /// Smallest possible completion, used to check the API is reachable
+    fn build_ping_body(&self) -> Value {
+        json!({
+            "model": self.get_model(),
+            "max_tokens": 1,
+            "messages": [
+                {
+                    "role": "user",
+                    "content": "ping"
+                }
+            ]
+        })
+    }
+
+    fn build_completion_body(&self, system: Option<&str>, prompt: &str, max_tokens: u32) -> Value {
         let mut request_body = json!({
             "model": self.get_model(),
-            "max_tokens": self.config.max_tokens.unwrap_or(1024),
+            "max_tokens": max_tokens,
             "messages": [
                 {
                     "role": "user",
@@ -57,28 +113,39 @@ impl ClaudeProvider {
             ]
         });
 
+        if let Some(system) = system {
+            request_body["system"] = json!(system);
+        }
+
         // Add optional parameters
         if let Some(temperature) = self.config.temperature {
             request_body["temperature"] = json!(temperature);
         }
 
-        let response: Value = self.client.post_json(&url, request_body).await?;
-
-        // Extract the response content
-        let content = response["content"][0]["text"]
-            .as_str()
-            .ok_or_else(|| AppError::api_error("Invalid response format from Claude"))?;
+        request_body
+    }
 
-        Ok(content.to_string())
+    fn build_simplification_system(&self) -> String {
+        if self.config.include_grammar_notes {
+            format!(
+                "You are a helpful assistant that simplifies text, identifies difficult words and notes notable grammar \
+                (e.g. \"passive voice\", \"subjunctive\") as short labels. \
+                Respond with JSON in this format: {{\"simplified\": \"simplified text\", \"words\": [{{\"word\": \"word\", \"meaning\": \"definition\", \"is_phrase\": false}}], \"grammar_notes\": [\"note\"]}}\n\n\
+                {USER_TEXT_NOTICE}"
+            )
+        } else {
+            format!(
+                "You are a helpful assistant that simplifies text and identifies difficult words. \
+                Respond with JSON in this format: {{\"simplified\": \"simplified text\", \"words\": [{{\"word\": \"word\", \"meaning\": \"definition\", \"is_phrase\": false}}]}}\n\n\
+                {USER_TEXT_NOTICE}"
+            )
+        }
     }
 
     fn parse_simplification_response(&self, content: &str, original: &str) -> Result<SimplificationResponse, AppError> {
         // Try to parse as JSON first
-        if let Ok(parsed) = serde_json::from_str::<Value>(content) {
-            let simplified = parsed["simplified"]
-                .as_str()
-                .unwrap_or(original)
-                .to_string();
+        if let Ok(parsed) = serde_json::from_str::<Value>(strip_code_fences(content)) {
+            let simplified = parsed["simplified"].as_str().unwrap_or_default();
 
             let words = if let Some(words_array) = parsed["words"].as_array() {
                 words_array.iter()
@@ -99,69 +166,154 @@ impl ClaudeProvider {
                 Vec::new()
             };
 
-            Ok(SimplificationResponse {
-                original: original.to_string(),
-                simplified,
-                words,
-            })
+            let grammar_notes = parsed["grammar_notes"]
+                .as_array()
+                .map(|notes| notes.iter().filter_map(|note| note.as_str().map(str::to_string)).collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            Ok(SimplificationResponse::builder(original)
+                .simplified(simplified)
+                .words(words)
+                .grammar_notes(grammar_notes)
+                .build())
         } else {
             // Fallback: treat entire response as simplified text
-            Ok(SimplificationResponse {
-                original: original.to_string(),
-                simplified: content.to_string(),
-                words: Vec::new(),
-            })
+            Ok(SimplificationResponse::builder(original)
+                .simplified(content)
+                .build())
         }
     }
 }
 
 #[async_trait]
 impl LLMClient for ClaudeProvider {
+    #[instrument(skip(self, request), fields(provider = "Claude", model = %self.get_model(), sentence_length = request.sentence.len(), request_id = tracing::field::Empty))]
     async fn simplify(&self, request: SimplificationRequest) -> Result<SimplificationResponse, AppError> {
-        let prompt = format!(
-            "You are a helpful assistant that simplifies text and identifies difficult words. \
-            Respond with JSON in this format: {{\"simplified\": \"simplified text\", \"words\": [{{\"word\": \"word\", \"meaning\": \"definition\", \"is_phrase\": false}}]}}\n\n\
-            Simplify this sentence and identify difficult words: {}",
-            request.sentence
-        );
+        let system = self.build_simplification_system();
+        let prompt = self.config.render_simplification_prompt(&request.sentence).unwrap_or_else(|| {
+            format!(
+                "Simplify this sentence and identify difficult words:\n{}",
+                fence_user_text(&request.sentence)
+            )
+        });
 
-        let response_content = self.make_completion_request(&prompt).await?;
-        self.parse_simplification_response(&response_content, &request.sentence)
+        let request_id = RequestId::new();
+        request_id.record_in_current_span();
+        request_id.scope(async {
+            let budget = self.config.new_retry_budget();
+            let response_content = self.make_completion_request_with_system(Some(&system), &prompt, LLMOperation::Simplify, &budget).await?;
+            self.parse_simplification_response(&response_content, &request.sentence)
+        }).await
     }
 
+    #[instrument(skip(self, context), fields(provider = "Claude", model = %self.get_model(), word = word, context_length = context.len()))]
     async fn get_word_meaning(&self, word: &str, context: &str) -> Result<String, AppError> {
+        let language_instruction = match &self.config.definition_language {
+            Some(language) => format!(" Write the definition in {language}, keeping the word itself in English."),
+            None => String::new(),
+        };
+        let system = format!("Provide a brief definition of the user's word as used in the given context.{language_instruction} {USER_TEXT_NOTICE}");
         let prompt = format!(
-            "What does the word '{word}' mean in this context: '{context}'? Provide a brief definition."
+            "Word:\n{}\n\nContext:\n{}",
+            fence_user_text(word),
+            fence_user_text(context)
         );
 
-        self.make_completion_request(&prompt).await
+        let budget = self.config.new_retry_budget();
+        let meaning = self.make_completion_request_with_system(Some(&system), &prompt, LLMOperation::WordMeaning, &budget).await?;
+        // An empty meaning may have run out of tokens, so the retry gets twice the limit
+        let meaning = meaning_or_retry(meaning, word, self.config.retry_empty_meaning, || {
+            let retry_max_tokens = self.max_tokens().saturating_mul(2);
+            self.make_completion_request_with_limit(Some(&system), &prompt, retry_max_tokens, LLMOperation::WordMeaning, &budget)
+        }).await?;
+        let strict_system = format!("{system} {}", non_circular_instruction(word));
+        let meaning = non_circular_meaning_or_retry(meaning, word, || {
+            self.make_completion_request_with_system(Some(&strict_system), &prompt, LLMOperation::WordMeaning, &budget)
+        }).await?;
+        Ok(truncate_meaning(meaning, word, self.config.max_definition_words, self.config.max_definition_chars))
     }
 
+    #[instrument(skip(self, request), fields(provider = "Claude", model = %self.get_model(), word = %request.word, context_length = request.sentence_context.len()))]
     async fn optimize_image_query(&self, request: ImageQueryOptimizationRequest) -> Result<ImageQueryOptimizationResponse, AppError> {
-        let prompt = format!(
-            "Optimize this word for image search: '{}'. Context: '{}'. \
-            Make it more specific and visual. Respond with just the optimized query.",
-            request.word,
-            request.sentence_context
-        );
+        let prompt = if is_phrase(&request.word) {
+            if self.config.phrase_image_queries == PhraseImageQueries::Skip {
+                return Ok(unoptimized_query(&request.word));
+            }
+            phrase_image_query_prompt(&request)
+        } else {
+            format!(
+                "Optimize this word for image search: '{}'. Context: '{}'. \
+                Make it more specific and visual, maximum 4 words. \
+                Respond ONLY with JSON in this format: {{\"optimized_query\": \"your query\"}}",
+                request.word,
+                request.sentence_context
+            )
+        };
 
-        let optimized_query = self.make_completion_request(&prompt).await?;
-        
-        Ok(ImageQueryOptimizationResponse {
-            optimized_query: optimized_query.trim().to_string(),
-        })
+        // The JSON re-request draws from the same budget as the first request
+        let budget = self.config.new_retry_budget();
+        let response_content = self.make_completion_request(&prompt, LLMOperation::ImageQuery, &budget).await?;
+        let optimization_response: ImageQueryOptimizationResponse = parse_or_retry(
+            &response_content,
+            "image query optimization",
+            || {
+                let retry_prompt = format!("{prompt}\n\n{STRICT_JSON_INSTRUCTION}");
+                async move { self.make_completion_request(&retry_prompt, LLMOperation::ImageQuery, &budget).await }
+            },
+        ).await?;
+
+        Ok(validate_optimized_query(optimization_response, &request.word))
     }
 
     fn provider_name(&self) -> &str {
         "Claude"
     }
 
+    /// Single-token completion instead of a full health check
+    async fn ping(&self) -> Result<(), AppError> {
+        let url = format!("{}/messages", self.get_base_url());
+        let _response: Value = self.client.post_json_unlimited(&url, self.build_ping_body()).await?;
+        Ok(())
+    }
+
+    fn model_name(&self) -> String {
+        self.get_model()
+    }
+
+    fn request_stats(&self) -> Option<RequestStats> {
+        Some(self.client.get_request_stats())
+    }
+
+    async fn circuit_state(&self) -> Option<CircuitState> {
+        Some(self.client.circuit_state().await)
+    }
+
+    async fn reset_circuit(&self) {
+        self.client.reset_circuit().await;
+    }
+
+    async fn set_rate_limit(&self, permits: usize, window: std::time::Duration) -> Result<(), AppError> {
+        self.client.set_rate_limit(&self.get_base_url(), permits, window).await
+    }
+
+    fn recommended_timeout(&self, operation: LLMOperation) -> Option<std::time::Duration> {
+        self.operation_latency
+            .stats(operation.as_str())
+            .and_then(|stats| stats.recommended_timeout())
+    }
+
     async fn health_check(&self) -> Result<(), AppError> {
         // Claude doesn't have a simple health check endpoint like OpenAI
         // We'll do a minimal completion request instead
-        let prompt = "Hello";
-        let _response = self.make_completion_request(prompt).await?;
-        Ok(())
+        // Bypass the rate limiter so startup health checks do not queue behind user requests
+        let url = format!("{}/messages", self.get_base_url());
+        let response: Value = self.client.post_json_unlimited(&url, self.build_completion_body(None, "Hello", self.max_tokens())).await?;
+
+        if response["content"][0]["text"].is_string() {
+            Ok(())
+        } else {
+            Err(AppError::api_error("Invalid response format from Claude"))
+        }
     }
 }
 
@@ -179,6 +331,99 @@ mod tests {
         assert!(provider.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_model_name_reports_configured_model() {
+        let config = LLMConfig::new(ProviderType::Claude)
+            .with_api_key("test-key".to_string());
+        assert_eq!(ClaudeProvider::new(config).unwrap().model_name(), "claude-3-haiku-20240307");
+
+        let config = LLMConfig::new(ProviderType::Claude)
+            .with_api_key("test-key".to_string())
+            .with_model("claude-3-5-sonnet-20241022".to_string());
+        assert_eq!(ClaudeProvider::new(config).unwrap().model_name(), "claude-3-5-sonnet-20241022");
+    }
+
+    #[tokio::test]
+    async fn test_ping_body_requests_a_single_token() {
+        let config = LLMConfig::new(ProviderType::Claude)
+            .with_api_key("test-key".to_string())
+            .with_max_tokens(2048);
+        let provider = ClaudeProvider::new(config).unwrap();
+
+        let body = provider.build_ping_body();
+        assert_eq!(body["max_tokens"], 1);
+        assert_eq!(body["messages"].as_array().unwrap().len(), 1);
+        assert!(body.get("system").is_none());
+    }
+
+    /// Server answering every request with `text` as the completion, recording request bodies
+    async fn spawn_recording_messages_server(text: &'static str) -> (String, std::sync::Arc<std::sync::Mutex<Vec<Value>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    loop {
+                        let Ok(n) = socket.read(&mut buf).await else { return };
+                        if n == 0 {
+                            return;
+                        }
+                        request.extend_from_slice(&buf[..n]);
+                        let text = String::from_utf8_lossy(&request);
+                        if let Some(header_end) = text.find("\r\n\r\n") {
+                            let content_length = text[..header_end]
+                                .lines()
+                                .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap_or(0)))
+                                .unwrap_or(0);
+                            if request.len() >= header_end + 4 + content_length {
+                                if let Ok(body) = serde_json::from_slice::<Value>(&request[header_end + 4..]) {
+                                    recorded.lock().unwrap().push(body);
+                                }
+                                break;
+                            }
+                        }
+                    }
+
+                    let body = json!({"content": [{"type": "text", "text": text}]}).to_string();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        (format!("http://{addr}"), requests)
+    }
+
+    #[tokio::test]
+    async fn test_empty_word_meaning_retries_with_a_larger_token_limit() {
+        let (base_url, requests) = spawn_recording_messages_server("  ").await;
+        let config = LLMConfig::new(ProviderType::Claude)
+            .with_api_key("test-key".to_string())
+            .with_base_url(base_url)
+            .with_max_tokens(100);
+        let provider = ClaudeProvider::new(config).unwrap();
+
+        let meaning = provider.get_word_meaning("feline", "The feline sat.").await.unwrap();
+        assert_eq!(meaning, crate::NO_DEFINITION_PLACEHOLDER);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0]["max_tokens"], 100);
+        assert_eq!(requests[1]["max_tokens"], 200);
+    }
+
     #[tokio::test]
     async fn test_claude_provider_without_api_key() {
         let config = LLMConfig::new(ProviderType::Claude);
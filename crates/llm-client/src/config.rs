@@ -1,5 +1,41 @@
 use glossia_shared::AppError;
+use glossia_http_client::RetryBudget;
+use crate::prompt_sanitizer::fence_user_text;
+use std::path::Path;
 use std::time::Duration;
+use tracing::warn;
+
+/// Model used for OpenAI when none is configured
+pub const DEFAULT_OPENAI_MODEL: &str = "gpt-4o-mini";
+
+/// Model used for Claude when none is configured
+pub const DEFAULT_CLAUDE_MODEL: &str = "claude-3-haiku-20240307";
+
+/// Learner level the simplification prompts target, substituted for `{level}`
+pub const SIMPLIFICATION_LEVEL: &str = "C1/C2";
+
+/// Longest word meaning, in words, kept whole; matches the limit the prompts ask for
+pub const DEFAULT_MAX_DEFINITION_WORDS: usize = 15;
+
+const SENTENCE_PLACEHOLDER: &str = "{sentence}";
+const LEVEL_PLACEHOLDER: &str = "{level}";
+
+const KNOWN_OPENAI_MODELS: &[&str] = &[
+    "gpt-4o-mini",
+    "gpt-4o",
+    "gpt-4-turbo",
+    "gpt-4",
+    "gpt-3.5-turbo",
+];
+
+const KNOWN_CLAUDE_MODELS: &[&str] = &[
+    "claude-3-haiku-20240307",
+    "claude-3-sonnet-20240229",
+    "claude-3-opus-20240229",
+    "claude-3-5-sonnet-20240620",
+    "claude-3-5-sonnet-20241022",
+    "claude-3-5-haiku-20241022",
+];
 
 /// Supported LLM providers
 #[derive(Debug, Clone, PartialEq)]
@@ -9,6 +45,26 @@ pub enum ProviderType {
     Mock,
 }
 
+impl ProviderType {
+    /// Model used when the configuration doesn't name one
+    pub fn default_model(&self) -> &'static str {
+        match self {
+            ProviderType::OpenAI => DEFAULT_OPENAI_MODEL,
+            ProviderType::Claude => DEFAULT_CLAUDE_MODEL,
+            ProviderType::Mock => "mock",
+        }
+    }
+
+    /// Models this provider is known to serve; empty when any name is accepted
+    pub fn known_models(&self) -> &'static [&'static str] {
+        match self {
+            ProviderType::OpenAI => KNOWN_OPENAI_MODELS,
+            ProviderType::Claude => KNOWN_CLAUDE_MODELS,
+            ProviderType::Mock => &[],
+        }
+    }
+}
+
 impl std::str::FromStr for ProviderType {
     type Err = AppError;
 
@@ -22,6 +78,48 @@ impl std::str::FromStr for ProviderType {
     }
 }
 
+/// How image queries are built for multi-word phrases ("heavy heart"),
+/// which make poor literal image searches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PhraseImageQueries {
+    /// Ask for a query depicting what the phrase means, not its words
+    #[default]
+    PhrasePrompt,
+    /// Don't call the LLM; the phrase itself is the query
+    Skip,
+}
+
+impl std::str::FromStr for PhraseImageQueries {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "prompt" | "phrase_prompt" => Ok(PhraseImageQueries::PhrasePrompt),
+            "skip" => Ok(PhraseImageQueries::Skip),
+            _ => Err(AppError::config_error(format!("Unknown phrase image query mode: {s}"))),
+        }
+    }
+}
+
+/// Kinds of LLM request, which can each have their own timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LLMOperation {
+    Simplify,
+    WordMeaning,
+    ImageQuery,
+}
+
+impl LLMOperation {
+    /// Stable name, e.g. for keying per-operation latency
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LLMOperation::Simplify => "simplify",
+            LLMOperation::WordMeaning => "word_meaning",
+            LLMOperation::ImageQuery => "image_query",
+        }
+    }
+}
+
 /// Configuration for LLM clients
 #[derive(Debug, Clone)]
 pub struct LLMConfig {
@@ -33,6 +131,32 @@ pub struct LLMConfig {
     pub max_retries: usize,
     pub temperature: Option<f32>,
     pub max_tokens: Option<u32>,
+    /// Per-operation timeouts; `timeout` applies when unset
+    pub simplify_timeout: Option<Duration>,
+    pub word_meaning_timeout: Option<Duration>,
+    pub image_query_timeout: Option<Duration>,
+    /// Most HTTP attempts one operation may make across all retry layers; unlimited when unset
+    pub retry_budget: Option<usize>,
+    /// Ask OpenAI for schema-constrained simplification output on models that support it
+    pub structured_output: bool,
+    /// Re-request a word meaning once, with more tokens, when the reply is empty
+    pub retry_empty_meaning: bool,
+    /// Re-request a JSON completion once, with more tokens, when the model hit its token limit
+    pub retry_on_truncation: bool,
+    /// Language for word definitions (e.g. "Portuguese"); `None` keeps simple English
+    pub definition_language: Option<String>,
+    /// Ask for short grammar notes ("passive voice") alongside each simplification
+    pub include_grammar_notes: bool,
+    /// Replaces the built-in simplification prompt; must contain `{sentence}` and may contain `{level}`
+    pub simplification_prompt_template: Option<String>,
+    /// OpenAI model to retry with once when the configured model is reported as not found
+    pub fallback_model: Option<String>,
+    /// Word meanings longer than this many words are cut short with an ellipsis
+    pub max_definition_words: Option<usize>,
+    /// Word meanings longer than this many characters are cut short with an ellipsis
+    pub max_definition_chars: Option<usize>,
+    /// How image queries are optimized when the word is a multi-word phrase
+    pub phrase_image_queries: PhraseImageQueries,
 }
 
 impl Default for LLMConfig {
@@ -46,6 +170,20 @@ impl Default for LLMConfig {
             max_retries: 3,
             temperature: None,
             max_tokens: None,
+            simplify_timeout: None,
+            word_meaning_timeout: None,
+            image_query_timeout: None,
+            retry_budget: None,
+            structured_output: false,
+            retry_empty_meaning: true,
+            retry_on_truncation: true,
+            definition_language: None,
+            include_grammar_notes: false,
+            simplification_prompt_template: None,
+            fallback_model: None,
+            max_definition_words: Some(DEFAULT_MAX_DEFINITION_WORDS),
+            max_definition_chars: None,
+            phrase_image_queries: PhraseImageQueries::default(),
         }
     }
 }
@@ -59,6 +197,13 @@ impl LLMConfig {
     }
 
     pub fn from_env() -> Result<Self, AppError> {
+        Self::from_env_with(false)
+    }
+
+    /// Like `from_env`, but with `defer_missing_key` a missing API key yields a
+    /// config with `api_key: None` instead of an error, so the app can start and
+    /// ask for a key. `validate()` (run when a provider is created) still rejects it.
+    pub fn from_env_with(defer_missing_key: bool) -> Result<Self, AppError> {
         // Load .env file and provide helpful error if it fails
         match dotenvy::dotenv() {
             Ok(path) => {
@@ -73,20 +218,8 @@ impl LLMConfig {
         let provider = provider_str.parse()?;
 
         let api_key = match provider {
-            ProviderType::OpenAI => {
-                tracing::debug!("Loading OpenAI API key from environment");
-                let key = std::env::var("OPENAI_API_KEY")
-                    .map_err(|_| AppError::config_error("OPENAI_API_KEY environment variable must be set. Please check your .env file or environment variables."))?;
-                tracing::debug!("OpenAI API key loaded successfully (length: {})", key.len());
-                Some(key)
-            }
-            ProviderType::Claude => {
-                tracing::debug!("Loading Claude API key from environment");
-                let key = std::env::var("CLAUDE_API_KEY")
-                    .map_err(|_| AppError::config_error("CLAUDE_API_KEY environment variable must be set. Please check your .env file or environment variables."))?;
-                tracing::debug!("Claude API key loaded successfully (length: {})", key.len());
-                Some(key)
-            }
+            ProviderType::OpenAI => api_key_from_env("OPENAI_API_KEY", defer_missing_key)?,
+            ProviderType::Claude => api_key_from_env("CLAUDE_API_KEY", defer_missing_key)?,
             ProviderType::Mock => None,
         };
 
@@ -94,7 +227,8 @@ impl LLMConfig {
             ProviderType::OpenAI => std::env::var("OPENAI_BASE_URL").ok(),
             ProviderType::Claude => std::env::var("CLAUDE_BASE_URL").ok(),
             ProviderType::Mock => None,
-        };
+        }
+        .map(|url| normalize_base_url(&url));
 
         let model = match provider {
             ProviderType::OpenAI => std::env::var("OPENAI_MODEL").ok(),
@@ -102,6 +236,11 @@ impl LLMConfig {
             ProviderType::Mock => None,
         };
 
+        let fallback_model = match provider {
+            ProviderType::OpenAI => std::env::var("OPENAI_FALLBACK_MODEL").ok().filter(|s| !s.trim().is_empty()),
+            _ => None,
+        };
+
         let timeout = std::env::var("LLM_TIMEOUT")
             .ok()
             .and_then(|s| s.parse().ok())
@@ -121,6 +260,19 @@ impl LLMConfig {
             .ok()
             .and_then(|s| s.parse().ok());
 
+        // A template file takes precedence over an inline template
+        let simplification_prompt_template = match std::env::var("LLM_SIMPLIFICATION_PROMPT_FILE") {
+            Ok(path) => Some(read_prompt_template(Path::new(&path))?),
+            Err(_) => std::env::var("LLM_SIMPLIFICATION_PROMPT").ok().filter(|s| !s.trim().is_empty()),
+        };
+
+        let timeout_from_env = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .map(Duration::from_secs)
+        };
+
         Ok(Self {
             provider,
             api_key,
@@ -130,6 +282,39 @@ impl LLMConfig {
             max_retries,
             temperature,
             max_tokens,
+            simplify_timeout: timeout_from_env("LLM_SIMPLIFY_TIMEOUT"),
+            word_meaning_timeout: timeout_from_env("LLM_WORD_MEANING_TIMEOUT"),
+            image_query_timeout: timeout_from_env("LLM_IMAGE_QUERY_TIMEOUT"),
+            retry_budget: std::env::var("LLM_RETRY_BUDGET").ok().and_then(|s| s.parse().ok()),
+            structured_output: std::env::var("LLM_STRUCTURED_OUTPUT")
+                .map(|s| matches!(s.to_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(false),
+            retry_empty_meaning: std::env::var("LLM_RETRY_EMPTY_MEANING")
+                .map(|s| !matches!(s.to_lowercase().as_str(), "0" | "false" | "no"))
+                .unwrap_or(true),
+            retry_on_truncation: std::env::var("LLM_RETRY_ON_TRUNCATION")
+                .map(|s| !matches!(s.to_lowercase().as_str(), "0" | "false" | "no"))
+                .unwrap_or(true),
+            definition_language: std::env::var("LLM_DEFINITION_LANGUAGE")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            include_grammar_notes: std::env::var("LLM_INCLUDE_GRAMMAR_NOTES")
+                .map(|s| matches!(s.to_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(false),
+            simplification_prompt_template,
+            fallback_model,
+            // 0 turns the word limit off
+            max_definition_words: std::env::var("LLM_MAX_DEFINITION_WORDS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .map_or(Some(DEFAULT_MAX_DEFINITION_WORDS), |n: usize| (n > 0).then_some(n)),
+            max_definition_chars: std::env::var("LLM_MAX_DEFINITION_CHARS").ok().and_then(|s| s.parse().ok()).filter(|&n| n > 0),
+            phrase_image_queries: std::env::var("LLM_PHRASE_IMAGE_QUERIES")
+                .ok()
+                .map(|s| s.parse())
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 
@@ -139,7 +324,7 @@ impl LLMConfig {
     }
 
     pub fn with_base_url(mut self, base_url: String) -> Self {
-        self.base_url = Some(base_url);
+        self.base_url = Some(normalize_base_url(&base_url));
         self
     }
 
@@ -168,7 +353,131 @@ impl LLMConfig {
         self
     }
 
+    pub fn with_simplify_timeout(mut self, timeout: Duration) -> Self {
+        self.simplify_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_word_meaning_timeout(mut self, timeout: Duration) -> Self {
+        self.word_meaning_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_image_query_timeout(mut self, timeout: Duration) -> Self {
+        self.image_query_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_retry_budget(mut self, max_attempts: usize) -> Self {
+        self.retry_budget = Some(max_attempts);
+        self
+    }
+
+    pub fn with_structured_output(mut self, enabled: bool) -> Self {
+        self.structured_output = enabled;
+        self
+    }
+
+    pub fn with_retry_empty_meaning(mut self, enabled: bool) -> Self {
+        self.retry_empty_meaning = enabled;
+        self
+    }
+
+    pub fn with_retry_on_truncation(mut self, enabled: bool) -> Self {
+        self.retry_on_truncation = enabled;
+        self
+    }
+
+    pub fn with_definition_language(mut self, language: impl Into<String>) -> Self {
+        self.definition_language = Some(language.into());
+        self
+    }
+
+    pub fn with_grammar_notes(mut self, enabled: bool) -> Self {
+        self.include_grammar_notes = enabled;
+        self
+    }
+
+    pub fn with_simplification_prompt_template(mut self, template: impl Into<String>) -> Self {
+        self.simplification_prompt_template = Some(template.into());
+        self
+    }
+
+    pub fn with_fallback_model(mut self, model: impl Into<String>) -> Self {
+        self.fallback_model = Some(model.into());
+        self
+    }
+
+    /// Limit word meanings to `max_words` words and `max_chars` characters; `None` leaves that limit off
+    pub fn with_max_definition_length(mut self, max_words: Option<usize>, max_chars: Option<usize>) -> Self {
+        self.max_definition_words = max_words;
+        self.max_definition_chars = max_chars;
+        self
+    }
+
+    pub fn with_phrase_image_queries(mut self, mode: PhraseImageQueries) -> Self {
+        self.phrase_image_queries = mode;
+        self
+    }
+
+    /// Read the simplification prompt template from a file
+    pub fn with_simplification_prompt_file(mut self, path: impl AsRef<Path>) -> Result<Self, AppError> {
+        self.simplification_prompt_template = Some(read_prompt_template(path.as_ref())?);
+        Ok(self)
+    }
+
+    /// The custom simplification prompt for `sentence`, fenced like the built-in
+    /// prompts, or `None` to use the built-in one
+    pub fn render_simplification_prompt(&self, sentence: &str) -> Option<String> {
+        let template = self.simplification_prompt_template.as_ref()?;
+        // `{level}` first, so a sentence containing the text "{level}" is left alone
+        Some(
+            template
+                .replace(LEVEL_PLACEHOLDER, SIMPLIFICATION_LEVEL)
+                .replace(SENTENCE_PLACEHOLDER, &fence_user_text(sentence)),
+        )
+    }
+
+    /// Fresh attempt budget for one operation, shared by every layer that may retry it
+    pub fn new_retry_budget(&self) -> RetryBudget {
+        self.retry_budget.map_or_else(RetryBudget::unlimited, RetryBudget::new)
+    }
+
+    /// Timeout for one kind of request, falling back to `timeout`
+    pub fn timeout_for(&self, operation: LLMOperation) -> Duration {
+        self.effective_timeout(operation, None)
+    }
+
+    /// Like `timeout_for`, but an operation without its own timeout uses
+    /// `recommended` (from observed latency) when known, capped at `timeout`
+    pub fn effective_timeout(&self, operation: LLMOperation, recommended: Option<Duration>) -> Duration {
+        let explicit = match operation {
+            LLMOperation::Simplify => self.simplify_timeout,
+            LLMOperation::WordMeaning => self.word_meaning_timeout,
+            LLMOperation::ImageQuery => self.image_query_timeout,
+        };
+        explicit.unwrap_or_else(|| recommended.map_or(self.timeout, |recommended| recommended.min(self.timeout)))
+    }
+
+    /// Configured model, or the provider default when unset
+    pub fn model_or_default(&self) -> String {
+        self.model.clone()
+            .unwrap_or_else(|| self.provider.default_model().to_string())
+    }
+
+    /// Configured model when it isn't in the provider's known set
+    pub fn unknown_model(&self) -> Option<&str> {
+        let model = self.model.as_deref()?;
+        let known = self.provider.known_models();
+        if known.is_empty() || known.contains(&model) {
+            None
+        } else {
+            Some(model)
+        }
+    }
+
     /// Validate the configuration
+    /// Unknown model names only log a warning, since providers release new models often
     pub fn validate(&self) -> Result<(), AppError> {
         match self.provider {
             ProviderType::OpenAI => {
@@ -209,6 +518,14 @@ impl LLMConfig {
             }
         }
 
+        if let Some(ref base_url) = self.base_url {
+            if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+                return Err(AppError::config_error(format!(
+                    "Invalid base URL '{base_url}'. The URL must start with http:// or https://"
+                )));
+            }
+        }
+
         if let Some(temperature) = self.temperature {
             if !(0.0..=2.0).contains(&temperature) {
                 return Err(AppError::config_error(
@@ -217,6 +534,14 @@ impl LLMConfig {
             }
         }
 
+        if let Some(ref template) = self.simplification_prompt_template {
+            if !template.contains(SENTENCE_PLACEHOLDER) {
+                return Err(AppError::config_error(format!(
+                    "Simplification prompt template must contain the {SENTENCE_PLACEHOLDER} placeholder"
+                )));
+            }
+        }
+
         if let Some(max_tokens) = self.max_tokens {
             if max_tokens == 0 {
                 return Err(AppError::config_error(
@@ -225,6 +550,199 @@ impl LLMConfig {
             }
         }
 
+        if let Some(model) = self.unknown_model() {
+            warn!(
+                "Model '{}' is not a known {:?} model; using it anyway",
+                model, self.provider
+            );
+        }
+
         Ok(())
     }
 }
+
+fn read_prompt_template(path: &Path) -> Result<String, AppError> {
+    std::fs::read_to_string(path).map_err(|e| {
+        AppError::config_error(format!("Failed to read prompt template {}: {e}", path.display()))
+    })
+}
+
+/// Read a provider API key, returning `None` for a missing key when deferred
+fn api_key_from_env(var: &str, defer_missing_key: bool) -> Result<Option<String>, AppError> {
+    tracing::debug!("Loading {} from environment", var);
+    match std::env::var(var) {
+        Ok(key) => {
+            tracing::debug!("{} loaded successfully (length: {})", var, key.len());
+            Ok(Some(key))
+        }
+        Err(_) if defer_missing_key => {
+            tracing::warn!("{} is not set; the API key must be provided before first use", var);
+            Ok(None)
+        }
+        Err(_) => Err(AppError::config_error(format!(
+            "{var} environment variable must be set. Please check your .env file or environment variables."
+        ))),
+    }
+}
+
+/// Trim surrounding whitespace and trailing slashes so endpoint paths can be appended safely
+fn normalize_base_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url_trailing_slash_is_stripped() {
+        let config = LLMConfig::new(ProviderType::OpenAI)
+            .with_base_url("https://api.openai.com/v1/".to_string());
+        assert_eq!(config.base_url.as_deref(), Some("https://api.openai.com/v1"));
+
+        let config = LLMConfig::new(ProviderType::OpenAI)
+            .with_base_url("https://api.openai.com/v1".to_string());
+        assert_eq!(config.base_url.as_deref(), Some("https://api.openai.com/v1"));
+    }
+
+    #[test]
+    fn test_invalid_base_url_scheme_rejected() {
+        let config = LLMConfig::new(ProviderType::Mock)
+            .with_base_url("ftp://api.openai.com/v1".to_string());
+
+        let result = config.validate();
+        assert!(matches!(result, Err(AppError::ConfigError { .. })));
+    }
+
+    #[test]
+    fn test_valid_base_url_scheme_accepted() {
+        let config = LLMConfig::new(ProviderType::Mock)
+            .with_base_url("http://localhost:8080/v1/".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_default_model_applied_when_unset() {
+        assert_eq!(LLMConfig::new(ProviderType::OpenAI).model_or_default(), DEFAULT_OPENAI_MODEL);
+        assert_eq!(LLMConfig::new(ProviderType::Claude).model_or_default(), DEFAULT_CLAUDE_MODEL);
+        assert!(ProviderType::OpenAI.known_models().contains(&DEFAULT_OPENAI_MODEL));
+        assert!(ProviderType::Claude.known_models().contains(&DEFAULT_CLAUDE_MODEL));
+    }
+
+    #[test]
+    fn test_unknown_model_warns_but_validates() {
+        let config = LLMConfig::new(ProviderType::Claude)
+            .with_api_key("claude-key".to_string())
+            .with_model("claude-next-preview".to_string());
+
+        assert_eq!(config.unknown_model(), Some("claude-next-preview"));
+        assert!(config.validate().is_ok());
+        assert_eq!(config.model_or_default(), "claude-next-preview");
+    }
+
+    #[test]
+    fn test_known_model_is_not_flagged() {
+        let config = LLMConfig::new(ProviderType::OpenAI).with_model("gpt-4o".to_string());
+        assert_eq!(config.unknown_model(), None);
+        assert_eq!(LLMConfig::new(ProviderType::OpenAI).unknown_model(), None);
+    }
+
+    #[test]
+    fn test_recommended_timeout_applies_only_without_explicit_override() {
+        let config = LLMConfig::new(ProviderType::OpenAI)
+            .with_timeout(Duration::from_secs(30))
+            .with_simplify_timeout(Duration::from_secs(60));
+        let recommended = Some(Duration::from_secs(6));
+
+        assert_eq!(config.effective_timeout(LLMOperation::Simplify, recommended), Duration::from_secs(60));
+        assert_eq!(config.effective_timeout(LLMOperation::WordMeaning, recommended), Duration::from_secs(6));
+        assert_eq!(config.effective_timeout(LLMOperation::WordMeaning, None), Duration::from_secs(30));
+        assert_eq!(
+            config.effective_timeout(LLMOperation::ImageQuery, Some(Duration::from_secs(90))),
+            Duration::from_secs(30)
+        );
+    }
+
+    #[test]
+    fn test_operation_timeouts_fall_back_to_timeout() {
+        let config = LLMConfig::new(ProviderType::OpenAI)
+            .with_timeout(Duration::from_secs(20))
+            .with_word_meaning_timeout(Duration::from_secs(5));
+
+        assert_eq!(config.timeout_for(LLMOperation::WordMeaning), Duration::from_secs(5));
+        assert_eq!(config.timeout_for(LLMOperation::Simplify), Duration::from_secs(20));
+        assert_eq!(config.timeout_for(LLMOperation::ImageQuery), Duration::from_secs(20));
+
+        let config = config
+            .with_simplify_timeout(Duration::from_secs(60))
+            .with_image_query_timeout(Duration::from_secs(8));
+        assert_eq!(config.timeout_for(LLMOperation::Simplify), Duration::from_secs(60));
+        assert_eq!(config.timeout_for(LLMOperation::ImageQuery), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn test_retry_budget_is_fresh_per_operation() {
+        assert!(!LLMConfig::new(ProviderType::OpenAI).new_retry_budget().is_exhausted());
+
+        let config = LLMConfig::new(ProviderType::OpenAI).with_retry_budget(1);
+        let budget = config.new_retry_budget();
+        assert!(budget.try_acquire());
+        assert!(budget.is_exhausted());
+        assert_eq!(config.new_retry_budget().remaining(), 1);
+    }
+
+    #[test]
+    fn test_custom_simplification_template_is_interpolated() {
+        let config = LLMConfig::new(ProviderType::Mock)
+            .with_simplification_prompt_template("Rewrite for a {level} reader:\n{sentence}\nReply in JSON.");
+        assert!(config.validate().is_ok());
+
+        let prompt = config.render_simplification_prompt("The {level} was precipitous.").unwrap();
+        assert_eq!(
+            prompt,
+            format!("Rewrite for a {SIMPLIFICATION_LEVEL} reader:\n<<<USER_TEXT>>>\nThe {{level}} was precipitous.\n<<<END_USER_TEXT>>>\nReply in JSON.")
+        );
+        assert_eq!(LLMConfig::new(ProviderType::Mock).render_simplification_prompt("Hi."), None);
+    }
+
+    #[test]
+    fn test_template_without_sentence_placeholder_is_rejected() {
+        let config = LLMConfig::new(ProviderType::Mock)
+            .with_simplification_prompt_template("Simplify for a {level} reader.");
+
+        let error = config.validate().unwrap_err();
+        assert!(matches!(error, AppError::ConfigError { .. }));
+        assert!(error.to_string().contains("{sentence}"));
+    }
+
+    #[test]
+    fn test_template_is_loaded_from_file() {
+        let path = std::env::temp_dir().join(format!("glossia-prompt-{}.txt", std::process::id()));
+        std::fs::write(&path, "Simplify: {sentence}").unwrap();
+
+        let config = LLMConfig::new(ProviderType::Mock).with_simplification_prompt_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(config.simplification_prompt_template.as_deref(), Some("Simplify: {sentence}"));
+
+        assert!(LLMConfig::new(ProviderType::Mock).with_simplification_prompt_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_missing_key_is_deferred_only_when_requested() {
+        let var = "GLOSSIA_TEST_UNSET_API_KEY";
+        assert_eq!(api_key_from_env(var, true).unwrap(), None);
+
+        let error = api_key_from_env(var, false).unwrap_err();
+        assert!(error.to_string().contains(var));
+    }
+
+    #[test]
+    fn test_deferred_config_fails_validation_on_use() {
+        let config = LLMConfig::new(ProviderType::OpenAI);
+        assert!(config.api_key.is_none());
+
+        let error = config.validate().unwrap_err();
+        assert!(error.to_string().contains("API key is required"));
+        assert!(crate::OpenAIProvider::new(config).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{LLMClient, LLMConfig, ProviderType, OpenAIProvider, ClaudeProvider, MockLLMClient};
 use glossia_shared::AppError;
+use futures_util::future::join_all;
+use std::time::Duration;
 
 /// Factory for creating LLM clients based on configuration
 pub struct LLMClientFactory;
@@ -74,6 +76,32 @@ impl LLMClientFactory {
         ]
     }
 
+    /// Validate several provider configurations at once, e.g. from a settings screen
+    /// Each config's client is built and pinged concurrently within its `timeout`;
+    /// results come back in the order of `configs`
+    pub async fn validate_keys(configs: Vec<LLMConfig>) -> Vec<(ProviderType, Result<(), AppError>)> {
+        join_all(configs.into_iter().map(|config| async move {
+            let provider = config.provider.clone();
+            let timeout = config.timeout;
+            let result = match Self::create(config) {
+                Ok(client) => Self::ping_client(client.as_ref(), timeout).await,
+                Err(e) => Err(e),
+            };
+            (provider, result)
+        }))
+        .await
+    }
+
+    /// Run `ping` on an existing client, failing with a timeout error if it exceeds `timeout`
+    pub async fn ping_client(client: &dyn LLMClient, timeout: Duration) -> Result<(), AppError> {
+        match tokio::time::timeout(timeout, client.ping()).await {
+            Ok(result) => result,
+            Err(_) => Err(AppError::TimeoutError {
+                message: format!("{} ping timed out after {:?}", client.provider_name(), timeout),
+            }),
+        }
+    }
+
     /// Check if a provider is available (has necessary configuration)
     pub async fn check_provider_availability(provider: ProviderType) -> Result<bool, AppError> {
         let config = match provider {
@@ -99,7 +127,7 @@ impl LLMClientFactory {
         };
 
         let client = Self::create(config)?;
-        match client.health_check().await {
+        match client.ping().await {
             Ok(_) => Ok(true),
             Err(_) => Ok(false),
         }
@@ -136,4 +164,29 @@ mod tests {
         let client = LLMClientFactory::create_mock_with_config(true, Some(100));
         assert_eq!(client.provider_name(), "Mock");
     }
+
+    #[tokio::test]
+    async fn test_validate_keys_reports_each_provider() {
+        let configs = vec![
+            LLMConfig::new(ProviderType::Mock),
+            LLMConfig::new(ProviderType::OpenAI).with_api_key("not-a-key".to_string()),
+        ];
+
+        let results = LLMClientFactory::validate_keys(configs).await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, ProviderType::Mock);
+        assert!(results[0].1.is_ok());
+        assert_eq!(results[1].0, ProviderType::OpenAI);
+        assert!(matches!(results[1].1, Err(AppError::ConfigError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_ping_client_reports_failure() {
+        let healthy = LLMClientFactory::create_mock();
+        let failing = LLMClientFactory::create_mock_with_config(true, None);
+
+        assert!(LLMClientFactory::ping_client(healthy.as_ref(), Duration::from_secs(1)).await.is_ok());
+        assert!(LLMClientFactory::ping_client(failing.as_ref(), Duration::from_secs(1)).await.is_err());
+    }
 }
@@ -0,0 +1,92 @@
+use glossia_shared::{ImageQueryOptimizationRequest, ImageQueryOptimizationResponse};
+use tracing::warn;
+
+/// Maximum number of words allowed in an optimized image query
+pub(crate) const MAX_QUERY_WORDS: usize = 4;
+
+/// Whether the word to illustrate is a multi-word phrase
+pub(crate) fn is_phrase(word: &str) -> bool {
+    word.split_whitespace().nth(1).is_some()
+}
+
+/// The phrase itself as the query, for when phrase optimization is skipped
+pub(crate) fn unoptimized_query(word: &str) -> ImageQueryOptimizationResponse {
+    ImageQueryOptimizationResponse { optimized_query: word.split_whitespace().collect::<Vec<_>>().join(" ") }
+}
+
+/// Prompt asking for a query that depicts what a phrase means rather than its literal words
+pub(crate) fn phrase_image_query_prompt(request: &ImageQueryOptimizationRequest) -> String {
+    format!(
+        r#"Generate an image search query that illustrates the MEANING of the phrase '{}', not its literal words.
+
+Context: "{}"
+Meaning: {}
+
+RULES:
+1. Describe a concrete, photographable scene showing the meaning (e.g., "heavy heart" → a sad person)
+2. Never search for the phrase's words literally ("heavy heart" must not become "heavy heart organ")
+3. Output ONLY valid JSON: {{"optimized_query": "your query"}}
+4. Maximum 4 words
+5. NO nudity, sexual or suggestive content
+
+Examples:
+- "heavy heart" + "left with a heavy heart" → {{"optimized_query": "sad person alone"}}
+- "break the ice" + "a joke to break the ice" → {{"optimized_query": "people laughing together"}}"#,
+        request.word, request.sentence_context, request.word_meaning
+    )
+}
+
+/// Trim the optimizer's query and fall back to `word` when it is empty or too long
+pub(crate) fn validate_optimized_query(response: ImageQueryOptimizationResponse, word: &str) -> ImageQueryOptimizationResponse {
+    let query = response.optimized_query.split_whitespace().collect::<Vec<_>>();
+
+    if query.is_empty() {
+        warn!("Image query optimizer returned an empty query for '{}', falling back to the word", word);
+        return ImageQueryOptimizationResponse { optimized_query: word.trim().to_string() };
+    }
+
+    if query.len() > MAX_QUERY_WORDS {
+        warn!(
+            "Image query optimizer returned {} words for '{}' (max {}), falling back to the word: '{}'",
+            query.len(), word, MAX_QUERY_WORDS, response.optimized_query
+        );
+        return ImageQueryOptimizationResponse { optimized_query: word.trim().to_string() };
+    }
+
+    ImageQueryOptimizationResponse { optimized_query: query.join(" ") }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(query: &str) -> ImageQueryOptimizationResponse {
+        ImageQueryOptimizationResponse { optimized_query: query.to_string() }
+    }
+
+    #[test]
+    fn test_empty_query_falls_back_to_word() {
+        assert_eq!(validate_optimized_query(response(""), "lighthouse").optimized_query, "lighthouse");
+        assert_eq!(validate_optimized_query(response("   "), "lighthouse").optimized_query, "lighthouse");
+    }
+
+    #[test]
+    fn test_overlong_query_falls_back_to_word() {
+        let result = validate_optimized_query(response("old stone lighthouse on rocky coast"), "lighthouse");
+        assert_eq!(result.optimized_query, "lighthouse");
+    }
+
+    #[test]
+    fn test_valid_query_is_trimmed_and_kept() {
+        let result = validate_optimized_query(response("  lighthouse   coastal tower "), "lighthouse");
+        assert_eq!(result.optimized_query, "lighthouse coastal tower");
+    }
+
+    #[test]
+    fn test_phrases_are_detected_by_word_count() {
+        assert!(is_phrase("heavy heart"));
+        assert!(is_phrase("  break  the ice "));
+        assert!(!is_phrase(" lighthouse "));
+        assert_eq!(unoptimized_query(" heavy   heart ").optimized_query, "heavy heart");
+    }
+}
@@ -0,0 +1,96 @@
+use glossia_shared::{strip_code_fences, AppError};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use tracing::{error, warn};
+
+/// Stricter instruction appended when re-prompting after a non-JSON reply
+pub(crate) const STRICT_JSON_INSTRUCTION: &str = "Your previous reply was not valid JSON. \
+Respond with ONLY the JSON object described above: no prose, no explanations, no code fences.";
+
+/// Parse `content` as JSON, re-issuing the request once via `retry` if it is malformed
+/// `context` names the call site in logs and errors
+pub(crate) async fn parse_or_retry<T, F, Fut>(content: &str, context: &str, retry: F) -> Result<T, AppError>
+where
+    T: DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<String, AppError>>,
+{
+    match serde_json::from_str::<T>(strip_code_fences(content)) {
+        Ok(parsed) => Ok(parsed),
+        Err(first_error) => {
+            warn!("Invalid JSON response for {}: {}, retrying with stricter instruction", context, first_error);
+
+            let retried = retry().await?;
+            serde_json::from_str::<T>(strip_code_fences(&retried)).map_err(|e| {
+                error!("Invalid JSON response for {} after retry: {}", context, e);
+                AppError::ParseError { message: format!("Invalid JSON response for {context}: {e}") }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glossia_shared::ImageQueryOptimizationResponse;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_prose_then_valid_json_succeeds_on_retry() {
+        let retries = AtomicUsize::new(0);
+        let result: ImageQueryOptimizationResponse = parse_or_retry(
+            "Sure! A good query would be \"lighthouse coastal tower\".",
+            "image query optimization",
+            || async {
+                retries.fetch_add(1, Ordering::SeqCst);
+                Ok(r#"{"optimized_query": "lighthouse coastal tower"}"#.to_string())
+            },
+        ).await.unwrap();
+
+        assert_eq!(result.optimized_query, "lighthouse coastal tower");
+        assert_eq!(retries.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_valid_json_does_not_retry() {
+        let retries = AtomicUsize::new(0);
+        let result: ImageQueryOptimizationResponse = parse_or_retry(
+            r#"{"optimized_query": "royal crown gold"}"#,
+            "image query optimization",
+            || async {
+                retries.fetch_add(1, Ordering::SeqCst);
+                Ok(String::new())
+            },
+        ).await.unwrap();
+
+        assert_eq!(result.optimized_query, "royal crown gold");
+        assert_eq!(retries.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_fenced_json_does_not_retry() {
+        let retries = AtomicUsize::new(0);
+        let result: ImageQueryOptimizationResponse = parse_or_retry(
+            "```json\n{\"optimized_query\": \"river bank shore\"}\n```",
+            "image query optimization",
+            || async {
+                retries.fetch_add(1, Ordering::SeqCst);
+                Ok(String::new())
+            },
+        ).await.unwrap();
+
+        assert_eq!(result.optimized_query, "river bank shore");
+        assert_eq!(retries.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_second_malformed_response_is_parse_error() {
+        let result: Result<ImageQueryOptimizationResponse, AppError> = parse_or_retry(
+            "not json",
+            "image query optimization",
+            || async { Ok("still not json".to_string()) },
+        ).await;
+
+        assert!(matches!(result, Err(AppError::ParseError { .. })));
+    }
+}
@@ -3,12 +3,21 @@ mod claude_provider;
 mod llm_trait;
 mod config;
 mod factory;
+mod prompt_sanitizer;
+mod image_query;
+mod json_retry;
+mod meaning_retry;
+mod meaning_stream;
+mod usage;
 
 pub use openai_provider::OpenAIProvider;
 pub use claude_provider::ClaudeProvider;
-pub use llm_trait::{LLMClient, MockLLMClient};
-pub use config::{LLMConfig, ProviderType};
+pub use llm_trait::{LLMClient, MockLLMClient, UnavailableLLMClient};
+pub use config::{LLMConfig, LLMOperation, PhraseImageQueries, ProviderType, DEFAULT_OPENAI_MODEL, DEFAULT_CLAUDE_MODEL, DEFAULT_MAX_DEFINITION_WORDS, SIMPLIFICATION_LEVEL};
 pub use factory::LLMClientFactory;
+pub use meaning_retry::{is_circular_meaning, is_missing_meaning, NO_DEFINITION_PLACEHOLDER};
+pub use meaning_stream::MeaningStream;
+pub use usage::{estimate_cost_usd, TokenUsage, UsageAccumulator};
 
 // Re-export commonly used types
 pub use glossia_shared::{
@@ -16,3 +25,4 @@ pub use glossia_shared::{
     ImageQueryOptimizationRequest, ImageQueryOptimizationResponse,
     AppError
 };
+pub use glossia_http_client::{CircuitState, RequestStats, RetryBudget};
@@ -1,6 +1,10 @@
 use async_trait::async_trait;
 use glossia_shared::{AppError, SimplificationRequest, SimplificationResponse, ImageQueryOptimizationRequest, ImageQueryOptimizationResponse};
+use glossia_http_client::{CircuitState, RequestStats};
+use crate::{LLMOperation, MeaningStream, UsageAccumulator};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Trait for Language Model clients that can simplify text and define words
 #[async_trait]
@@ -10,15 +14,63 @@ pub trait LLMClient: Send + Sync {
     
     /// Get the meaning of a word in context
     async fn get_word_meaning(&self, word: &str, context: &str) -> Result<String, AppError>;
+
+    /// Stream the meaning of a word in pieces as the provider produces them, so
+    /// it can be shown progressively; providers without streaming yield the
+    /// whole `get_word_meaning` result as a single chunk
+    fn get_word_meaning_stream<'a>(&'a self, word: &'a str, context: &'a str) -> MeaningStream<'a> {
+        Box::pin(futures_util::stream::once(self.get_word_meaning(word, context)))
+    }
     
     /// Optimize image search queries based on word context
     async fn optimize_image_query(&self, request: ImageQueryOptimizationRequest) -> Result<ImageQueryOptimizationResponse, AppError>;
     
     /// Get provider name for debugging/logging
     fn provider_name(&self) -> &str;
+
+    /// Get the model name for telemetry tagging
+    fn model_name(&self) -> String {
+        "unknown".to_string()
+    }
     
     /// Check if the client is properly configured
     async fn health_check(&self) -> Result<(), AppError>;
+
+    /// Cheap liveness check for startup; providers whose health check costs a
+    /// completion override this with something lighter
+    async fn ping(&self) -> Result<(), AppError> {
+        self.health_check().await
+    }
+
+    /// Request statistics from the underlying http client, if there is one
+    fn request_stats(&self) -> Option<RequestStats> {
+        None
+    }
+
+    /// Token usage recorded from the provider's responses, if it reports any
+    fn usage(&self) -> Option<Arc<UsageAccumulator>> {
+        None
+    }
+
+    /// Circuit breaker state of the underlying http client, if there is one
+    async fn circuit_state(&self) -> Option<CircuitState> {
+        None
+    }
+
+    /// Close the underlying http client's circuit breaker, if there is one
+    async fn reset_circuit(&self) {}
+
+    /// Allow `permits` requests per `window` to the provider from now on, if
+    /// requests go through a rate-limited http client
+    async fn set_rate_limit(&self, _permits: usize, _window: Duration) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    /// Timeout suggested by the observed p95 latency of `operation`, once enough
+    /// of its requests have completed to estimate it
+    fn recommended_timeout(&self, _operation: LLMOperation) -> Option<Duration> {
+        None
+    }
 }
 
 /// Mock implementation for testing
@@ -81,6 +133,8 @@ impl LLMClient for MockLLMClient {
             original: request.sentence.clone(),
             simplified,
             words: vec![],
+            grammar_notes: vec![],
+            was_already_simple: false,
         })
     }
 
@@ -123,6 +177,42 @@ impl LLMClient for MockLLMClient {
     }
 }
 
+/// Stand-in client for when no provider could be configured
+/// Every request fails with the configuration error, so the app can keep
+/// running and show that error instead of panicking at startup
+pub struct UnavailableLLMClient {
+    reason: AppError,
+}
+
+impl UnavailableLLMClient {
+    pub fn new(reason: AppError) -> Self {
+        Self { reason }
+    }
+}
+
+#[async_trait]
+impl LLMClient for UnavailableLLMClient {
+    async fn simplify(&self, _request: SimplificationRequest) -> Result<SimplificationResponse, AppError> {
+        Err(self.reason.clone())
+    }
+
+    async fn get_word_meaning(&self, _word: &str, _context: &str) -> Result<String, AppError> {
+        Err(self.reason.clone())
+    }
+
+    async fn optimize_image_query(&self, _request: ImageQueryOptimizationRequest) -> Result<ImageQueryOptimizationResponse, AppError> {
+        Err(self.reason.clone())
+    }
+
+    fn provider_name(&self) -> &str {
+        "Unavailable"
+    }
+
+    async fn health_check(&self) -> Result<(), AppError> {
+        Err(self.reason.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,6 +239,17 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_mock_client_uses_default_model_name() {
+        assert_eq!(MockLLMClient::new().model_name(), "unknown");
+    }
+
+    #[tokio::test]
+    async fn test_mock_client_ping() {
+        assert!(MockLLMClient::new().ping().await.is_ok());
+        assert!(MockLLMClient::new().with_failure().ping().await.is_err());
+    }
+
     #[tokio::test]
     async fn test_mock_client_custom_response() {
         let client = MockLLMClient::new()
@@ -161,4 +262,14 @@ mod tests {
         let result = client.simplify(request).await.unwrap();
         assert_eq!(result.simplified, "custom response");
     }
+
+    #[tokio::test]
+    async fn test_unavailable_client_reports_its_reason() {
+        let client = UnavailableLLMClient::new(AppError::config_error("OpenAI API key is required"));
+
+        let error = client.simplify(SimplificationRequest { sentence: "Hi.".to_string() }).await.unwrap_err();
+        assert!(error.to_string().contains("OpenAI API key is required"));
+        assert!(client.get_word_meaning("hi", "Hi.").await.is_err());
+        assert!(client.health_check().await.is_err());
+    }
 }
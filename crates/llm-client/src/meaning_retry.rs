@@ -0,0 +1,196 @@
+use glossia_shared::AppError;
+use std::future::Future;
+use tracing::{debug, warn};
+
+/// Shown instead of a blank meaning when the model returns nothing usable
+pub const NO_DEFINITION_PLACEHOLDER: &str = "No definition available";
+
+/// Meanings shorter than this (after trimming) are treated as missing
+const MIN_MEANING_CHARS: usize = 2;
+
+/// Whether `meaning` is empty, too short to be a definition, or the placeholder
+/// Such values should never be cached as real definitions
+pub fn is_missing_meaning(meaning: &str) -> bool {
+    let trimmed = meaning.trim();
+    trimmed.chars().count() < MIN_MEANING_CHARS || trimmed == NO_DEFINITION_PLACEHOLDER
+}
+
+/// Return `meaning`, or when it is missing re-issue the request once via `retry`
+/// (if enabled), falling back to `NO_DEFINITION_PLACEHOLDER`
+pub(crate) async fn meaning_or_retry<F, Fut>(meaning: String, word: &str, retry_enabled: bool, retry: F) -> Result<String, AppError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<String, AppError>>,
+{
+    if !is_missing_meaning(&meaning) {
+        return Ok(meaning);
+    }
+
+    if retry_enabled {
+        warn!("Empty meaning for '{}', retrying once", word);
+        let retried = retry().await?;
+        if !is_missing_meaning(&retried) {
+            return Ok(retried);
+        }
+    }
+
+    warn!("No usable meaning for '{}', using placeholder", word);
+    Ok(NO_DEFINITION_PLACEHOLDER.to_string())
+}
+
+/// Added to a word meaning re-request after a circular definition
+pub(crate) fn non_circular_instruction(word: &str) -> String {
+    format!("Define the word without using the word \"{word}\" itself in the definition.")
+}
+
+/// Whether `meaning` uses the headword itself ("happy means happy"), compared
+/// case-insensitively as a whole word or phrase, so "cat" in "category" doesn't count
+pub fn is_circular_meaning(meaning: &str, word: &str) -> bool {
+    let word = word.trim().to_lowercase();
+    if word.is_empty() || is_missing_meaning(meaning) {
+        return false;
+    }
+    let words = |text: &str| -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '-')
+            .filter(|part| !part.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let headword = words(&word);
+    !headword.is_empty() && words(&meaning.to_lowercase()).windows(headword.len()).any(|window| window == headword.as_slice())
+}
+
+/// Return `meaning`, or when it uses the headword re-issue the request once via
+/// `retry` (which should ask not to use the word), keeping the first answer if
+/// the retry is unusable
+pub(crate) async fn non_circular_meaning_or_retry<F, Fut>(meaning: String, word: &str, retry: F) -> Result<String, AppError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<String, AppError>>,
+{
+    if !is_circular_meaning(&meaning, word) {
+        debug!("Meaning for '{}' accepted, retried: false", word);
+        return Ok(meaning);
+    }
+
+    debug!("Circular meaning for '{}', retried: true", word);
+    let retried = retry().await?;
+    if is_missing_meaning(&retried) {
+        return Ok(meaning);
+    }
+    Ok(retried)
+}
+
+/// Cut `meaning` at a word boundary so it has at most `max_words` words and
+/// `max_chars` characters (ellipsis included), ending it with an ellipsis
+pub(crate) fn truncate_meaning(meaning: String, word: &str, max_words: Option<usize>, max_chars: Option<usize>) -> String {
+    let words: Vec<&str> = meaning.split_whitespace().collect();
+    let joined_len = |count: usize| words[..count].iter().map(|w| w.chars().count()).sum::<usize>() + count.saturating_sub(1);
+    let max_words = max_words.unwrap_or(usize::MAX);
+    if words.len() <= max_words && max_chars.is_none_or(|max| joined_len(words.len()) <= max) {
+        return meaning;
+    }
+
+    // Something is cut, so leave room for the ellipsis
+    let mut kept = words.len().min(max_words);
+    if let Some(max_chars) = max_chars {
+        while kept > 0 && joined_len(kept) >= max_chars {
+            kept -= 1;
+        }
+    }
+    let truncated = match kept {
+        // A single word longer than the limit is cut mid-word
+        0 => words[0].chars().take(max_chars.unwrap_or(1).saturating_sub(1)).collect(),
+        _ => words[..kept].join(" "),
+    };
+
+    warn!("Meaning for '{}' exceeded the length limit, truncated from {} to {} words", word, words.len(), kept);
+    format!("{}\u{2026}", truncated.trim_end_matches([',', ';', ':', '-', '\u{2014}']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_empty_meaning_retries_then_uses_placeholder() {
+        let retries = AtomicUsize::new(0);
+        let meaning = meaning_or_retry("  ".to_string(), "cat", true, || async {
+            retries.fetch_add(1, Ordering::SeqCst);
+            Ok(String::new())
+        }).await.unwrap();
+
+        assert_eq!(meaning, NO_DEFINITION_PLACEHOLDER);
+        assert_eq!(retries.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_can_recover_a_meaning() {
+        let meaning = meaning_or_retry(String::new(), "cat", true, || async {
+            Ok("a small pet".to_string())
+        }).await.unwrap();
+        assert_eq!(meaning, "a small pet");
+    }
+
+    #[tokio::test]
+    async fn test_retry_disabled_goes_straight_to_placeholder() {
+        let retries = AtomicUsize::new(0);
+        let meaning = meaning_or_retry(".".to_string(), "cat", false, || async {
+            retries.fetch_add(1, Ordering::SeqCst);
+            Ok("a small pet".to_string())
+        }).await.unwrap();
+
+        assert_eq!(meaning, NO_DEFINITION_PLACEHOLDER);
+        assert_eq!(retries.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_is_missing_meaning() {
+        assert!(is_missing_meaning(""));
+        assert!(is_missing_meaning(" a "));
+        assert!(is_missing_meaning(NO_DEFINITION_PLACEHOLDER));
+        assert!(!is_missing_meaning("a small pet"));
+    }
+
+    #[test]
+    fn test_is_circular_meaning() {
+        assert!(is_circular_meaning("Happy means happy.", "happy"));
+        assert!(is_circular_meaning("when you give up on something", "give up"));
+        assert!(!is_circular_meaning("a group of things", "cat"));
+        assert!(!is_circular_meaning("a category of things", "cat"));
+    }
+
+    #[tokio::test]
+    async fn test_circular_meaning_retries_once() {
+        let retries = AtomicUsize::new(0);
+        let meaning = non_circular_meaning_or_retry("happy: being happy".to_string(), "happy", || async {
+            retries.fetch_add(1, Ordering::SeqCst);
+            Ok("feeling pleasure".to_string())
+        }).await.unwrap();
+
+        assert_eq!(meaning, "feeling pleasure");
+        assert_eq!(retries.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_overlong_meaning_is_truncated_at_a_word_boundary() {
+        let meaning = "a large, heavy animal with thick grey skin, big ears and a long trunk used for lifting food and water".to_string();
+
+        assert_eq!(
+            truncate_meaning(meaning.clone(), "elephant", Some(8), None),
+            "a large, heavy animal with thick grey skin\u{2026}"
+        );
+        // "a large," is cut back to "a large" before the ellipsis
+        let by_chars = truncate_meaning(meaning, "elephant", None, Some(12));
+        assert_eq!(by_chars, "a large\u{2026}");
+        assert!(by_chars.chars().count() <= 12);
+    }
+
+    #[test]
+    fn test_short_meaning_passes_unchanged() {
+        let meaning = "a  small pet".to_string();
+        assert_eq!(truncate_meaning(meaning.clone(), "cat", Some(15), Some(40)), meaning);
+        assert_eq!(truncate_meaning("unbelievably".to_string(), "word", None, Some(5)), "unbe\u{2026}");
+    }
+}
@@ -0,0 +1,143 @@
+use futures_util::stream::{self, Stream};
+use glossia_shared::AppError;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::pin::Pin;
+
+/// Pieces of a word definition in the order the provider produces them;
+/// concatenated they form the whole definition
+pub type MeaningStream<'a> = Pin<Box<dyn Stream<Item = Result<String, AppError>> + Send + 'a>>;
+
+/// Splits a server-sent events body into the text deltas of a streamed chat completion
+#[derive(Debug, Default)]
+pub(crate) struct CompletionDeltaDecoder {
+    /// Bytes after the last complete line, which may end inside a UTF-8 character
+    pending: Vec<u8>,
+    done: bool,
+}
+
+impl CompletionDeltaDecoder {
+    /// Decode every complete `data:` line in `bytes`, keeping a partial line for the next call
+    pub(crate) fn feed(&mut self, bytes: &[u8]) -> Vec<Result<String, AppError>> {
+        self.pending.extend_from_slice(bytes);
+        let mut deltas = Vec::new();
+
+        while let Some(newline) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=newline).collect();
+            if self.done {
+                continue;
+            }
+            if let Some(delta) = self.decode_line(String::from_utf8_lossy(&line).trim()) {
+                deltas.push(delta);
+            }
+        }
+        deltas
+    }
+
+    /// Decode a final line the body ended without terminating
+    pub(crate) fn finish(&mut self) -> Vec<Result<String, AppError>> {
+        if self.pending.is_empty() {
+            return Vec::new();
+        }
+        self.feed(b"\n")
+    }
+
+    /// Whether the `[DONE]` marker has been seen
+    pub(crate) fn is_done(&self) -> bool {
+        self.done
+    }
+
+    fn decode_line(&mut self, line: &str) -> Option<Result<String, AppError>> {
+        let data = line.strip_prefix("data:")?.trim();
+        if data == "[DONE]" {
+            self.done = true;
+            return None;
+        }
+
+        let event: Value = match serde_json::from_str(data) {
+            Ok(event) => event,
+            Err(e) => return Some(Err(e.into())),
+        };
+        if let Some(error) = event.get("error") {
+            let message = error["message"].as_str().unwrap_or("unknown error");
+            return Some(Err(AppError::api_error(format!("Streaming completion failed: {message}"))));
+        }
+
+        event["choices"][0]["delta"]["content"]
+            .as_str()
+            .filter(|content| !content.is_empty())
+            .map(|content| Ok(content.to_string()))
+    }
+}
+
+/// Text deltas of a streamed chat completion response, read as the body arrives
+pub(crate) fn completion_deltas(response: reqwest::Response) -> impl Stream<Item = Result<String, AppError>> + Send {
+    let initial = Some((response, CompletionDeltaDecoder::default(), VecDeque::new()));
+
+    stream::unfold(initial, |state| async move {
+        let (mut response, mut decoder, mut ready) = state?;
+        loop {
+            if let Some(delta) = ready.pop_front() {
+                return Some((delta, Some((response, decoder, ready))));
+            }
+            if decoder.is_done() {
+                return None;
+            }
+            match response.chunk().await {
+                Ok(Some(bytes)) => ready.extend(decoder.feed(&bytes)),
+                Ok(None) => {
+                    ready.extend(decoder.finish());
+                    return ready.pop_front().map(|delta| (delta, Some((response, decoder, ready))));
+                }
+                Err(e) => return Some((Err(AppError::from(e)), None)),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LLMClient, MockLLMClient};
+    use futures_util::StreamExt;
+
+    fn texts(deltas: Vec<Result<String, AppError>>) -> Vec<String> {
+        deltas.into_iter().map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn test_events_split_across_chunks_are_decoded() {
+        let mut decoder = CompletionDeltaDecoder::default();
+        let body = "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n\
+                    data: {\"choices\":[{\"delta\":{\"content\":\"A caf\u{e9}\"}}]}\r\n\n\
+                    data: {\"choices\":[{\"delta\":{\"content\":\" for coffee\"}}]}\n\n\
+                    data: [DONE]\n\n\
+                    data: {\"choices\":[{\"delta\":{\"content\":\"ignored\"}}]}\n\n";
+        let (first, second) = body.as_bytes().split_at(62);
+
+        let mut deltas = texts(decoder.feed(first));
+        deltas.extend(texts(decoder.feed(second)));
+
+        assert_eq!(deltas, vec!["A caf\u{e9}", " for coffee"]);
+        assert!(decoder.is_done());
+    }
+
+    #[test]
+    fn test_error_event_is_reported() {
+        let mut decoder = CompletionDeltaDecoder::default();
+        let deltas = decoder.feed(b"data: {\"error\":{\"message\":\"overloaded\"}}");
+        assert!(deltas.is_empty());
+
+        let error = decoder.finish().remove(0).unwrap_err();
+        assert!(error.to_string().contains("overloaded"));
+    }
+
+    #[tokio::test]
+    async fn test_default_stream_yields_whole_meaning_once() {
+        let client = MockLLMClient::new();
+        let chunks: Vec<_> = client.get_word_meaning_stream("cat", "The cat sat.").collect().await;
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0], client.get_word_meaning("cat", "The cat sat.").await);
+    }
+}
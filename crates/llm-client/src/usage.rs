@@ -0,0 +1,127 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// USD per million tokens for model name prefixes as (prompt, completion);
+/// the longest matching prefix wins, so dated snapshots share their family's price
+const MODEL_PRICES: &[(&str, f64, f64)] = &[
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("gpt-4o", 2.50, 10.00),
+    ("gpt-4.1-nano", 0.10, 0.40),
+    ("gpt-4.1-mini", 0.40, 1.60),
+    ("gpt-4.1", 2.00, 8.00),
+    ("gpt-4-turbo", 10.00, 30.00),
+    ("gpt-4", 30.00, 60.00),
+    ("gpt-3.5-turbo", 0.50, 1.50),
+    ("claude-3-haiku", 0.25, 1.25),
+    ("claude-3-5-haiku", 0.80, 4.00),
+    ("claude-3-sonnet", 3.00, 15.00),
+    ("claude-3-5-sonnet", 3.00, 15.00),
+    ("claude-3-opus", 15.00, 75.00),
+];
+
+/// Tokens used by one or more completions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+
+    /// Usage reported in an OpenAI-style completion response, if present
+    pub(crate) fn from_response(response: &Value) -> Option<Self> {
+        let usage = response.get("usage")?;
+        Some(Self {
+            prompt_tokens: usage["prompt_tokens"].as_u64()?,
+            completion_tokens: usage["completion_tokens"].as_u64().unwrap_or(0),
+        })
+    }
+
+    fn add(&mut self, other: TokenUsage) {
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+    }
+}
+
+/// Estimated USD cost of `usage` on `model`, or `None` when the model has no known price
+pub fn estimate_cost_usd(model: &str, usage: TokenUsage) -> Option<f64> {
+    let (_, prompt_price, completion_price) = MODEL_PRICES
+        .iter()
+        .filter(|(prefix, _, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _, _)| prefix.len())?;
+
+    Some((usage.prompt_tokens as f64 * prompt_price + usage.completion_tokens as f64 * completion_price) / 1_000_000.0)
+}
+
+/// Running token totals per model for a session, shared between clients with an `Arc`
+#[derive(Debug, Default)]
+pub struct UsageAccumulator {
+    by_model: Mutex<HashMap<String, TokenUsage>>,
+}
+
+impl UsageAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, model: &str, usage: TokenUsage) {
+        self.by_model.lock().unwrap().entry(model.to_string()).or_default().add(usage);
+    }
+
+    /// Combined usage across all models
+    pub fn usage(&self) -> TokenUsage {
+        let mut total = TokenUsage::default();
+        for usage in self.by_model.lock().unwrap().values() {
+            total.add(*usage);
+        }
+        total
+    }
+
+    pub fn usage_by_model(&self) -> HashMap<String, TokenUsage> {
+        self.by_model.lock().unwrap().clone()
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.usage().total_tokens()
+    }
+
+    /// Estimated cost so far; tokens from models without a known price count as free
+    pub fn estimated_cost_usd(&self) -> f64 {
+        self.by_model
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|(model, usage)| estimate_cost_usd(model, *usage))
+            .sum()
+    }
+
+    pub fn reset(&self) {
+        self.by_model.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dated_snapshots_use_their_family_price() {
+        let usage = TokenUsage { prompt_tokens: 1_000_000, completion_tokens: 1_000_000 };
+        let cost = |model: &str| estimate_cost_usd(model, usage).unwrap();
+
+        assert!((cost("gpt-4o-mini-2024-07-18") - 0.75).abs() < 1e-9);
+        assert!((cost("gpt-4o-2024-08-06") - 12.5).abs() < 1e-9);
+        assert_eq!(estimate_cost_usd("my-local-model", usage), None);
+    }
+
+    #[test]
+    fn test_usage_is_parsed_from_response() {
+        let response = serde_json::json!({ "usage": { "prompt_tokens": 12, "completion_tokens": 5, "total_tokens": 17 } });
+        assert_eq!(TokenUsage::from_response(&response), Some(TokenUsage { prompt_tokens: 12, completion_tokens: 5 }));
+        assert_eq!(TokenUsage::from_response(&serde_json::json!({})), None);
+    }
+}
@@ -57,7 +57,7 @@ impl LoggingConfig {
             config.format = match format.to_lowercase().as_str() {
                 "json" => LogFormat::Json,
                 "compact" => LogFormat::Compact,
-                "pretty" | _ => LogFormat::Pretty,
+                _ => LogFormat::Pretty,
             };
         }
         
@@ -1,4 +1,4 @@
-/// Convenience macros for common logging patterns
+//! Convenience macros for common logging patterns
 
 /// Log an error with structured context
 #[macro_export]
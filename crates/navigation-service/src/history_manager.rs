@@ -3,6 +3,9 @@ pub struct HistoryManager {
     history: Vec<usize>,
     current_index: Option<usize>,
     max_history: usize,
+    collapse_runs: bool,
+    /// Direction (+1/-1) of the single-step run ending at the last entry
+    run_step: Option<i64>,
 }
 
 impl HistoryManager {
@@ -11,9 +14,18 @@ impl HistoryManager {
             history: Vec::new(),
             current_index: None,
             max_history: 50, // Limit history to prevent memory issues
+            collapse_runs: false,
+            run_step: None,
         }
     }
 
+    /// Collapse runs of single steps in one direction (e.g. 1, 2, 3, 4) down to
+    /// their first and last positions, so going back skips over the run
+    pub fn with_collapse_runs(mut self, collapse_runs: bool) -> Self {
+        self.collapse_runs = collapse_runs;
+        self
+    }
+
     /// Add a position to history
     pub fn add_position(&mut self, position: usize) {
         // If we're in the middle of history, truncate everything after current position
@@ -21,7 +33,23 @@ impl HistoryManager {
             self.history.truncate(current + 1);
         }
 
-        self.history.push(position);
+        // Skip consecutive duplicates
+        if self.history.last() == Some(&position) {
+            self.current_index = Some(self.history.len() - 1);
+            return;
+        }
+
+        let step = self.history.last().map(|last| position as i64 - *last as i64);
+        let extends_run = self.collapse_runs && step.is_some() && step == self.run_step;
+
+        if extends_run {
+            if let Some(last) = self.history.last_mut() {
+                *last = position;
+            }
+        } else {
+            self.history.push(position);
+        }
+        self.run_step = step.filter(|s| s.abs() == 1);
         
         // Limit history size
         if self.history.len() > self.max_history {
@@ -36,6 +64,7 @@ impl HistoryManager {
         if let Some(current) = self.current_index {
             if current > 0 {
                 self.current_index = Some(current - 1);
+                self.run_step = None;
                 return Some(self.history[current - 1]);
             }
         }
@@ -47,6 +76,7 @@ impl HistoryManager {
         if let Some(current) = self.current_index {
             if current + 1 < self.history.len() {
                 self.current_index = Some(current + 1);
+                self.run_step = None;
                 return Some(self.history[current + 1]);
             }
         }
@@ -67,6 +97,7 @@ impl HistoryManager {
     pub fn clear(&mut self) {
         self.history.clear();
         self.current_index = None;
+        self.run_step = None;
     }
 
     /// Get history length
@@ -97,3 +128,57 @@ impl Default for HistoryManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consecutive_duplicates_are_skipped() {
+        let mut history = HistoryManager::new();
+        history.add_position(2);
+        history.add_position(2);
+        history.add_position(5);
+        history.add_position(5);
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.go_back(), Some(2));
+    }
+
+    #[test]
+    fn test_without_collapse_go_back_steps_one_position() {
+        let mut history = HistoryManager::new();
+        for position in [1, 2, 3] {
+            history.add_position(position);
+        }
+
+        assert_eq!(history.go_back(), Some(2));
+    }
+
+    #[test]
+    fn test_collapse_runs_skips_over_sequential_moves() {
+        let mut history = HistoryManager::new().with_collapse_runs(true);
+        for position in [1, 2, 3] {
+            history.add_position(position);
+        }
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.go_back(), Some(1));
+        assert!(!history.can_go_back());
+        assert_eq!(history.go_forward(), Some(3));
+    }
+
+    #[test]
+    fn test_collapse_runs_keeps_direction_changes() {
+        let mut history = HistoryManager::new().with_collapse_runs(true);
+        for position in [1, 2, 3, 4, 3, 2, 9] {
+            history.add_position(position);
+        }
+
+        // 1..4 collapses to [1, 4], 4..2 to [4, 2], then the jump to 9
+        assert_eq!(history.len(), 4);
+        assert_eq!(history.go_back(), Some(2));
+        assert_eq!(history.go_back(), Some(4));
+        assert_eq!(history.go_back(), Some(1));
+    }
+}
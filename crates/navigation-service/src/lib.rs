@@ -2,22 +2,60 @@ mod text_loader;
 mod position_tracker;
 mod history_manager;
 mod navigation_trait;
+mod visit_log;
 
-pub use text_loader::TextLoader;
+pub use text_loader::{ShortLineMode, TextLoader, DEFAULT_MAX_SENTENCES};
 pub use position_tracker::PositionTracker;
 pub use history_manager::HistoryManager;
+pub use visit_log::{SentenceVisit, VisitLog};
 pub use navigation_trait::{
     NavigationStrategy, LinearNavigationStrategy, 
     ParagraphNavigationStrategy, SpeedReadingStrategy
 };
 
 use glossia_shared::AppError;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// The current sentence together with its surrounding text
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContextView {
+    pub before_text: String,
+    pub current: String,
+    pub after_text: String,
+}
+
+/// Why a move between sentences did not happen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavError {
+    /// Already on the last sentence and wrap-around is off
+    AtEnd,
+    /// Already on the first sentence and wrap-around is off
+    AtBeginning,
+    NoTextLoaded,
+}
+
+impl std::fmt::Display for NavError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AtEnd => write!(f, "Already at the last sentence"),
+            Self::AtBeginning => write!(f, "Already at the first sentence"),
+            Self::NoTextLoaded => write!(f, "No text is loaded"),
+        }
+    }
+}
+
+impl std::error::Error for NavError {}
 
 /// Navigation service that handles text loading, position tracking, and history
 pub struct NavigationService {
     text_loader: TextLoader,
     position_tracker: PositionTracker,
     history_manager: HistoryManager,
+    /// Updated from `current_sentence`, which only borrows the service
+    visit_log: Mutex<VisitLog>,
+    /// Normalized text of each repeated sentence and every position it appears at
+    duplicates: HashMap<String, Vec<usize>>,
 }
 
 impl NavigationService {
@@ -25,50 +63,203 @@ impl NavigationService {
         Self {
             text_loader: TextLoader::new(),
             position_tracker: PositionTracker::new(),
-            history_manager: HistoryManager::new(),
+            history_manager: HistoryManager::new().with_collapse_runs(true),
+            visit_log: Mutex::new(VisitLog::new()),
+            duplicates: HashMap::new(),
         }
     }
 
+    /// Wrap `advance` past the end to the first sentence and `previous` at the start to the last
+    pub fn with_wrap_around(mut self, wrap_around: bool) -> Self {
+        self.position_tracker.set_wrap_around(wrap_around);
+        self
+    }
+
+    pub fn set_wrap_around(&mut self, wrap_around: bool) {
+        self.position_tracker.set_wrap_around(wrap_around);
+    }
+
+    pub fn wrap_around(&self) -> bool {
+        self.position_tracker.wrap_around()
+    }
+
+    /// Keep at most `max` sentences of each loaded text; see `TextLoader::with_max_sentences`
+    pub fn with_max_sentences(mut self, max: usize) -> Self {
+        self.text_loader = std::mem::take(&mut self.text_loader).with_max_sentences(max);
+        self
+    }
+
+    pub fn max_sentences(&self) -> usize {
+        self.text_loader.max_sentences()
+    }
+
+    /// Sentences of the loaded text dropped for exceeding `max_sentences`
+    pub fn truncated_count(&self) -> usize {
+        self.text_loader.truncated_count()
+    }
+
     /// Load text and reset position
     pub fn load_text(&mut self, text: &str) -> Result<(), AppError> {
         let sentences = self.text_loader.load_text(text)?;
         self.position_tracker.reset(sentences.len());
         self.history_manager.clear(); // Clear history when loading new text
+        self.visit_log.lock().unwrap().clear();
+        self.duplicates = find_duplicates(&sentences);
+        Ok(())
+    }
+
+    /// Load the first `initial_sentences` sentences now and parse the rest in the
+    /// background; `total_sentences` grows as `sync_loaded` collects them
+    pub fn load_text_incremental(&mut self, text: &str, initial_sentences: usize) -> Result<(), AppError> {
+        let sentences = self.text_loader.load_text_incremental(text, initial_sentences)?;
+        self.position_tracker.reset(sentences.len());
+        self.history_manager.clear();
+        self.visit_log.lock().unwrap().clear();
+        self.duplicates = find_duplicates(&sentences);
         Ok(())
     }
 
-    /// Get current sentence
+    /// Pick up sentences the background parser has finished, returning how many were added
+    pub fn sync_loaded(&mut self) -> usize {
+        let added = self.text_loader.sync_background();
+        self.position_tracker.set_total_sentences(self.text_loader.sentence_count());
+        if added > 0 {
+            self.duplicates = self.text_loader.get_sentences().map(|s| find_duplicates(s)).unwrap_or_default();
+        }
+        added
+    }
+
+    /// Whether every sentence of the loaded text is available
+    pub fn is_fully_loaded(&self) -> bool {
+        self.text_loader.is_fully_loaded()
+    }
+
+    /// Sentences available so far
+    pub fn loaded_count(&self) -> usize {
+        self.text_loader.loaded_count()
+    }
+
+    /// Sentences that appear more than once, keyed by their normalized text
+    /// (case and spacing ignored), with every position they appear at in order
+    pub fn duplicate_positions(&self) -> HashMap<String, Vec<usize>> {
+        self.duplicates.clone()
+    }
+
+    /// Get the paragraph index of a sentence
+    pub fn paragraph_of(&self, sentence_index: usize) -> Option<usize> {
+        self.text_loader.paragraph_of(sentence_index)
+    }
+
+    /// Whether the sentence at `sentence_index` was loaded as a heading
+    pub fn is_heading(&self, sentence_index: usize) -> bool {
+        self.text_loader.is_heading(sentence_index)
+    }
+
+    /// Get current sentence, recording the visit in the visit log
     pub fn current_sentence(&self) -> Option<String> {
-        if let Some(sentences) = self.text_loader.get_sentences() {
-            let position = self.position_tracker.current_position();
-            sentences.get(position).cloned()
-        } else {
-            None
+        let sentences = self.text_loader.get_sentences()?;
+        let position = self.position_tracker.current_position();
+        let sentence = sentences.get(position).cloned()?;
+        self.visit_log.lock().unwrap().record(position);
+        Some(sentence)
+    }
+
+    /// Every sentence displayed since the text was loaded, in first-seen order
+    pub fn visit_log(&self) -> Vec<SentenceVisit> {
+        self.visit_log.lock().unwrap().visits().to_vec()
+    }
+
+    /// Get up to `before` sentences preceding and `after` sentences following the
+    /// current one, each joined into a single string
+    pub fn rendered_context(&self, before: usize, after: usize) -> ContextView {
+        let Some(sentences) = self.text_loader.get_sentences() else {
+            return ContextView::default();
+        };
+        let position = self.position_tracker.current_position();
+        let Some(current) = sentences.get(position) else {
+            return ContextView::default();
+        };
+
+        let start = position.saturating_sub(before);
+        let end = (position + 1 + after).min(sentences.len());
+
+        ContextView {
+            before_text: sentences[start..position].join(" "),
+            current: current.clone(),
+            after_text: sentences[position + 1..end].join(" "),
         }
     }
 
     /// Move to next sentence
     pub fn advance(&mut self) -> bool {
+        self.try_advance().is_ok()
+    }
+
+    /// Move to next sentence, reporting why the move failed
+    pub fn try_advance(&mut self) -> Result<(), NavError> {
+        self.sync_loaded();
+        if self.total_sentences() == 0 {
+            return Err(NavError::NoTextLoaded);
+        }
+
         let old_position = self.position_tracker.current_position();
-        let moved = self.position_tracker.advance();
-        if moved {
-            self.history_manager.add_position(old_position);
+        if !self.position_tracker.advance() {
+            return Err(NavError::AtEnd);
         }
-        moved
+        self.history_manager.add_position(old_position);
+        Ok(())
     }
 
     /// Move to previous sentence
     pub fn previous(&mut self) -> bool {
+        self.try_previous().is_ok()
+    }
+
+    /// Move to previous sentence, reporting why the move failed
+    pub fn try_previous(&mut self) -> Result<(), NavError> {
+        if self.total_sentences() == 0 {
+            return Err(NavError::NoTextLoaded);
+        }
+
         let old_position = self.position_tracker.current_position();
-        let moved = self.position_tracker.previous();
-        if moved {
-            self.history_manager.add_position(old_position);
+        if !self.position_tracker.previous() {
+            return Err(NavError::AtBeginning);
         }
-        moved
+        self.history_manager.add_position(old_position);
+        Ok(())
+    }
+
+    /// Move forward up to `n` sentences, stopping at the last one; the jump is
+    /// one history entry. Returns how many sentences were actually moved.
+    pub fn advance_by(&mut self, n: usize) -> usize {
+        self.sync_loaded();
+        let position = self.position_tracker.current_position();
+        let last = self.total_sentences().saturating_sub(1);
+        self.jump_to(position.saturating_add(n).min(last))
+    }
+
+    /// Move back up to `n` sentences, stopping at the first one; the jump is
+    /// one history entry. Returns how many sentences were actually moved.
+    pub fn previous_by(&mut self, n: usize) -> usize {
+        self.sync_loaded();
+        let position = self.position_tracker.current_position();
+        self.jump_to(position.saturating_sub(n))
+    }
+
+    fn jump_to(&mut self, target: usize) -> usize {
+        let old_position = self.position_tracker.current_position();
+        if target == old_position || !self.position_tracker.goto(target) {
+            return 0;
+        }
+        // Record both ends so `go_back` undoes the whole jump in one step
+        self.history_manager.add_position(old_position);
+        self.history_manager.add_position(target);
+        target.abs_diff(old_position)
     }
 
     /// Jump to specific position
     pub fn goto_position(&mut self, position: usize) -> bool {
+        self.sync_loaded();
         let old_position = self.position_tracker.current_position();
         let moved = self.position_tracker.goto(position);
         if moved {
@@ -143,3 +334,210 @@ impl Default for NavigationService {
         Self::new()
     }
 }
+
+fn find_duplicates(sentences: &[String]) -> HashMap<String, Vec<usize>> {
+    let mut positions: HashMap<String, Vec<usize>> = HashMap::new();
+    for (position, sentence) in sentences.iter().enumerate() {
+        let key = sentence.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        positions.entry(key).or_default().push(position);
+    }
+    positions.retain(|_, positions| positions.len() > 1);
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loaded_service() -> NavigationService {
+        let mut service = NavigationService::new();
+        service.load_text("One is first. Two is next. Three is middle. Four is late. Five is last.").unwrap();
+        service
+    }
+
+    #[test]
+    fn test_try_advance_without_text() {
+        let mut service = NavigationService::new();
+        assert_eq!(service.try_advance(), Err(NavError::NoTextLoaded));
+        assert_eq!(service.try_previous(), Err(NavError::NoTextLoaded));
+        assert!(!service.advance());
+    }
+
+    #[test]
+    fn test_try_advance_at_end_and_beginning() {
+        let mut service = loaded_service();
+        assert_eq!(service.try_previous(), Err(NavError::AtBeginning));
+
+        service.goto_position(4);
+        assert_eq!(service.try_advance(), Err(NavError::AtEnd));
+        assert_eq!(service.current_position(), 4);
+
+        assert_eq!(service.try_previous(), Ok(()));
+        assert_eq!(service.current_position(), 3);
+        assert_eq!(service.try_advance(), Ok(()));
+    }
+
+    #[test]
+    fn test_rendered_context_at_start() {
+        let service = loaded_service();
+        let view = service.rendered_context(2, 2);
+
+        assert_eq!(view.before_text, "");
+        assert_eq!(view.current, "One is first.");
+        assert_eq!(view.after_text, "Two is next. Three is middle.");
+    }
+
+    #[test]
+    fn test_rendered_context_in_middle() {
+        let mut service = loaded_service();
+        service.goto_position(2);
+        let view = service.rendered_context(1, 1);
+
+        assert_eq!(view.before_text, "Two is next.");
+        assert_eq!(view.current, "Three is middle.");
+        assert_eq!(view.after_text, "Four is late.");
+    }
+
+    #[test]
+    fn test_rendered_context_at_end() {
+        let mut service = loaded_service();
+        service.goto_position(4);
+        let view = service.rendered_context(3, 5);
+
+        assert_eq!(view.before_text, "Two is next. Three is middle. Four is late.");
+        assert_eq!(view.current, "Five is last.");
+        assert_eq!(view.after_text, "");
+    }
+
+    #[test]
+    fn test_rendered_context_without_text() {
+        let service = NavigationService::new();
+        assert_eq!(service.rendered_context(1, 1), ContextView::default());
+    }
+
+    #[test]
+    fn test_advance_past_end_without_wrap() {
+        let mut service = loaded_service();
+        service.goto_position(4);
+
+        assert!(!service.advance());
+        assert_eq!(service.current_position(), 4);
+        assert!(!service.is_at_beginning());
+    }
+
+    #[test]
+    fn test_visit_log_counts_revisits_in_first_seen_order() {
+        let mut service = loaded_service();
+        service.current_sentence();
+        service.goto_position(3);
+        service.current_sentence();
+        service.current_sentence();
+        service.goto_position(0);
+        service.current_sentence();
+        service.goto_position(3);
+        service.current_sentence();
+
+        let visits: Vec<(usize, u32)> = service.visit_log().iter().map(|v| (v.position, v.visit_count)).collect();
+        assert_eq!(visits, vec![(0, 2), (3, 2)]);
+
+        service.load_text("A new text.").unwrap();
+        assert!(service.visit_log().is_empty());
+    }
+
+    #[test]
+    fn test_wrap_around_records_history() {
+        let mut service = NavigationService::new().with_wrap_around(true);
+        service.load_text("One is first. Two is next. Three is last.").unwrap();
+
+        assert!(service.previous());
+        assert_eq!(service.current_sentence().as_deref(), Some("Three is last."));
+
+        assert!(service.advance());
+        assert_eq!(service.current_sentence().as_deref(), Some("One is first."));
+
+        // Both wrapping moves were recorded
+        assert!(service.can_go_back());
+        assert!(service.go_back());
+        assert!(!service.can_go_back());
+    }
+
+    #[test]
+    fn test_advance_by_jumps_mid_document() {
+        let mut service = loaded_service();
+
+        assert_eq!(service.advance_by(2), 2);
+        assert_eq!(service.current_position(), 2);
+        assert_eq!(service.previous_by(1), 1);
+        assert_eq!(service.current_position(), 1);
+    }
+
+    #[test]
+    fn test_step_by_clamps_at_boundaries() {
+        let mut service = loaded_service();
+        service.goto_position(3);
+
+        assert_eq!(service.advance_by(10), 1);
+        assert_eq!(service.current_position(), 4);
+        assert_eq!(service.advance_by(3), 0);
+
+        assert_eq!(service.previous_by(usize::MAX), 4);
+        assert!(service.is_at_beginning());
+        assert_eq!(service.previous_by(1), 0);
+
+        assert_eq!(NavigationService::new().advance_by(3), 0);
+    }
+
+    #[test]
+    fn test_jump_is_one_history_entry() {
+        let mut service = loaded_service();
+
+        assert_eq!(service.advance_by(3), 3);
+        assert_eq!(service.history_manager.len(), 2);
+
+        assert!(service.go_back());
+        assert_eq!(service.current_position(), 0);
+        assert!(service.go_forward());
+        assert_eq!(service.current_position(), 3);
+
+        assert_eq!(service.previous_by(2), 2);
+        assert!(service.go_back());
+        assert_eq!(service.current_position(), 3);
+    }
+
+    #[test]
+    fn test_incremental_load_grows_total_sentences() {
+        let text = (0..200).map(|i| format!("Sentence {i} is here.")).collect::<Vec<_>>().join("\n\n");
+        let mut service = NavigationService::new();
+        service.load_text_incremental(&text, 2).unwrap();
+
+        assert_eq!(service.total_sentences(), 2);
+        assert_eq!(service.current_sentence().as_deref(), Some("Sentence 0 is here."));
+        assert!(!service.is_fully_loaded());
+
+        service.text_loader.wait_until_loaded();
+        assert_eq!(service.total_sentences(), 2);
+
+        assert!(service.advance());
+        assert_eq!(service.total_sentences(), 200);
+        assert_eq!(service.loaded_count(), 200);
+        assert!(service.is_fully_loaded());
+        assert!(service.goto_position(150));
+    }
+
+    #[test]
+    fn test_duplicate_positions_group_repeated_sentences() {
+        let mut service = NavigationService::new();
+        service
+            .load_text("We will rock you. Buddy you're a boy. We  will rock you. Sing it! we will ROCK you.\n\nSing it!")
+            .unwrap();
+
+        let duplicates = service.duplicate_positions();
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates["we will rock you."], vec![0, 2, 4]);
+        assert_eq!(duplicates["sing it!"], vec![3, 5]);
+        assert!(!duplicates.contains_key("buddy you're a boy."));
+
+        service.load_text("No repeats here. None at all.").unwrap();
+        assert!(service.duplicate_positions().is_empty());
+    }
+}
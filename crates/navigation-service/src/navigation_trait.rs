@@ -29,6 +29,24 @@ pub trait NavigationStrategy: Send + Sync {
     
     /// Get strategy name for debugging
     fn strategy_name(&self) -> &str;
+
+    /// Lowercase name of one content unit, for progress text like "Paragraph 3 of 12"
+    fn unit_label(&self) -> &str {
+        "sentence"
+    }
+
+    /// Number of content units in the loaded text
+    fn unit_count(&self) -> usize;
+
+    /// Zero-based index of the current content unit
+    fn current_unit(&self) -> usize;
+
+    /// Zero-based index of the unit holding the word at `word_offset`, counting words
+    /// from the start of the text as `extract_words` does; lets a reader navigating by
+    /// sentence show progress in this strategy's units. `None` when not supported.
+    fn unit_at_word(&self, _word_offset: usize) -> Option<usize> {
+        None
+    }
     
     /// Get recommended reading speed (words per minute)
     fn recommended_wpm(&self) -> Option<u32> {
@@ -50,6 +68,7 @@ pub trait NavigationStrategy: Send + Sync {
 /// Linear sentence-by-sentence navigation (current default)
 pub struct LinearNavigationStrategy {
     sentences: Vec<String>,
+    word_starts: Vec<usize>,
     current_position: usize,
     total_units_processed: usize,
 }
@@ -58,6 +77,7 @@ impl LinearNavigationStrategy {
     pub fn new() -> Self {
         Self {
             sentences: Vec::new(),
+            word_starts: Vec::new(),
             current_position: 0,
             total_units_processed: 0,
         }
@@ -75,6 +95,7 @@ impl NavigationStrategy for LinearNavigationStrategy {
         use glossia_text_parser::split_into_sentences;
         
         self.sentences = split_into_sentences(text);
+        self.word_starts = word_starts(&self.sentences);
         self.current_position = 0;
         self.total_units_processed = 0;
         Ok(())
@@ -138,6 +159,18 @@ impl NavigationStrategy for LinearNavigationStrategy {
     fn strategy_name(&self) -> &str {
         "Linear"
     }
+
+    fn unit_count(&self) -> usize {
+        self.sentences.len()
+    }
+
+    fn current_unit(&self) -> usize {
+        self.current_position
+    }
+
+    fn unit_at_word(&self, word_offset: usize) -> Option<usize> {
+        unit_containing(&self.word_starts, word_offset)
+    }
     
     fn units_processed(&self) -> usize {
         self.total_units_processed
@@ -152,6 +185,7 @@ impl NavigationStrategy for LinearNavigationStrategy {
 /// Paragraph-based navigation for faster reading
 pub struct ParagraphNavigationStrategy {
     paragraphs: Vec<String>,
+    word_starts: Vec<usize>,
     current_position: usize,
     total_units_processed: usize,
 }
@@ -160,6 +194,7 @@ impl ParagraphNavigationStrategy {
     pub fn new() -> Self {
         Self {
             paragraphs: Vec::new(),
+            word_starts: Vec::new(),
             current_position: 0,
             total_units_processed: 0,
         }
@@ -186,6 +221,7 @@ impl NavigationStrategy for ParagraphNavigationStrategy {
             // Fallback: treat entire text as one paragraph
             self.paragraphs.push(text.to_string());
         }
+        self.word_starts = word_starts(&self.paragraphs);
         
         self.current_position = 0;
         self.total_units_processed = 0;
@@ -250,6 +286,22 @@ impl NavigationStrategy for ParagraphNavigationStrategy {
     fn strategy_name(&self) -> &str {
         "Paragraph"
     }
+
+    fn unit_label(&self) -> &str {
+        "paragraph"
+    }
+
+    fn unit_count(&self) -> usize {
+        self.paragraphs.len()
+    }
+
+    fn current_unit(&self) -> usize {
+        self.current_position
+    }
+
+    fn unit_at_word(&self, word_offset: usize) -> Option<usize> {
+        unit_containing(&self.word_starts, word_offset)
+    }
     
     fn recommended_wpm(&self) -> Option<u32> {
         Some(200) // Faster reading for paragraphs
@@ -375,6 +427,23 @@ impl NavigationStrategy for SpeedReadingStrategy {
     fn strategy_name(&self) -> &str {
         "SpeedReading"
     }
+
+    fn unit_label(&self) -> &str {
+        "chunk"
+    }
+
+    fn unit_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    fn current_unit(&self) -> usize {
+        self.current_position
+    }
+
+    fn unit_at_word(&self, word_offset: usize) -> Option<usize> {
+        let last = self.chunks.len().checked_sub(1)?;
+        Some((word_offset / self.chunk_size).min(last))
+    }
     
     fn recommended_wpm(&self) -> Option<u32> {
         Some(self.wpm)
@@ -397,6 +466,25 @@ impl NavigationStrategy for SpeedReadingStrategy {
     }
 }
 
+/// Word offset at which each unit starts
+fn word_starts(units: &[String]) -> Vec<usize> {
+    use glossia_text_parser::extract_words;
+
+    units
+        .iter()
+        .scan(0, |offset, unit| {
+            let start = *offset;
+            *offset += extract_words(unit).len();
+            Some(start)
+        })
+        .collect()
+}
+
+/// Index of the last unit starting at or before `word_offset`
+fn unit_containing(word_starts: &[usize], word_offset: usize) -> Option<usize> {
+    word_starts.partition_point(|&start| start <= word_offset).checked_sub(1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -464,4 +552,40 @@ mod tests {
         strategy.next();
         assert_eq!(strategy.progress(), 1.0); // At end
     }
+
+    #[test]
+    fn test_unit_labels_and_counts() {
+        let text = "One two three. Four five six.\n\nSeven eight nine ten eleven.";
+        let mut strategies: Vec<Box<dyn NavigationStrategy>> = vec![
+            Box::new(LinearNavigationStrategy::new()),
+            Box::new(ParagraphNavigationStrategy::new()),
+            Box::new(SpeedReadingStrategy::new().with_chunk_size(4)),
+        ];
+        for strategy in &mut strategies {
+            strategy.load_text(text).unwrap();
+        }
+
+        let units: Vec<(&str, usize)> = strategies.iter().map(|s| (s.unit_label(), s.unit_count())).collect();
+        assert_eq!(units, vec![("sentence", 3), ("paragraph", 2), ("chunk", 3)]);
+
+        assert!(strategies[1].next());
+        assert_eq!(strategies[1].current_unit(), 1);
+    }
+
+    #[test]
+    fn test_unit_at_word_maps_offsets_to_units() {
+        let text = "One two three. Four five six.\n\nSeven eight nine ten eleven.";
+        let mut linear = LinearNavigationStrategy::new();
+        let mut paragraphs = ParagraphNavigationStrategy::new();
+        let mut chunks = SpeedReadingStrategy::new().with_chunk_size(4);
+        for strategy in [&mut linear as &mut dyn NavigationStrategy, &mut paragraphs, &mut chunks] {
+            strategy.load_text(text).unwrap();
+        }
+
+        let at = |strategy: &dyn NavigationStrategy| [0, 3, 5, 6, 10, 50].map(|offset| strategy.unit_at_word(offset));
+        assert_eq!(at(&linear), [0, 1, 1, 2, 2, 2].map(Some));
+        assert_eq!(at(&paragraphs), [0, 0, 0, 1, 1, 1].map(Some));
+        assert_eq!(at(&chunks), [0, 0, 1, 1, 2, 2].map(Some));
+        assert_eq!(SpeedReadingStrategy::new().unit_at_word(0), None);
+    }
 }
@@ -2,6 +2,7 @@
 pub struct PositionTracker {
     position: usize,
     total_sentences: usize,
+    wrap_around: bool,
 }
 
 impl PositionTracker {
@@ -9,20 +10,43 @@ impl PositionTracker {
         Self {
             position: 0,
             total_sentences: 0,
+            wrap_around: false,
         }
     }
 
+    /// Wrap past the last sentence to the first, and before the first to the last
+    pub fn with_wrap_around(mut self, wrap_around: bool) -> Self {
+        self.wrap_around = wrap_around;
+        self
+    }
+
+    pub fn set_wrap_around(&mut self, wrap_around: bool) {
+        self.wrap_around = wrap_around;
+    }
+
+    pub fn wrap_around(&self) -> bool {
+        self.wrap_around
+    }
+
     /// Reset position and set total sentences
     pub fn reset(&mut self, total_sentences: usize) {
         self.position = 0;
         self.total_sentences = total_sentences;
     }
 
+    /// Update the total as more sentences become available, keeping the position
+    pub fn set_total_sentences(&mut self, total_sentences: usize) {
+        self.total_sentences = total_sentences;
+    }
+
     /// Move to next position
     pub fn advance(&mut self) -> bool {
         if self.position + 1 < self.total_sentences {
             self.position += 1;
             true
+        } else if self.wrap_around && self.total_sentences > 1 {
+            self.position = 0;
+            true
         } else {
             false
         }
@@ -33,6 +57,9 @@ impl PositionTracker {
         if self.position > 0 {
             self.position -= 1;
             true
+        } else if self.wrap_around && self.total_sentences > 1 {
+            self.position = self.total_sentences - 1;
+            true
         } else {
             false
         }
@@ -88,3 +115,46 @@ impl Default for PositionTracker {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_wrap_stops_at_boundaries() {
+        let mut tracker = PositionTracker::new();
+        tracker.reset(3);
+
+        assert!(!tracker.previous());
+        assert_eq!(tracker.current_position(), 0);
+
+        tracker.goto(2);
+        assert!(!tracker.advance());
+        assert_eq!(tracker.current_position(), 2);
+    }
+
+    #[test]
+    fn test_wrap_around_at_both_boundaries() {
+        let mut tracker = PositionTracker::new().with_wrap_around(true);
+        tracker.reset(3);
+
+        assert!(tracker.previous());
+        assert_eq!(tracker.current_position(), 2);
+
+        assert!(tracker.advance());
+        assert_eq!(tracker.current_position(), 0);
+    }
+
+    #[test]
+    fn test_wrap_around_needs_more_than_one_sentence() {
+        let mut tracker = PositionTracker::new().with_wrap_around(true);
+
+        tracker.reset(1);
+        assert!(!tracker.advance());
+        assert!(!tracker.previous());
+
+        tracker.reset(0);
+        assert!(!tracker.advance());
+        assert!(!tracker.previous());
+    }
+}
@@ -1,32 +1,276 @@
-use glossia_text_parser::split_into_sentences;
+use glossia_text_parser::{PreprocessPipeline, SentenceSplitter, TextPreprocessor};
 use glossia_shared::AppError;
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+/// Lines with more words than this are never treated as headings or list items
+const MAX_SHORT_LINE_WORDS: usize = 10;
+
+/// Sentences kept from one text unless configured otherwise; the rest are dropped
+pub const DEFAULT_MAX_SENTENCES: usize = 20_000;
+
+/// How paragraphs made only of short lines without closing punctuation, such as
+/// headings and list items separated by blank lines, are turned into sentences
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShortLineMode {
+    /// Each such paragraph is split like any other, usually becoming its own sentence
+    #[default]
+    Separate,
+    /// Consecutive short paragraphs are joined onto the start of the next paragraph
+    JoinWithNext,
+    /// A single short line is kept whole as one sentence and flagged as a heading
+    Heading,
+}
+
+/// Sentences of one paragraph, as parsed up front or by the background loader
+struct ParsedParagraph {
+    index: usize,
+    sentences: Vec<String>,
+    is_heading: bool,
+}
 
 /// Handles text loading and sentence splitting
 pub struct TextLoader {
     sentences: Option<Vec<String>>,
+    sentence_paragraph_indices: Vec<usize>,
+    min_sentence_len: usize,
+    splitter: SentenceSplitter,
+    /// Paragraphs still being parsed by `load_text_incremental`
+    pending: Option<Receiver<ParsedParagraph>>,
+    preprocessors: PreprocessPipeline,
+    short_line_mode: ShortLineMode,
+    /// Indices of sentences loaded as headings under `ShortLineMode::Heading`
+    headings: HashSet<usize>,
+    max_sentences: usize,
+    /// Sentences of the loaded text dropped for exceeding `max_sentences`
+    truncated_count: usize,
 }
 
 impl TextLoader {
     pub fn new() -> Self {
         Self {
             sentences: None,
+            sentence_paragraph_indices: Vec::new(),
+            min_sentence_len: 1,
+            splitter: SentenceSplitter::new(),
+            pending: None,
+            preprocessors: PreprocessPipeline::new(),
+            short_line_mode: ShortLineMode::Separate,
+            headings: HashSet::new(),
+            max_sentences: DEFAULT_MAX_SENTENCES,
+            truncated_count: 0,
         }
     }
 
+    /// Keep at most `max` sentences of a loaded text, dropping the rest so very
+    /// long documents don't bog down the UI; `truncated_count` reports how many were cut
+    pub fn with_max_sentences(mut self, max: usize) -> Self {
+        self.max_sentences = max.max(1);
+        self
+    }
+
+    pub fn max_sentences(&self) -> usize {
+        self.max_sentences
+    }
+
+    /// Sentences of the loaded text dropped for exceeding `max_sentences`
+    pub fn truncated_count(&self) -> usize {
+        self.truncated_count
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated_count > 0
+    }
+
+    /// Merge sentences with fewer than `min_len` non-whitespace characters
+    /// into a neighboring sentence instead of keeping them on their own
+    pub fn with_min_sentence_len(mut self, min_len: usize) -> Self {
+        self.min_sentence_len = min_len;
+        self
+    }
+
+    /// Use a custom sentence splitter, e.g. one that keeps quoted dialogue together
+    pub fn with_sentence_splitter(mut self, splitter: SentenceSplitter) -> Self {
+        self.splitter = splitter;
+        self
+    }
+
+    /// Clean up loaded text with `preprocessors` (e.g. dehyphenation, Markdown
+    /// stripping) before it is split into paragraphs and sentences
+    pub fn with_preprocessors(mut self, preprocessors: PreprocessPipeline) -> Self {
+        self.preprocessors = preprocessors;
+        self
+    }
+
+    /// Choose how headings and list items without closing punctuation are loaded
+    pub fn with_short_line_mode(mut self, mode: ShortLineMode) -> Self {
+        self.short_line_mode = mode;
+        self
+    }
+
     /// Load text and split into sentences
     pub fn load_text(&mut self, text: &str) -> Result<Vec<String>, AppError> {
         if text.trim().is_empty() {
             return Err(AppError::config_error("Text cannot be empty"));
         }
+        let text = &self.preprocessors.process(text);
+
+        let mut sentences = Vec::new();
+        let mut paragraph_indices = Vec::new();
+        let mut headings = HashSet::new();
+
+        for (paragraph_index, paragraph) in self.paragraphs(text).iter().enumerate() {
+            let parsed = parse_paragraph(&self.splitter, self.min_sentence_len, self.short_line_mode, paragraph_index, paragraph);
+            append_paragraph(&mut sentences, &mut paragraph_indices, &mut headings, parsed);
+        }
+
+        if sentences.is_empty() {
+            return Err(AppError::config_error("No sentences found in text"));
+        }
+
+        self.pending = None;
+        self.sentences = Some(sentences);
+        self.sentence_paragraph_indices = paragraph_indices;
+        self.headings = headings;
+        self.truncated_count = 0;
+        self.enforce_max_sentences();
+        Ok(self.sentences.clone().unwrap_or_default())
+    }
+
+    /// Parse whole paragraphs until at least `initial_sentences` are available and
+    /// hand the rest of the text to a background thread. Sentences parsed there are
+    /// appended by `sync_background`; the initial ones are returned.
+    pub fn load_text_incremental(&mut self, text: &str, initial_sentences: usize) -> Result<Vec<String>, AppError> {
+        if text.trim().is_empty() {
+            return Err(AppError::config_error("Text cannot be empty"));
+        }
+        let text = &self.preprocessors.process(text);
+
+        let mut paragraphs = self.paragraphs(text).into_iter().enumerate();
+        let mut sentences = Vec::new();
+        let mut paragraph_indices = Vec::new();
+        let mut headings = HashSet::new();
+
+        for (paragraph_index, paragraph) in paragraphs.by_ref() {
+            let parsed = parse_paragraph(&self.splitter, self.min_sentence_len, self.short_line_mode, paragraph_index, &paragraph);
+            append_paragraph(&mut sentences, &mut paragraph_indices, &mut headings, parsed);
+            if sentences.len() >= initial_sentences.max(1) {
+                break;
+            }
+        }
 
-        let sentences = split_into_sentences(text);
-        
         if sentences.is_empty() {
             return Err(AppError::config_error("No sentences found in text"));
         }
 
-        self.sentences = Some(sentences.clone());
-        Ok(sentences)
+        let remaining: Vec<(usize, String)> = paragraphs.collect();
+        self.pending = if remaining.is_empty() {
+            None
+        } else {
+            let (sender, receiver) = mpsc::channel();
+            let splitter = self.splitter;
+            let min_sentence_len = self.min_sentence_len;
+            let short_line_mode = self.short_line_mode;
+            thread::spawn(move || {
+                for (index, paragraph) in remaining {
+                    let parsed = parse_paragraph(&splitter, min_sentence_len, short_line_mode, index, &paragraph);
+                    // The loader was cleared or reloaded, so nobody wants the rest
+                    if sender.send(parsed).is_err() {
+                        break;
+                    }
+                }
+            });
+            Some(receiver)
+        };
+
+        self.sentences = Some(sentences);
+        self.sentence_paragraph_indices = paragraph_indices;
+        self.headings = headings;
+        self.truncated_count = 0;
+        self.enforce_max_sentences();
+        Ok(self.sentences.clone().unwrap_or_default())
+    }
+
+    /// Append whatever the background parser has finished so far, returning how
+    /// many sentences were added
+    pub fn sync_background(&mut self) -> usize {
+        self.collect_background(false)
+    }
+
+    /// Block until the background parser is done and append everything it produced
+    pub fn wait_until_loaded(&mut self) -> usize {
+        self.collect_background(true)
+    }
+
+    fn collect_background(&mut self, block: bool) -> usize {
+        let Some(receiver) = self.pending.take() else {
+            return 0;
+        };
+
+        let before = self.sentence_count();
+        loop {
+            let parsed = if block {
+                receiver.recv().map_err(|_| TryRecvError::Disconnected)
+            } else {
+                receiver.try_recv()
+            };
+
+            match parsed {
+                Ok(paragraph) => {
+                    append_paragraph(
+                        self.sentences.get_or_insert_with(Vec::new),
+                        &mut self.sentence_paragraph_indices,
+                        &mut self.headings,
+                        paragraph,
+                    );
+                }
+                Err(TryRecvError::Empty) => {
+                    self.pending = Some(receiver);
+                    break;
+                }
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+        self.enforce_max_sentences();
+        self.sentence_count() - before
+    }
+
+    /// Drop sentences past `max_sentences`, counting them in `truncated_count`
+    fn enforce_max_sentences(&mut self) {
+        let max = self.max_sentences;
+        let Some(sentences) = self.sentences.as_mut().filter(|sentences| sentences.len() > max) else {
+            return;
+        };
+        self.truncated_count += sentences.len() - max;
+        sentences.truncate(max);
+        self.sentence_paragraph_indices.truncate(max);
+        self.headings.retain(|&index| index < max);
+    }
+
+    /// Whether the background parser has finished and all of its sentences were collected
+    pub fn is_fully_loaded(&self) -> bool {
+        self.pending.is_none()
+    }
+
+    /// Sentences available so far; same as `sentence_count`
+    pub fn loaded_count(&self) -> usize {
+        self.sentence_count()
+    }
+
+    /// Get the paragraph index a sentence came from
+    pub fn paragraph_of(&self, sentence_index: usize) -> Option<usize> {
+        self.sentence_paragraph_indices.get(sentence_index).copied()
+    }
+
+    /// Whether the sentence was loaded as a heading under `ShortLineMode::Heading`
+    pub fn is_heading(&self, sentence_index: usize) -> bool {
+        self.headings.contains(&sentence_index)
+    }
+
+    /// Get the paragraph index for every loaded sentence
+    pub fn sentence_paragraph_indices(&self) -> &[usize] {
+        &self.sentence_paragraph_indices
     }
 
     /// Get loaded sentences
@@ -36,7 +280,11 @@ impl TextLoader {
 
     /// Clear loaded sentences
     pub fn clear(&mut self) {
+        self.pending = None;
         self.sentences = None;
+        self.sentence_paragraph_indices.clear();
+        self.headings.clear();
+        self.truncated_count = 0;
     }
 
     /// Check if text is loaded
@@ -48,6 +296,14 @@ impl TextLoader {
     pub fn sentence_count(&self) -> usize {
         self.sentences.as_ref().map(|s| s.len()).unwrap_or(0)
     }
+
+    fn paragraphs(&self, text: &str) -> Vec<String> {
+        let paragraphs = split_into_paragraphs(text);
+        match self.short_line_mode {
+            ShortLineMode::JoinWithNext => join_short_paragraphs(paragraphs),
+            ShortLineMode::Separate | ShortLineMode::Heading => paragraphs,
+        }
+    }
 }
 
 impl Default for TextLoader {
@@ -55,3 +311,284 @@ impl Default for TextLoader {
         Self::new()
     }
 }
+
+fn parse_paragraph(
+    splitter: &SentenceSplitter,
+    min_sentence_len: usize,
+    short_line_mode: ShortLineMode,
+    index: usize,
+    paragraph: &str,
+) -> ParsedParagraph {
+    if short_line_mode == ShortLineMode::Heading && !paragraph.contains('\n') && is_short_line(paragraph) {
+        return ParsedParagraph { index, sentences: vec![paragraph.trim().to_string()], is_heading: true };
+    }
+    let sentences = merge_short_sentences(splitter.split(paragraph), min_sentence_len);
+    ParsedParagraph { index, sentences, is_heading: false }
+}
+
+fn append_paragraph(
+    sentences: &mut Vec<String>,
+    paragraph_indices: &mut Vec<usize>,
+    headings: &mut HashSet<usize>,
+    parsed: ParsedParagraph,
+) {
+    if parsed.is_heading {
+        headings.insert(sentences.len());
+    }
+    paragraph_indices.extend(std::iter::repeat_n(parsed.index, parsed.sentences.len()));
+    sentences.extend(parsed.sentences);
+}
+
+/// A line with only a few words and no sentence-ending punctuation,
+/// like a heading or a list item
+fn is_short_line(line: &str) -> bool {
+    let line = line.trim().trim_end_matches(['"', '\'', '\u{201D}', '\u{2019}', ')']);
+    let terminated = line.ends_with(['.', '?', '!', '|', ';', ':', '\u{2026}']);
+    !line.is_empty() && !terminated && line.split_whitespace().count() <= MAX_SHORT_LINE_WORDS
+}
+
+/// Carry paragraphs made only of short lines forward onto the start of the next
+/// paragraph; short paragraphs at the end of the text stay as they are
+fn join_short_paragraphs(paragraphs: Vec<String>) -> Vec<String> {
+    let mut joined = Vec::with_capacity(paragraphs.len());
+    let mut pending: Vec<String> = Vec::new();
+
+    for paragraph in paragraphs {
+        if paragraph.lines().all(is_short_line) {
+            pending.push(paragraph);
+        } else if pending.is_empty() {
+            joined.push(paragraph);
+        } else {
+            pending.push(paragraph);
+            joined.push(std::mem::take(&mut pending).join("\n"));
+        }
+    }
+
+    joined.extend(pending);
+    joined
+}
+
+/// Split text into paragraphs separated by one or more blank lines
+fn split_into_paragraphs(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current.join("\n"));
+                current.clear();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+
+    if !current.is_empty() {
+        paragraphs.push(current.join("\n"));
+    }
+
+    paragraphs
+}
+
+/// Append short fragments to the previous sentence, or to the next one
+/// when they appear before any full sentence
+fn merge_short_sentences(sentences: Vec<String>, min_len: usize) -> Vec<String> {
+    if min_len <= 1 {
+        return sentences;
+    }
+
+    let mut merged: Vec<String> = Vec::with_capacity(sentences.len());
+    let mut leading: Option<String> = None;
+
+    for sentence in sentences {
+        let is_short = sentence.chars().filter(|c| !c.is_whitespace()).count() < min_len;
+
+        if is_short {
+            match merged.last_mut() {
+                Some(previous) => {
+                    previous.push(' ');
+                    previous.push_str(&sentence);
+                }
+                None => {
+                    leading = Some(match leading {
+                        Some(fragments) => format!("{fragments} {sentence}"),
+                        None => sentence,
+                    });
+                }
+            }
+        } else {
+            merged.push(match leading.take() {
+                Some(fragments) => format!("{fragments} {sentence}"),
+                None => sentence,
+            });
+        }
+    }
+
+    // Text made up entirely of short fragments stays as a single sentence
+    if let Some(fragments) = leading {
+        merged.push(fragments);
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stray_fragments_are_merged() {
+        let mut loader = TextLoader::new().with_min_sentence_len(3);
+        let sentences = loader.load_text(". First sentence. . ! Second sentence.").unwrap();
+
+        assert_eq!(sentences, vec![
+            ". First sentence. . !".to_string(),
+            "Second sentence.".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_normal_text_is_unaffected() {
+        let text = "Hello world. This is a test! How are you?";
+        let mut default_loader = TextLoader::new();
+        let mut filtering_loader = TextLoader::new().with_min_sentence_len(3);
+
+        let expected = default_loader.load_text(text).unwrap();
+        assert_eq!(expected.len(), 3);
+        assert_eq!(filtering_loader.load_text(text).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_sentences_map_to_paragraphs() {
+        let mut loader = TextLoader::new();
+        let text = "First paragraph opens. It continues here.\n\n  \nSecond paragraph starts. It has\nthree sentences. The end.";
+        let sentences = loader.load_text(text).unwrap();
+
+        assert_eq!(sentences.len(), 5);
+        assert_eq!(loader.sentence_paragraph_indices(), &[0, 0, 1, 1, 1]);
+        assert_eq!(loader.paragraph_of(1), Some(0));
+        assert_eq!(loader.paragraph_of(2), Some(1));
+        assert_eq!(loader.paragraph_of(5), None);
+    }
+
+    #[test]
+    fn test_paragraph_break_ends_sentence_without_punctuation() {
+        let mut loader = TextLoader::new();
+        let sentences = loader.load_text("A Heading\n\nBody text follows.").unwrap();
+
+        assert_eq!(sentences, vec!["A Heading".to_string(), "Body text follows.".to_string()]);
+        assert_eq!(loader.paragraph_of(0), Some(0));
+        assert_eq!(loader.paragraph_of(1), Some(1));
+    }
+
+    #[test]
+    fn test_default_keeps_single_character_sentences() {
+        let mut loader = TextLoader::new();
+        let sentences = loader.load_text("First sentence. . Second sentence.").unwrap();
+
+        assert_eq!(sentences.len(), 3);
+    }
+
+    fn novel(paragraphs: usize) -> String {
+        (0..paragraphs)
+            .map(|i| format!("Paragraph {i} opens here. It has a second sentence."))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    #[test]
+    fn test_text_beyond_max_sentences_is_truncated() {
+        let mut loader = TextLoader::new().with_max_sentences(5);
+        let sentences = loader.load_text(&novel(4)).unwrap();
+
+        assert_eq!(sentences.len(), 5);
+        assert_eq!(loader.truncated_count(), 3);
+        assert!(loader.is_truncated());
+        assert_eq!(loader.sentence_paragraph_indices(), &[0, 0, 1, 1, 2]);
+
+        loader.load_text_incremental(&novel(10), 1).unwrap();
+        assert_eq!(loader.wait_until_loaded(), 3);
+        assert_eq!(loader.loaded_count(), 5);
+        assert_eq!(loader.truncated_count(), 15);
+
+        loader.load_text("One sentence. Two sentences.").unwrap();
+        assert!(!loader.is_truncated());
+    }
+
+    #[test]
+    fn test_incremental_load_exposes_early_sentences_first() {
+        let mut loader = TextLoader::new();
+        let initial = loader.load_text_incremental(&novel(500), 3).unwrap();
+
+        // Whole paragraphs are parsed up front, so the first chunk may exceed the request
+        assert_eq!(initial.len(), 4);
+        assert_eq!(loader.loaded_count(), 4);
+        assert_eq!(initial[0], "Paragraph 0 opens here.");
+        assert!(!loader.is_fully_loaded());
+
+        assert_eq!(loader.wait_until_loaded(), 996);
+        assert!(loader.is_fully_loaded());
+        assert_eq!(loader.loaded_count(), 1000);
+        assert_eq!(loader.paragraph_of(999), Some(499));
+        assert_eq!(loader.get_sentences(), TextLoader::new().load_text(&novel(500)).ok().as_ref());
+    }
+
+    #[test]
+    fn test_incremental_load_of_short_text_is_complete() {
+        let mut loader = TextLoader::new();
+        loader.load_text_incremental("One sentence. Another one.", 10).unwrap();
+
+        assert!(loader.is_fully_loaded());
+        assert_eq!(loader.loaded_count(), 2);
+        assert_eq!(loader.sync_background(), 0);
+    }
+
+    #[test]
+    fn test_reload_drops_background_parse() {
+        let mut loader = TextLoader::new();
+        loader.load_text_incremental(&novel(50), 1).unwrap();
+        loader.load_text("Fresh text.").unwrap();
+
+        assert!(loader.is_fully_loaded());
+        assert_eq!(loader.wait_until_loaded(), 0);
+        assert_eq!(loader.loaded_count(), 1);
+    }
+
+    #[test]
+    fn test_preprocessors_run_before_splitting() {
+        use glossia_text_parser::{CollapseWhitespace, Dehyphenate, StripMarkdown};
+
+        let mut loader = TextLoader::new().with_preprocessors(
+            PreprocessPipeline::new().with(Dehyphenate).with(StripMarkdown::default()).with(CollapseWhitespace),
+        );
+        let sentences = loader.load_text("An *inter-\nnational*   deal. It   held.").unwrap();
+        assert_eq!(sentences, vec!["An international deal.", "It held."]);
+    }
+
+    #[test]
+    fn test_bulleted_list_joins_the_following_sentence() {
+        let text = "Shopping list\n\n- eggs\n\n- milk\n\nWe went to the store. It was closed.";
+
+        let mut separate = TextLoader::new();
+        assert_eq!(separate.load_text(text).unwrap().len(), 5);
+
+        let mut loader = TextLoader::new().with_short_line_mode(ShortLineMode::JoinWithNext);
+        let sentences = loader.load_text(text).unwrap();
+        assert_eq!(sentences, vec!["Shopping list\n- eggs\n- milk\nWe went to the store.", "It was closed."]);
+        assert_eq!(loader.sentence_paragraph_indices(), &[0, 0]);
+    }
+
+    #[test]
+    fn test_heading_line_is_flagged() {
+        let text = "Chapter One: The Storm\n\nRain fell all night. Nobody slept.\n\nA closing line.";
+        let mut loader = TextLoader::new().with_short_line_mode(ShortLineMode::Heading);
+        let sentences = loader.load_text(text).unwrap();
+
+        assert_eq!(sentences[0], "Chapter One: The Storm");
+        assert!(loader.is_heading(0));
+        assert!(!loader.is_heading(1));
+        assert!(!loader.is_heading(3), "terminated lines are not headings");
+        assert_eq!(sentences.len(), 4);
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+/// One sentence the reader has viewed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentenceVisit {
+    pub position: usize,
+    pub first_seen: SystemTime,
+    pub visit_count: u32,
+}
+
+/// Every sentence the reader has viewed, one entry per position in first-seen order
+///
+/// Unlike `HistoryManager`, this is never truncated by back/forward moves.
+/// Showing the same sentence again without moving away counts as one visit.
+#[derive(Debug, Default)]
+pub struct VisitLog {
+    visits: Vec<SentenceVisit>,
+    index_by_position: HashMap<usize, usize>,
+    last_position: Option<usize>,
+}
+
+impl VisitLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that the sentence at `position` is being displayed
+    pub fn record(&mut self, position: usize) {
+        self.record_at(position, SystemTime::now());
+    }
+
+    pub fn record_at(&mut self, position: usize, now: SystemTime) {
+        if self.last_position == Some(position) {
+            return;
+        }
+        self.last_position = Some(position);
+
+        match self.index_by_position.get(&position) {
+            Some(&index) => self.visits[index].visit_count += 1,
+            None => {
+                self.index_by_position.insert(position, self.visits.len());
+                self.visits.push(SentenceVisit {
+                    position,
+                    first_seen: now,
+                    visit_count: 1,
+                });
+            }
+        }
+    }
+
+    /// Visited sentences in the order they were first seen
+    pub fn visits(&self) -> &[SentenceVisit] {
+        &self.visits
+    }
+
+    pub fn get(&self, position: usize) -> Option<&SentenceVisit> {
+        self.index_by_position.get(&position).map(|&index| &self.visits[index])
+    }
+
+    pub fn len(&self) -> usize {
+        self.visits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.visits.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.visits.clear();
+        self.index_by_position.clear();
+        self.last_position = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_revisits_increment_count_and_keep_first_seen_order() {
+        let start = SystemTime::UNIX_EPOCH;
+        let mut log = VisitLog::new();
+        log.record_at(2, start);
+        log.record_at(0, start + Duration::from_secs(1));
+        log.record_at(2, start + Duration::from_secs(2));
+
+        let positions: Vec<usize> = log.visits().iter().map(|visit| visit.position).collect();
+        assert_eq!(positions, vec![2, 0]);
+        assert_eq!(log.get(2).unwrap().visit_count, 2);
+        assert_eq!(log.get(2).unwrap().first_seen, start);
+        assert_eq!(log.get(0).unwrap().visit_count, 1);
+    }
+
+    #[test]
+    fn test_repeated_display_without_moving_is_one_visit() {
+        let mut log = VisitLog::new();
+        log.record(1);
+        log.record(1);
+        assert_eq!(log.get(1).unwrap().visit_count, 1);
+    }
+}
@@ -1,12 +1,24 @@
-use glossia_shared::{SimplificationResponse, ImageResult};
-use std::collections::HashMap;
+use glossia_shared::{AppError, SimplificationResponse, ImageResult};
+use crate::cache_trait::{Cache, CacheSnapshot};
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How long an optimized image query is reused before it is optimized again
+pub const DEFAULT_OPTIMIZED_QUERY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
 
 /// Centralized cache management for reading engine
 pub struct CacheEngine {
     simplified_cache: HashMap<String, SimplificationResponse>,
     image_cache: HashMap<String, Vec<ImageResult>>,
+    /// Image cache keys, oldest first
+    image_order: VecDeque<String>,
     word_meaning_cache: HashMap<String, String>,
-    optimized_query_cache: HashMap<String, String>,
+    /// Query and when it was cached
+    optimized_query_cache: HashMap<String, (String, Instant)>,
+    optimized_query_ttl: Option<Duration>,
+    normalize_sentence_keys: bool,
 }
 
 impl CacheEngine {
@@ -14,120 +26,228 @@ impl CacheEngine {
         Self {
             simplified_cache: HashMap::new(),
             image_cache: HashMap::new(),
+            image_order: VecDeque::new(),
             word_meaning_cache: HashMap::new(),
             optimized_query_cache: HashMap::new(),
+            optimized_query_ttl: Some(DEFAULT_OPTIMIZED_QUERY_TTL),
+            normalize_sentence_keys: true,
+        }
+    }
+
+    /// Key simplifications by the trimmed, whitespace-collapsed sentence (on by default)
+    /// so sentences differing only in spacing share one entry
+    pub fn with_sentence_key_normalization(mut self, normalize: bool) -> Self {
+        self.normalize_sentence_keys = normalize;
+        self
+    }
+
+    /// Expire optimized image queries after `ttl`; `None` keeps them until cleared
+    pub fn with_optimized_query_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.optimized_query_ttl = ttl;
+        self
+    }
+
+    fn sentence_key<'a>(&self, sentence: &'a str) -> Cow<'a, str> {
+        if self.normalize_sentence_keys {
+            Cow::Owned(sentence.split_whitespace().collect::<Vec<_>>().join(" "))
+        } else {
+            Cow::Borrowed(sentence)
+        }
+    }
+
+    /// Sentences with a cached simplification, sorted, as keyed (normalized when
+    /// sentence key normalization is on)
+    pub fn cached_sentences(&self) -> Vec<String> {
+        let mut sentences: Vec<String> = self.simplified_cache.keys().cloned().collect();
+        sentences.sort();
+        sentences
+    }
+
+    /// Memory management
+    pub fn cleanup_old_entries(&mut self, max_entries: usize) {
+        if self.simplified_cache.len() > max_entries {
+            // Keep only the most recent entries (simplified approach)
+            let excess = self.simplified_cache.len() - max_entries;
+            let keys_to_remove: Vec<String> = self.simplified_cache.keys().take(excess).cloned().collect();
+            for key in keys_to_remove {
+                self.simplified_cache.remove(&key);
+            }
+        }
+
+        while self.image_cache.len() > max_entries {
+            let Some(oldest) = self.image_order.pop_front() else { break };
+            self.image_cache.remove(&oldest);
+        }
+
+        if self.word_meaning_cache.len() > max_entries {
+            let excess = self.word_meaning_cache.len() - max_entries;
+            let keys_to_remove: Vec<String> = self.word_meaning_cache.keys().take(excess).cloned().collect();
+            for key in keys_to_remove {
+                self.word_meaning_cache.remove(&key);
+            }
         }
     }
+}
 
+impl Cache for CacheEngine {
     /// Simplification cache methods
-    pub fn get_simplified(&self, sentence: &str) -> Option<SimplificationResponse> {
-        self.simplified_cache.get(sentence).cloned()
+    fn get_simplified(&self, sentence: &str) -> Option<SimplificationResponse> {
+        self.simplified_cache.get(self.sentence_key(sentence).as_ref()).cloned()
     }
 
-    pub fn cache_simplified(&mut self, sentence: String, response: SimplificationResponse) {
-        self.simplified_cache.insert(sentence, response);
+    /// The response is stored unchanged; only the lookup key is normalized
+    fn cache_simplified(&mut self, sentence: String, response: SimplificationResponse) {
+        let key = self.sentence_key(&sentence).into_owned();
+        self.simplified_cache.insert(key, response);
     }
 
-    pub fn has_simplified(&self, sentence: &str) -> bool {
-        self.simplified_cache.contains_key(sentence)
+    fn has_simplified(&self, sentence: &str) -> bool {
+        self.simplified_cache.contains_key(self.sentence_key(sentence).as_ref())
     }
 
     /// Image cache methods
-    pub fn get_images(&self, word: &str) -> Option<Vec<ImageResult>> {
+    fn get_images(&self, word: &str) -> Option<Vec<ImageResult>> {
         self.image_cache.get(word).cloned()
     }
 
-    pub fn cache_images(&mut self, word: String, images: Vec<ImageResult>) {
+    fn cache_images(&mut self, word: String, images: Vec<ImageResult>) {
+        self.image_order.retain(|key| key != &word);
+        self.image_order.push_back(word.clone());
         self.image_cache.insert(word, images);
     }
 
-    pub fn has_images(&self, word: &str) -> bool {
+    fn has_images(&self, word: &str) -> bool {
         self.image_cache.contains_key(word)
     }
 
     /// Word meaning cache methods
-    pub fn get_word_meaning(&self, word: &str) -> Option<String> {
+    fn get_word_meaning(&self, word: &str) -> Option<String> {
         self.word_meaning_cache.get(word).cloned()
     }
 
-    pub fn cache_word_meaning(&mut self, word: String, meaning: String) {
+    fn cache_word_meaning(&mut self, word: String, meaning: String) {
         self.word_meaning_cache.insert(word, meaning);
     }
 
-    pub fn has_word_meaning(&self, word: &str) -> bool {
+    fn has_word_meaning(&self, word: &str) -> bool {
         self.word_meaning_cache.contains_key(word)
     }
 
     /// Optimized query cache methods
-    pub fn get_optimized_query(&self, context_key: &str) -> Option<String> {
-        self.optimized_query_cache.get(context_key).cloned()
+    fn get_optimized_query(&self, context_key: &str) -> Option<String> {
+        let (query, cached_at) = self.optimized_query_cache.get(context_key)?;
+        let expired = self.optimized_query_ttl.is_some_and(|ttl| cached_at.elapsed() >= ttl);
+        (!expired).then(|| query.clone())
     }
 
-    pub fn cache_optimized_query(&mut self, context_key: String, query: String) {
-        self.optimized_query_cache.insert(context_key, query);
+    fn cache_optimized_query(&mut self, context_key: String, query: String) {
+        self.optimized_query_cache.insert(context_key, (query, Instant::now()));
     }
 
-    pub fn has_optimized_query(&self, context_key: &str) -> bool {
-        self.optimized_query_cache.contains_key(context_key)
+    fn invalidate_optimized_query(&mut self, context_key: &str) {
+        self.optimized_query_cache.remove(context_key);
     }
 
     /// Cache management
-    pub fn clear_all_caches(&mut self) {
+    fn clear_all_caches(&mut self) {
         self.simplified_cache.clear();
         self.image_cache.clear();
+        self.image_order.clear();
         self.word_meaning_cache.clear();
         self.optimized_query_cache.clear();
     }
 
-    pub fn clear_text_caches(&mut self) {
+    fn clear_text_caches(&mut self) {
         self.simplified_cache.clear();
         self.word_meaning_cache.clear();
         // Keep image cache for reuse across texts
     }
 
-    pub fn clear_simplified_cache(&mut self) {
+    fn clear_simplified_cache(&mut self) {
         self.simplified_cache.clear();
     }
 
+    fn clear_image_cache(&mut self) {
+        self.image_cache.clear();
+        self.image_order.clear();
+    }
+
     /// Cache statistics
-    pub fn simplified_cache_size(&self) -> usize {
+    fn simplified_cache_size(&self) -> usize {
         self.simplified_cache.len()
     }
 
-    pub fn image_cache_size(&self) -> usize {
+    fn image_cache_size(&self) -> usize {
         self.image_cache.len()
     }
 
-    pub fn word_meaning_cache_size(&self) -> usize {
+    fn word_meaning_cache_size(&self) -> usize {
         self.word_meaning_cache.len()
     }
 
-    /// Memory management
-    pub fn cleanup_old_entries(&mut self, max_entries: usize) {
-        if self.simplified_cache.len() > max_entries {
-            // Keep only the most recent entries (simplified approach)
-            let excess = self.simplified_cache.len() - max_entries;
-            let keys_to_remove: Vec<String> = self.simplified_cache.keys().take(excess).cloned().collect();
-            for key in keys_to_remove {
-                self.simplified_cache.remove(&key);
+    fn image_result_count(&self) -> usize {
+        self.image_cache.values().map(Vec::len).sum()
+    }
+
+    fn evict_oldest_images(&mut self, target_count: usize) -> usize {
+        let mut remaining = self.image_result_count();
+        let mut evicted = 0;
+        while remaining > target_count {
+            let Some(oldest) = self.image_order.pop_front() else { break };
+            if let Some(images) = self.image_cache.remove(&oldest) {
+                remaining -= images.len();
+                evicted += 1;
             }
         }
+        evicted
+    }
 
-        if self.image_cache.len() > max_entries {
-            let excess = self.image_cache.len() - max_entries;
-            let keys_to_remove: Vec<String> = self.image_cache.keys().take(excess).cloned().collect();
-            for key in keys_to_remove {
-                self.image_cache.remove(&key);
-            }
+    fn export_snapshot(&self) -> Option<CacheSnapshot> {
+        Some(CacheSnapshot {
+            simplified: self.simplified_cache.clone(),
+            word_meanings: self.word_meaning_cache.clone(),
+        })
+    }
+
+    /// Merge a snapshot into the caches, keeping existing entries not in it
+    fn import_snapshot(&mut self, snapshot: CacheSnapshot) {
+        for (sentence, response) in snapshot.simplified {
+            let key = self.sentence_key(&sentence).into_owned();
+            self.simplified_cache.insert(key, response);
+        }
+        self.word_meaning_cache.extend(snapshot.word_meanings);
+    }
+
+    /// Persist the simplification and word meaning caches to a JSON file
+    fn save_to_file(&self, path: &Path) -> Result<(), AppError> {
+        let data = self.export_snapshot().unwrap_or_default();
+
+        let json = serde_json::to_string(&data)
+            .map_err(|e| AppError::config_error(format!("Failed to serialize cache: {e}")))?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| AppError::config_error(format!("Failed to create directory: {e}")))?;
         }
 
-        if self.word_meaning_cache.len() > max_entries {
-            let excess = self.word_meaning_cache.len() - max_entries;
-            let keys_to_remove: Vec<String> = self.word_meaning_cache.keys().take(excess).cloned().collect();
-            for key in keys_to_remove {
-                self.word_meaning_cache.remove(&key);
-            }
+        std::fs::write(path, json)
+            .map_err(|e| AppError::config_error(format!("Failed to write cache file: {e}")))
+    }
+
+    /// Load caches previously written by `save_to_file`, keeping existing entries
+    fn load_from_file(&mut self, path: &Path) -> Result<(), AppError> {
+        if !path.exists() {
+            return Ok(());
         }
+
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| AppError::config_error(format!("Failed to read cache file: {e}")))?;
+
+        let data: CacheSnapshot = serde_json::from_str(&json)
+            .map_err(|e| AppError::config_error(format!("Failed to deserialize cache: {e}")))?;
+
+        self.import_snapshot(data);
+        Ok(())
     }
 }
 
@@ -136,3 +256,143 @@ impl Default for CacheEngine {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache_trait::{word_meaning_key, ImageCacheSize};
+
+    fn response(original: &str) -> SimplificationResponse {
+        SimplificationResponse {
+            original: original.to_string(),
+            simplified: "Hi world.".to_string(),
+            words: vec![],
+            grammar_notes: vec![],
+            was_already_simple: false,
+        }
+    }
+
+    fn images(count: usize) -> Vec<ImageResult> {
+        (0..count)
+            .map(|i| ImageResult {
+                url: format!("https://example.com/{i}.jpg"),
+                title: format!("Image {i}"),
+                thumbnail_url: format!("https://example.com/{i}-thumb.jpg"),
+                width: None,
+                height: None,
+                display_url: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_image_cache_footprint_and_eviction() {
+        let mut cache = CacheEngine::new();
+        cache.cache_images("cat".to_string(), images(3));
+        cache.cache_images("dog".to_string(), images(2));
+        cache.cache_images("owl".to_string(), images(4));
+        // Re-caching makes "cat" the newest entry
+        cache.cache_images("cat".to_string(), images(3));
+
+        assert_eq!(cache.image_cache_footprint(), ImageCacheSize { words: 3, images: 9 });
+
+        assert_eq!(cache.evict_oldest_images(5), 2);
+        assert_eq!(cache.image_cache_footprint(), ImageCacheSize { words: 1, images: 3 });
+        assert!(cache.has_images("cat"));
+        assert!(!cache.has_images("dog"));
+
+        assert_eq!(cache.evict_oldest_images(10), 0);
+        assert_eq!(cache.evict_oldest_images(0), 1);
+        assert_eq!(cache.image_cache_footprint(), ImageCacheSize::default());
+    }
+
+    #[test]
+    fn test_optimized_query_expires_after_ttl() {
+        let mut cache = CacheEngine::new().with_optimized_query_ttl(Some(Duration::from_millis(20)));
+        cache.cache_optimized_query("bank#1".to_string(), "river bank".to_string());
+        assert_eq!(cache.get_optimized_query("bank#1").as_deref(), Some("river bank"));
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!cache.has_optimized_query("bank#1"));
+
+        // Re-caching refreshes the entry
+        cache.cache_optimized_query("bank#1".to_string(), "steep river bank".to_string());
+        assert_eq!(cache.get_optimized_query("bank#1").as_deref(), Some("steep river bank"));
+    }
+
+    #[test]
+    fn test_invalidated_optimized_query_is_gone() {
+        let mut cache = CacheEngine::new().with_optimized_query_ttl(None);
+        cache.cache_optimized_query("bank#1".to_string(), "money bank".to_string());
+        cache.cache_optimized_query("owl#2".to_string(), "owl at night".to_string());
+
+        cache.invalidate_optimized_query("bank#1");
+        assert!(cache.get_optimized_query("bank#1").is_none());
+        assert!(cache.has_optimized_query("owl#2"));
+    }
+
+    #[test]
+    fn test_whitespace_variants_share_an_entry() {
+        let mut cache = CacheEngine::new();
+        cache.cache_simplified("Hello world. ".to_string(), response("Hello world. "));
+
+        for variant in ["Hello world.", "  Hello   world.", "Hello\n\tworld.\n"] {
+            assert!(cache.has_simplified(variant), "variant: {variant:?}");
+            assert_eq!(cache.get_simplified(variant).unwrap().simplified, "Hi world.");
+        }
+        assert_eq!(cache.simplified_cache_size(), 1);
+    }
+
+    #[test]
+    fn test_stored_response_keeps_original_text() {
+        let mut cache = CacheEngine::new();
+        cache.cache_simplified("Hello  world.".to_string(), response("Hello  world."));
+
+        assert_eq!(cache.get_simplified("Hello world.").unwrap().original, "Hello  world.");
+    }
+
+    #[test]
+    fn test_same_word_in_different_contexts_has_distinct_entries() {
+        let mut cache = CacheEngine::new();
+        cache.cache_word_meaning_in_context("bank".to_string(), "The river bank was steep.", "side of a river".to_string());
+        cache.cache_word_meaning_in_context("bank".to_string(), "I went to the bank.", "a financial institution".to_string());
+
+        assert_eq!(
+            cache.get_word_meaning_in_context("Bank", "The  river bank was steep."),
+            Some("side of a river".to_string())
+        );
+        assert_eq!(
+            cache.get_word_meaning_in_context("bank", "I went to the bank."),
+            Some("a financial institution".to_string())
+        );
+        assert_eq!(cache.get_word_meaning_in_context("bank", "A new sentence."), None);
+
+        // The word-only fallback holds the latest meaning for placeholders
+        assert_eq!(cache.get_word_meaning("bank"), Some("a financial institution".to_string()));
+        assert_ne!(
+            word_meaning_key("bank", "The river bank was steep."),
+            word_meaning_key("bank", "I went to the bank.")
+        );
+    }
+
+    #[test]
+    fn test_normalization_can_be_disabled() {
+        let mut cache = CacheEngine::new().with_sentence_key_normalization(false);
+        cache.cache_simplified("Hello world. ".to_string(), response("Hello world. "));
+
+        assert!(cache.get_simplified("Hello world.").is_none());
+        assert!(cache.get_simplified("Hello world. ").is_some());
+    }
+
+    #[test]
+    fn test_cached_sentences_lists_simplified_entries() {
+        let mut cache = CacheEngine::new();
+        assert!(cache.cached_sentences().is_empty());
+
+        cache.cache_simplified("Second  one.".to_string(), response("Second one."));
+        cache.cache_simplified("First one.".to_string(), response("First one."));
+        cache.cache_word_meaning("cat".to_string(), "a pet".to_string());
+
+        assert_eq!(cache.cached_sentences(), vec!["First one.", "Second one."]);
+    }
+}
@@ -0,0 +1,164 @@
+use glossia_shared::{text_hash, AppError, ImageResult, SimplificationResponse};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Key for a word meaning in a particular sentence: the lowercased word plus a
+/// hash of the whitespace-normalized context, so the same word in different
+/// sentences gets separate entries. The hash is stable, so saved keys stay valid.
+pub fn word_meaning_key(word: &str, context: &str) -> String {
+    format!("{}#{}", word.trim().to_lowercase(), text_hash(context))
+}
+
+/// Canonical key for the optimized image query cache: the lowercased word plus
+/// a hash of the whitespace-normalized sentence, since the best query depends
+/// on the sense the word is used in. Every reader and writer of that cache
+/// should build keys with this.
+pub fn optimized_query_key(word: &str, context: &str) -> String {
+    format!("{}@{}", word.trim().to_lowercase(), text_hash(context))
+}
+
+/// The text caches (simplifications and word meanings), as written to disk
+/// and carried in a `SessionState`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct CacheSnapshot {
+    pub simplified: HashMap<String, SimplificationResponse>,
+    pub word_meanings: HashMap<String, String>,
+}
+
+/// How much the image cache holds: words with cached galleries and the images across them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ImageCacheSize {
+    pub words: usize,
+    pub images: usize,
+}
+
+/// Trait for reading engine cache backends
+/// Enables different cache implementations (memory, disk, Redis)
+pub trait Cache: Send + Sync {
+    /// Simplification cache
+    fn get_simplified(&self, sentence: &str) -> Option<SimplificationResponse>;
+    fn cache_simplified(&mut self, sentence: String, response: SimplificationResponse);
+    fn has_simplified(&self, sentence: &str) -> bool {
+        self.get_simplified(sentence).is_some()
+    }
+
+    /// Image cache
+    fn get_images(&self, word: &str) -> Option<Vec<ImageResult>>;
+    fn cache_images(&mut self, word: String, images: Vec<ImageResult>);
+    fn has_images(&self, word: &str) -> bool {
+        self.get_images(word).is_some()
+    }
+
+    /// Word meaning cache
+    fn get_word_meaning(&self, word: &str) -> Option<String>;
+    fn cache_word_meaning(&mut self, word: String, meaning: String);
+    fn has_word_meaning(&self, word: &str) -> bool {
+        self.get_word_meaning(word).is_some()
+    }
+
+    /// Meaning cached for `word` as used in `context`
+    fn get_word_meaning_in_context(&self, word: &str, context: &str) -> Option<String> {
+        self.get_word_meaning(&word_meaning_key(word, context))
+    }
+
+    /// Cache a context-specific meaning, also keeping it as the word-only
+    /// fallback used for display placeholders
+    fn cache_word_meaning_in_context(&mut self, word: String, context: &str, meaning: String) {
+        self.cache_word_meaning(word_meaning_key(&word, context), meaning.clone());
+        self.cache_word_meaning(word, meaning);
+    }
+
+    /// Optimized image query cache
+    fn get_optimized_query(&self, context_key: &str) -> Option<String>;
+    fn cache_optimized_query(&mut self, context_key: String, query: String);
+    /// Forget the query for `context_key` so it is optimized again
+    fn invalidate_optimized_query(&mut self, context_key: &str);
+    fn has_optimized_query(&self, context_key: &str) -> bool {
+        self.get_optimized_query(context_key).is_some()
+    }
+
+    /// Clear every cache
+    fn clear_all_caches(&mut self);
+
+    /// Clear caches tied to the loaded text, keeping reusable ones such as images
+    fn clear_text_caches(&mut self);
+
+    /// Clear cached simplifications only
+    fn clear_simplified_cache(&mut self);
+
+    /// Clear cached images only
+    fn clear_image_cache(&mut self) {
+        self.evict_oldest_images(0);
+    }
+
+    /// Cache statistics
+    fn simplified_cache_size(&self) -> usize;
+    fn image_cache_size(&self) -> usize;
+    fn word_meaning_cache_size(&self) -> usize;
+    /// Total `ImageResult` entries across all cached words
+    fn image_result_count(&self) -> usize;
+
+    fn image_cache_footprint(&self) -> ImageCacheSize {
+        ImageCacheSize {
+            words: self.image_cache_size(),
+            images: self.image_result_count(),
+        }
+    }
+
+    /// Drop the oldest cached galleries until at most `target_count` images remain,
+    /// returning how many words were evicted
+    fn evict_oldest_images(&mut self, target_count: usize) -> usize;
+
+    /// Copy of the text caches for saving a session
+    /// Backends that cannot enumerate their entries return `None`
+    fn export_snapshot(&self) -> Option<CacheSnapshot> {
+        None
+    }
+
+    /// Restore entries from `export_snapshot`
+    fn import_snapshot(&mut self, _snapshot: CacheSnapshot) {}
+
+    /// Persist cache contents to disk
+    /// Backends that persist on their own can leave this as a no-op
+    fn save_to_file(&self, _path: &Path) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    /// Load contents written by `save_to_file`, keeping existing entries
+    fn load_from_file(&mut self, _path: &Path) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimized_query_key_ignores_case_and_spacing() {
+        assert_eq!(
+            optimized_query_key(" Bank", "The bank  was\nsteep."),
+            optimized_query_key("bank", "The bank was steep."),
+        );
+    }
+
+    #[test]
+    fn test_optimized_query_key_separates_contexts() {
+        assert_ne!(
+            optimized_query_key("bank", "The bank was steep."),
+            optimized_query_key("bank", "The bank was closed."),
+        );
+        assert_ne!(
+            optimized_query_key("bank", "The bank was steep."),
+            optimized_query_key("river", "The bank was steep."),
+        );
+    }
+
+    #[test]
+    fn test_context_keys_use_the_stable_text_hash() {
+        let context = "The bank  was steep.";
+        assert_eq!(word_meaning_key("Bank", context), format!("bank#{}", text_hash("The bank was steep.")));
+        assert_eq!(optimized_query_key("Bank", context), format!("bank@{}", text_hash("The bank was steep.")));
+    }
+}
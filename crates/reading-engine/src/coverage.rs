@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// Share of a text's running words the reader already knows
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Coverage {
+    pub total_words: usize,
+    pub known_words: usize,
+    pub unknown_words: usize,
+    /// `known_words / total_words`, or zero for a text without words
+    pub coverage_fraction: f64,
+}
+
+impl Coverage {
+    /// Count every occurrence in `words`, given which of them are known
+    pub fn from_words(words: &[String], is_known: impl Fn(&str) -> bool) -> Self {
+        if words.is_empty() {
+            return Self::default();
+        }
+
+        let total_words = words.len();
+        let known_words = words.iter().filter(|word| is_known(word)).count();
+
+        Self {
+            total_words,
+            known_words,
+            unknown_words: total_words - known_words,
+            coverage_fraction: known_words as f64 / total_words as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_words_count_each_time() {
+        let words: Vec<String> = ["the", "cat", "the", "dog"].iter().map(|w| w.to_string()).collect();
+        let coverage = Coverage::from_words(&words, |word| word == "the");
+
+        assert_eq!(coverage.known_words, 2);
+        assert_eq!(coverage.unknown_words, 2);
+        assert_eq!(coverage.coverage_fraction, 0.5);
+        assert_eq!(Coverage::from_words(&[], |_| true), Coverage::default());
+    }
+}
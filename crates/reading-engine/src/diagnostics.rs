@@ -0,0 +1,25 @@
+use crate::reading_orchestrator::CacheStats;
+use crate::state_manager::SessionStats;
+use glossia_llm_client::{CircuitState, RequestStats};
+use serde::Serialize;
+
+/// Vocabulary counts for diagnostics
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VocabularyStats {
+    pub known_words: usize,
+    pub tracked_words: usize,
+    pub manual_words: usize,
+}
+
+/// Snapshot of every reading engine subsystem for a diagnostics panel
+/// Request stats and circuit state are `None` when the LLM client has no http client
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostics {
+    pub provider: String,
+    pub model: String,
+    pub cache: CacheStats,
+    pub requests: Option<RequestStats>,
+    pub circuit_state: Option<CircuitState>,
+    pub session: SessionStats,
+    pub vocabulary: VocabularyStats,
+}
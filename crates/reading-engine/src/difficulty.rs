@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+/// Weight of the unknown-word ratio in `DifficultyScore::score`
+const UNKNOWN_WEIGHT: f64 = 0.6;
+/// Weight of the average word length in `DifficultyScore::score`
+const WORD_LENGTH_WEIGHT: f64 = 0.25;
+/// Weight of the sentence length in `DifficultyScore::score`
+const SENTENCE_LENGTH_WEIGHT: f64 = 0.15;
+
+/// Average word lengths at or below this count as easy
+const EASY_WORD_LENGTH: f64 = 3.0;
+/// Average word lengths at or above this count as hard
+const HARD_WORD_LENGTH: f64 = 9.0;
+/// Sentences with this many words or more count as maximally long
+const LONG_SENTENCE_WORDS: f64 = 40.0;
+
+/// How hard a sentence is likely to be for the reader
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyScore {
+    /// Normalized difficulty in `0.0..=1.0`
+    pub score: f64,
+    pub word_count: usize,
+    pub unknown_words: usize,
+    /// Mean number of characters per word
+    pub average_word_length: f64,
+}
+
+impl DifficultyScore {
+    /// Score a sentence from its extracted words, given which of them are unknown
+    pub fn from_words(words: &[String], is_unknown: impl Fn(&str) -> bool) -> Self {
+        if words.is_empty() {
+            return Self::default();
+        }
+
+        let word_count = words.len();
+        let unknown_words = words.iter().filter(|word| is_unknown(word)).count();
+        let average_word_length =
+            words.iter().map(|word| word.chars().count()).sum::<usize>() as f64 / word_count as f64;
+
+        let unknown_ratio = unknown_words as f64 / word_count as f64;
+        let length_factor =
+            ((average_word_length - EASY_WORD_LENGTH) / (HARD_WORD_LENGTH - EASY_WORD_LENGTH)).clamp(0.0, 1.0);
+        let sentence_factor = (word_count as f64 / LONG_SENTENCE_WORDS).min(1.0);
+
+        let score = UNKNOWN_WEIGHT * unknown_ratio
+            + WORD_LENGTH_WEIGHT * length_factor
+            + SENTENCE_LENGTH_WEIGHT * sentence_factor;
+
+        Self {
+            score: score.clamp(0.0, 1.0),
+            word_count,
+            unknown_words,
+            average_word_length,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<String> {
+        glossia_text_parser::extract_words(text)
+    }
+
+    #[test]
+    fn test_empty_sentence_scores_zero() {
+        assert_eq!(DifficultyScore::from_words(&[], |_| true), DifficultyScore::default());
+    }
+
+    #[test]
+    fn test_unknown_words_raise_the_score() {
+        let sentence = words("The cat sat on the mat.");
+        let known = DifficultyScore::from_words(&sentence, |_| false);
+        let unknown = DifficultyScore::from_words(&sentence, |_| true);
+
+        assert_eq!(unknown.unknown_words, 6);
+        assert!(unknown.score > known.score);
+        assert!((0.0..=1.0).contains(&unknown.score));
+    }
+}
@@ -0,0 +1,20 @@
+use glossia_shared::{ImageResult, SimplificationResponse};
+use std::collections::HashMap;
+
+/// Simplification plus galleries for its hardest words, from `ReadingEngine::process_sentence_full`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FullSentenceResult {
+    pub simplification: SimplificationResponse,
+    /// Gallery for each illustrated word; words whose search failed are absent
+    pub images: HashMap<String, Vec<ImageResult>>,
+}
+
+/// The `count` longest distinct words, longest first; length stands in for
+/// difficulty, as in `DifficultyScore`
+pub(crate) fn hardest_words(words: &[String], count: usize) -> Vec<String> {
+    let mut words = words.to_vec();
+    words.sort_by(|a, b| b.chars().count().cmp(&a.chars().count()).then_with(|| a.cmp(b)));
+    words.dedup();
+    words.truncate(count);
+    words
+}
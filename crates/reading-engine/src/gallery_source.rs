@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use glossia_image_client::ImageClient;
+use glossia_shared::{AppError, ImageResult};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Where `ReadingEngine::process_sentence_full` gets the images for a word's gallery
+#[async_trait]
+pub trait GallerySource: Send + Sync {
+    /// Images illustrating `word`; `context` is the sentence it appears in, or
+    /// the image search query built from it when `wants_search_query` is set
+    async fn images_for(&self, word: &str, context: &str) -> Result<Vec<ImageResult>, AppError>;
+
+    /// Whether the engine should optimize a search query with the LLM and pass it as `context`
+    fn wants_search_query(&self) -> bool {
+        false
+    }
+}
+
+/// The real pipeline: searches an image client with the optimized query,
+/// falling back to the word itself when too few images come back
+pub struct ImageClientGallerySource {
+    image_client: Arc<dyn ImageClient>,
+    images_per_word: usize,
+}
+
+impl ImageClientGallerySource {
+    pub fn new(image_client: Arc<dyn ImageClient>, images_per_word: usize) -> Self {
+        Self { image_client, images_per_word: images_per_word.max(1) }
+    }
+}
+
+#[async_trait]
+impl GallerySource for ImageClientGallerySource {
+    async fn images_for(&self, word: &str, context: &str) -> Result<Vec<ImageResult>, AppError> {
+        self.image_client.search_gallery_with_fallback(context, word, self.images_per_word).await
+    }
+
+    fn wants_search_query(&self) -> bool {
+        true
+    }
+}
+
+/// Fixed images per word, for tests and offline demos; words without images get an empty gallery
+#[derive(Debug, Clone, Default)]
+pub struct StaticGallerySource {
+    images: HashMap<String, Vec<ImageResult>>,
+}
+
+impl StaticGallerySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve `images` for `word` (case-insensitive)
+    pub fn with_images(mut self, word: &str, images: Vec<ImageResult>) -> Self {
+        self.images.insert(word.to_lowercase(), images);
+        self
+    }
+}
+
+#[async_trait]
+impl GallerySource for StaticGallerySource {
+    async fn images_for(&self, word: &str, _context: &str) -> Result<Vec<ImageResult>, AppError> {
+        Ok(self.images.get(&word.to_lowercase()).cloned().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(url: &str) -> ImageResult {
+        ImageResult {
+            url: url.to_string(),
+            thumbnail_url: url.to_string(),
+            title: url.to_string(),
+            width: None,
+            height: None,
+            display_url: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_static_source_returns_configured_images() {
+        let source = StaticGallerySource::new().with_images("Feline", vec![image("cat.jpg"), image("lion.jpg")]);
+
+        let images = source.images_for("feline", "The feline sat.").await.unwrap();
+        assert_eq!(images, vec![image("cat.jpg"), image("lion.jpg")]);
+        assert!(source.images_for("dog", "The dog ran.").await.unwrap().is_empty());
+        assert!(!source.wants_search_query());
+    }
+}
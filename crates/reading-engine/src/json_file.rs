@@ -0,0 +1,49 @@
+use glossia_shared::AppError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+
+/// Write `value` as pretty JSON to `path`, creating parent directories
+/// `what` names the data in error messages, e.g. "reading positions"
+pub(crate) fn write_json_file<T: Serialize + ?Sized>(path: &Path, value: &T, what: &str) -> Result<(), AppError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| AppError::config_error(format!("Failed to serialize {what}: {e}")))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| AppError::config_error(format!("Failed to create directory: {e}")))?;
+    }
+    std::fs::write(path, json).map_err(|e| AppError::config_error(format!("Failed to write {what}: {e}")))
+}
+
+/// JSON saved by `write_json_file`, or `None` when the file doesn't exist
+pub(crate) fn read_json_file<T: DeserializeOwned>(path: &Path, what: &str) -> Result<Option<T>, AppError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| AppError::config_error(format!("Failed to read {what}: {e}")))?;
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| AppError::config_error(format!("Failed to deserialize {what}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_round_trips_and_treats_missing_file_as_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("data.json");
+        assert_eq!(read_json_file::<HashMap<String, usize>>(&path, "data").unwrap(), None);
+
+        let value = HashMap::from([("abc".to_string(), 3usize)]);
+        write_json_file(&path, &value, "data").unwrap();
+        assert_eq!(read_json_file::<HashMap<String, usize>>(&path, "data").unwrap(), Some(value));
+
+        std::fs::write(&path, "not json").unwrap();
+        let error = read_json_file::<HashMap<String, usize>>(&path, "data").unwrap_err();
+        assert!(error.to_string().contains("Failed to deserialize data"));
+    }
+}
@@ -1,59 +1,716 @@
 mod cache_engine;
+mod cache_trait;
 mod reading_orchestrator;
 mod state_manager;
+mod diagnostics;
+mod offline_dictionary;
+mod shared_simplifier;
+mod load_report;
+mod difficulty;
+mod session_state;
+mod text_file;
+mod nav_snapshot;
+mod full_sentence;
+mod coverage;
+mod task_registry;
+mod reading_activity;
+mod manual_words_store;
+mod promotion_history;
+mod position_store;
+mod gallery_source;
+mod json_file;
 
-pub use cache_engine::CacheEngine;
-pub use reading_orchestrator::ReadingOrchestrator;
-pub use state_manager::StateManager;
+pub use cache_engine::{CacheEngine, DEFAULT_OPTIMIZED_QUERY_TTL};
+pub use cache_trait::{Cache, ImageCacheSize, optimized_query_key, word_meaning_key};
+pub use reading_orchestrator::{ReadingOrchestrator, WarmResult, CacheStats, SimplificationStatus};
+pub use state_manager::{StateManager, SessionStats};
+pub use diagnostics::{Diagnostics, VocabularyStats};
+pub use offline_dictionary::OfflineDictionary;
+pub use shared_simplifier::{SharedSimplifier, DEFAULT_SHARED_SIMPLIFIER_CAPACITY};
+pub use load_report::{LoadReport, READING_WORDS_PER_MINUTE};
+pub use difficulty::DifficultyScore;
+pub use cache_trait::CacheSnapshot;
+pub use session_state::SessionState;
+pub use text_file::MAX_TEXT_FILE_BYTES;
+pub use nav_snapshot::NavSnapshot;
+pub use full_sentence::FullSentenceResult;
+pub use coverage::Coverage;
+pub use task_registry::TaskRegistry;
+pub use reading_activity::{ActivityClock, DailyActivity, ReadingActivity, SystemClock, ACTIVITY_FILE};
+pub use manual_words_store::MANUAL_WORDS_FILE;
+pub use promotion_history::{PromotionEvent, DEFAULT_PROMOTION_HISTORY_SIZE};
+pub use position_store::POSITIONS_FILE;
+pub use gallery_source::{GallerySource, ImageClientGallerySource, StaticGallerySource};
 
-use glossia_shared::{AppError, WordMeaning, SimplificationResponse};
-use glossia_navigation_service::NavigationService;
-use glossia_vocabulary_manager::VocabularyManager;
-use std::collections::HashSet;
+use manual_words_store::ManualWordsStore;
+use promotion_history::PromotionHistory;
+use position_store::PositionStore;
+
+use glossia_shared::{AppError, ImageQueryOptimizationRequest, WordMeaning, SimplificationResponse};
+use glossia_llm_client::{is_missing_meaning, LLMClient, UnavailableLLMClient};
+use glossia_image_client::ImageClient;
+use glossia_text_parser::{looks_like_english, strip_markup, InputFormat};
+use glossia_navigation_service::{NavError, NavigationService, NavigationStrategy, SentenceVisit};
+use glossia_vocabulary_manager::{RemovedWord, VocabularyManager, VOCABULARY_FILE};
+use futures_util::future::join_all;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+
+const CACHE_FILE: &str = "simplification_cache.json";
+
+/// Number of images fetched for a word's gallery unless configured otherwise
+pub const DEFAULT_IMAGES_PER_WORD: usize = 5;
+
+/// Background simplifications (cache warming and prefetch) allowed to run at once
+pub const DEFAULT_BACKGROUND_CONCURRENCY: usize = 2;
+
+/// Words illustrated by `ReadingEngine::process_sentence_full` unless configured otherwise
+pub const DEFAULT_ILLUSTRATED_WORDS: usize = 3;
+
+/// Which caches survive loading a new document with `ReadingEngine::load_text_opts`;
+/// simplifications are always cleared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadOptions {
+    pub keep_images: bool,
+    pub keep_word_meanings: bool,
+}
+
+impl Default for LoadOptions {
+    /// What `load_text` does: images are reused across texts, word meanings are not
+    fn default() -> Self {
+        Self {
+            keep_images: true,
+            keep_word_meanings: false,
+        }
+    }
+}
 
 /// High-level reading engine that orchestrates all reading functionality
 /// This replaces the complex ReadingState from book-reader
 pub struct ReadingEngine {
     navigation: NavigationService,
     vocabulary: VocabularyManager,
-    cache: CacheEngine,
+    cache: Box<dyn Cache>,
     orchestrator: ReadingOrchestrator,
     state: StateManager,
+    data_dir: Option<PathBuf>,
+    skip_known_sentences: bool,
+    rank_by_novelty: bool,
+    prefer_simplified_context: bool,
+    images_per_word: usize,
+    offline_dictionary: Option<OfflineDictionary>,
+    background_permits: Arc<Semaphore>,
+    image_client: Option<Arc<dyn ImageClient>>,
+    /// Replaces the image client pipeline when set
+    gallery_source: Option<Arc<dyn GallerySource>>,
+    /// Advanced whenever the current sentence changes, so prefetches for an old one are dropped
+    prefetch_epoch: Arc<AtomicU64>,
+    illustrated_words: usize,
+    check_language: bool,
+    language_warning: bool,
+    navigation_strategy: Option<Box<dyn NavigationStrategy>>,
+    activity: ReadingActivity,
+    /// Known words count at the last `record_activity`, to count words learned since
+    known_words_at_last_activity: Option<usize>,
+    manual_words_store: ManualWordsStore,
+    promotions: PromotionHistory,
+    positions: PositionStore,
+    resume_position: bool,
 }
 
 impl ReadingEngine {
     pub fn new() -> Result<Self, AppError> {
-        Ok(Self {
+        Ok(Self::with_orchestrator(ReadingOrchestrator::new()?))
+    }
+
+    /// Create engine with custom LLM client (useful for testing)
+    pub fn with_llm_client(llm_client: Box<dyn LLMClient>) -> Result<Self, AppError> {
+        Ok(Self::with_orchestrator(ReadingOrchestrator::with_llm_client(llm_client)))
+    }
+
+    /// Engine whose LLM requests all fail with `reason`, for when no client could be configured
+    /// Navigation, vocabulary and caches keep working, so the app can start and show the error
+    pub fn with_unavailable_llm(reason: AppError) -> Self {
+        Self::with_orchestrator(ReadingOrchestrator::with_llm_client(Box::new(UnavailableLLMClient::new(reason))))
+    }
+
+    /// Fallible counterpart of `Default`, for callers that must not panic
+    pub fn try_default() -> Result<Self, AppError> {
+        Self::new()
+    }
+
+    fn with_orchestrator(orchestrator: ReadingOrchestrator) -> Self {
+        Self {
             navigation: NavigationService::new(),
-            vocabulary: VocabularyManager::new()?,
-            cache: CacheEngine::new(),
-            orchestrator: ReadingOrchestrator::new()?,
+            vocabulary: VocabularyManager::default(),
+            cache: Box::new(CacheEngine::new()),
+            orchestrator,
             state: StateManager::new(),
+            data_dir: None,
+            skip_known_sentences: false,
+            rank_by_novelty: false,
+            prefer_simplified_context: false,
+            images_per_word: DEFAULT_IMAGES_PER_WORD,
+            offline_dictionary: None,
+            background_permits: Arc::new(Semaphore::new(DEFAULT_BACKGROUND_CONCURRENCY)),
+            image_client: None,
+            gallery_source: None,
+            prefetch_epoch: Arc::new(AtomicU64::new(0)),
+            illustrated_words: DEFAULT_ILLUSTRATED_WORDS,
+            check_language: true,
+            language_warning: false,
+            navigation_strategy: None,
+            activity: ReadingActivity::new(),
+            known_words_at_last_activity: None,
+            manual_words_store: ManualWordsStore::default(),
+            promotions: PromotionHistory::default(),
+            positions: PositionStore::default(),
+            resume_position: false,
+        }
+    }
+
+    /// Replace the default in-memory cache with another backend
+    pub fn with_cache(mut self, cache: Box<dyn Cache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Cap how many background simplifications run at once, across cache
+    /// warming and prefetch
+    pub fn with_background_concurrency(mut self, permits: usize) -> Self {
+        self.background_permits = Arc::new(Semaphore::new(permits.max(1)));
+        self
+    }
+
+    /// Image client used by `process_sentence_full`; without one it only simplifies
+    pub fn with_image_client(mut self, image_client: Box<dyn ImageClient>) -> Self {
+        self.image_client = Some(Arc::from(image_client));
+        self
+    }
+
+    /// Keep at most `max` sentences of a loaded text (`DEFAULT_MAX_SENTENCES` by default);
+    /// `load_text_report` reports how many were dropped
+    pub fn with_max_sentences(mut self, max: usize) -> Self {
+        self.navigation = std::mem::take(&mut self.navigation).with_max_sentences(max);
+        self
+    }
+
+    /// Resume at the saved position when a previously read text is loaded again (off by default)
+    pub fn with_resume_position(mut self, resume: bool) -> Self {
+        self.resume_position = resume;
+        self
+    }
+
+    pub fn set_resume_position(&mut self, resume: bool) {
+        self.resume_position = resume;
+    }
+
+    /// Number of promotions kept for `recent_promotions`
+    pub fn with_promotion_history_size(mut self, size: usize) -> Self {
+        self.promotions.set_capacity(size);
+        self
+    }
+
+    /// Take `process_sentence_full` galleries from `source` instead of the image client,
+    /// e.g. a `StaticGallerySource` for tests and offline demos
+    pub fn with_gallery_source(mut self, source: Arc<dyn GallerySource>) -> Self {
+        self.gallery_source = Some(source);
+        self
+    }
+
+    /// The gallery source in use: the configured one, otherwise the image client pipeline
+    fn gallery(&self) -> Option<Arc<dyn GallerySource>> {
+        self.gallery_source.clone().or_else(|| {
+            let image_client = self.image_client.clone()?;
+            Some(Arc::new(ImageClientGallerySource::new(image_client, self.images_per_word)))
         })
     }
 
+    /// Number of words `process_sentence_full` fetches galleries for
+    pub fn with_illustrated_words(mut self, count: usize) -> Self {
+        self.illustrated_words = count;
+        self
+    }
+
+    /// Check loaded texts with `looks_like_english` and set `language_warning` (on by default)
+    pub fn with_language_check(mut self, enabled: bool) -> Self {
+        self.check_language = enabled;
+        self
+    }
+
+    /// Whether the loaded text looks like it isn't English, which the
+    /// simplification prompts expect; loading is never blocked by this
+    pub fn language_warning(&self) -> bool {
+        self.language_warning
+    }
+
+    /// Strategy whose units (paragraphs, speed-reading chunks) the progress
+    /// methods report; it is loaded with every text. Sentence navigation is unaffected,
+    /// and the current unit is the one holding the current sentence.
+    pub fn with_navigation_strategy(mut self, strategy: Box<dyn NavigationStrategy>) -> Self {
+        self.navigation_strategy = Some(strategy);
+        self
+    }
+
+    /// The navigation strategy, for stepping through its units
+    pub fn navigation_strategy_mut(&mut self) -> Option<&mut (dyn NavigationStrategy + 'static)> {
+        self.navigation_strategy.as_deref_mut()
+    }
+
+    /// Lowercase name of the unit progress is counted in; "sentence" without a strategy
+    pub fn unit_label(&self) -> &str {
+        self.navigation_strategy.as_ref().map_or("sentence", |strategy| strategy.unit_label())
+    }
+
+    /// Number of units in the loaded text
+    pub fn unit_count(&self) -> usize {
+        self.navigation_strategy.as_ref().map_or_else(|| self.total_sentences(), |strategy| strategy.unit_count())
+    }
+
+    /// Zero-based index of the unit holding the current sentence
+    pub fn current_unit(&self) -> usize {
+        let Some(strategy) = self.navigation_strategy.as_ref() else {
+            return self.position();
+        };
+        let word_offset: usize = self.loaded_sentences()
+            .iter()
+            .take(self.position())
+            .map(|sentence| glossia_text_parser::extract_words(sentence).len())
+            .sum();
+        strategy.unit_at_word(word_offset).unwrap_or_else(|| strategy.current_unit())
+    }
+
+    /// Progress in units, e.g. "Paragraph 3 of 12"
+    pub fn unit_progress_text(&self) -> String {
+        let mut label = self.unit_label().chars();
+        let label = match label.next() {
+            Some(first) => first.to_uppercase().chain(label).collect(),
+            None => String::new(),
+        };
+        format!("{} {} of {}", label, self.current_unit() + 1, self.unit_count())
+    }
+
+    /// Permits shared by every background simplification path
+    /// Clone this before spawning work that must not hold the engine borrow
+    pub fn background_permits(&self) -> Arc<Semaphore> {
+        self.background_permits.clone()
+    }
+
+    /// Run background `work` once a permit from `permits` is free
+    pub async fn with_background_permit<F: Future>(permits: &Semaphore, work: F) -> F::Output {
+        // The semaphore is never closed, so a failed acquire only means no limit applies
+        let _permit = permits.acquire().await.ok();
+        work.await
+    }
+
+    /// Skip the LLM call for sentences whose words are all known
+    pub fn with_skip_known_sentences(mut self, skip: bool) -> Self {
+        self.skip_known_sentences = skip;
+        self
+    }
+
+    pub fn set_skip_known_sentences(&mut self, skip: bool) {
+        self.skip_known_sentences = skip;
+    }
+
+    /// Show the least-encountered words first in the meanings panel
+    pub fn with_rank_by_novelty(mut self, rank: bool) -> Self {
+        self.rank_by_novelty = rank;
+        self
+    }
+
+    pub fn set_rank_by_novelty(&mut self, rank: bool) {
+        self.rank_by_novelty = rank;
+    }
+
+    pub fn rank_by_novelty(&self) -> bool {
+        self.rank_by_novelty
+    }
+
+    /// Answer repeat requests for a sentence whose simplification just failed
+    /// (e.g. rate limited) with the same error for `ttl` instead of calling the LLM again
+    pub fn with_failure_cache_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.orchestrator.set_failure_cache_ttl(Some(ttl));
+        self
+    }
+
+    /// Define words against the cached simplified sentence when one exists
+    pub fn with_prefer_simplified_context(mut self, prefer: bool) -> Self {
+        self.prefer_simplified_context = prefer;
+        self
+    }
+
+    pub fn set_prefer_simplified_context(&mut self, prefer: bool) {
+        self.prefer_simplified_context = prefer;
+    }
+
+    /// Number of images requested for each word's gallery
+    pub fn with_images_per_word(mut self, count: usize) -> Self {
+        self.images_per_word = count.max(1);
+        self
+    }
+
+    pub fn set_images_per_word(&mut self, count: usize) {
+        self.images_per_word = count.max(1);
+    }
+
+    pub fn images_per_word(&self) -> usize {
+        self.images_per_word
+    }
+
+    /// Consult a local word→definition file before asking the LLM for meanings
+    pub fn with_offline_dictionary(mut self, path: impl AsRef<Path>) -> Result<Self, AppError> {
+        self.offline_dictionary = Some(OfflineDictionary::load_from_file(path.as_ref())?);
+        Ok(self)
+    }
+
+    pub fn set_offline_dictionary(&mut self, dictionary: OfflineDictionary) {
+        self.offline_dictionary = Some(dictionary);
+    }
+
+    /// Definition from the offline dictionary, if one is loaded and has the word
+    pub fn offline_word_meaning(&self, word: &str) -> Option<String> {
+        self.offline_dictionary
+            .as_ref()
+            .and_then(|dictionary| dictionary.lookup(word))
+            .map(str::to_string)
+    }
+
+    /// Context used to define a word found in `sentence`
+    /// Returns the cached simplification when preferred and available, otherwise the sentence
+    pub fn word_meaning_context(&self, sentence: &str) -> String {
+        if self.prefer_simplified_context {
+            if let Some(cached) = self.cache.get_simplified(sentence) {
+                if !cached.simplified.trim().is_empty() {
+                    return cached.simplified;
+                }
+            }
+        }
+        sentence.to_string()
+    }
+
+    /// Set the directory used to persist vocabulary and caches on shutdown
+    /// Also loads the vocabulary, caches, reading activity log, saved manual words
+    /// and reading positions kept there, if any
+    pub fn with_data_dir(mut self, data_dir: impl Into<PathBuf>) -> Self {
+        let data_dir = data_dir.into();
+        if let Err(e) = self.vocabulary.load_from_file(&data_dir.join(VOCABULARY_FILE)) {
+            tracing::warn!("Failed to load saved vocabulary, starting fresh: {}", e);
+        }
+        if let Err(e) = self.cache.load_from_file(&data_dir.join(CACHE_FILE)) {
+            tracing::warn!("Failed to load saved caches, starting fresh: {}", e);
+        }
+        if let Err(e) = self.activity.load_from_file(&data_dir.join(ACTIVITY_FILE)) {
+            tracing::warn!("Failed to load reading activity, starting fresh: {}", e);
+        }
+        match ManualWordsStore::load_from_file(&data_dir.join(MANUAL_WORDS_FILE)) {
+            Ok(store) => self.manual_words_store = store,
+            Err(e) => tracing::warn!("Failed to load saved manual words: {}", e),
+        }
+        match PositionStore::load_from_file(&data_dir.join(POSITIONS_FILE)) {
+            Ok(store) => self.positions = store,
+            Err(e) => tracing::warn!("Failed to load saved reading positions: {}", e),
+        }
+        self.data_dir = Some(data_dir);
+        self
+    }
+
+    /// Use `clock` to decide which day reading activity counts towards
+    pub fn with_activity_clock(mut self, clock: Arc<dyn ActivityClock>) -> Self {
+        self.activity.set_clock(clock);
+        self
+    }
+
+    /// Daily reading totals, for streaks and history
+    pub fn activity(&self) -> &ReadingActivity {
+        &self.activity
+    }
+
+    /// Count one sentence read today, plus any words that became known since the last call
+    /// Called on every move to the next sentence
+    pub fn record_activity(&mut self) {
+        let known_words = self.vocabulary.get_known_words_count();
+        let learned = self.known_words_at_last_activity.map_or(0, |before| known_words.saturating_sub(before));
+        self.known_words_at_last_activity = Some(known_words);
+        self.activity.record(1, learned);
+    }
+
+    /// Directory used for persistence, if configured
+    pub fn data_dir(&self) -> Option<&Path> {
+        self.data_dir.as_deref()
+    }
+
+    /// Flush vocabulary and persist caches to the data directory
+    /// Does nothing when no data directory is configured
+    pub fn shutdown(&self) -> Result<(), AppError> {
+        let Some(data_dir) = &self.data_dir else {
+            return Ok(());
+        };
+
+        self.vocabulary.save_to_file(&data_dir.join(VOCABULARY_FILE))?;
+        self.cache.save_to_file(&data_dir.join(CACHE_FILE))?;
+        self.activity.save_to_file(&data_dir.join(ACTIVITY_FILE))?;
+        let mut positions = self.positions.clone();
+        if self.total_sentences() > 0 {
+            positions.set(self.text_hash(), self.position());
+        }
+        positions.save_to_file(&data_dir.join(POSITIONS_FILE))?;
+        Ok(())
+    }
+
+    /// Last position read in the text with `text_hash`, so the UI can offer to resume there
+    pub fn saved_position(&self, text_hash: &str) -> Option<usize> {
+        self.positions.get(text_hash)
+    }
+
+    /// Remember the position in the loaded text, writing all positions to the
+    /// data directory when one is configured
+    pub fn save_position(&mut self) -> Result<(), AppError> {
+        self.remember_position();
+        match &self.data_dir {
+            Some(data_dir) => self.positions.save_to_file(&data_dir.join(POSITIONS_FILE)),
+            None => Ok(()),
+        }
+    }
+
+    fn remember_position(&mut self) {
+        if self.total_sentences() > 0 {
+            self.positions.set(self.text_hash(), self.position());
+        }
+    }
+
+    /// Capture the reading position and manual words for the loaded text
+    pub fn export_state(&self) -> SessionState {
+        let mut manual_words: Vec<String> = self.get_manual_words().iter().cloned().collect();
+        manual_words.sort();
+
+        SessionState {
+            text_hash: self.text_hash(),
+            position: self.navigation.current_position(),
+            manual_words,
+            caches: None,
+        }
+    }
+
+    /// Hash identifying the loaded text, as used by `SessionState` and `restore_manual_words`
+    pub fn text_hash(&self) -> String {
+        session_state::text_hash(self.loaded_sentences())
+    }
+
+    /// Remember the manual word selection for the loaded text, writing it to
+    /// the data directory when one is configured
+    pub fn save_manual_words(&mut self) -> Result<(), AppError> {
+        let words = self.get_manual_words().iter().cloned().collect();
+        self.manual_words_store.set(self.text_hash(), words);
+        match &self.data_dir {
+            Some(data_dir) => self.manual_words_store.save_to_file(&data_dir.join(MANUAL_WORDS_FILE)),
+            None => Ok(()),
+        }
+    }
+
+    /// Add the manual words saved for `text_hash`, if it is the loaded text
+    /// Returns how many words were restored; a different text restores none
+    pub fn restore_manual_words(&mut self, text_hash: &str) -> usize {
+        if text_hash != self.text_hash() {
+            return 0;
+        }
+        let Some(words) = self.manual_words_store.get(text_hash).cloned() else {
+            return 0;
+        };
+        let restored = words.len();
+        for word in words {
+            self.vocabulary.add_manual_word(word);
+        }
+        restored
+    }
+
+    /// Like `export_state`, also including the simplification and word meaning caches
+    pub fn export_state_with_caches(&self) -> SessionState {
+        SessionState {
+            caches: self.cache.export_snapshot(),
+            ..self.export_state()
+        }
+    }
+
+    /// Restore a session saved by `export_state` on top of the same, already loaded text
+    /// Sessions for a different text are rejected without changing any state
+    pub fn import_state(&mut self, state: SessionState) -> Result<(), AppError> {
+        if state.text_hash != session_state::text_hash(self.loaded_sentences()) {
+            return Err(AppError::config_error("Saved session belongs to a different text"));
+        }
+        if state.position >= self.navigation.total_sentences() {
+            return Err(AppError::config_error(format!("Saved position {} is outside the text", state.position)));
+        }
+
+        self.navigation.goto_position(state.position);
+        self.advance_prefetch_epoch();
+        self.vocabulary.clear_manual_words();
+        for word in state.manual_words {
+            self.vocabulary.add_manual_word(word);
+        }
+        if let Some(caches) = state.caches {
+            self.cache.import_snapshot(caches);
+        }
+        Ok(())
+    }
+
+    fn loaded_sentences(&self) -> &[String] {
+        self.navigation.get_sentences().map(Vec::as_slice).unwrap_or_default()
+    }
+
     /// Load text and reset all state
+    /// Empty or whitespace-only text is rejected with `AppError::EmptyBook`,
+    /// leaving the currently loaded text untouched
     pub fn load_text(&mut self, text: &str) -> Result<(), AppError> {
+        self.load_text_opts(text, LoadOptions::default())
+    }
+
+    /// Load text like `load_text`, with `options` choosing which caches are kept
+    pub fn load_text_opts(&mut self, text: &str, options: LoadOptions) -> Result<(), AppError> {
+        if text.trim().is_empty() {
+            return Err(AppError::EmptyBook);
+        }
+
+        self.remember_position();
         self.navigation.load_text(text)?;
+        self.advance_prefetch_epoch();
+        if self.navigation.truncated_count() > 0 {
+            tracing::warn!(
+                "Text has more than {} sentences; dropped the last {}",
+                self.navigation.max_sentences(),
+                self.navigation.truncated_count()
+            );
+        }
+        if let Some(strategy) = self.navigation_strategy.as_mut() {
+            strategy.load_text(text)?;
+        }
         self.vocabulary.clear_manual_words();
-        self.cache.clear_text_caches();
+        let text_hash = self.text_hash();
+        let restored = self.restore_manual_words(&text_hash);
+        if restored > 0 {
+            tracing::info!("Restored {} saved manual words for this text", restored);
+        }
+        if self.resume_position {
+            if let Some(position) = self.positions.get(&text_hash).filter(|&p| p > 0 && p < self.total_sentences()) {
+                self.navigation.goto_position(position);
+                tracing::info!("Resumed this text at sentence {}", position + 1);
+            }
+        }
+        if options.keep_word_meanings {
+            self.cache.clear_simplified_cache();
+        } else {
+            self.cache.clear_text_caches();
+            SharedSimplifier::global().clear();
+        }
+        if !options.keep_images {
+            self.cache.clear_image_cache();
+        }
         self.state.reset();
+        self.language_warning = self.check_language && !looks_like_english(text);
+        if self.language_warning {
+            tracing::warn!("Loaded text does not look like English; simplifications may be unreliable");
+        }
         Ok(())
     }
 
+    /// Start a fresh session without restarting: unload the text and clear the
+    /// position, history, manual words, session stats and text caches
+    /// Unlike `load_text`, no new text is loaded; known words, encounter counts
+    /// and the image cache are kept, as they outlive any one text
+    pub fn reset_session(&mut self) {
+        self.navigation = NavigationService::new()
+            .with_wrap_around(self.navigation.wrap_around())
+            .with_max_sentences(self.navigation.max_sentences());
+        self.vocabulary.clear_manual_words();
+        self.cache.clear_text_caches();
+        SharedSimplifier::global().clear();
+        self.orchestrator.clear_failures();
+        self.advance_prefetch_epoch();
+        self.state.reset();
+        self.language_warning = false;
+    }
+
+    /// Strip `format` markup (Markdown, HTML) from the text, then load it like `load_text`
+    pub fn load_formatted_text(&mut self, text: &str, format: InputFormat) -> Result<(), AppError> {
+        self.load_text(&strip_markup(text, format))
+    }
+
+    /// Load text like `load_text` and summarize what was parsed
+    pub fn load_text_report(&mut self, text: &str) -> Result<LoadReport, AppError> {
+        self.load_text(text)?;
+
+        let sentence_count = self.total_sentences();
+        let paragraph_count = sentence_count
+            .checked_sub(1)
+            .and_then(|last| self.navigation.paragraph_of(last))
+            .map_or(0, |index| index + 1);
+        Ok(LoadReport::from_text(text, sentence_count, paragraph_count).with_truncated_count(self.navigation.truncated_count()))
+    }
+
+    /// Read a UTF-8 text file of at most `MAX_TEXT_FILE_BYTES` and load it like `load_text_report`
+    pub fn load_from_file(&mut self, path: &Path) -> Result<LoadReport, AppError> {
+        let text = text_file::read_text_file(path, MAX_TEXT_FILE_BYTES)?;
+        self.load_text_report(&text)
+    }
+
     /// Get current sentence
     pub fn current_sentence(&self) -> Option<String> {
         self.navigation.current_sentence()
     }
 
+    /// The sentences before and after the current one, for faint context around it
+    /// Either is `None` at the start or end of the text, even with wrap-around
+    pub fn neighbors(&self) -> (Option<String>, Option<String>) {
+        let sentences = self.loaded_sentences();
+        let position = self.position();
+        let previous = position.checked_sub(1).and_then(|index| sentences.get(index)).cloned();
+        let next = sentences.get(position + 1).cloned();
+        (previous, next)
+    }
+
     /// Move to next sentence
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> bool {
-        self.navigation.advance()
+        let moved = self.navigation.advance();
+        if moved {
+            self.advance_prefetch_epoch();
+            self.record_activity();
+        }
+        moved
     }
 
     /// Move to previous sentence
     pub fn previous(&mut self) -> bool {
-        self.navigation.previous()
+        let moved = self.navigation.previous();
+        if moved {
+            self.advance_prefetch_epoch();
+        }
+        moved
+    }
+
+    /// Move to next sentence, reporting whether the text ended or none is loaded
+    pub fn try_next(&mut self) -> Result<(), NavError> {
+        self.navigation.try_advance()?;
+        self.advance_prefetch_epoch();
+        self.record_activity();
+        Ok(())
+    }
+
+    /// Move to previous sentence, reporting whether this is the first one or no text is loaded
+    pub fn try_previous(&mut self) -> Result<(), NavError> {
+        self.navigation.try_previous()?;
+        self.advance_prefetch_epoch();
+        Ok(())
+    }
+
+    /// Loop from the last sentence back to the first (and vice versa) when navigating
+    pub fn set_wrap_around(&mut self, wrap_around: bool) {
+        self.navigation.set_wrap_around(wrap_around);
     }
 
     /// Get navigation position info
@@ -61,6 +718,11 @@ impl ReadingEngine {
         self.navigation.current_position()
     }
 
+    /// Every sentence shown since the text was loaded, in first-seen order with visit counts
+    pub fn visit_log(&self) -> Vec<SentenceVisit> {
+        self.navigation.visit_log()
+    }
+
     pub fn total_sentences(&self) -> usize {
         self.navigation.total_sentences()
     }
@@ -85,6 +747,13 @@ impl ReadingEngine {
         self.vocabulary.get_manual_words()
     }
 
+    /// Manual words not visible in the current sentence, which are left out of
+    /// the combined word list
+    pub fn manual_words_missing_from_current_sentence(&self) -> Vec<String> {
+        let current_sentence = self.current_sentence().unwrap_or_default();
+        self.vocabulary.manual_words_missing_from(&current_sentence)
+    }
+
     /// Get combined words for current sentence
     pub fn get_combined_words(&self, api_words: &[WordMeaning]) -> Vec<WordMeaning> {
         // Get the current sentence to filter manual words
@@ -97,23 +766,57 @@ impl ReadingEngine {
         // Get the current sentence to filter manual words
         let current_sentence = self.current_sentence().unwrap_or_default();
         self.vocabulary.get_combined_words_with_cache(api_words, &current_sentence, |word| {
-            self.cache.get_word_meaning(word)
+            self.cache
+                .get_word_meaning_in_context(word, &current_sentence)
+                .or_else(|| self.cache.get_word_meaning(word))
         })
     }
 
+    /// Order words for display, most novel (fewest encounters) first
+    /// Known words are dropped; manual selections are always kept
+    pub fn rank_display_words(&self, words: &[WordMeaning]) -> Vec<WordMeaning> {
+        self.vocabulary.rank_by_encounter_count(words)
+    }
+
     /// Vocabulary management
     pub fn add_word_encounter(&mut self, word: &str) -> Result<(usize, bool), AppError> {
-        self.vocabulary.add_word_encounter(word)
+        let (count, promoted) = self.vocabulary.add_word_encounter(word)?;
+        if promoted {
+            self.promotions.record(word, count, std::time::SystemTime::now());
+        }
+        Ok((count, promoted))
+    }
+
+    /// Up to `n` words promoted to known this session, newest first
+    pub fn recent_promotions(&self, n: usize) -> Vec<PromotionEvent> {
+        self.promotions.recent(n)
     }
 
     pub fn add_known_word(&mut self, word: &str) -> Result<(), AppError> {
         self.vocabulary.add_known_word(word)
     }
 
-    pub fn remove_known_word(&mut self, word: &str) -> Result<(), AppError> {
+    pub fn remove_known_word(&mut self, word: &str) -> Result<RemovedWord, AppError> {
         self.vocabulary.remove_known_word(word)
     }
 
+    /// Mark every word in the sentence as known
+    /// Returns the words that weren't known before, in sentence order
+    pub fn mark_sentence_words_known(&mut self, sentence: &str) -> Result<Vec<String>, AppError> {
+        let mut added = Vec::new();
+        for word in glossia_text_parser::extract_words(sentence) {
+            if !self.vocabulary.is_known_word(&word) {
+                self.vocabulary.confirm_known_word(&word)?;
+                added.push(word);
+            }
+        }
+        Ok(added)
+    }
+
+    pub fn restore_known_word(&mut self, removed: RemovedWord) -> Result<(), AppError> {
+        self.vocabulary.restore_known_word(removed)
+    }
+
     pub fn get_all_known_words(&self) -> Result<Vec<String>, AppError> {
         self.vocabulary.get_all_known_words()
     }
@@ -126,18 +829,202 @@ impl ReadingEngine {
         self.vocabulary.get_known_words_count()
     }
 
+    /// Snapshot of cache, request, circuit, session and vocabulary stats
+    pub async fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            provider: self.orchestrator.provider_name().to_string(),
+            model: self.orchestrator.model_name(),
+            cache: self.orchestrator.get_cache_stats(self.cache.as_ref()),
+            requests: self.orchestrator.request_stats(),
+            circuit_state: self.orchestrator.circuit_state().await,
+            session: self.state.get_session_stats(),
+            vocabulary: VocabularyStats {
+                known_words: self.vocabulary.get_known_words_count(),
+                tracked_words: self.vocabulary.tracked_words_count(),
+                manual_words: self.vocabulary.manual_words_count(),
+            },
+        }
+    }
+
+    /// Close the API circuit breaker so requests are retried right away,
+    /// e.g. after the user has fixed their API key
+    pub async fn reset_api_circuit(&self) {
+        self.orchestrator.reset_circuit().await;
+    }
+
+    /// Allow `permits` LLM requests per `window` from now on, e.g. after the user
+    /// upgrades their API tier; requests already in flight are unaffected
+    pub async fn set_llm_rate_limit(&self, permits: usize, window: std::time::Duration) -> Result<(), AppError> {
+        self.orchestrator.set_rate_limit(permits, window).await
+    }
+
     /// Cache management
     pub fn get_cached_simplification(&self, sentence: &str) -> Option<SimplificationResponse> {
         self.cache.get_simplified(sentence)
     }
 
+    /// Cached simplifications of the loaded text's sentences, in reading order,
+    /// for reviewing them without calling the API; repeated sentences appear once
+    pub fn cached_simplifications(&self) -> Vec<SimplificationResponse> {
+        let mut seen = HashSet::new();
+        self.loaded_sentences()
+            .iter()
+            .filter(|sentence| seen.insert(sentence.as_str()))
+            .filter_map(|sentence| self.get_cached_simplification(sentence))
+            .collect()
+    }
+
     pub fn cache_simplification(&mut self, sentence: String, response: SimplificationResponse) {
         self.cache.cache_simplified(sentence, response);
     }
 
+    /// Check whether a sentence contains any word that isn't known yet
+    pub fn sentence_needs_help(&self, sentence: &str) -> bool {
+        let words: Vec<WordMeaning> = glossia_text_parser::extract_words(sentence)
+            .into_iter()
+            .map(|word| WordMeaning {
+                word,
+                meaning: String::new(),
+                is_phrase: false,
+                timestamp: None,
+            })
+            .collect();
+
+        !self.vocabulary.filter_known_words(&words).is_empty()
+    }
+
+    /// Difficulty of the sentence at `index`, combining unknown words, word
+    /// length and sentence length; out-of-range indices score zero
+    pub fn sentence_difficulty(&self, index: usize) -> DifficultyScore {
+        match self.get_sentence_at_position(index) {
+            Some(sentence) => self.score_sentence(&sentence),
+            None => DifficultyScore::default(),
+        }
+    }
+
+    /// Difficulty of every loaded sentence, in reading order
+    pub fn all_sentence_difficulties(&self) -> Vec<DifficultyScore> {
+        self.navigation
+            .get_sentences()
+            .map(|sentences| sentences.iter().map(|sentence| self.score_sentence(sentence)).collect())
+            .unwrap_or_default()
+    }
+
+    /// How much of the loaded text's running words are already known
+    pub fn coverage(&self) -> Coverage {
+        let words: Vec<String> = self
+            .loaded_sentences()
+            .iter()
+            .flat_map(|sentence| glossia_text_parser::extract_words(sentence))
+            .collect();
+        Coverage::from_words(&words, |word| self.vocabulary.is_known_word(word))
+    }
+
+    fn score_sentence(&self, sentence: &str) -> DifficultyScore {
+        let words = glossia_text_parser::extract_words(sentence);
+        DifficultyScore::from_words(&words, |word| !self.vocabulary.is_known_word(word))
+    }
+
     /// High-level orchestration
+    /// When `skip_known_sentences` is set, sentences without unknown words are
+    /// returned unchanged without calling the LLM
     pub async fn process_sentence(&mut self, sentence: &str) -> Result<SimplificationResponse, AppError> {
-        self.orchestrator.process_sentence(sentence, &mut self.cache).await
+        if self.skip_known_sentences && !self.sentence_needs_help(sentence) {
+            return Ok(SimplificationResponse {
+                original: sentence.to_string(),
+                simplified: sentence.to_string(),
+                words: vec![],
+                grammar_notes: vec![],
+                was_already_simple: true,
+            });
+        }
+
+        self.orchestrator.process_sentence(sentence, self.cache.as_mut()).await
+    }
+
+    /// Simplify a sentence, then fetch galleries for its hardest unknown words
+    /// (up to `with_illustrated_words`) concurrently, each optimizing its image
+    /// query first and holding a background permit while it works. Queries and
+    /// galleries are cached; a failed search leaves its word out of the result.
+    /// Galleries come from `with_gallery_source` when set, otherwise the image client.
+    pub async fn process_sentence_full(&mut self, sentence: &str) -> Result<FullSentenceResult, AppError> {
+        let simplification = self.process_sentence(sentence).await?;
+        let Some(gallery) = self.gallery() else {
+            return Ok(FullSentenceResult { simplification, images: HashMap::new() });
+        };
+
+        let candidates: Vec<String> = self
+            .filter_known_words(&simplification.words)
+            .into_iter()
+            .map(|word| word.word.to_lowercase())
+            .filter(|word| self.get_images(word).is_none())
+            .collect();
+        let words = full_sentence::hardest_words(&candidates, self.illustrated_words);
+
+        let engine = &*self;
+        let meanings: HashMap<String, String> = simplification
+            .words
+            .iter()
+            .map(|word| (word.word.to_lowercase(), word.meaning.clone()))
+            .collect();
+        let fetches = words.into_iter().map(|word| {
+            let gallery = gallery.clone();
+            let meaning = meanings.get(&word).cloned().unwrap_or_default();
+            async move {
+                let key = optimized_query_key(&word, sentence);
+                let work = async {
+                    // Sources that don't search by query are given the sentence instead
+                    let (query, optimized) = match engine.get_optimized_query(&key) {
+                        _ if !gallery.wants_search_query() => (sentence.to_string(), false),
+                        Some(query) => (query, false),
+                        None => {
+                            let request = ImageQueryOptimizationRequest {
+                                word: word.clone(),
+                                sentence_context: sentence.to_string(),
+                                word_meaning: meaning,
+                            };
+                            match engine.orchestrator.optimize_image_query(request).await {
+                                Ok(response) => (response.optimized_query, true),
+                                Err(e) => {
+                                    tracing::warn!("Failed to optimize image query for '{}', using the word: {}", word, e);
+                                    (word.clone(), false)
+                                }
+                            }
+                        }
+                    };
+                    let images = gallery.images_for(&word, &query).await;
+                    (query, optimized, images)
+                };
+                let (query, optimized, images) = Self::with_background_permit(&engine.background_permits, work).await;
+                (word, key, optimized.then_some(query), images)
+            }
+        });
+        let results = join_all(fetches).await;
+
+        let mut images = HashMap::new();
+        for (word, key, optimized_query, gallery) in results {
+            if let Some(query) = optimized_query {
+                self.cache_optimized_query(key, query);
+            }
+            match gallery {
+                Ok(gallery) => {
+                    self.cache_images(word.clone(), gallery.clone());
+                    images.insert(word, gallery);
+                }
+                Err(e) => tracing::warn!("Image search failed for '{}': {}", word, e),
+            }
+        }
+
+        Ok(FullSentenceResult { simplification, images })
+    }
+
+    /// Precompute simplifications for up to `max_sentences` sentences of the loaded text
+    /// without changing the current navigation position
+    pub async fn warm_cache(&mut self, max_sentences: usize) -> WarmResult {
+        let sentences = self.navigation.get_sentences().cloned().unwrap_or_default();
+        self.orchestrator
+            .warm_cache(&sentences, self.cache.as_mut(), max_sentences, &self.background_permits)
+            .await
     }
 
     /// Get reading progress (0.0 to 1.0)
@@ -145,6 +1032,11 @@ impl ReadingEngine {
         self.navigation.progress()
     }
 
+    /// Position, progress and history state together, for rendering a consistent view
+    pub fn nav_snapshot(&self) -> NavSnapshot {
+        NavSnapshot::of(&self.navigation)
+    }
+
     /// Navigation history
     pub fn can_go_back(&self) -> bool {
         self.navigation.can_go_back()
@@ -155,11 +1047,19 @@ impl ReadingEngine {
     }
 
     pub fn go_back(&mut self) -> bool {
-        self.navigation.go_back()
+        let moved = self.navigation.go_back();
+        if moved {
+            self.advance_prefetch_epoch();
+        }
+        moved
     }
 
     pub fn go_forward(&mut self) -> bool {
-        self.navigation.go_forward()
+        let moved = self.navigation.go_forward();
+        if moved {
+            self.advance_prefetch_epoch();
+        }
+        moved
     }
 
     /// State management
@@ -171,6 +1071,28 @@ impl ReadingEngine {
         self.state.set_processing(processing);
     }
 
+    /// Registry for spawning simplifications and image fetches that `abort_all` can cancel
+    /// Clone it to spawn work without holding the engine borrow
+    pub fn task_registry(&self) -> TaskRegistry {
+        self.orchestrator.tasks().clone()
+    }
+
+    /// Labels of the tracked tasks still running
+    pub fn in_flight_tasks(&self) -> Vec<String> {
+        self.orchestrator.tasks().in_flight()
+    }
+
+    /// Stop everything: cancel every tracked task, drop pending marks and clear `is_processing`
+    /// Returns how many tasks were cancelled
+    pub fn abort_all(&mut self) -> usize {
+        let aborted = self.orchestrator.abort_all();
+        self.state.set_processing(false);
+        if aborted > 0 {
+            tracing::warn!("Aborted {} in-flight tasks", aborted);
+        }
+        aborted
+    }
+
     // Compatibility methods for the app to match the old ReadingState API
     
     /// Get cached simplified response (alias for get_cached_simplification)
@@ -198,19 +1120,131 @@ impl ReadingEngine {
         client.simplify(request).await
     }
 
+    /// Static simplification through the process-wide `SharedSimplifier`
+    /// Concurrent calls for the same sentence share one LLM request and results are reused
+    pub async fn simplify_sentence_shared(sentence: &str) -> Result<SimplificationResponse, AppError> {
+        SharedSimplifier::global()
+            .simplify_with(sentence, || Self::simplify_sentence_static(sentence))
+            .await
+    }
+
+    /// Simplify `sentence` in the background, in a task `abort_all` can cancel
+    /// The task waits for a background permit and shares the request with concurrent
+    /// `simplify_sentence_shared` calls. The sentence stays Pending until the result
+    /// is passed to `cache_simplification_result` or the mark is cleared.
+    pub fn spawn_background_simplification(&self, sentence: &str) -> JoinHandle<Result<SimplificationResponse, AppError>> {
+        self.orchestrator.mark_in_flight(sentence);
+        let client = self.orchestrator.llm_client();
+        let permits = self.background_permits();
+        let sentence = sentence.to_string();
+        self.orchestrator.tasks().spawn(format!("simplify: {sentence}"), async move {
+            let request = || client.simplify(glossia_shared::SimplificationRequest { sentence: sentence.clone() });
+            Self::with_background_permit(&permits, SharedSimplifier::global().simplify_with(&sentence, request)).await
+        })
+    }
+
     /// Cache a simplification result (separate from the async operation)
     pub fn cache_simplification_result(&mut self, sentence: String, response: SimplificationResponse) {
+        self.orchestrator.clear_in_flight(&sentence);
         self.cache.cache_simplified(sentence, response);
     }
 
-    /// Get word meaning (delegated to LLM client through orchestrator)
-    pub async fn get_word_meaning(&mut self, word: &str, context: &str) -> Result<String, AppError> {
-        // For now, we'll need to add this functionality to the orchestrator
-        // This is a temporary compatibility method
-        use glossia_llm_client::LLMClientFactory;
-        let factory = LLMClientFactory::new();
-        let client = factory.create_client()?;
-        client.get_word_meaning(word, context).await
+    /// Whether the sentence's simplification is cached, being fetched, or neither
+    /// Single source of truth for the UI's loading state
+    pub fn simplification_status(&self, sentence: &str) -> SimplificationStatus {
+        self.orchestrator.simplification_status(sentence, self.cache.as_ref())
+    }
+
+    /// Mark a sentence as being simplified outside the engine (e.g. via `simplify_sentence_static`)
+    pub fn mark_simplification_pending(&self, sentence: &str) {
+        self.orchestrator.mark_in_flight(sentence);
+    }
+
+    /// Clear the pending mark after an outside simplification failed
+    pub fn clear_simplification_pending(&self, sentence: &str) {
+        self.orchestrator.clear_in_flight(sentence);
+    }
+
+    /// Get word meaning from the offline dictionary, else from the LLM client through the orchestrator
+    pub async fn get_word_meaning(&mut self, word: &str, context: &str) -> Result<String, AppError> {
+        if let Some(meaning) = self.offline_word_meaning(word) {
+            return Ok(meaning);
+        }
+        let context = self.word_meaning_context(context);
+        self.orchestrator.get_word_meaning(word, &context).await
+    }
+
+    /// Generation of the current sentence; it advances on every navigation and text load
+    pub fn prefetch_epoch(&self) -> u64 {
+        self.prefetch_epoch.load(Ordering::SeqCst)
+    }
+
+    /// Start a new prefetch generation, so prefetches tagged with an earlier one are discarded
+    /// Returns the new epoch
+    pub fn advance_prefetch_epoch(&self) -> u64 {
+        self.prefetch_epoch.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// The prefetch epoch counter itself, for code that must supersede a prefetch
+    /// while it holds the engine borrow; `fetch_add(1, SeqCst)` matches `advance_prefetch_epoch`
+    pub fn prefetch_epoch_handle(&self) -> Arc<AtomicU64> {
+        self.prefetch_epoch.clone()
+    }
+
+    /// Fetch and cache meanings for `words` as used in the sentence `context`, concurrently
+    /// within the background permits, so later lookups are instant
+    /// Words already cached for this sentence are skipped and failures are left uncached.
+    /// Returns the number of meanings newly cached.
+    pub async fn prefetch_definitions(&mut self, words: &[String], context: &str) -> usize {
+        let epoch = self.prefetch_epoch();
+        self.prefetch_definitions_at_epoch(words, context, epoch).await
+    }
+
+    /// Like `prefetch_definitions`, for a prefetch started at `epoch` (see `prefetch_epoch`)
+    /// Once the epoch has moved on, the sentence is superseded: remaining fetches are
+    /// skipped and nothing is cached, so stale results can't overwrite newer entries.
+    /// While this runs, the epoch can only move through `prefetch_epoch_handle`.
+    pub async fn prefetch_definitions_at_epoch(&mut self, words: &[String], context: &str, epoch: u64) -> usize {
+        let mut seen = HashSet::new();
+        let pending: Vec<&String> = words
+            .iter()
+            .filter(|word| seen.insert(word.to_lowercase()))
+            .filter(|word| self.get_cached_word_meaning_in_context(word, context).is_none())
+            .collect();
+
+        let meaning_context = &self.word_meaning_context(context);
+        let engine = &*self;
+        let fetches = pending.into_iter().map(|word| async move {
+            let meaning = match engine.offline_word_meaning(word) {
+                Some(meaning) => Ok(meaning),
+                None => {
+                    Self::with_background_permit(&engine.background_permits, async {
+                        if engine.prefetch_epoch() != epoch {
+                            return Err(AppError::api_error("Prefetch superseded by a newer sentence"));
+                        }
+                        engine.orchestrator.get_word_meaning(word, meaning_context).await
+                    })
+                    .await
+                }
+            };
+            (word.to_string(), meaning)
+        });
+        let results = join_all(fetches).await;
+
+        if self.prefetch_epoch() != epoch {
+            tracing::debug!("Discarding definitions prefetched for a superseded sentence");
+            return 0;
+        }
+        let mut cached = 0;
+        for (word, meaning) in results {
+            if let Ok(meaning) = meaning {
+                if !is_missing_meaning(&meaning) {
+                    self.cache_word_meaning_in_context(word, context, meaning);
+                    cached += 1;
+                }
+            }
+        }
+        cached
     }
 
     /// Static method to get word meaning without any state access
@@ -230,10 +1264,26 @@ impl ReadingEngine {
         self.cache.get_optimized_query(context_key)
     }
 
+    /// Drop a cached image query, e.g. after a poor gallery, so the next search re-optimizes it
+    pub fn invalidate_optimized_query(&mut self, context_key: &str) {
+        self.cache.invalidate_optimized_query(context_key);
+    }
+
     pub fn cache_images(&mut self, word: String, images: Vec<glossia_shared::ImageResult>) {
         self.cache.cache_images(word, images);
     }
 
+    /// Words with cached galleries and the total images held for them
+    pub fn image_cache_size(&self) -> ImageCacheSize {
+        self.cache.image_cache_footprint()
+    }
+
+    /// Evict the oldest galleries until at most `target_count` images remain
+    /// Returns the number of words evicted
+    pub fn evict_oldest_images(&mut self, target_count: usize) -> usize {
+        self.cache.evict_oldest_images(target_count)
+    }
+
     pub fn get_images(&self, word: &str) -> Option<Vec<glossia_shared::ImageResult>> {
         self.cache.get_images(word)
     }
@@ -242,13 +1292,30 @@ impl ReadingEngine {
         self.cache.get_word_meaning(word)
     }
 
+    /// Empty or placeholder meanings are dropped so they are never served as definitions
     pub fn cache_word_meaning(&mut self, word: String, meaning: String) {
+        if is_missing_meaning(&meaning) {
+            return;
+        }
         self.cache.cache_word_meaning(word, meaning);
     }
 
+    /// Meaning cached for `word` in the sentence `context`, ignoring meanings from other sentences
+    pub fn get_cached_word_meaning_in_context(&self, word: &str, context: &str) -> Option<String> {
+        self.cache.get_word_meaning_in_context(word, context)
+    }
+
+    /// Cache a meaning for `word` as used in the sentence `context`
+    pub fn cache_word_meaning_in_context(&mut self, word: String, context: &str, meaning: String) {
+        if is_missing_meaning(&meaning) {
+            return;
+        }
+        self.cache.cache_word_meaning_in_context(word, context, meaning);
+    }
+
     /// Cache a word meaning result (separate from the async operation)
     pub fn cache_word_meaning_result(&mut self, word: String, meaning: String) {
-        self.cache.cache_word_meaning(word, meaning);
+        self.cache_word_meaning(word, meaning);
     }
 
     /// Get sentence at specific position without changing current position
@@ -261,8 +1328,1227 @@ impl ReadingEngine {
     }
 }
 
+/// Panics if no LLM client can be configured; use `try_default`, or fall back
+/// to `with_unavailable_llm`, to handle the error
 impl Default for ReadingEngine {
     fn default() -> Self {
         Self::new().expect("Failed to create ReadingEngine")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glossia_llm_client::MockLLMClient;
+
+    /// Cache stub that records every call and stores nothing
+    struct RecordingCache {
+        calls: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl RecordingCache {
+        fn record(&self, call: &str) {
+            self.calls.lock().unwrap().push(call.to_string());
+        }
+    }
+
+    impl Cache for RecordingCache {
+        fn get_simplified(&self, sentence: &str) -> Option<SimplificationResponse> {
+            self.record(&format!("get_simplified:{sentence}"));
+            None
+        }
+        fn cache_simplified(&mut self, sentence: String, _response: SimplificationResponse) {
+            self.record(&format!("cache_simplified:{sentence}"));
+        }
+        fn get_images(&self, word: &str) -> Option<Vec<glossia_shared::ImageResult>> {
+            self.record(&format!("get_images:{word}"));
+            None
+        }
+        fn cache_images(&mut self, word: String, _images: Vec<glossia_shared::ImageResult>) {
+            self.record(&format!("cache_images:{word}"));
+        }
+        fn get_word_meaning(&self, word: &str) -> Option<String> {
+            self.record(&format!("get_word_meaning:{word}"));
+            None
+        }
+        fn cache_word_meaning(&mut self, word: String, _meaning: String) {
+            self.record(&format!("cache_word_meaning:{word}"));
+        }
+        fn get_optimized_query(&self, context_key: &str) -> Option<String> {
+            self.record(&format!("get_optimized_query:{context_key}"));
+            None
+        }
+        fn cache_optimized_query(&mut self, context_key: String, _query: String) {
+            self.record(&format!("cache_optimized_query:{context_key}"));
+        }
+        fn invalidate_optimized_query(&mut self, context_key: &str) {
+            self.record(&format!("invalidate_optimized_query:{context_key}"));
+        }
+        fn clear_all_caches(&mut self) {
+            self.record("clear_all_caches");
+        }
+        fn clear_text_caches(&mut self) {
+            self.record("clear_text_caches");
+        }
+        fn clear_simplified_cache(&mut self) {
+            self.record("clear_simplified_cache");
+        }
+        fn simplified_cache_size(&self) -> usize { 0 }
+        fn image_cache_size(&self) -> usize { 0 }
+        fn word_meaning_cache_size(&self) -> usize { 0 }
+        fn image_result_count(&self) -> usize { 0 }
+        fn evict_oldest_images(&mut self, _target_count: usize) -> usize { 0 }
+    }
+
+    #[tokio::test]
+    async fn test_engine_routes_cache_access_through_trait() {
+        let calls = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_cache(Box::new(RecordingCache { calls: calls.clone() }));
+
+        engine.load_text("Only sentence.").unwrap();
+        engine.cache_word_meaning("word".to_string(), "meaning".to_string());
+        assert_eq!(engine.get_cached_word_meaning("word"), None);
+        engine.cache_images("cat".to_string(), vec![]);
+        engine.cache_optimized_query("key".to_string(), "query".to_string());
+        engine.process_sentence("Only sentence.").await.unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), vec![
+            "clear_text_caches",
+            "cache_word_meaning:word",
+            "get_word_meaning:word",
+            "cache_images:cat",
+            "cache_optimized_query:key",
+            "get_simplified:Only sentence.",
+            "cache_simplified:Only sentence.",
+        ]);
+    }
+
+    #[test]
+    fn test_load_text_report_summarizes_known_input() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        let text = "The cat sat on the mat. It was warm and the sun was out.\n\nThe dog barked at the cat! Then it slept.";
+
+        let report = engine.load_text_report(text).unwrap();
+
+        assert_eq!(report, LoadReport {
+            sentence_count: 4,
+            paragraph_count: 2,
+            estimated_reading_minutes: 1,
+            detected_language: Some("en".to_string()),
+            truncated: false,
+            truncated_count: 0,
+        });
+        assert_eq!(report.sentence_count, engine.total_sentences());
+        assert!(engine.load_text_report("   ").is_err());
+    }
+
+    #[test]
+    fn test_load_text_report_flags_truncated_text() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_max_sentences(2);
+
+        let report = engine.load_text_report("The cat sat. The dog ran. The owl slept. The fox hid.").unwrap();
+
+        assert_eq!(report.sentence_count, 2);
+        assert!(report.truncated);
+        assert_eq!(report.truncated_count, 2);
+        assert_eq!(engine.total_sentences(), 2);
+
+        engine.reset_session();
+        let report = engine.load_text_report("The cat sat. The dog ran. The owl slept.").unwrap();
+        assert_eq!(report.truncated_count, 1);
+    }
+
+    #[test]
+    fn test_load_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("story.txt");
+        std::fs::write(&path, "The cat sat. The dog ran.").unwrap();
+
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        let report = engine.load_from_file(&path).unwrap();
+        assert_eq!(report.sentence_count, 2);
+        assert_eq!(engine.current_sentence().as_deref(), Some("The cat sat."));
+
+        let missing = engine.load_from_file(&dir.path().join("missing.txt")).unwrap_err();
+        assert!(missing.to_string().contains("File not found"));
+        assert_eq!(engine.total_sentences(), 2);
+    }
+
+    #[test]
+    fn test_formatted_text_is_stripped_before_splitting() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        let markdown = "# Notes\n\nRead the **first** part. Then see [the docs](https://example.com/a.b).";
+
+        engine.load_formatted_text(markdown, InputFormat::Markdown { keep_code_blocks: false }).unwrap();
+
+        assert_eq!(engine.total_sentences(), 3);
+        assert_eq!(engine.current_sentence(), Some("Notes".to_string()));
+        engine.next();
+        assert_eq!(engine.current_sentence(), Some("Read the first part.".to_string()));
+        engine.next();
+        assert_eq!(engine.current_sentence(), Some("Then see the docs.".to_string()));
+    }
+
+    fn engine_knowing(words: &[&str], llm_client: MockLLMClient) -> ReadingEngine {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(llm_client))
+            .unwrap()
+            .with_skip_known_sentences(true);
+        for word in words {
+            engine.add_known_word(word).unwrap();
+        }
+        engine
+    }
+
+    #[tokio::test]
+    async fn test_fully_known_sentence_skips_llm_call() {
+        // A failing client proves the LLM is never reached
+        let mut engine = engine_knowing(&["the", "cat", "sat"], MockLLMClient::new().with_failure());
+
+        assert!(!engine.sentence_needs_help("The cat sat."));
+        let response = engine.process_sentence("The cat sat.").await.unwrap();
+        assert_eq!(response.simplified, "The cat sat.");
+        assert!(response.words.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_mixed_sentence_still_calls_llm() {
+        let mut engine = engine_knowing(&["the", "cat"], MockLLMClient::new());
+
+        assert!(engine.sentence_needs_help("The cat pondered."));
+        let response = engine.process_sentence("The cat pondered.").await.unwrap();
+        assert_eq!(response.simplified, "Simplified: The cat pondered.");
+    }
+
+    /// LLM stub that records the context of every word meaning request
+    struct ContextRecordingClient {
+        contexts: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl LLMClient for ContextRecordingClient {
+        async fn simplify(&self, request: glossia_shared::SimplificationRequest) -> Result<SimplificationResponse, AppError> {
+            MockLLMClient::new().simplify(request).await
+        }
+
+        async fn get_word_meaning(&self, word: &str, context: &str) -> Result<String, AppError> {
+            self.contexts.lock().unwrap().push(context.to_string());
+            Ok(format!("meaning of {word}"))
+        }
+
+        async fn optimize_image_query(&self, request: glossia_shared::ImageQueryOptimizationRequest) -> Result<glossia_shared::ImageQueryOptimizationResponse, AppError> {
+            MockLLMClient::new().optimize_image_query(request).await
+        }
+
+        fn provider_name(&self) -> &str {
+            "ContextRecording"
+        }
+
+        async fn health_check(&self) -> Result<(), AppError> {
+            Ok(())
+        }
+    }
+
+    fn context_recording_engine(prefer_simplified: bool) -> (ReadingEngine, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        let contexts = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let client = ContextRecordingClient { contexts: contexts.clone() };
+        let mut engine = ReadingEngine::with_llm_client(Box::new(client))
+            .unwrap()
+            .with_prefer_simplified_context(prefer_simplified);
+        engine.cache_simplification("The bank was steep.".to_string(), SimplificationResponse {
+            original: "The bank was steep.".to_string(),
+            simplified: "The river's edge was steep.".to_string(),
+            words: vec![],
+            grammar_notes: vec![],
+            was_already_simple: false,
+        });
+        (engine, contexts)
+    }
+
+    #[tokio::test]
+    async fn test_word_meaning_uses_cached_simplified_context() {
+        let (mut engine, contexts) = context_recording_engine(true);
+
+        engine.get_word_meaning("bank", "The bank was steep.").await.unwrap();
+        assert_eq!(*contexts.lock().unwrap(), vec!["The river's edge was steep.".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_word_meaning_falls_back_to_original_context() {
+        let (mut engine, contexts) = context_recording_engine(true);
+        engine.get_word_meaning("bank", "He went to the bank.").await.unwrap();
+
+        let (mut engine_without_flag, contexts_without_flag) = context_recording_engine(false);
+        engine_without_flag.get_word_meaning("bank", "The bank was steep.").await.unwrap();
+
+        assert_eq!(*contexts.lock().unwrap(), vec!["He went to the bank.".to_string()]);
+        assert_eq!(*contexts_without_flag.lock().unwrap(), vec!["The bank was steep.".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_offline_dictionary_answers_without_llm() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dictionary.json");
+        std::fs::write(&path, r#"{"bank": "the land beside a river"}"#).unwrap();
+
+        let (engine, contexts) = context_recording_engine(false);
+        let mut engine = engine.with_offline_dictionary(&path).unwrap();
+
+        let meaning = engine.get_word_meaning("Bank", "The bank was steep.").await.unwrap();
+        assert_eq!(meaning, "the land beside a river");
+        assert!(contexts.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_word_missing_from_offline_dictionary_falls_through() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dictionary.txt");
+        std::fs::write(&path, "river\ta large stream of water\n").unwrap();
+
+        let (engine, contexts) = context_recording_engine(false);
+        let mut engine = engine.with_offline_dictionary(&path).unwrap();
+
+        let meaning = engine.get_word_meaning("bank", "The bank was steep.").await.unwrap();
+        assert_eq!(meaning, "meaning of bank");
+        assert_eq!(*contexts.lock().unwrap(), vec!["The bank was steep.".to_string()]);
+    }
+
+    #[test]
+    fn test_missing_offline_dictionary_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        assert!(engine.with_offline_dictionary(dir.path().join("missing.json")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_diagnostics_aggregates_subsystems() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.load_text("The cat sat. The dog ran.").unwrap();
+        engine.process_sentence("The cat sat.").await.unwrap();
+        engine.cache_word_meaning("cat".to_string(), "a small pet".to_string());
+        engine.add_known_word("the").unwrap();
+        engine.add_word_encounter("dog").unwrap();
+        engine.add_manual_word("dog".to_string());
+
+        let diagnostics = engine.diagnostics().await;
+
+        assert_eq!(diagnostics.provider, "Mock");
+        assert_eq!(diagnostics.model, "unknown");
+        assert_eq!(diagnostics.cache.simplified_entries, 1);
+        assert_eq!(diagnostics.cache.word_meaning_entries, 1);
+        assert_eq!(diagnostics.cache.image_entries, 0);
+        assert_eq!(diagnostics.vocabulary, VocabularyStats { known_words: 1, tracked_words: 1, manual_words: 1 });
+        assert_eq!(diagnostics.session.sentences_read, 0);
+        // The mock client has no http client behind it
+        assert!(diagnostics.requests.is_none());
+        assert!(diagnostics.circuit_state.is_none());
+
+        let json = serde_json::to_value(&diagnostics).unwrap();
+        assert_eq!(json["vocabulary"]["known_words"], 1);
+        assert_eq!(json["cache"]["simplified_entries"], 1);
+    }
+
+    /// LLM stub that records how many simplifications run at the same time
+    struct ConcurrencyTrackingClient {
+        active: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        peak: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+        calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    async fn track_concurrency(
+        active: &std::sync::atomic::AtomicUsize,
+        peak: &std::sync::atomic::AtomicUsize,
+    ) {
+        use std::sync::atomic::Ordering;
+        let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+        peak.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+        active.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    #[async_trait::async_trait]
+    impl LLMClient for ConcurrencyTrackingClient {
+        async fn simplify(&self, request: glossia_shared::SimplificationRequest) -> Result<SimplificationResponse, AppError> {
+            track_concurrency(&self.active, &self.peak).await;
+            MockLLMClient::new().simplify(request).await
+        }
+
+        async fn get_word_meaning(&self, word: &str, _context: &str) -> Result<String, AppError> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            track_concurrency(&self.active, &self.peak).await;
+            Ok(format!("meaning of {word}"))
+        }
+
+        async fn optimize_image_query(&self, request: glossia_shared::ImageQueryOptimizationRequest) -> Result<glossia_shared::ImageQueryOptimizationResponse, AppError> {
+            MockLLMClient::new().optimize_image_query(request).await
+        }
+
+        fn provider_name(&self) -> &str {
+            "ConcurrencyTracking"
+        }
+
+        async fn health_check(&self) -> Result<(), AppError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_session_state_round_trips() {
+        let text = "The cat sat. The dog ran. The owl slept.";
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.load_text(text).unwrap();
+        engine.next();
+        engine.next();
+        engine.add_manual_word("owl".to_string());
+        engine.add_manual_word("dog".to_string());
+        engine.cache_word_meaning("owl".to_string(), "a night bird".to_string());
+
+        let json = serde_json::to_string(&engine.export_state_with_caches()).unwrap();
+        let state: SessionState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state.manual_words, vec!["dog", "owl"]);
+
+        let mut restored = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        restored.load_text(text).unwrap();
+        restored.import_state(state).unwrap();
+
+        assert_eq!(restored.position(), 2);
+        assert!(restored.is_manual_word("owl"));
+        assert!(restored.is_manual_word("dog"));
+        assert_eq!(restored.get_cached_word_meaning("owl").as_deref(), Some("a night bird"));
+    }
+
+    #[test]
+    fn test_session_for_other_text_is_rejected() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.load_text("The cat sat. The dog ran.").unwrap();
+        engine.next();
+        let state = engine.export_state();
+        assert!(state.caches.is_none());
+
+        let mut other = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        other.load_text("A different text. With other sentences.").unwrap();
+        other.add_manual_word("different".to_string());
+
+        assert!(other.import_state(state).is_err());
+        assert_eq!(other.position(), 0);
+        assert!(other.is_manual_word("different"));
+    }
+
+    #[test]
+    fn test_missing_meanings_are_not_cached() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+
+        engine.cache_word_meaning_result("cat".to_string(), "  ".to_string());
+        engine.cache_word_meaning_in_context("cat".to_string(), "The cat sat.", glossia_llm_client::NO_DEFINITION_PLACEHOLDER.to_string());
+        assert_eq!(engine.get_cached_word_meaning("cat"), None);
+        assert_eq!(engine.get_cached_word_meaning_in_context("cat", "The cat sat."), None);
+
+        engine.cache_word_meaning_result("cat".to_string(), "a small pet".to_string());
+        assert_eq!(engine.get_cached_word_meaning("cat").as_deref(), Some("a small pet"));
+    }
+
+    #[tokio::test]
+    async fn test_background_paths_share_one_concurrency_cap() {
+        let active = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let peak = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let client = ConcurrencyTrackingClient { active: active.clone(), peak: peak.clone(), calls: Default::default() };
+        let mut engine = ReadingEngine::with_llm_client(Box::new(client))
+            .unwrap()
+            .with_background_concurrency(1);
+        engine.load_text("First sentence. Second sentence. Third sentence.").unwrap();
+        let permits = engine.background_permits();
+
+        // A prefetch running alongside cache warming draws from the same permits
+        let prefetch = async {
+            for _ in 0..3 {
+                ReadingEngine::with_background_permit(&permits, track_concurrency(&active, &peak)).await;
+            }
+        };
+        let (warmed, ()) = tokio::join!(engine.warm_cache(3), prefetch);
+
+        assert_eq!(warmed.simplified, 3);
+        assert_eq!(peak.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_load_text_rejects_empty_input() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+
+        assert!(matches!(engine.load_text(""), Err(AppError::EmptyBook)));
+        assert_eq!(engine.current_sentence(), None);
+        assert_eq!(engine.total_sentences(), 0);
+    }
+
+    #[test]
+    fn test_load_text_rejects_whitespace_and_keeps_state() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.load_text("First sentence here. Second sentence here.").unwrap();
+        engine.next();
+        engine.add_manual_word("second".to_string());
+
+        assert!(matches!(engine.load_text("   \n\t  "), Err(AppError::EmptyBook)));
+        assert_eq!(engine.total_sentences(), 2);
+        assert_eq!(engine.position(), 1);
+        assert_eq!(engine.current_sentence().as_deref(), Some("Second sentence here."));
+        assert!(engine.is_manual_word("second"));
+    }
+
+    #[test]
+    fn test_load_text_accepts_valid_input() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+
+        engine.load_text("  A valid sentence.  ").unwrap();
+        assert_eq!(engine.total_sentences(), 1);
+        assert_eq!(engine.current_sentence().as_deref(), Some("A valid sentence."));
+    }
+
+    fn word(word: &str) -> WordMeaning {
+        WordMeaning {
+            word: word.to_string(),
+            meaning: format!("meaning of {word}"),
+            is_phrase: false,
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn test_rank_display_words_orders_by_encounter_count() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        let counts = [("ubiquitous", 3), ("ephemeral", 0), ("lucid", 1), ("serene", 1)];
+        for (word, count) in counts {
+            for _ in 0..count {
+                engine.add_word_encounter(word).unwrap();
+            }
+        }
+
+        let words: Vec<WordMeaning> = ["ubiquitous", "lucid", "ephemeral", "serene"]
+            .into_iter()
+            .map(word)
+            .collect();
+        let ranked: Vec<String> = engine.rank_display_words(&words)
+            .into_iter()
+            .map(|w| w.word)
+            .collect();
+
+        // Ties keep their original order
+        assert_eq!(ranked, vec!["ephemeral", "lucid", "serene", "ubiquitous"]);
+    }
+
+    #[test]
+    fn test_rank_display_words_drops_known_but_keeps_manual() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.add_known_word("cat").unwrap();
+        engine.add_known_word("lucid").unwrap();
+        engine.add_manual_word("lucid".to_string());
+        engine.add_word_encounter("lucid").unwrap();
+
+        let words: Vec<WordMeaning> = ["cat", "lucid", "ephemeral"].into_iter().map(word).collect();
+        let ranked: Vec<String> = engine.rank_display_words(&words)
+            .into_iter()
+            .map(|w| w.word)
+            .collect();
+
+        assert_eq!(ranked, vec!["ephemeral", "lucid"]);
+    }
+
+    #[test]
+    fn test_shutdown_persists_vocabulary_and_cache() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_data_dir(temp_dir.path());
+
+        engine.add_known_word("Serendipity").unwrap();
+        engine.cache_simplification("Hello there.".to_string(), SimplificationResponse {
+            original: "Hello there.".to_string(),
+            simplified: "Hi.".to_string(),
+            words: vec![],
+            grammar_notes: vec![],
+            was_already_simple: false,
+        });
+        engine.cache_word_meaning("ephemeral".to_string(), "short-lived".to_string());
+
+        engine.shutdown().unwrap();
+
+        let restarted = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_data_dir(temp_dir.path());
+        assert_eq!(restarted.get_all_known_words().unwrap(), vec!["serendipity".to_string()]);
+        assert_eq!(restarted.get_cached_simplification("Hello there.").unwrap().simplified, "Hi.");
+        assert_eq!(restarted.get_cached_word_meaning("ephemeral"), Some("short-lived".to_string()));
+
+        // A second session's shutdown keeps what the first one saved
+        restarted.shutdown().unwrap();
+        let reopened = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_data_dir(temp_dir.path());
+        assert_eq!(reopened.get_all_known_words().unwrap(), vec!["serendipity".to_string()]);
+    }
+
+    #[test]
+    fn test_mark_sentence_words_known_returns_only_new_words() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_data_dir(temp_dir.path());
+        engine.add_known_word("the").unwrap();
+
+        let added = engine.mark_sentence_words_known("The cat saw the Dog's cat.").unwrap();
+        assert_eq!(added, vec!["cat", "saw", "dog's"]);
+        assert!(engine.mark_sentence_words_known("The cat.").unwrap().is_empty());
+
+        engine.shutdown().unwrap();
+        let restarted = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_data_dir(temp_dir.path());
+        let mut known = restarted.get_all_known_words().unwrap();
+        known.sort();
+        assert_eq!(known, vec!["cat", "dog's", "saw", "the"]);
+    }
+
+    #[test]
+    fn test_sentence_difficulty_ranks_easy_and_hard_sentences() {
+        let mut engine = engine_knowing(&["the", "cat", "sat", "on", "mat"], MockLLMClient::new());
+        engine
+            .load_text("The cat sat on the mat. Incomprehensible bureaucratic procrastination notwithstanding.")
+            .unwrap();
+
+        let easy = engine.sentence_difficulty(0);
+        let hard = engine.sentence_difficulty(1);
+        assert_eq!(easy.unknown_words, 0);
+        assert!(easy.score < 0.2, "easy scored {}", easy.score);
+        assert_eq!(hard.unknown_words, 4);
+        assert!(hard.score > 0.7, "hard scored {}", hard.score);
+
+        assert_eq!(engine.all_sentence_difficulties(), vec![easy, hard]);
+        assert_eq!(engine.sentence_difficulty(5), DifficultyScore::default());
+    }
+
+    #[test]
+    fn test_load_options_choose_which_caches_survive() {
+        for keep_images in [false, true] {
+            for keep_word_meanings in [false, true] {
+                let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+                engine.load_text("First text.").unwrap();
+                engine.cache_simplification("First text.".to_string(), SimplificationResponse {
+                    original: "First text.".to_string(),
+                    simplified: "Text.".to_string(),
+                    words: vec![],
+                    grammar_notes: vec![],
+                    was_already_simple: false,
+                });
+                engine.cache_images("cat".to_string(), vec![]);
+                engine.cache_word_meaning("cat".to_string(), "a small feline".to_string());
+
+                let options = LoadOptions { keep_images, keep_word_meanings };
+                engine.load_text_opts("Second text.", options).unwrap();
+
+                assert_eq!(engine.get_images("cat").is_some(), keep_images, "{options:?}");
+                assert_eq!(engine.get_cached_word_meaning("cat").is_some(), keep_word_meanings, "{options:?}");
+                assert!(engine.get_cached_simplification("First text.").is_none(), "{options:?}");
+                assert_eq!(engine.current_sentence().as_deref(), Some("Second text."));
+            }
+        }
+    }
+
+    #[test]
+    fn test_load_text_keeps_images_but_not_word_meanings() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.cache_images("cat".to_string(), vec![]);
+        engine.cache_word_meaning("cat".to_string(), "a small feline".to_string());
+
+        engine.load_text("New text.").unwrap();
+        assert!(engine.get_images("cat").is_some());
+        assert!(engine.get_cached_word_meaning("cat").is_none());
+        assert_eq!(LoadOptions::default(), LoadOptions { keep_images: true, keep_word_meanings: false });
+    }
+
+    #[tokio::test]
+    async fn test_loading_new_text_clears_shared_simplifications() {
+        let sentence = "The shared simplifier forgets this sentence.";
+        let cache_sentence = || async {
+            let response = SimplificationResponse {
+                original: sentence.to_string(),
+                simplified: sentence.to_string(),
+                words: vec![],
+                grammar_notes: vec![],
+                was_already_simple: true,
+            };
+            SharedSimplifier::global().simplify_with(sentence, || async { Ok(response) }).await.unwrap()
+        };
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+
+        cache_sentence().await;
+        engine.load_text("A new text.").unwrap();
+        assert!(SharedSimplifier::global().get(sentence).is_none());
+
+        cache_sentence().await;
+        engine.reset_session();
+        assert!(SharedSimplifier::global().get(sentence).is_none());
+    }
+
+    #[test]
+    fn test_non_english_text_sets_language_warning_without_blocking() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+
+        engine.load_text("Кошка сидела на коврике. Она смотрела в окно на дождь.").unwrap();
+        assert!(engine.language_warning());
+        assert_eq!(engine.total_sentences(), 2);
+
+        engine.load_text("The cat sat on the mat. It looked out of the window.").unwrap();
+        assert!(!engine.language_warning());
+
+        let mut unchecked = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_language_check(false);
+        unchecked.load_text("Кошка сидела на коврике. Она смотрела в окно на дождь.").unwrap();
+        assert!(!unchecked.language_warning());
+    }
+
+    #[test]
+    fn test_coverage_counts_known_words_across_the_document() {
+        let mut engine = engine_knowing(&["the", "cat", "sat", "on"], MockLLMClient::new());
+        engine.load_text("The cat sat on the mat. The dog barked.").unwrap();
+
+        let coverage = engine.coverage();
+        assert_eq!(coverage.total_words, 9);
+        assert_eq!(coverage.known_words, 6);
+        assert_eq!(coverage.unknown_words, 3);
+        assert!((coverage.coverage_fraction - 6.0 / 9.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_llm_surfaces_error_instead_of_panicking() {
+        let mut engine = ReadingEngine::with_unavailable_llm(AppError::config_error("OpenAI API key is required"));
+
+        engine.load_text("The cat sat.").unwrap();
+        engine.add_known_word("cat").unwrap();
+        let error = engine.process_sentence("The cat sat.").await.unwrap_err();
+        assert!(error.to_string().contains("OpenAI API key is required"));
+    }
+
+    #[test]
+    fn test_shutdown_without_data_dir_is_noop() {
+        let engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        assert!(engine.shutdown().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_warm_cache_skips_cached_sentences() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.load_text("First sentence. Second sentence. Third sentence.").unwrap();
+        engine.next();
+
+        let cached = SimplificationResponse {
+            original: "Second sentence.".to_string(),
+            simplified: "Already simplified".to_string(),
+            words: vec![],
+            grammar_notes: vec![],
+            was_already_simple: false,
+        };
+        engine.cache_simplification("Second sentence.".to_string(), cached);
+
+        let result = engine.warm_cache(10).await;
+
+        assert_eq!(result, WarmResult { simplified: 2, skipped_cached: 1, failed: 0 });
+        assert_eq!(engine.position(), 1);
+        assert!(engine.get_cached_simplification("First sentence.").is_some());
+        assert!(engine.get_cached_simplification("Third sentence.").is_some());
+        assert_eq!(
+            engine.get_cached_simplification("Second sentence.").unwrap().simplified,
+            "Already simplified"
+        );
+    }
+
+    #[test]
+    fn test_images_per_word_is_adjustable_and_at_least_one() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        assert_eq!(engine.images_per_word(), DEFAULT_IMAGES_PER_WORD);
+
+        engine.set_images_per_word(12);
+        assert_eq!(engine.images_per_word(), 12);
+
+        let engine = engine.with_images_per_word(0);
+        assert_eq!(engine.images_per_word(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_simplification_status() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        let sentence = "The cat sat.";
+        assert_eq!(engine.simplification_status(sentence), SimplificationStatus::None);
+
+        engine.mark_simplification_pending(sentence);
+        assert_eq!(engine.simplification_status(sentence), SimplificationStatus::Pending);
+
+        engine.clear_simplification_pending(sentence);
+        assert_eq!(engine.simplification_status(sentence), SimplificationStatus::None);
+
+        engine.process_sentence(sentence).await.unwrap();
+        assert_eq!(engine.simplification_status(sentence), SimplificationStatus::Cached);
+    }
+
+    #[test]
+    fn test_cached_status_wins_over_pending() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        let response = |sentence: &str| SimplificationResponse {
+            original: sentence.to_string(),
+            simplified: sentence.to_string(),
+            words: vec![],
+            grammar_notes: vec![],
+            was_already_simple: false,
+        };
+
+        engine.mark_simplification_pending("The dog ran.");
+        engine.cache_simplification("The dog ran.".to_string(), response("The dog ran."));
+        assert_eq!(engine.simplification_status("The dog ran."), SimplificationStatus::Cached);
+        assert_eq!(engine.simplification_status("The cow slept."), SimplificationStatus::None);
+
+        // Caching a fetched result also clears the pending mark
+        engine.mark_simplification_pending("The bird sang.");
+        engine.cache_simplification_result("The bird sang.".to_string(), response("The bird sang."));
+        assert!(!engine.orchestrator.is_in_flight("The bird sang."));
+    }
+
+    #[tokio::test]
+    async fn test_stale_prefetch_does_not_write_to_cache() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.load_text("The erudite scholar wrote. The terse reply came.").unwrap();
+        let sentence = engine.current_sentence().unwrap();
+        let words = vec!["erudite".to_string(), "scholar".to_string()];
+
+        let stale = engine.prefetch_epoch();
+        assert!(engine.next());
+        assert!(engine.prefetch_epoch() > stale);
+
+        assert_eq!(engine.prefetch_definitions_at_epoch(&words, &sentence, stale).await, 0);
+        assert_eq!(engine.get_cached_word_meaning_in_context("erudite", &sentence), None);
+
+        assert_eq!(engine.prefetch_definitions(&words, &sentence).await, 2);
+        assert!(engine.get_cached_word_meaning_in_context("erudite", &sentence).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_epoch_advanced_mid_prefetch_skips_remaining_fetches() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let client = ConcurrencyTrackingClient { active: Default::default(), peak: Default::default(), calls: calls.clone() };
+        let mut engine = ReadingEngine::with_llm_client(Box::new(client))
+            .unwrap()
+            .with_background_concurrency(1);
+        let sentence = "The erudite scholar wrote a lucid essay.";
+        let words: Vec<String> = ["erudite", "scholar", "lucid", "essay"].iter().map(|word| word.to_string()).collect();
+
+        let epoch = engine.prefetch_epoch_handle();
+        let (cached, _) = tokio::join!(engine.prefetch_definitions(&words, sentence), async {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            epoch.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert_eq!(cached, 0);
+        // Only the fetch already running when the epoch moved reached the client
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        for word in &words {
+            assert_eq!(engine.get_cached_word_meaning_in_context(word, sentence), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_definitions_caches_all_with_bounded_concurrency() {
+        let active = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let peak = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let client = ConcurrencyTrackingClient { active: active.clone(), peak: peak.clone(), calls: Default::default() };
+        let mut engine = ReadingEngine::with_llm_client(Box::new(client))
+            .unwrap()
+            .with_background_concurrency(2);
+
+        let sentence = "The erudite scholar wrote a lucid, terse and cogent essay.";
+        engine.cache_word_meaning_in_context("lucid".to_string(), sentence, "clear".to_string());
+        let words: Vec<String> = ["erudite", "lucid", "terse", "cogent", "Erudite", "essay"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+
+        assert_eq!(engine.prefetch_definitions(&words, sentence).await, 4);
+
+        assert_eq!(peak.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(engine.get_cached_word_meaning_in_context("lucid", sentence).as_deref(), Some("clear"));
+        for word in ["erudite", "terse", "cogent", "essay"] {
+            assert_eq!(
+                engine.get_cached_word_meaning_in_context(word, sentence),
+                Some(format!("meaning of {word}"))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reset_session_keeps_known_words_only() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.load_text("The cat sat. The dog ran.").unwrap();
+        engine.next();
+        engine.process_sentence("The dog ran.").await.unwrap();
+        engine.cache_word_meaning("dog".to_string(), "a pet".to_string());
+        engine.add_known_word("cat").unwrap();
+        engine.add_manual_word("dog".to_string());
+
+        engine.reset_session();
+
+        assert_eq!(engine.get_all_known_words().unwrap(), vec!["cat".to_string()]);
+        assert!(engine.get_manual_words().is_empty());
+        assert_eq!(engine.position(), 0);
+        assert_eq!(engine.total_sentences(), 0);
+        assert!(engine.current_sentence().is_none());
+        assert!(engine.get_cached_simplification("The dog ran.").is_none());
+        assert!(engine.get_cached_word_meaning("dog").is_none());
+    }
+
+    #[test]
+    fn test_nav_snapshot_is_consistent_after_moves() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.load_text("One. Two. Three. Four.").unwrap();
+
+        let start = engine.nav_snapshot();
+        assert_eq!((start.position, start.total), (0, 4));
+        assert!(start.at_beginning && !start.at_end && !start.can_back && !start.can_forward);
+
+        let moves: [fn(&mut ReadingEngine) -> bool; 5] =
+            [ReadingEngine::next, ReadingEngine::next, ReadingEngine::next, ReadingEngine::go_back, ReadingEngine::previous];
+        for step in moves {
+            step(&mut engine);
+            let snapshot = engine.nav_snapshot();
+            assert_eq!(snapshot.position, engine.position());
+            assert_eq!(snapshot.total, engine.total_sentences());
+            assert_eq!(snapshot.progress, snapshot.position as f64 / snapshot.total as f64);
+            assert_eq!(snapshot.at_beginning, snapshot.position == 0);
+            assert_eq!(snapshot.at_end, snapshot.position + 1 == snapshot.total);
+            assert_eq!((snapshot.can_back, snapshot.can_forward), (engine.can_go_back(), engine.can_go_forward()));
+        }
+
+        let json = serde_json::to_value(engine.nav_snapshot()).unwrap();
+        assert_eq!(json["total"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_process_sentence_full_fetches_and_caches_images() {
+        let sentence = "The feline pondered the bird.";
+        let image_client = glossia_image_client::MockImageClient::new();
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_image_client(Box::new(image_client.clone()))
+            .with_illustrated_words(2)
+            .with_images_per_word(2);
+        engine.add_known_word("bird").unwrap();
+        engine.cache_simplification(sentence.to_string(), SimplificationResponse {
+            original: sentence.to_string(),
+            simplified: "The cat thought about the bird.".to_string(),
+            words: ["feline", "pondered", "bird", "the"]
+                .iter()
+                .map(|word| WordMeaning::new_word(word.to_string(), format!("meaning of {word}")))
+                .collect(),
+            grammar_notes: vec![],
+            was_already_simple: false,
+        });
+
+        let result = engine.process_sentence_full(sentence).await.unwrap();
+
+        assert_eq!(result.simplification.simplified, "The cat thought about the bird.");
+        let mut illustrated: Vec<&String> = result.images.keys().collect();
+        illustrated.sort();
+        assert_eq!(illustrated, vec!["feline", "pondered"]);
+        assert_eq!(result.images["feline"].len(), 2);
+        assert_eq!(engine.get_images("feline"), Some(result.images["feline"].clone()));
+        assert_eq!(
+            engine.get_optimized_query(&optimized_query_key("pondered", sentence)),
+            Some("optimized pondered".to_string())
+        );
+
+        let mut queries = image_client.requested_queries();
+        queries.sort();
+        assert_eq!(queries, vec!["optimized feline", "optimized pondered"]);
+    }
+
+    #[tokio::test]
+    async fn test_process_sentence_full_uses_static_gallery_source() {
+        let sentence = "The feline sat.";
+        let image = glossia_shared::ImageResult {
+            url: "https://example.com/cat.jpg".to_string(),
+            thumbnail_url: "https://example.com/cat-small.jpg".to_string(),
+            title: "Cat".to_string(),
+            width: None,
+            height: None,
+            display_url: None,
+        };
+        let image_client = glossia_image_client::MockImageClient::new();
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_image_client(Box::new(image_client.clone()))
+            .with_gallery_source(Arc::new(StaticGallerySource::new().with_images("feline", vec![image.clone()])));
+        engine.cache_simplification(sentence.to_string(), SimplificationResponse {
+            original: sentence.to_string(),
+            simplified: "The cat sat.".to_string(),
+            words: vec![WordMeaning::new_word("feline".to_string(), "cat".to_string())],
+            grammar_notes: vec![],
+            was_already_simple: false,
+        });
+
+        let result = engine.process_sentence_full(sentence).await.unwrap();
+
+        assert_eq!(result.images["feline"], vec![image]);
+        assert!(image_client.requested_queries().is_empty());
+        assert_eq!(engine.get_optimized_query(&optimized_query_key("feline", sentence)), None);
+    }
+
+    #[tokio::test]
+    async fn test_cached_simplifications_follow_reading_order() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.load_text("One here. Two here. Three here. One here.").unwrap();
+        assert!(engine.cached_simplifications().is_empty());
+
+        engine.process_sentence("Three here.").await.unwrap();
+        engine.process_sentence("One here.").await.unwrap();
+        engine.process_sentence("Not in the text.").await.unwrap();
+
+        let originals: Vec<String> = engine.cached_simplifications().into_iter().map(|response| response.original).collect();
+        assert_eq!(originals, vec!["One here.", "Three here."]);
+    }
+
+    /// LLM stub whose simplifications always fail, counting the attempts
+    struct FailingSimplifyClient {
+        calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl LLMClient for FailingSimplifyClient {
+        async fn simplify(&self, _request: glossia_shared::SimplificationRequest) -> Result<SimplificationResponse, AppError> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(AppError::api_error("rate limited"))
+        }
+
+        async fn get_word_meaning(&self, word: &str, _context: &str) -> Result<String, AppError> {
+            Ok(format!("meaning of {word}"))
+        }
+
+        async fn optimize_image_query(&self, request: glossia_shared::ImageQueryOptimizationRequest) -> Result<glossia_shared::ImageQueryOptimizationResponse, AppError> {
+            MockLLMClient::new().optimize_image_query(request).await
+        }
+
+        fn provider_name(&self) -> &str {
+            "FailingSimplify"
+        }
+
+        async fn health_check(&self) -> Result<(), AppError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_simplification_is_cached_until_ttl_expires() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let client = FailingSimplifyClient { calls: calls.clone() };
+        let mut engine = ReadingEngine::with_llm_client(Box::new(client))
+            .unwrap()
+            .with_failure_cache_ttl(std::time::Duration::from_millis(50));
+
+        let first = engine.process_sentence("The cat sat.").await.unwrap_err();
+        let second = engine.process_sentence("The cat sat.").await.unwrap_err();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        tokio::time::sleep(std::time::Duration::from_millis(80)).await;
+        assert!(engine.process_sentence("The cat sat.").await.is_err());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_retried_without_failure_cache() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let client = FailingSimplifyClient { calls: calls.clone() };
+        let mut engine = ReadingEngine::with_llm_client(Box::new(client)).unwrap();
+
+        assert!(engine.process_sentence("The cat sat.").await.is_err());
+        assert!(engine.process_sentence("The cat sat.").await.is_err());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_abort_all_cancels_background_simplification() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new().with_delay(10_000))).unwrap();
+        let sentence = "The abort test sentence is never simplified.";
+        let simplify = engine.spawn_background_simplification(sentence);
+        engine.set_processing(true);
+
+        assert_eq!(engine.simplification_status(sentence), SimplificationStatus::Pending);
+        assert_eq!(engine.in_flight_tasks(), vec![format!("simplify: {sentence}")]);
+        assert_eq!(engine.abort_all(), 1);
+
+        assert!(simplify.await.unwrap_err().is_cancelled());
+        assert!(engine.in_flight_tasks().is_empty());
+        assert!(!engine.is_processing());
+        assert_eq!(engine.simplification_status(sentence), SimplificationStatus::None);
+    }
+
+    #[tokio::test]
+    async fn test_background_simplification_result_can_be_cached() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        let sentence = "The background simplification finishes.";
+
+        let response = engine.spawn_background_simplification(sentence).await.unwrap().unwrap();
+        engine.cache_simplification_result(sentence.to_string(), response);
+
+        assert_eq!(engine.simplification_status(sentence), SimplificationStatus::Cached);
+        assert!(engine.in_flight_tasks().is_empty());
+    }
+
+    #[test]
+    fn test_unit_progress_follows_navigation_strategy() {
+        use glossia_navigation_service::{ParagraphNavigationStrategy, SpeedReadingStrategy};
+        let text = "The cat sat. The dog ran.\n\nThe owl slept.";
+
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        engine.load_text(text).unwrap();
+        engine.next();
+        assert_eq!((engine.unit_label(), engine.unit_count()), ("sentence", 3));
+        assert_eq!(engine.unit_progress_text(), "Sentence 2 of 3");
+
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_navigation_strategy(Box::new(ParagraphNavigationStrategy::new()));
+        engine.load_text(text).unwrap();
+        assert_eq!((engine.unit_label(), engine.unit_count()), ("paragraph", 2));
+        assert_eq!(engine.unit_progress_text(), "Paragraph 1 of 2");
+        engine.next();
+        assert_eq!(engine.unit_progress_text(), "Paragraph 1 of 2");
+        engine.next();
+        assert_eq!(engine.unit_progress_text(), "Paragraph 2 of 2");
+        engine.previous();
+        assert_eq!(engine.unit_progress_text(), "Paragraph 1 of 2");
+
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_navigation_strategy(Box::new(SpeedReadingStrategy::new().with_chunk_size(4)));
+        engine.load_text(text).unwrap();
+        assert_eq!(engine.unit_progress_text(), "Chunk 1 of 3");
+        engine.next();
+        engine.next();
+        assert_eq!(engine.unit_progress_text(), "Chunk 2 of 3");
+    }
+
+    #[test]
+    fn test_navigation_records_daily_activity() {
+        use crate::reading_activity::tests::FixedClock;
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let clock = Arc::new(FixedClock(AtomicU64::new(200)));
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_activity_clock(clock.clone());
+        engine.load_text("The cat sat. The dog ran. The owl slept.").unwrap();
+
+        engine.next();
+        engine.add_known_word("owl").unwrap();
+        clock.0.store(201, Ordering::SeqCst);
+        engine.next();
+
+        let days = engine.activity().activity_last_n_days(2);
+        assert_eq!(days.iter().map(|d| (d.sentences_read, d.words_learned)).collect::<Vec<_>>(), vec![(1, 0), (1, 1)]);
+        assert_eq!(engine.activity().current_streak(), 2);
+    }
+
+    #[test]
+    fn test_manual_words_round_trip_through_data_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let text = "The cat sat. The dog ran.";
+
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_data_dir(temp_dir.path());
+        engine.load_text(text).unwrap();
+        engine.add_manual_word("dog".to_string());
+        engine.add_manual_word("cat".to_string());
+        engine.save_manual_words().unwrap();
+        let saved_hash = engine.text_hash();
+
+        let mut restarted = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_data_dir(temp_dir.path());
+        restarted.load_text(text).unwrap();
+        assert!(restarted.is_manual_word("cat") && restarted.is_manual_word("dog"));
+
+        restarted.load_text("A different text entirely.").unwrap();
+        assert!(restarted.get_manual_words().is_empty());
+        assert_eq!(restarted.restore_manual_words(&saved_hash), 0);
+        assert!(restarted.get_manual_words().is_empty());
+    }
+
+    #[test]
+    fn test_neighbors_at_start_middle_and_end() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new())).unwrap();
+        assert_eq!(engine.neighbors(), (None, None));
+
+        engine.load_text("The cat sat. The dog ran. The owl slept.").unwrap();
+        assert_eq!(engine.neighbors(), (None, Some("The dog ran.".to_string())));
+
+        engine.next();
+        assert_eq!(engine.neighbors(), (Some("The cat sat.".to_string()), Some("The owl slept.".to_string())));
+        assert_eq!(engine.position(), 1);
+
+        engine.next();
+        assert_eq!(engine.neighbors(), (Some("The dog ran.".to_string()), None));
+    }
+
+    #[test]
+    fn test_promotions_are_appended_to_capped_history() {
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_promotion_history_size(2);
+        assert!(engine.recent_promotions(5).is_empty());
+
+        for word in ["lucid", "serene", "zeal"] {
+            let mut promoted = false;
+            while !promoted {
+                promoted = engine.add_word_encounter(word).unwrap().1;
+            }
+        }
+
+        let recent = engine.recent_promotions(5);
+        let words: Vec<&str> = recent.iter().map(|event| event.word.as_str()).collect();
+        assert_eq!(words, vec!["zeal", "serene"]);
+        assert!(recent[0].count > 0);
+        assert_eq!(engine.recent_promotions(1).len(), 1);
+    }
+
+    #[test]
+    fn test_previously_read_text_resumes_at_saved_position() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let text = "The cat sat. The dog ran. The owl slept.";
+
+        let mut engine = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_data_dir(temp_dir.path())
+            .with_resume_position(true);
+        engine.load_text(text).unwrap();
+        engine.next();
+        engine.next();
+        engine.shutdown().unwrap();
+
+        let mut restarted = ReadingEngine::with_llm_client(Box::new(MockLLMClient::new()))
+            .unwrap()
+            .with_data_dir(temp_dir.path())
+            .with_resume_position(true);
+        restarted.load_text(text).unwrap();
+        assert_eq!(restarted.position(), 2);
+
+        restarted.load_text("A different text. It starts fresh.").unwrap();
+        assert_eq!(restarted.position(), 0);
+
+        restarted.load_text(text).unwrap();
+        assert_eq!(restarted.position(), 2);
+
+        restarted.set_resume_position(false);
+        restarted.load_text(text).unwrap();
+        assert_eq!(restarted.position(), 0);
+        assert_eq!(restarted.saved_position(&restarted.text_hash()), Some(2));
+    }
+}
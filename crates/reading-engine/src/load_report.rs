@@ -0,0 +1,98 @@
+use glossia_text_parser::extract_words;
+use serde::{Deserialize, Serialize};
+
+/// Reading speed used for `LoadReport::estimated_reading_minutes`
+pub const READING_WORDS_PER_MINUTE: usize = 200;
+
+/// Fewest stop-word hits needed before a language is reported
+const MIN_LANGUAGE_HITS: usize = 3;
+
+/// Common function words per ISO 639-1 code, used to guess the text's language
+const STOP_WORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "and", "of", "to", "is", "was", "that", "with", "it", "he", "she"]),
+    ("es", &["el", "los", "las", "y", "que", "es", "por", "con", "una", "para", "del"]),
+    ("fr", &["le", "les", "et", "est", "une", "des", "dans", "que", "pour", "avec", "du"]),
+    ("de", &["der", "die", "das", "und", "ist", "nicht", "ein", "eine", "mit", "sich", "auf"]),
+    ("it", &["il", "gli", "che", "di", "una", "sono", "per", "con", "della", "non", "è"]),
+    ("pt", &["os", "as", "que", "não", "uma", "com", "para", "do", "da", "em", "é"]),
+];
+
+/// Summary of a text after `ReadingEngine::load_text_report`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadReport {
+    pub sentence_count: usize,
+    pub paragraph_count: usize,
+    /// Whole minutes at `READING_WORDS_PER_MINUTE`, at least 1 for non-empty text
+    pub estimated_reading_minutes: usize,
+    /// ISO 639-1 code when the language can be guessed with reasonable confidence
+    pub detected_language: Option<String>,
+    /// Whether sentences past the engine's `max_sentences` were dropped
+    #[serde(default)]
+    pub truncated: bool,
+    /// Number of sentences dropped; `sentence_count` counts only the kept ones
+    #[serde(default)]
+    pub truncated_count: usize,
+}
+
+impl LoadReport {
+    /// Build a report for `text` from the sentence and paragraph counts the navigator produced
+    pub fn from_text(text: &str, sentence_count: usize, paragraph_count: usize) -> Self {
+        let words = extract_words(text);
+
+        Self {
+            sentence_count,
+            paragraph_count,
+            estimated_reading_minutes: words.len().div_ceil(READING_WORDS_PER_MINUTE),
+            detected_language: detect_language(&words).map(str::to_string),
+            truncated: false,
+            truncated_count: 0,
+        }
+    }
+
+    /// Record that `truncated_count` sentences were dropped from the text
+    pub fn with_truncated_count(mut self, truncated_count: usize) -> Self {
+        self.truncated = truncated_count > 0;
+        self.truncated_count = truncated_count;
+        self
+    }
+}
+
+/// Language whose stop words appear most often, if it clearly leads
+fn detect_language(words: &[String]) -> Option<&'static str> {
+    let mut scores: Vec<(&str, usize)> = STOP_WORDS
+        .iter()
+        .map(|(code, stop_words)| {
+            let hits = words.iter().filter(|word| stop_words.contains(&word.as_str())).count();
+            (*code, hits)
+        })
+        .collect();
+    scores.sort_by_key(|&(_, hits)| std::cmp::Reverse(hits));
+
+    match scores.as_slice() {
+        [(code, best), (_, runner_up), ..] if *best >= MIN_LANGUAGE_HITS && best > runner_up => Some(code),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reading_minutes_round_up() {
+        let text = "word ".repeat(READING_WORDS_PER_MINUTE + 1);
+        assert_eq!(LoadReport::from_text(&text, 1, 1).estimated_reading_minutes, 2);
+        assert_eq!(LoadReport::from_text("A few words.", 1, 1).estimated_reading_minutes, 1);
+    }
+
+    #[test]
+    fn test_detects_language_from_stop_words() {
+        let english = LoadReport::from_text("The cat sat on the mat and it was happy with the sun.", 1, 1);
+        assert_eq!(english.detected_language.as_deref(), Some("en"));
+
+        let spanish = LoadReport::from_text("El perro y el gato están en la casa con los niños para jugar.", 1, 1);
+        assert_eq!(spanish.detected_language.as_deref(), Some("es"));
+
+        assert_eq!(LoadReport::from_text("Hello world.", 1, 1).detected_language, None);
+    }
+}
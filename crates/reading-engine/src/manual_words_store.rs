@@ -0,0 +1,39 @@
+use crate::json_file::{read_json_file, write_json_file};
+use glossia_shared::AppError;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// File name of the saved manual words inside the engine's data directory
+pub const MANUAL_WORDS_FILE: &str = "manual_words.json";
+
+/// Manual word selections per document, keyed by the text hash of `SessionState`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ManualWordsStore {
+    by_text: HashMap<String, Vec<String>>,
+}
+
+impl ManualWordsStore {
+    pub(crate) fn get(&self, text_hash: &str) -> Option<&Vec<String>> {
+        self.by_text.get(text_hash)
+    }
+
+    /// Remember `words` for the text, sorted; an empty selection forgets the text
+    pub(crate) fn set(&mut self, text_hash: String, mut words: Vec<String>) {
+        if words.is_empty() {
+            self.by_text.remove(&text_hash);
+        } else {
+            words.sort();
+            self.by_text.insert(text_hash, words);
+        }
+    }
+
+    pub(crate) fn save_to_file(&self, path: &Path) -> Result<(), AppError> {
+        write_json_file(path, &self.by_text, "manual words")
+    }
+
+    /// Store saved by `save_to_file`, or an empty one when the file doesn't exist
+    pub(crate) fn load_from_file(path: &Path) -> Result<Self, AppError> {
+        let by_text = read_json_file(path, "manual words")?.unwrap_or_default();
+        Ok(Self { by_text })
+    }
+}
@@ -0,0 +1,31 @@
+use glossia_navigation_service::NavigationService;
+use serde::{Deserialize, Serialize};
+
+/// Navigation state read in one call, so the UI never mixes values from
+/// before and after a move
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NavSnapshot {
+    pub position: usize,
+    pub total: usize,
+    /// 0.0 to 1.0
+    pub progress: f64,
+    pub at_beginning: bool,
+    pub at_end: bool,
+    /// History can go back or forward (see `ReadingEngine::go_back`)
+    pub can_back: bool,
+    pub can_forward: bool,
+}
+
+impl NavSnapshot {
+    pub(crate) fn of(navigation: &NavigationService) -> Self {
+        Self {
+            position: navigation.current_position(),
+            total: navigation.total_sentences(),
+            progress: navigation.progress(),
+            at_beginning: navigation.is_at_beginning(),
+            at_end: navigation.is_at_end(),
+            can_back: navigation.can_go_back(),
+            can_forward: navigation.can_go_forward(),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use glossia_shared::AppError;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Local word→definition lookup used before asking the LLM for a meaning
+///
+/// Files are either a JSON object (`{"word": "definition"}`) or plain text
+/// with one `word<TAB>definition` entry per line. Lookups ignore case.
+#[derive(Debug, Clone, Default)]
+pub struct OfflineDictionary {
+    entries: HashMap<String, String>,
+}
+
+impl OfflineDictionary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a dictionary file from disk
+    pub fn load_from_file(path: &Path) -> Result<Self, AppError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| AppError::config_error(format!("Failed to read dictionary file: {e}")))?;
+        Self::parse(&contents)
+    }
+
+    /// Parse dictionary contents in either supported format
+    pub fn parse(contents: &str) -> Result<Self, AppError> {
+        let mut dictionary = Self::new();
+
+        if contents.trim_start().starts_with('{') {
+            let entries: HashMap<String, String> = serde_json::from_str(contents)
+                .map_err(|e| AppError::config_error(format!("Failed to parse dictionary: {e}")))?;
+            for (word, definition) in entries {
+                dictionary.insert(&word, &definition);
+            }
+        } else {
+            for line in contents.lines() {
+                if let Some((word, definition)) = line.split_once('\t') {
+                    dictionary.insert(word, definition);
+                }
+            }
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Add or replace a definition; blank words or definitions are ignored
+    pub fn insert(&mut self, word: &str, definition: &str) {
+        let word = word.trim().to_lowercase();
+        let definition = definition.trim();
+        if !word.is_empty() && !definition.is_empty() {
+            self.entries.insert(word, definition.to_string());
+        }
+    }
+
+    pub fn lookup(&self, word: &str) -> Option<&str> {
+        self.entries.get(&word.trim().to_lowercase()).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json_dictionary() {
+        let dictionary = OfflineDictionary::parse(r#"{"Cat": "a small pet", "dog": " a loyal pet "}"#).unwrap();
+
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(dictionary.lookup("cat"), Some("a small pet"));
+        assert_eq!(dictionary.lookup("DOG"), Some("a loyal pet"));
+        assert_eq!(dictionary.lookup("bird"), None);
+    }
+
+    #[test]
+    fn test_parse_tab_separated_dictionary() {
+        let dictionary = OfflineDictionary::parse("cat\ta small pet\nnot an entry\n\tmissing word\n").unwrap();
+
+        assert_eq!(dictionary.len(), 1);
+        assert_eq!(dictionary.lookup("Cat"), Some("a small pet"));
+    }
+
+    #[test]
+    fn test_invalid_json_is_an_error() {
+        assert!(OfflineDictionary::parse("{not json").is_err());
+    }
+}
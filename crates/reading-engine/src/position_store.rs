@@ -0,0 +1,33 @@
+use crate::json_file::{read_json_file, write_json_file};
+use glossia_shared::AppError;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// File name of the saved reading positions inside the engine's data directory
+pub const POSITIONS_FILE: &str = "positions.json";
+
+/// Last sentence index read in each document, keyed by the text hash of `SessionState`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PositionStore {
+    by_text: HashMap<String, usize>,
+}
+
+impl PositionStore {
+    pub(crate) fn get(&self, text_hash: &str) -> Option<usize> {
+        self.by_text.get(text_hash).copied()
+    }
+
+    pub(crate) fn set(&mut self, text_hash: String, position: usize) {
+        self.by_text.insert(text_hash, position);
+    }
+
+    pub(crate) fn save_to_file(&self, path: &Path) -> Result<(), AppError> {
+        write_json_file(path, &self.by_text, "reading positions")
+    }
+
+    /// Store saved by `save_to_file`, or an empty one when the file doesn't exist
+    pub(crate) fn load_from_file(path: &Path) -> Result<Self, AppError> {
+        let by_text = read_json_file(path, "reading positions")?.unwrap_or_default();
+        Ok(Self { by_text })
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+/// Promotions kept by `ReadingEngine` unless configured otherwise
+pub const DEFAULT_PROMOTION_HISTORY_SIZE: usize = 50;
+
+/// A word that became known by reaching the encounter threshold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromotionEvent {
+    pub word: String,
+    /// Encounters counted when the word was promoted
+    pub count: usize,
+    pub at: SystemTime,
+}
+
+/// The most recent promotions, oldest dropped first once full
+#[derive(Debug, Clone)]
+pub(crate) struct PromotionHistory {
+    events: VecDeque<PromotionEvent>,
+    capacity: usize,
+}
+
+impl PromotionHistory {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    pub(crate) fn record(&mut self, word: &str, count: usize, at: SystemTime) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.events.len() >= self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back(PromotionEvent { word: word.to_string(), count, at });
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.events.len() > capacity {
+            self.events.pop_front();
+        }
+    }
+
+    /// Up to `n` promotions, newest first
+    pub(crate) fn recent(&self, n: usize) -> Vec<PromotionEvent> {
+        self.events.iter().rev().take(n).cloned().collect()
+    }
+}
+
+impl Default for PromotionHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_PROMOTION_HISTORY_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_caps_at_capacity_dropping_oldest() {
+        let mut history = PromotionHistory::new(2);
+        history.record("lucid", 5, SystemTime::UNIX_EPOCH);
+        history.record("serene", 6, SystemTime::UNIX_EPOCH);
+        history.record("zeal", 5, SystemTime::UNIX_EPOCH);
+
+        let words: Vec<String> = history.recent(10).into_iter().map(|event| event.word).collect();
+        assert_eq!(words, vec!["zeal", "serene"]);
+
+        history.set_capacity(1);
+        assert_eq!(history.recent(10).len(), 1);
+        assert_eq!(history.recent(0), Vec::new());
+    }
+}
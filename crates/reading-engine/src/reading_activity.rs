@@ -0,0 +1,172 @@
+use crate::json_file::{read_json_file, write_json_file};
+use glossia_shared::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File name of the activity log inside the engine's data directory
+pub const ACTIVITY_FILE: &str = "reading_activity.json";
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Source of the current day, replaceable in tests
+pub trait ActivityClock: Send + Sync {
+    /// Days since the Unix epoch (UTC)
+    fn today(&self) -> u64;
+}
+
+/// Clock reading the system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl ActivityClock for SystemClock {
+    fn today(&self) -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() / SECONDS_PER_DAY)
+    }
+}
+
+/// Reading done on one day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyActivity {
+    /// Days since the Unix epoch (UTC)
+    pub day: u64,
+    pub sentences_read: usize,
+    pub words_learned: usize,
+}
+
+/// Per-day reading activity, for streaks and history charts
+pub struct ReadingActivity {
+    days: BTreeMap<u64, DailyActivity>,
+    clock: Arc<dyn ActivityClock>,
+}
+
+impl ReadingActivity {
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(clock: Arc<dyn ActivityClock>) -> Self {
+        Self { days: BTreeMap::new(), clock }
+    }
+
+    pub fn set_clock(&mut self, clock: Arc<dyn ActivityClock>) {
+        self.clock = clock;
+    }
+
+    /// Add to today's totals
+    pub fn record(&mut self, sentences_read: usize, words_learned: usize) {
+        let day = self.clock.today();
+        let entry = self.days.entry(day).or_insert(DailyActivity { day, ..DailyActivity::default() });
+        entry.sentences_read += sentences_read;
+        entry.words_learned += words_learned;
+    }
+
+    /// Consecutive days with reading, ending today, or yesterday when
+    /// nothing has been read yet today
+    pub fn current_streak(&self) -> usize {
+        let today = self.clock.today();
+        let Some(mut day) = [Some(today), today.checked_sub(1)].into_iter().flatten().find(|day| self.has_read(*day)) else {
+            return 0;
+        };
+
+        let mut streak = 0;
+        while self.has_read(day) {
+            streak += 1;
+            match day.checked_sub(1) {
+                Some(previous) => day = previous,
+                None => break,
+            }
+        }
+        streak
+    }
+
+    /// One entry per day for the last `n` days, oldest first and ending today;
+    /// days without reading have zero totals
+    pub fn activity_last_n_days(&self, n: usize) -> Vec<DailyActivity> {
+        let today = self.clock.today();
+        (0..n as u64)
+            .rev()
+            .filter_map(|offset| today.checked_sub(offset))
+            .map(|day| self.days.get(&day).copied().unwrap_or(DailyActivity { day, ..DailyActivity::default() }))
+            .collect()
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> Result<(), AppError> {
+        let days: Vec<&DailyActivity> = self.days.values().collect();
+        write_json_file(path, &days, "reading activity")
+    }
+
+    /// Merge days saved by `save_to_file`; a missing file leaves the log unchanged
+    pub fn load_from_file(&mut self, path: &Path) -> Result<(), AppError> {
+        let days: Vec<DailyActivity> = read_json_file(path, "reading activity")?.unwrap_or_default();
+        self.days.extend(days.into_iter().map(|activity| (activity.day, activity)));
+        Ok(())
+    }
+
+    fn has_read(&self, day: u64) -> bool {
+        self.days.get(&day).is_some_and(|activity| activity.sentences_read > 0 || activity.words_learned > 0)
+    }
+}
+
+impl Default for ReadingActivity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// Clock showing whatever day the test sets
+    #[derive(Default)]
+    pub(crate) struct FixedClock(pub(crate) AtomicU64);
+
+    impl ActivityClock for FixedClock {
+        fn today(&self) -> u64 {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    #[test]
+    fn test_consecutive_days_build_a_streak_and_a_gap_resets_it() {
+        let clock = Arc::new(FixedClock(AtomicU64::new(100)));
+        let mut activity = ReadingActivity::with_clock(clock.clone());
+
+        for day in 100..103 {
+            clock.0.store(day, Ordering::SeqCst);
+            activity.record(5, 1);
+        }
+        assert_eq!(activity.current_streak(), 3);
+
+        // Not having read yet today keeps yesterday's streak alive
+        clock.0.store(103, Ordering::SeqCst);
+        assert_eq!(activity.current_streak(), 3);
+
+        clock.0.store(105, Ordering::SeqCst);
+        assert_eq!(activity.current_streak(), 0);
+        activity.record(1, 0);
+        assert_eq!(activity.current_streak(), 1);
+    }
+
+    #[test]
+    fn test_last_n_days_fill_gaps_and_survive_saving() {
+        let clock = Arc::new(FixedClock(AtomicU64::new(10)));
+        let mut activity = ReadingActivity::with_clock(clock.clone());
+        activity.record(3, 2);
+        clock.0.store(12, Ordering::SeqCst);
+        activity.record(4, 0);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(ACTIVITY_FILE);
+        activity.save_to_file(&path).unwrap();
+        let mut loaded = ReadingActivity::with_clock(clock);
+        loaded.load_from_file(&path).unwrap();
+
+        let days = loaded.activity_last_n_days(3);
+        assert_eq!(days.iter().map(|d| (d.day, d.sentences_read, d.words_learned)).collect::<Vec<_>>(), vec![(10, 3, 2), (11, 0, 0), (12, 4, 0)]);
+    }
+}
@@ -1,42 +1,159 @@
-use glossia_shared::{AppError, SimplificationResponse, SimplificationRequest};
-use glossia_llm_client::{LLMClient, LLMClientFactory};
-use crate::cache_engine::CacheEngine;
+use glossia_shared::{AppError, ImageQueryOptimizationRequest, ImageQueryOptimizationResponse, SimplificationResponse, SimplificationRequest};
+use glossia_llm_client::{CircuitState, LLMClient, LLMClientFactory, RequestStats};
+use serde::Serialize;
+use crate::cache_trait::Cache;
+use crate::task_registry::TaskRegistry;
+use glossia_logging::log_performance_metric;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+/// Whether a sentence's simplification is ready, being fetched, or neither
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimplificationStatus {
+    Cached,
+    Pending,
+    None,
+}
 
 /// Orchestrates the high-level reading workflow
 pub struct ReadingOrchestrator {
-    llm_client: Box<dyn LLMClient>,
+    llm_client: Arc<dyn LLMClient>,
+    in_flight: Mutex<HashSet<String>>,
+    failure_ttl: Option<Duration>,
+    failures: Mutex<HashMap<String, (Instant, AppError)>>,
+    tasks: TaskRegistry,
 }
 
 impl ReadingOrchestrator {
     pub fn new() -> Result<Self, AppError> {
         let factory = LLMClientFactory::new();
         Ok(Self {
-            llm_client: factory.create_client()?,
+            llm_client: Arc::from(factory.create_client()?),
+            in_flight: Mutex::new(HashSet::new()),
+            failure_ttl: None,
+            failures: Mutex::new(HashMap::new()),
+            tasks: TaskRegistry::new(),
         })
     }
 
     /// Create orchestrator with custom LLM client (useful for testing)
     pub fn with_llm_client(llm_client: Box<dyn LLMClient>) -> Self {
-        Self { llm_client }
+        Self {
+            llm_client: Arc::from(llm_client),
+            in_flight: Mutex::new(HashSet::new()),
+            failure_ttl: None,
+            failures: Mutex::new(HashMap::new()),
+            tasks: TaskRegistry::new(),
+        }
+    }
+
+    /// Remember failed simplifications for `ttl` and return the same error
+    /// without calling the LLM again; `None` (the default) disables this
+    pub fn set_failure_cache_ttl(&mut self, ttl: Option<Duration>) {
+        self.failure_ttl = ttl;
+        if ttl.is_none() {
+            self.clear_failures();
+        }
+    }
+
+    /// The error from a recent failed simplification of `sentence`, while its TTL lasts
+    pub fn cached_failure(&self, sentence: &str) -> Option<AppError> {
+        let ttl = self.failure_ttl?;
+        let mut failures = self.failures.lock().unwrap();
+        match failures.get(sentence) {
+            Some((failed_at, error)) if failed_at.elapsed() < ttl => Some(error.clone()),
+            Some(_) => {
+                failures.remove(sentence);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn clear_failures(&self) {
+        self.failures.lock().unwrap().clear();
+    }
+
+    /// Record that a simplification request for `sentence` has started
+    pub fn mark_in_flight(&self, sentence: &str) {
+        self.in_flight.lock().unwrap().insert(sentence.to_string());
+    }
+
+    /// Record that the request for `sentence` finished, successfully or not
+    pub fn clear_in_flight(&self, sentence: &str) {
+        self.in_flight.lock().unwrap().remove(sentence);
+    }
+
+    pub fn is_in_flight(&self, sentence: &str) -> bool {
+        self.in_flight.lock().unwrap().contains(sentence)
+    }
+
+    /// The LLM client, for work spawned outside the orchestrator's borrow
+    pub(crate) fn llm_client(&self) -> Arc<dyn LLMClient> {
+        self.llm_client.clone()
+    }
+
+    /// Tasks spawned for this orchestrator's work (simplifications, image fetches)
+    pub fn tasks(&self) -> &TaskRegistry {
+        &self.tasks
+    }
+
+    /// Cancel every tracked task and forget which sentences were in flight
+    /// Returns how many tasks were cancelled
+    pub fn abort_all(&self) -> usize {
+        let aborted = self.tasks.abort_all();
+        self.in_flight.lock().unwrap().clear();
+        aborted
+    }
+
+    /// Cached when the cache has the sentence, Pending while a request is in flight
+    pub fn simplification_status(&self, sentence: &str, cache: &dyn Cache) -> SimplificationStatus {
+        if cache.has_simplified(sentence) {
+            SimplificationStatus::Cached
+        } else if self.is_in_flight(sentence) {
+            SimplificationStatus::Pending
+        } else {
+            SimplificationStatus::None
+        }
     }
 
     /// Process a sentence through the complete reading pipeline
     pub async fn process_sentence(
         &self,
         sentence: &str,
-        cache: &mut CacheEngine,
+        cache: &mut dyn Cache,
     ) -> Result<SimplificationResponse, AppError> {
         // Check cache first
         if let Some(cached_response) = cache.get_simplified(sentence) {
             return Ok(cached_response);
         }
+        if let Some(error) = self.cached_failure(sentence) {
+            return Err(error);
+        }
 
         // Process with LLM
         let request = SimplificationRequest {
             sentence: sentence.to_string(),
         };
 
-        let response = self.llm_client.simplify(request).await?;
+        let started = Instant::now();
+        self.mark_in_flight(sentence);
+        let result = self.llm_client.simplify(request).await;
+        self.clear_in_flight(sentence);
+        let context = format!("provider={} model={}", self.llm_client.provider_name(), self.llm_client.model_name());
+        log_performance_metric(
+            "simplify",
+            "reading_orchestrator",
+            started.elapsed().as_millis() as u64,
+            result.is_ok(),
+            Some(&context),
+        );
+        if let (Some(_), Err(error)) = (self.failure_ttl, &result) {
+            self.failures.lock().unwrap().insert(sentence.to_string(), (Instant::now(), error.clone()));
+        }
+        let response = result?;
 
         // Cache the response
         cache.cache_simplified(sentence.to_string(), response.clone());
@@ -44,11 +161,21 @@ impl ReadingOrchestrator {
         Ok(response)
     }
 
+    /// Get the meaning of a word in the given context
+    pub async fn get_word_meaning(&self, word: &str, context: &str) -> Result<String, AppError> {
+        self.llm_client.get_word_meaning(word, context).await
+    }
+
+    /// Turn a word into a focused image search query
+    pub async fn optimize_image_query(&self, request: ImageQueryOptimizationRequest) -> Result<ImageQueryOptimizationResponse, AppError> {
+        self.llm_client.optimize_image_query(request).await
+    }
+
     /// Process multiple sentences in batch
     pub async fn process_sentences_batch(
         &self,
         sentences: &[String],
-        cache: &mut CacheEngine,
+        cache: &mut dyn Cache,
     ) -> Result<Vec<SimplificationResponse>, AppError> {
         let mut results = Vec::new();
         
@@ -65,7 +192,7 @@ impl ReadingOrchestrator {
         &self,
         current_position: usize,
         sentences: &[String],
-        cache: &mut CacheEngine,
+        cache: &mut dyn Cache,
         lookahead: usize,
     ) -> Result<(), AppError> {
         let start = current_position + 1;
@@ -83,25 +210,97 @@ impl ReadingOrchestrator {
         Ok(())
     }
 
+    /// Simplify up to `max_sentences` sentences ahead of time, skipping cached ones
+    ///
+    /// Sentences are simplified one at a time, each holding a permit from
+    /// `permits` so warming shares its concurrency cap with other background
+    /// work. Failures are counted rather than aborting the warm-up.
+    pub async fn warm_cache(
+        &self,
+        sentences: &[String],
+        cache: &mut dyn Cache,
+        max_sentences: usize,
+        permits: &Semaphore,
+    ) -> WarmResult {
+        let mut result = WarmResult::default();
+
+        for sentence in sentences.iter().take(max_sentences) {
+            if cache.has_simplified(sentence) {
+                result.skipped_cached += 1;
+                continue;
+            }
+
+            let _permit = permits.acquire().await.ok();
+            match self.process_sentence(sentence, cache).await {
+                Ok(_) => result.simplified += 1,
+                Err(_) => result.failed += 1,
+            }
+        }
+
+        result
+    }
+
     /// Check if a sentence needs processing
-    pub fn needs_processing(&self, sentence: &str, cache: &CacheEngine) -> bool {
+    pub fn needs_processing(&self, sentence: &str, cache: &dyn Cache) -> bool {
         !cache.has_simplified(sentence)
     }
 
+    /// Name of the LLM provider in use
+    pub fn provider_name(&self) -> &str {
+        self.llm_client.provider_name()
+    }
+
+    /// Name of the LLM model in use
+    pub fn model_name(&self) -> String {
+        self.llm_client.model_name()
+    }
+
+    /// Request statistics of the LLM client's http client
+    pub fn request_stats(&self) -> Option<RequestStats> {
+        self.llm_client.request_stats()
+    }
+
+    /// Circuit breaker state of the LLM client's http client
+    pub async fn circuit_state(&self) -> Option<CircuitState> {
+        self.llm_client.circuit_state().await
+    }
+
+    /// Close the LLM client's circuit breaker
+    pub async fn reset_circuit(&self) {
+        self.llm_client.reset_circuit().await;
+    }
+
+    /// Change the LLM client's rate limit without recreating it
+    pub async fn set_rate_limit(&self, permits: usize, window: std::time::Duration) -> Result<(), AppError> {
+        self.llm_client.set_rate_limit(permits, window).await
+    }
+
     /// Get processing statistics
-    pub fn get_cache_stats(&self, cache: &CacheEngine) -> CacheStats {
+    pub fn get_cache_stats(&self, cache: &dyn Cache) -> CacheStats {
         CacheStats {
             simplified_entries: cache.simplified_cache_size(),
             image_entries: cache.image_cache_size(),
+            image_results: cache.image_result_count(),
             word_meaning_entries: cache.word_meaning_cache_size(),
         }
     }
 }
 
+/// Outcome of a cache warm-up run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WarmResult {
+    pub simplified: usize,
+    pub skipped_cached: usize,
+    pub failed: usize,
+}
+
 /// Statistics about cache usage
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CacheStats {
     pub simplified_entries: usize,
     pub image_entries: usize,
+    /// Images across all `image_entries`
+    pub image_results: usize,
     pub word_meaning_entries: usize,
 }
 
@@ -0,0 +1,20 @@
+use crate::cache_trait::CacheSnapshot;
+use serde::{Deserialize, Serialize};
+
+/// A saved reading session, produced by `ReadingEngine::export_state`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionState {
+    /// Hash of the loaded sentences; `import_state` refuses a session for different text
+    pub text_hash: String,
+    pub position: usize,
+    /// Sorted alphabetically
+    pub manual_words: Vec<String>,
+    /// Simplifications and word meanings, when exported with caches
+    #[serde(default)]
+    pub caches: Option<CacheSnapshot>,
+}
+
+/// Hash identifying a text by its split sentences, stable across runs and platforms
+pub(crate) fn text_hash(sentences: &[String]) -> String {
+    glossia_shared::text_hash(&sentences.join(" "))
+}
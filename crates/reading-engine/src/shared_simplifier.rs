@@ -0,0 +1,190 @@
+use glossia_shared::{AppError, SimplificationResponse};
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// Sentences kept by a `SharedSimplifier` unless configured otherwise
+pub const DEFAULT_SHARED_SIMPLIFIER_CAPACITY: usize = 500;
+
+static GLOBAL: Lazy<SharedSimplifier> = Lazy::new(SharedSimplifier::new);
+
+type Entry = Arc<OnceCell<SimplificationResponse>>;
+
+#[derive(Default)]
+struct Entries {
+    cells: HashMap<String, Entry>,
+    /// Keys oldest first, for evicting once `capacity` is exceeded
+    order: VecDeque<String>,
+}
+
+impl Entries {
+    fn remove(&mut self, key: &str) {
+        self.cells.remove(key);
+        self.order.retain(|existing| existing != key);
+    }
+}
+
+/// Concurrency-safe simplification cache shared across callers
+///
+/// Concurrent requests for the same sentence are single-flighted: the first
+/// caller fetches while the others wait for its result. Failures are not
+/// cached, so the next caller retries. Once more than `capacity` sentences are
+/// held, the oldest are evicted.
+pub struct SharedSimplifier {
+    entries: Mutex<Entries>,
+    capacity: usize,
+}
+
+impl SharedSimplifier {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_SHARED_SIMPLIFIER_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(Entries::default()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Process-wide instance used by `ReadingEngine::simplify_sentence_shared`
+    pub fn global() -> &'static SharedSimplifier {
+        &GLOBAL
+    }
+
+    /// Cached simplification for `sentence`, calling `fetch` only if no result
+    /// exists and no identical request is already in flight
+    pub async fn simplify_with<F, Fut>(&self, sentence: &str, fetch: F) -> Result<SimplificationResponse, AppError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<SimplificationResponse, AppError>>,
+    {
+        let key = sentence.split_whitespace().collect::<Vec<_>>().join(" ");
+        let cell = {
+            let mut entries = self.entries.lock().unwrap();
+            match entries.cells.get(&key) {
+                Some(cell) => cell.clone(),
+                None => {
+                    let cell = Entry::default();
+                    entries.cells.insert(key.clone(), cell.clone());
+                    entries.order.push_back(key.clone());
+                    while entries.order.len() > self.capacity {
+                        let Some(oldest) = entries.order.pop_front() else { break };
+                        entries.cells.remove(&oldest);
+                    }
+                    cell
+                }
+            }
+        };
+
+        let result = cell.get_or_try_init(fetch).await.cloned();
+        if result.is_err() {
+            let mut entries = self.entries.lock().unwrap();
+            if entries.cells.get(&key).is_some_and(|current| Arc::ptr_eq(current, &cell)) {
+                entries.remove(&key);
+            }
+        }
+        result
+    }
+
+    pub fn get(&self, sentence: &str) -> Option<SimplificationResponse> {
+        let key = sentence.split_whitespace().collect::<Vec<_>>().join(" ");
+        let entries = self.entries.lock().unwrap();
+        entries.cells.get(&key).and_then(|cell| cell.get().cloned())
+    }
+
+    /// Number of sentences with a cached result
+    pub fn len(&self) -> usize {
+        let entries = self.entries.lock().unwrap();
+        entries.cells.values().filter(|cell| cell.initialized()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        *self.entries.lock().unwrap() = Entries::default();
+    }
+}
+
+impl Default for SharedSimplifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glossia_llm_client::{LLMClient, MockLLMClient, SimplificationRequest};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn counted_simplify(calls: &AtomicUsize, sentence: &str) -> Result<SimplificationResponse, AppError> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        MockLLMClient::new()
+            .with_delay(50)
+            .simplify(SimplificationRequest { sentence: sentence.to_string() })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_identical_requests_share_one_call() {
+        let simplifier = SharedSimplifier::new();
+        let calls = AtomicUsize::new(0);
+        let sentence = "The cat sat on the mat.";
+
+        let (first, second) = tokio::join!(
+            simplifier.simplify_with(sentence, || counted_simplify(&calls, sentence)),
+            simplifier.simplify_with(sentence, || counted_simplify(&calls, sentence)),
+        );
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first.unwrap(), second.unwrap());
+        assert_eq!(simplifier.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cached_result_is_reused() {
+        let simplifier = SharedSimplifier::new();
+        let calls = AtomicUsize::new(0);
+
+        simplifier.simplify_with("Hello world.", || counted_simplify(&calls, "Hello world.")).await.unwrap();
+        simplifier.simplify_with(" Hello  world. ", || counted_simplify(&calls, "Hello world.")).await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(simplifier.get("Hello world.").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_not_cached() {
+        let simplifier = SharedSimplifier::new();
+
+        let failed = simplifier
+            .simplify_with("Hello world.", || async { Err(AppError::api_error("offline")) })
+            .await;
+        assert!(failed.is_err());
+        assert!(simplifier.is_empty());
+        assert!(simplifier.entries.lock().unwrap().cells.is_empty());
+
+        let calls = AtomicUsize::new(0);
+        simplifier.simplify_with("Hello world.", || counted_simplify(&calls, "Hello world.")).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_oldest_sentences_are_evicted_past_capacity() {
+        let simplifier = SharedSimplifier::with_capacity(2);
+        let calls = AtomicUsize::new(0);
+
+        for sentence in ["One.", "Two.", "Three."] {
+            simplifier.simplify_with(sentence, || counted_simplify(&calls, sentence)).await.unwrap();
+        }
+
+        assert_eq!(simplifier.len(), 2);
+        assert!(simplifier.get("One.").is_none());
+        assert!(simplifier.get("Three.").is_some());
+    }
+}
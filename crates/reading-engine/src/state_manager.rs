@@ -1,3 +1,5 @@
+use serde::Serialize;
+
 /// Manages transient state for the reading engine
 pub struct StateManager {
     is_processing: bool,
@@ -104,6 +106,7 @@ impl Default for StateManager {
 }
 
 /// Session statistics for reporting
+#[derive(Debug, Clone, Serialize)]
 pub struct SessionStats {
     pub duration: std::time::Duration,
     pub sentences_read: usize,
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::task::{AbortHandle, JoinHandle};
+
+/// Spawned tasks that are still running, so they can all be cancelled at once
+///
+/// Clones share the same set of tasks. Finished tasks remove themselves.
+#[derive(Debug, Clone, Default)]
+pub struct TaskRegistry {
+    next_id: Arc<AtomicU64>,
+    tasks: Arc<Mutex<HashMap<u64, (String, AbortHandle)>>>,
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn `future` on the tokio runtime and track it under `label` until it finishes
+    pub fn spawn<F>(&self, label: impl Into<String>, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let tasks = self.tasks.clone();
+
+        // Hold the lock while spawning so a task that finishes immediately
+        // can't try to remove itself before it has been registered
+        let mut registered = self.tasks.lock().unwrap();
+        let handle = tokio::spawn(async move {
+            let output = future.await;
+            tasks.lock().unwrap().remove(&id);
+            output
+        });
+        registered.insert(id, (label.into(), handle.abort_handle()));
+        handle
+    }
+
+    /// Labels of the tasks still running, sorted
+    pub fn in_flight(&self) -> Vec<String> {
+        let mut labels: Vec<String> = self.tasks.lock().unwrap().values().map(|(label, _)| label.clone()).collect();
+        labels.sort();
+        labels
+    }
+
+    pub fn len(&self) -> usize {
+        self.tasks.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Cancel every tracked task, returning how many were cancelled
+    pub fn abort_all(&self) -> usize {
+        let tasks: Vec<_> = self.tasks.lock().unwrap().drain().collect();
+        for (_, (_, handle)) in &tasks {
+            handle.abort();
+        }
+        tasks.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_finished_tasks_unregister_themselves() {
+        let registry = TaskRegistry::new();
+        let handle = registry.spawn("quick", async { 7 });
+
+        assert_eq!(handle.await.unwrap(), 7);
+        assert!(registry.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_abort_all_cancels_running_tasks() {
+        let registry = TaskRegistry::new();
+        let first = registry.spawn("simplify", tokio::time::sleep(Duration::from_secs(10)));
+        let second = registry.spawn("images", tokio::time::sleep(Duration::from_secs(10)));
+        assert_eq!(registry.in_flight(), vec!["images", "simplify"]);
+
+        assert_eq!(registry.abort_all(), 2);
+        assert!(first.await.unwrap_err().is_cancelled());
+        assert!(second.await.unwrap_err().is_cancelled());
+        assert!(registry.is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+use glossia_shared::AppError;
+use std::io::Read;
+use std::path::Path;
+use tracing::warn;
+
+/// Largest text file `ReadingEngine::load_from_file` will open
+pub const MAX_TEXT_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Read a text file of at most `max_bytes`, replacing invalid UTF-8 with U+FFFD
+pub(crate) fn read_text_file(path: &Path, max_bytes: u64) -> Result<String, AppError> {
+    let file = std::fs::File::open(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => AppError::config_error(format!("File not found: {}", path.display())),
+        _ => AppError::config_error(format!("Failed to open {}: {e}", path.display())),
+    })?;
+
+    // Read one byte past the limit to detect oversize files without loading them whole
+    let mut bytes = Vec::new();
+    file.take(max_bytes + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| AppError::config_error(format!("Failed to read {}: {e}", path.display())))?;
+    if bytes.len() as u64 > max_bytes {
+        return Err(AppError::config_error(format!(
+            "{} is larger than the {} byte limit",
+            path.display(),
+            max_bytes
+        )));
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok(text),
+        Err(e) => {
+            warn!("{} is not valid UTF-8, invalid bytes were replaced", path.display());
+            Ok(String::from_utf8_lossy(e.as_bytes()).into_owned())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oversize_file_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("big.txt");
+        std::fs::write(&path, "x".repeat(101)).unwrap();
+
+        let error = read_text_file(&path, 100).unwrap_err();
+        assert!(error.to_string().contains("100 byte limit"));
+        assert_eq!(read_text_file(&path, 101).unwrap().len(), 101);
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_decoded_lossily() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("latin1.txt");
+        std::fs::write(&path, b"Caf\xe9 au lait.").unwrap();
+
+        assert_eq!(read_text_file(&path, MAX_TEXT_FILE_BYTES).unwrap(), "Caf\u{FFFD} au lait.");
+    }
+}
@@ -19,6 +19,9 @@ pub enum AppError {
         retry_after: Option<u64>,
     },
 
+    #[error("API quota exceeded: {message}")]
+    QuotaExceededError { message: String },
+
     #[error("Invalid API request: {message}")]
     BadRequestError { 
         message: String,
@@ -40,9 +43,15 @@ pub enum AppError {
     #[error("Network request failed: {message}")]
     NetworkError { message: String },
 
+    #[error("Request timed out: {message}")]
+    TimeoutError { message: String },
+
     #[error("API response content is missing or invalid")]
     InvalidResponseContent,
 
+    #[error("The model declined to answer: {message}")]
+    ContentRefused { message: String },
+
     #[error("Book is empty or could not be loaded")]
     EmptyBook,
 
@@ -59,7 +68,11 @@ impl From<serde_json::Error> for AppError {
 
 impl From<reqwest::Error> for AppError {
     fn from(e: reqwest::Error) -> Self {
-        Self::NetworkError { message: e.to_string() }
+        if e.is_timeout() {
+            Self::TimeoutError { message: e.to_string() }
+        } else {
+            Self::NetworkError { message: e.to_string() }
+        }
     }
 }
 
@@ -90,6 +103,10 @@ impl AppError {
         }
     }
 
+    pub fn quota_exceeded_error(message: impl Into<String>) -> Self {
+        Self::QuotaExceededError { message: message.into() }
+    }
+
     pub fn bad_request_error(
         message: impl Into<String>,
         error_type: Option<String>,
@@ -135,12 +152,23 @@ impl AppError {
         matches!(self, Self::HttpError { status, .. } if *status == 401 || *status == 403)
     }
 
+    /// Check if the request gave up waiting for a response
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::TimeoutError { .. })
+    }
+
+    /// Check if the model declined the request rather than failing to answer
+    pub fn is_refusal(&self) -> bool {
+        matches!(self, Self::ContentRefused { .. })
+    }
+
     /// Check if this error is retryable
     pub fn is_retryable(&self) -> bool {
         match self {
             Self::RateLimitError { .. } => true,
             Self::HttpError { status, .. } => matches!(*status, 429 | 500..=599),
             Self::NetworkError { .. } => true,
+            Self::TimeoutError { .. } => true,
             _ => false,
         }
     }
@@ -165,6 +193,10 @@ impl AppError {
                 format!("{} {}", base_msg, advice)
             }
 
+            Self::QuotaExceededError { .. } => {
+                "Your API quota has been exceeded. Please check your plan and billing details at https://platform.openai.com/account/billing before trying again.".to_string()
+            }
+
             Self::BadRequestError { message, .. } => {
                 if message.contains("model") || message.contains("Model") {
                     format!("Invalid model configuration: {}. Please check that the model name is correct and available to your API key.", message)
@@ -187,6 +219,10 @@ impl AppError {
                 format!("Network connection failed: {}. Please check your internet connection.", message)
             }
 
+            Self::TimeoutError { .. } => {
+                "The service took too long to respond. Please check your connection and try again.".to_string()
+            }
+
             Self::HttpError { status, message, .. } => {
                 match *status {
                     401 => "Authentication failed. Please check your API key.".to_string(),
@@ -202,6 +238,10 @@ impl AppError {
                 format!("Configuration error: {}. Please check your environment variables and settings.", message)
             }
 
+            Self::ContentRefused { .. } => {
+                "The AI model declined to process this text. Try another sentence or rephrase it.".to_string()
+            }
+
             _ => self.to_string(),
         }
     }
@@ -212,16 +252,37 @@ impl AppError {
             Self::AuthenticationError { .. } => "authentication",
             Self::BadRequestError { .. } => "bad_request",
             Self::RateLimitError { .. } => "rate_limit",
+            Self::QuotaExceededError { .. } => "quota",
             Self::NetworkError { .. } => "network",
+            Self::TimeoutError { .. } => "timeout",
             Self::HttpError { .. } => "http",
             Self::ParseError { .. } => "parse",
             Self::ConfigError { .. } => "config",
             Self::ApiError { .. } => "api",
             Self::InvalidResponseContent => "invalid_response",
+            Self::ContentRefused { .. } => "refusal",
             Self::EmptyBook => "empty_book",
         }
     }
 
+    /// HTTP status a server wrapping the engine should answer with for this error
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Self::AuthenticationError { status_code: Some(403), .. } => 403,
+            Self::AuthenticationError { .. } => 401,
+            Self::RateLimitError { .. } | Self::QuotaExceededError { .. } => 429,
+            Self::BadRequestError { .. } | Self::EmptyBook => 400,
+            Self::NetworkError { .. } => 502,
+            Self::TimeoutError { .. } => 504,
+            Self::HttpError { status: 401, .. } => 401,
+            Self::HttpError { status: 403, .. } => 403,
+            Self::HttpError { status: 429, .. } => 429,
+            // Any other upstream failure is a bad gateway, not our own error
+            Self::HttpError { status: 400..=599, .. } => 502,
+            _ => 500,
+        }
+    }
+
     /// Check if error suggests immediate retry (vs backoff)
     pub fn should_retry_immediately(&self) -> bool {
         matches!(self, Self::NetworkError { .. })
@@ -233,8 +294,42 @@ impl AppError {
             Self::RateLimitError { retry_after, .. } => *retry_after,
             Self::HttpError { status, .. } if *status == 429 => Some(60),
             Self::HttpError { status, .. } if matches!(*status, 500..=599) => Some(5),
-            Self::NetworkError { .. } => Some(1),
+            Self::NetworkError { .. } | Self::TimeoutError { .. } => Some(1),
             _ => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_status_per_variant() {
+        let cases = [
+            (AppError::authentication_error("bad key", None, None, None), 401),
+            (AppError::authentication_error("no access", Some(403), None, None), 403),
+            (AppError::rate_limit_error("slow down", Some(5)), 429),
+            (AppError::quota_exceeded_error("out of credit"), 429),
+            (AppError::bad_request_error("bad field", None, None), 400),
+            (AppError::EmptyBook, 400),
+            (AppError::NetworkError { message: "refused".to_string() }, 502),
+            (AppError::TimeoutError { message: "too slow".to_string() }, 504),
+            (AppError::http_error(401, "unauthorized"), 401),
+            (AppError::http_error(403, "forbidden"), 403),
+            (AppError::http_error(429, "busy"), 429),
+            (AppError::http_error(503, "unavailable"), 502),
+            (AppError::http_error(404, "missing"), 502),
+            (AppError::http_error(418, "teapot"), 502),
+            (AppError::ParseError { message: "eof".to_string() }, 500),
+            (AppError::api_error("failed"), 500),
+            (AppError::InvalidResponseContent, 500),
+            (AppError::ContentRefused { message: "no".to_string() }, 500),
+            (AppError::config_error("missing key"), 500),
+        ];
+
+        for (error, status) in cases {
+            assert_eq!(error.http_status(), status, "{error:?}");
+        }
+    }
+}
@@ -0,0 +1,50 @@
+/// Strip a surrounding markdown code fence, with or without a language tag, from model output
+/// Text without a fence is returned trimmed
+pub fn strip_code_fences(text: &str) -> &str {
+    let trimmed = text.trim();
+
+    let Some(after_open) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+
+    // Drop the optional language tag on the opening fence line
+    let body = match after_open.find('\n') {
+        Some(newline) => &after_open[newline + 1..],
+        None => after_open,
+    };
+
+    body.trim_end()
+        .strip_suffix("```")
+        .unwrap_or(body)
+        .trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const JSON: &str = r#"{"optimized_query": "lighthouse coastal tower"}"#;
+
+    fn parse(text: &str) -> Value {
+        serde_json::from_str(strip_code_fences(text)).unwrap()
+    }
+
+    #[test]
+    fn test_fenced_json_parses() {
+        let fenced = format!("```\n{JSON}\n```");
+        assert_eq!(parse(&fenced)["optimized_query"], "lighthouse coastal tower");
+    }
+
+    #[test]
+    fn test_fenced_json_with_language_tag_parses() {
+        let fenced = format!("  ```json\n{JSON}\n```\n");
+        assert_eq!(parse(&fenced)["optimized_query"], "lighthouse coastal tower");
+    }
+
+    #[test]
+    fn test_bare_json_is_unchanged() {
+        assert_eq!(strip_code_fences(JSON), JSON);
+        assert_eq!(parse(&format!("\n{JSON}  "))["optimized_query"], "lighthouse coastal tower");
+    }
+}
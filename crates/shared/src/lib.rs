@@ -1,5 +1,11 @@
 pub mod error;
 pub mod types;
+pub mod json_utils;
+pub mod text_diff;
+pub mod text_hash;
 
 pub use error::AppError;
-pub use types::{SimplificationRequest, SimplificationResponse, WordMeaning, ImageResult, ImageSearchRequest, ImageQueryOptimizationRequest, ImageQueryOptimizationResponse};
\ No newline at end of file
+pub use json_utils::strip_code_fences;
+pub use text_diff::{diff_words, DiffOp};
+pub use text_hash::text_hash;
+pub use types::{SimplificationRequest, SimplificationResponse, SimplificationResponseBuilder, WordMeaning, WordClassification, classify_words, ImageResult, ImageSearchRequest, ImageQueryOptimizationRequest, ImageQueryOptimizationResponse};
\ No newline at end of file
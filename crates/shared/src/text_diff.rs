@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+/// One step in a word-level diff between an original and a simplified sentence
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffOp {
+    /// Word present in both texts
+    Keep(String),
+    /// Word only in the simplified text
+    Insert(String),
+    /// Word only in the original text
+    Delete(String),
+}
+
+/// Word-level diff of two texts using a longest common subsequence over whitespace tokens
+/// For a substitution the deleted word comes before the inserted one
+pub fn diff_words(original: &str, simplified: &str) -> Vec<DiffOp> {
+    let old: Vec<&str> = original.split_whitespace().collect();
+    let new: Vec<&str> = simplified.split_whitespace().collect();
+
+    // lcs[i][j] = LCS length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            ops.push(DiffOp::Keep(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(DiffOp::Delete(old[i].to_string()));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Insert(new[j].to_string()));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|word| DiffOp::Delete(word.to_string())));
+    ops.extend(new[j..].iter().map(|word| DiffOp::Insert(word.to_string())));
+
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keep(word: &str) -> DiffOp {
+        DiffOp::Keep(word.to_string())
+    }
+
+    #[test]
+    fn test_identical_text_is_all_keep() {
+        let ops = diff_words("The cat sat down.", "The  cat sat\ndown.");
+        assert_eq!(ops, vec![keep("The"), keep("cat"), keep("sat"), keep("down.")]);
+    }
+
+    #[test]
+    fn test_substituted_word() {
+        let ops = diff_words("The feline sat.", "The cat sat.");
+        assert_eq!(ops, vec![
+            keep("The"),
+            DiffOp::Delete("feline".to_string()),
+            DiffOp::Insert("cat".to_string()),
+            keep("sat."),
+        ]);
+    }
+
+    #[test]
+    fn test_inserted_clause() {
+        let ops = diff_words("The dog barked loudly.", "The dog that was old barked loudly.");
+        assert_eq!(ops, vec![
+            keep("The"),
+            keep("dog"),
+            DiffOp::Insert("that".to_string()),
+            DiffOp::Insert("was".to_string()),
+            DiffOp::Insert("old".to_string()),
+            keep("barked"),
+            keep("loudly."),
+        ]);
+    }
+
+    #[test]
+    fn test_empty_inputs() {
+        assert!(diff_words("", "").is_empty());
+        assert_eq!(diff_words("", "hi"), vec![DiffOp::Insert("hi".to_string())]);
+        assert_eq!(diff_words("hi", " "), vec![DiffOp::Delete("hi".to_string())]);
+    }
+}
@@ -0,0 +1,48 @@
+/// FNV-1a 64-bit parameters; fixed by the algorithm, so hashes never change between builds
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Stable hash identifying a text regardless of surrounding or repeated whitespace
+///
+/// Whitespace runs (spaces, tabs, line endings of any platform) count as one space,
+/// so the same logical text hashes equally wherever it was pasted or saved. Unlike
+/// `DefaultHasher`, the result is the same across platforms and Rust versions.
+pub fn text_hash(text: &str) -> String {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut feed = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    };
+
+    for (i, word) in text.split_whitespace().enumerate() {
+        if i > 0 {
+            feed(b" ");
+        }
+        feed(word.as_bytes());
+    }
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whitespace_variants_hash_equally() {
+        let hash = text_hash("The cat sat. The dog ran.");
+        assert_eq!(text_hash("  The cat sat.\r\n\r\nThe  dog\tran.\n"), hash);
+        assert_eq!(text_hash("The cat sat.\nThe dog ran."), hash);
+        assert_eq!(hash.len(), 16);
+    }
+
+    #[test]
+    fn test_different_text_hashes_differently() {
+        assert_ne!(text_hash("The cat sat."), text_hash("The cat sat!"));
+        assert_ne!(text_hash("The cat sat."), text_hash("The Cat sat."));
+        assert_ne!(text_hash("ab c"), text_hash("a bc"));
+        // Known FNV-1a value, so the hash can't silently change
+        assert_eq!(text_hash(""), "cbf29ce484222325");
+    }
+}
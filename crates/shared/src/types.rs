@@ -11,6 +11,97 @@ pub struct SimplificationResponse {
     pub original: String,
     pub simplified: String,
     pub words: Vec<WordMeaning>,
+    /// Short grammar observations ("passive voice"), only requested when grammar notes are enabled
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grammar_notes: Vec<String>,
+    /// The simplification matches the original apart from case, spacing and quote
+    /// style, so there is nothing new to show
+    #[serde(default)]
+    pub was_already_simple: bool,
+}
+
+impl SimplificationResponse {
+    /// Start a validating builder for the simplification of `original`
+    pub fn builder(original: impl Into<String>) -> SimplificationResponseBuilder {
+        SimplificationResponseBuilder {
+            original: original.into(),
+            simplified: String::new(),
+            words: Vec::new(),
+            grammar_notes: Vec::new(),
+        }
+    }
+}
+
+/// Builds a `SimplificationResponse` from possibly messy provider output
+///
+/// An empty `simplified` falls back to the original sentence, and words are
+/// deduplicated by normalized spelling, keeping the first meaning given.
+#[derive(Debug, Clone)]
+pub struct SimplificationResponseBuilder {
+    original: String,
+    simplified: String,
+    words: Vec<WordMeaning>,
+    grammar_notes: Vec<String>,
+}
+
+impl SimplificationResponseBuilder {
+    pub fn simplified(mut self, simplified: impl Into<String>) -> Self {
+        self.simplified = simplified.into();
+        self
+    }
+
+    pub fn word(mut self, word: WordMeaning) -> Self {
+        self.words.push(word);
+        self
+    }
+
+    pub fn words(mut self, words: impl IntoIterator<Item = WordMeaning>) -> Self {
+        self.words.extend(words);
+        self
+    }
+
+    /// Blank notes are dropped
+    pub fn grammar_notes(mut self, notes: impl IntoIterator<Item = String>) -> Self {
+        self.grammar_notes.extend(
+            notes.into_iter().map(|note| note.trim().to_string()).filter(|note| !note.is_empty()),
+        );
+        self
+    }
+
+    pub fn build(self) -> SimplificationResponse {
+        let simplified = match self.simplified.trim() {
+            "" => self.original.clone(),
+            trimmed => trimmed.to_string(),
+        };
+
+        let mut seen = HashSet::new();
+        let words = self
+            .words
+            .into_iter()
+            .filter(|meaning| {
+                let key = meaning.word.trim().to_lowercase();
+                !key.is_empty() && seen.insert(key)
+            })
+            .collect();
+
+        SimplificationResponse {
+            was_already_simple: normalize_for_comparison(&simplified) == normalize_for_comparison(&self.original),
+            original: self.original,
+            simplified,
+            words,
+            grammar_notes: self.grammar_notes,
+        }
+    }
+}
+
+/// Lowercased text with collapsed whitespace and straight quotes
+fn normalize_for_comparison(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(['\u{2018}', '\u{2019}'], "'")
+        .replace(['\u{201C}', '\u{201D}'], "\"")
+        .to_lowercase()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -60,6 +151,29 @@ impl WordMeaning {
     }
 }
 
+/// Counts of single words and phrases among a sentence's meanings
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WordClassification {
+    pub single_count: usize,
+    pub phrase_count: usize,
+    /// Phrase spellings in the order they were given
+    pub phrases: Vec<String>,
+}
+
+/// Split meanings into single words and phrases, e.g. for a "3 phrases, 5 words" header
+pub fn classify_words(words: &[WordMeaning]) -> WordClassification {
+    let mut classification = WordClassification::default();
+    for meaning in words {
+        if meaning.is_phrase {
+            classification.phrase_count += 1;
+            classification.phrases.push(meaning.word.clone());
+        } else {
+            classification.single_count += 1;
+        }
+    }
+    classification
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ImageResult {
     pub url: String,
@@ -67,6 +181,28 @@ pub struct ImageResult {
     pub title: String,
     pub width: Option<u32>,
     pub height: Option<u32>,
+    /// URL the gallery should show, chosen by the provider's thumbnail preference;
+    /// `url` stays the full image for click-through
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_url: Option<String>,
+}
+
+impl ImageResult {
+    /// URL to display, falling back to the full image when no preference was applied
+    pub fn primary_url(&self) -> &str {
+        self.display_url.as_deref().unwrap_or(&self.url)
+    }
+
+    /// Show the thumbnail when preferred and present, otherwise the full image
+    pub fn with_display_preference(mut self, prefer_thumbnails: bool) -> Self {
+        let display_url = if prefer_thumbnails && !self.thumbnail_url.trim().is_empty() {
+            self.thumbnail_url.clone()
+        } else {
+            self.url.clone()
+        };
+        self.display_url = Some(display_url);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -153,7 +289,7 @@ impl WordEncounters {
         let mut words: Vec<(String, u32)> = self.encounters.iter()
             .map(|(word, count)| (word.clone(), *count))
             .collect();
-        words.sort_by(|a, b| b.1.cmp(&a.1)); // Sort by count descending
+        words.sort_by_key(|&(_, count)| std::cmp::Reverse(count)); // Sort by count descending
         words
     }
 
@@ -161,3 +297,74 @@ impl WordEncounters {
         self.get_count(word) >= threshold
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_falls_back_to_original_when_simplified_is_empty() {
+        let response = SimplificationResponse::builder("The cat sat.").simplified("   ").build();
+        assert_eq!(response.simplified, "The cat sat.");
+
+        let response = SimplificationResponse::builder("The cat sat.").build();
+        assert_eq!(response.simplified, "The cat sat.");
+
+        let response = SimplificationResponse::builder("The cat sat.").simplified(" A cat sat. ").build();
+        assert_eq!(response.simplified, "A cat sat.");
+    }
+
+    #[test]
+    fn test_builder_dedups_words_by_normalized_spelling() {
+        let response = SimplificationResponse::builder("Ephemeral things.")
+            .word(WordMeaning::new_word("Ephemeral".to_string(), "short-lived".to_string()))
+            .words(vec![
+                WordMeaning::new_word(" ephemeral ".to_string(), "brief".to_string()),
+                WordMeaning::new_word("things".to_string(), "objects".to_string()),
+                WordMeaning::new_word("  ".to_string(), "nothing".to_string()),
+            ])
+            .build();
+
+        let words: Vec<&str> = response.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, vec!["Ephemeral", "things"]);
+        assert_eq!(response.words[0].meaning, "short-lived");
+    }
+
+    #[test]
+    fn test_grammar_notes_are_optional_in_json() {
+        let old: SimplificationResponse =
+            serde_json::from_str(r#"{"original": "a", "simplified": "b", "words": []}"#).unwrap();
+        assert!(old.grammar_notes.is_empty());
+        assert!(!serde_json::to_string(&old).unwrap().contains("grammar_notes"));
+    }
+
+    #[test]
+    fn test_unchanged_simplification_is_flagged() {
+        let response = SimplificationResponse::builder("The cat\u{2019}s  bowl is empty.")
+            .simplified("the cat's bowl is empty.")
+            .build();
+        assert!(response.was_already_simple);
+
+        let response = SimplificationResponse::builder("The feline's receptacle was vacant.")
+            .simplified("The cat's bowl was empty.")
+            .build();
+        assert!(!response.was_already_simple);
+    }
+
+    #[test]
+    fn test_classify_words_counts_phrases_and_words() {
+        let words = vec![
+            WordMeaning::new_word("cat".to_string(), "a small pet".to_string()),
+            WordMeaning::new_phrase("give up".to_string(), "to stop trying".to_string()),
+            WordMeaning::new_word("mat".to_string(), "a floor covering".to_string()),
+            WordMeaning::new_phrase("in spite of".to_string(), "despite".to_string()),
+            WordMeaning::new_with_timestamp("sat".to_string(), "past of sit".to_string(), 1),
+        ];
+
+        let classification = classify_words(&words);
+        assert_eq!(classification.single_count, 3);
+        assert_eq!(classification.phrase_count, 2);
+        assert_eq!(classification.phrases, vec!["give up", "in spite of"]);
+        assert_eq!(classify_words(&[]), WordClassification::default());
+    }
+}
@@ -0,0 +1,80 @@
+/// Dominant writing direction of a text, for the UI's `dir` attribute
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Direction {
+    /// Value for an HTML `dir` attribute
+    pub fn as_html_dir(&self) -> &'static str {
+        match self {
+            Direction::LeftToRight => "ltr",
+            Direction::RightToLeft => "rtl",
+        }
+    }
+
+    pub fn is_rtl(&self) -> bool {
+        *self == Direction::RightToLeft
+    }
+}
+
+/// Direction of the majority of letters; texts with no letters are left-to-right
+pub fn detect_text_direction(text: &str) -> Direction {
+    let (rtl, ltr) = text
+        .chars()
+        .filter(|c| c.is_alphabetic() && !is_combining_mark(*c))
+        .fold((0usize, 0usize), |(rtl, ltr), c| if is_rtl_char(c) { (rtl + 1, ltr) } else { (rtl, ltr + 1) });
+
+    if rtl > ltr {
+        Direction::RightToLeft
+    } else {
+        Direction::LeftToRight
+    }
+}
+
+/// Letters of right-to-left scripts: Hebrew, Arabic, Syriac, Thaana, NKo and their presentation forms
+pub(crate) fn is_rtl_char(c: char) -> bool {
+    matches!(c,
+        '\u{0590}'..='\u{08FF}'
+        | '\u{FB1D}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}'
+    )
+}
+
+/// Vowel points, cantillation and other marks that attach to the preceding letter,
+/// plus the zero-width (non-)joiners used inside Persian and Arabic words
+pub(crate) fn is_combining_mark(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'
+        | '\u{0591}'..='\u{05BD}'
+        | '\u{05BF}'
+        | '\u{05C1}'..='\u{05C2}'
+        | '\u{05C4}'..='\u{05C5}'
+        | '\u{05C7}'
+        | '\u{0610}'..='\u{061A}'
+        | '\u{064B}'..='\u{065F}'
+        | '\u{0670}'
+        | '\u{06D6}'..='\u{06DC}'
+        | '\u{06DF}'..='\u{06E4}'
+        | '\u{06E7}'..='\u{06E8}'
+        | '\u{06EA}'..='\u{06ED}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{FB1E}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_rtl_scripts() {
+        assert_eq!(detect_text_direction("שָׁלוֹם עוֹלָם"), Direction::RightToLeft);
+        assert_eq!(detect_text_direction("مرحبا بالعالم"), Direction::RightToLeft);
+        assert_eq!(detect_text_direction("He said שלום."), Direction::LeftToRight);
+        assert_eq!(detect_text_direction("123 !?"), Direction::LeftToRight);
+        assert_eq!(Direction::RightToLeft.as_html_dir(), "rtl");
+    }
+}
@@ -0,0 +1,63 @@
+use crate::extract_words;
+
+/// Texts with fewer letters than this are too short to judge and count as English
+const MIN_LETTERS: usize = 20;
+/// Share of letters that must be ASCII for a text to look English
+const MIN_ASCII_RATIO: f64 = 0.9;
+/// Texts with fewer words than this skip the stopword check
+const MIN_WORDS_FOR_STOPWORDS: usize = 5;
+/// Share of words that must be common English function words
+const MIN_STOPWORD_RATIO: f64 = 0.05;
+
+const ENGLISH_STOPWORDS: &[&str] = &[
+    "the", "a", "an", "and", "or", "but", "of", "to", "in", "on", "at", "for", "with", "is", "are",
+    "was", "were", "be", "it", "he", "she", "they", "we", "you", "i", "that", "this", "not", "have", "has",
+];
+
+/// Cheap guess at whether `text` is English, from the share of ASCII letters and
+/// how often common English function words appear. Short or letterless texts
+/// are too ambiguous to judge and count as English, so callers only warn on
+/// texts that clearly look like another language.
+pub fn looks_like_english(text: &str) -> bool {
+    let (letters, ascii_letters) = text
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .fold((0usize, 0usize), |(all, ascii), c| (all + 1, ascii + usize::from(c.is_ascii())));
+    if letters < MIN_LETTERS {
+        return true;
+    }
+    if (ascii_letters as f64 / letters as f64) < MIN_ASCII_RATIO {
+        return false;
+    }
+
+    let words = extract_words(text);
+    if words.len() < MIN_WORDS_FOR_STOPWORDS {
+        return true;
+    }
+    let stopwords = words.iter().filter(|word| ENGLISH_STOPWORDS.contains(&word.as_str())).count();
+    stopwords as f64 / words.len() as f64 >= MIN_STOPWORD_RATIO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clearly_english_text() {
+        assert!(looks_like_english("The cat sat on the mat and looked out of the window at the rain."));
+    }
+
+    #[test]
+    fn test_non_latin_and_other_latin_languages() {
+        assert!(!looks_like_english("Кошка сидела на коврике и смотрела в окно на дождь."));
+        assert!(!looks_like_english("猫はマットの上に座って、窓の外の雨を見ていました。"));
+        assert!(!looks_like_english("El gato se sentó sobre la alfombra y miró por la ventana."));
+    }
+
+    #[test]
+    fn test_ambiguous_text_is_not_flagged() {
+        assert!(looks_like_english("OK."));
+        assert!(looks_like_english("12:30 — 14:45"));
+        assert!(looks_like_english("Hello Привет"));
+    }
+}
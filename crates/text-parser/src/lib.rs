@@ -1,49 +1,223 @@
 use regex::Regex;
 use once_cell::sync::Lazy;
 
+mod tokenizer;
+mod markup;
+mod direction;
+mod preprocess;
+mod language;
+
+pub use tokenizer::{DefaultTokenizer, Token, Tokenizer};
+pub use markup::{strip_markup, InputFormat};
+pub use direction::{detect_text_direction, Direction};
+pub use language::looks_like_english;
+pub use preprocess::{CollapseWhitespace, Dehyphenate, PreprocessPipeline, StripMarkdown, TextPreprocessor, Trim};
+
 // Compile regex patterns once at startup for better performance
 static SENTENCE_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"([.?!|;])\s+").expect("Invalid sentence splitting regex")
 });
 
-static WORD_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"\b[a-zA-Z']+\b").expect("Invalid word extraction regex")
+// A letter, a hyphen at the end of a line, then a lowercase letter on the next line
+static LINE_BREAK_HYPHEN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(\p{L})[-\x{00AD}][ \t]*\r?\n[ \t]*(\p{Ll})").expect("Invalid line-break hyphen regex")
 });
 
 /// Splits a block of text into a list of sentences.
 /// Sentences are split by '.', '?', '!', and '|'.
 pub fn split_into_sentences(text: &str) -> Vec<String> {
-    if text.is_empty() {
-        return vec![];
-    }
-    let mut sentences = Vec::new();
-    let mut last_end = 0;
-    
-    for mat in SENTENCE_REGEX.find_iter(text) {
-        let sentence = text[last_end..mat.end()].trim();
-        if !sentence.is_empty() {
-            sentences.push(sentence.to_string());
+    SentenceSplitter::new().split(text)
+}
+
+/// Configurable sentence splitter
+///
+/// A sentence ends at '.', '?', '!', '|' or ';' followed by whitespace. Text without
+/// a terminator runs on across single line breaks into the next sentence, and text
+/// left at the end is kept as a final sentence. With `with_line_breaks_end_sentences`,
+/// every line break also ends a sentence, so verse can be read line by line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SentenceSplitter {
+    respect_enclosures: bool,
+    dehyphenate: bool,
+    line_breaks_end_sentences: bool,
+}
+
+impl SentenceSplitter {
+    pub fn new() -> Self {
+        Self { respect_enclosures: false, dehyphenate: false, line_breaks_end_sentences: false }
+    }
+
+    /// End a sentence at every line break, even without terminal punctuation,
+    /// for line-based text like poetry
+    pub fn with_line_breaks_end_sentences(mut self, enabled: bool) -> Self {
+        self.line_breaks_end_sentences = enabled;
+        self
+    }
+
+    /// Rejoin words hyphenated across line breaks (see `dehyphenate`) before splitting
+    pub fn with_dehyphenate(mut self, dehyphenate: bool) -> Self {
+        self.dehyphenate = dehyphenate;
+        self
+    }
+
+    /// Don't end sentences inside balanced quotes or parentheses, so quoted
+    /// dialogue stays in one piece. Ignored when the text is unbalanced.
+    pub fn with_respect_enclosures(mut self, respect: bool) -> Self {
+        self.respect_enclosures = respect;
+        self
+    }
+
+    pub fn split(&self, text: &str) -> Vec<String> {
+        if text.is_empty() {
+            return vec![];
+        }
+        let dehyphenated;
+        let text = if self.dehyphenate {
+            dehyphenated = dehyphenate(text);
+            dehyphenated.as_str()
+        } else {
+            text
+        };
+        if self.line_breaks_end_sentences {
+            return text.lines().flat_map(|line| self.split_line(line)).collect();
+        }
+        self.split_line(text)
+    }
+
+    /// Split on terminal punctuation only, with no special treatment of line breaks
+    fn split_line(&self, text: &str) -> Vec<String> {
+        let mut sentences = Vec::new();
+        let mut last_end = 0;
+
+        let track_enclosures = self.respect_enclosures && Enclosures::is_balanced(text);
+        let mut enclosures = Enclosures::default();
+        let mut scanned = 0;
+
+        for mat in SENTENCE_REGEX.find_iter(text) {
+            if track_enclosures {
+                enclosures.scan(&text[scanned..mat.start()]);
+                scanned = mat.start();
+                if enclosures.is_open() {
+                    continue;
+                }
+            }
+
+            let sentence = trim_sentence(&text[last_end..mat.end()]);
+            if !sentence.is_empty() {
+                sentences.push(sentence.to_string());
+            }
+            last_end = mat.end();
+        }
+
+        // Add the remaining text if any
+        if last_end < text.len() {
+            let remaining = trim_sentence(&text[last_end..]);
+            if !remaining.is_empty() {
+                sentences.push(remaining.to_string());
+            }
+        }
+
+        sentences
+    }
+}
+
+/// Strip whitespace of any kind, plus zero-width spaces and byte order marks,
+/// from both ends of a sentence, so boundaries don't depend on how sentences were separated
+fn trim_sentence(sentence: &str) -> &str {
+    sentence.trim_matches(|c: char| c.is_whitespace() || matches!(c, '\u{200B}' | '\u{FEFF}'))
+}
+
+/// Open quote and parenthesis depth while scanning text
+#[derive(Debug, Default)]
+struct Enclosures {
+    parens: usize,
+    curly_quotes: usize,
+    straight_quote_open: bool,
+    /// Set when a closer appears without a matching opener
+    unmatched_close: bool,
+}
+
+impl Enclosures {
+    fn scan(&mut self, text: &str) {
+        for c in text.chars() {
+            match c {
+                '(' => self.parens += 1,
+                ')' => self.close_paren(),
+                '\u{201C}' => self.curly_quotes += 1,
+                '\u{201D}' => self.close_curly_quote(),
+                '"' => self.straight_quote_open = !self.straight_quote_open,
+                _ => {}
+            }
+        }
+    }
+
+    fn close_paren(&mut self) {
+        match self.parens.checked_sub(1) {
+            Some(depth) => self.parens = depth,
+            None => self.unmatched_close = true,
         }
-        last_end = mat.end();
-    }
-    
-    // Add the remaining text if any
-    if last_end < text.len() {
-        let remaining = text[last_end..].trim();
-        if !remaining.is_empty() {
-            sentences.push(remaining.to_string());
+    }
+
+    fn close_curly_quote(&mut self) {
+        match self.curly_quotes.checked_sub(1) {
+            Some(depth) => self.curly_quotes = depth,
+            None => self.unmatched_close = true,
         }
     }
-    
-    sentences
+
+    fn is_open(&self) -> bool {
+        self.parens > 0 || self.curly_quotes > 0 || self.straight_quote_open
+    }
+
+    /// Whether every quote and parenthesis in the text is closed
+    fn is_balanced(text: &str) -> bool {
+        let mut enclosures = Self::default();
+        enclosures.scan(text);
+        !enclosures.is_open() && !enclosures.unmatched_close
+    }
+}
+
+/// Join words split by a hyphen at the end of a line, as in text copied from PDFs
+/// ("inter-\nnational" becomes "international"). Hyphens not followed by a line
+/// break are kept, and so are line-break hyphens before a capital ("Anglo-\nSaxon").
+pub fn dehyphenate(text: &str) -> String {
+    LINE_BREAK_HYPHEN_REGEX.replace_all(text, "$1$2").into_owned()
 }
 
 /// Extracts words from a text sentence, removing punctuation
+/// Uses `DefaultTokenizer`, so it agrees with the app's click tokenization
 pub fn extract_words(text: &str) -> Vec<String> {
-    WORD_REGEX
-        .find_iter(text)
-        .map(|mat| mat.as_str().to_lowercase())
-        .collect()
+    DefaultTokenizer.words(text)
+}
+
+/// Groups of `chunk_size` words for speed reading, keeping the original spelling
+/// and punctuation ("Hello, world!" rather than "hello world")
+/// Text is split on whitespace; pieces without a word, such as a lone dash,
+/// stay attached to the word before them.
+pub fn word_chunks(text: &str, chunk_size: usize) -> Vec<String> {
+    let mut pieces: Vec<String> = Vec::new();
+    let mut leading = String::new();
+
+    for piece in text.split_whitespace() {
+        if DefaultTokenizer.tokenize(piece).iter().any(|token| token.is_word) {
+            let piece = if leading.is_empty() { piece.to_string() } else { format!("{} {piece}", std::mem::take(&mut leading)) };
+            pieces.push(piece);
+        } else {
+            match pieces.last_mut() {
+                Some(last) => {
+                    last.push(' ');
+                    last.push_str(piece);
+                }
+                None if leading.is_empty() => leading.push_str(piece),
+                None => {
+                    leading.push(' ');
+                    leading.push_str(piece);
+                }
+            }
+        }
+    }
+
+    pieces.chunks(chunk_size.max(1)).map(|chunk| chunk.join(" ")).collect()
 }
 
 #[cfg(test)]
@@ -62,6 +236,30 @@ mod tests {
         assert_eq!(sentences[3], "Final sentence");
     }
 
+    #[test]
+    fn test_sentence_boundaries_are_trimmed() {
+        let separators = [" ", "   ", "\t", " \t ", "\n", "\r\n", "\n\n  ", "\u{00A0} ", " \u{200B}"];
+        for separator in separators {
+            let text = format!("  First one.{separator}Second one?{separator}Third one!{separator}");
+            assert_eq!(
+                split_into_sentences(&text),
+                vec!["First one.", "Second one?", "Third one!"],
+                "separator {separator:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_sentence_has_surrounding_whitespace() {
+        let text = "\u{FEFF}Alpha.\t\tBeta |\n Gamma;   \r\nDelta\n\n\tEpsilon is last  \n";
+        let sentences = split_into_sentences(text);
+
+        assert_eq!(sentences, vec!["Alpha.", "Beta |", "Gamma;", "Delta\n\n\tEpsilon is last"]);
+        for sentence in &sentences {
+            assert_eq!(sentence.as_str(), trim_sentence(sentence));
+        }
+    }
+
     #[test]
     fn test_empty_text() {
         let sentences = split_into_sentences("");
@@ -88,6 +286,69 @@ mod tests {
         assert_eq!(sentences[2], "Third sentence.");
     }
 
+    #[test]
+    fn test_quoted_dialogue_is_split_by_default() {
+        let text = "He said, \"Go home. Now.\" and left.";
+        let sentences = split_into_sentences(text);
+
+        assert_eq!(sentences, vec!["He said, \"Go home.", "Now.\" and left."]);
+    }
+
+    #[test]
+    fn test_quoted_dialogue_stays_together_when_respecting_enclosures() {
+        let splitter = SentenceSplitter::new().with_respect_enclosures(true);
+
+        let text = "He said, \"Go home. Now.\" and left. She stayed.";
+        assert_eq!(splitter.split(text), vec!["He said, \"Go home. Now.\" and left.", "She stayed."]);
+
+        let curly = "She whispered, \u{201C}Wait. Listen! Do you hear it?\u{201D} Nobody moved.";
+        assert_eq!(splitter.split(curly).len(), 1);
+    }
+
+    #[test]
+    fn test_parentheticals_stay_together_when_respecting_enclosures() {
+        let splitter = SentenceSplitter::new().with_respect_enclosures(true);
+        let text = "The plan failed (again. As usual.) but we tried. Then we rested.";
+
+        assert_eq!(splitter.split(text), vec![
+            "The plan failed (again. As usual.) but we tried.",
+            "Then we rested.",
+        ]);
+    }
+
+    #[test]
+    fn test_unbalanced_quotes_fall_back_to_default_splitting() {
+        let splitter = SentenceSplitter::new().with_respect_enclosures(true);
+        let text = "He said, \"Go home. Then he left. The end.";
+
+        assert_eq!(splitter.split(text), split_into_sentences(text));
+        assert_eq!(splitter.split(text).len(), 3);
+    }
+
+    #[test]
+    fn test_verse_is_split_per_line_when_line_breaks_end_sentences() {
+        let verse = "Shall I compare thee to a summer's day?\nThou art more lovely and more temperate\nRough winds do shake the darling buds of May,\n\nAnd summer's lease hath all too short a date";
+        let splitter = SentenceSplitter::new().with_line_breaks_end_sentences(true);
+
+        assert_eq!(splitter.split(verse), vec![
+            "Shall I compare thee to a summer's day?",
+            "Thou art more lovely and more temperate",
+            "Rough winds do shake the darling buds of May,",
+            "And summer's lease hath all too short a date",
+        ]);
+        assert_eq!(split_into_sentences(verse).len(), 2);
+    }
+
+    #[test]
+    fn test_prose_is_unaffected_by_line_break_option() {
+        let prose = "The cat sat on the mat. It was warm! Was the dog there? No.";
+        let splitter = SentenceSplitter::new().with_line_breaks_end_sentences(true);
+        assert_eq!(splitter.split(prose), split_into_sentences(prose));
+
+        let wrapped = "The cat sat on\nthe mat. Then it slept.";
+        assert_eq!(split_into_sentences(wrapped), vec!["The cat sat on\nthe mat.", "Then it slept."]);
+    }
+
     #[test]
     fn test_extract_words() {
         let text = "Hello, world! This is a test.";
@@ -114,4 +375,56 @@ mod tests {
         assert_eq!(words[3], "it's");
         assert_eq!(words[4], "great");
     }
+
+    #[test]
+    fn test_extract_words_with_hyphens_and_unicode() {
+        let words = extract_words("A well-known café in Zürich isn\u{2019}t cheap.");
+        assert_eq!(words, vec!["a", "well-known", "café", "in", "zürich", "isn\u{2019}t", "cheap"]);
+    }
+
+    #[test]
+    fn test_dehyphenate_joins_line_break_words() {
+        assert_eq!(dehyphenate("an inter-\nnational deal"), "an international deal");
+        assert_eq!(dehyphenate("a com-  \r\n   plex case"), "a complex case");
+        assert_eq!(dehyphenate("soft\u{00AD}\nhyphen"), "softhyphen");
+    }
+
+    #[test]
+    fn test_dehyphenate_keeps_genuine_hyphens() {
+        let text = "A well-known, state-of-the-art tool.\nItems:\n- first\n- second\nAnglo-\nSaxon 1990-\n2000";
+        assert_eq!(dehyphenate(text), text);
+    }
+
+    #[test]
+    fn test_splitter_dehyphenates_when_enabled() {
+        let text = "The inter-\nnational team won. They cele-\nbrated.";
+
+        let sentences = SentenceSplitter::new().with_dehyphenate(true).split(text);
+        assert_eq!(sentences, vec!["The international team won.", "They celebrated."]);
+
+        let raw = SentenceSplitter::new().split(text);
+        assert_eq!(raw[0], "The inter-\nnational team won.");
+    }
+
+    #[test]
+    fn test_word_chunks_keep_punctuation() {
+        let text = "Hello, world! It's a well-known fact — isn't it?";
+        let chunks = word_chunks(text, 3);
+
+        assert_eq!(chunks, vec!["Hello, world! It's", "a well-known fact —", "isn't it?"]);
+
+        // Same words per chunk as the punctuation-free version
+        let extracted: Vec<String> = extract_words(text).chunks(3).map(|chunk| chunk.join(" ")).collect();
+        let stripped: Vec<String> = chunks.iter().map(|chunk| extract_words(chunk).join(" ")).collect();
+        assert_eq!(stripped, extracted);
+    }
+
+    #[test]
+    fn test_word_chunks_edge_cases() {
+        assert!(word_chunks("", 3).is_empty());
+        assert!(word_chunks("... —", 3).is_empty());
+        assert_eq!(word_chunks("\"Quoted start\" ends.", 0), vec!["\"Quoted", "start\"", "ends."]);
+        assert_eq!(word_chunks("-- Leading dash here.", 5), vec!["-- Leading dash here."]);
+    }
 }
+
@@ -0,0 +1,159 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// How pasted text is formatted, so markup can be removed before sentence splitting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputFormat {
+    /// Used as-is
+    #[default]
+    PlainText,
+    /// Emphasis, links, headings and list markers are stripped; fenced code
+    /// blocks keep their contents only when `keep_code_blocks` is set
+    Markdown { keep_code_blocks: bool },
+    /// Tags are stripped, block-level tags become line breaks and common entities are decoded
+    Html,
+}
+
+static MD_HEADING: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s{0,3}#{1,6}\s+(.*?)(?:\s+#+)?\s*$").unwrap());
+static MD_LIST_MARKER: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(?:>\s*)*(?:[-*+]|\d+[.)])\s+").unwrap());
+static MD_BLOCKQUOTE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(?:>\s?)+").unwrap());
+static MD_RULE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(?:[-*_]\s*){3,}$").unwrap());
+static MD_IMAGE: Lazy<Regex> = Lazy::new(|| Regex::new(r"!\[([^\]]*)\]\([^)]*\)").unwrap());
+static MD_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[([^\]]+)\](?:\([^)]*\)|\[[^\]]*\])").unwrap());
+static MD_INLINE_CODE: Lazy<Regex> = Lazy::new(|| Regex::new(r"`([^`]+)`").unwrap());
+static MD_STRONG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:\*\*|__)(\S(?:.*?\S)?)(?:\*\*|__)").unwrap());
+static MD_STRIKE: Lazy<Regex> = Lazy::new(|| Regex::new(r"~~(\S(?:.*?\S)?)~~").unwrap());
+static MD_EM_STAR: Lazy<Regex> = Lazy::new(|| Regex::new(r"\*(\S(?:[^*]*?\S)?)\*").unwrap());
+// Underscores inside words (snake_case) are left alone
+static MD_EM_UNDERSCORE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b_([^_\n]+?)_\b").unwrap());
+
+static HTML_HIDDEN: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<(script|style|head)\b.*?</(?:script|style|head)\s*>").unwrap());
+static HTML_COMMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<!--.*?-->").unwrap());
+static HTML_BLOCK_TAG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)</?(?:p|div|br|h[1-6]|li|ul|ol|blockquote|pre|tr|table|section|article)\b[^>]*>").unwrap()
+});
+static HTML_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<[^>]+>").unwrap());
+
+/// Remove formatting markup so only the readable text reaches the sentence splitter
+pub fn strip_markup(text: &str, format: InputFormat) -> String {
+    match format {
+        InputFormat::PlainText => text.to_string(),
+        InputFormat::Markdown { keep_code_blocks } => strip_markdown(text, keep_code_blocks),
+        InputFormat::Html => strip_html(text),
+    }
+}
+
+fn strip_markdown(text: &str, keep_code_blocks: bool) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            if keep_code_blocks {
+                lines.push(line.to_string());
+            }
+            continue;
+        }
+        if MD_RULE.is_match(line) {
+            lines.push(String::new());
+            continue;
+        }
+
+        let line = match MD_HEADING.captures(line) {
+            Some(caps) => caps[1].to_string(),
+            None => line.to_string(),
+        };
+        let line = MD_LIST_MARKER.replace(&line, "");
+        let line = MD_BLOCKQUOTE.replace(&line, "");
+        lines.push(strip_inline_markdown(&line));
+    }
+
+    lines.join("\n")
+}
+
+fn strip_inline_markdown(line: &str) -> String {
+    let line = MD_IMAGE.replace_all(line, "$1");
+    let line = MD_LINK.replace_all(&line, "$1");
+    let line = MD_INLINE_CODE.replace_all(&line, "$1");
+    let line = MD_STRONG.replace_all(&line, "$1");
+    let line = MD_STRIKE.replace_all(&line, "$1");
+    let line = MD_EM_STAR.replace_all(&line, "$1");
+    MD_EM_UNDERSCORE.replace_all(&line, "$1").into_owned()
+}
+
+fn strip_html(text: &str) -> String {
+    let text = HTML_HIDDEN.replace_all(text, "");
+    let text = HTML_COMMENT.replace_all(&text, "");
+    let text = HTML_BLOCK_TAG.replace_all(&text, "\n\n");
+    let text = HTML_TAG.replace_all(&text, "");
+    let text = decode_entities(&text);
+
+    // Collapse the blank lines left behind by nested block tags
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if !line.is_empty() || lines.last().is_some_and(|last| !last.is_empty()) {
+            lines.push(line);
+        }
+    }
+    lines.join("\n").trim().to_string()
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKDOWN: InputFormat = InputFormat::Markdown { keep_code_blocks: false };
+
+    #[test]
+    fn test_plain_text_is_unchanged() {
+        let text = "**Not** touched [here](url).";
+        assert_eq!(strip_markup(text, InputFormat::PlainText), text);
+    }
+
+    #[test]
+    fn test_markdown_emphasis_and_links_are_cleaned() {
+        let text = "# The Title\n\nThis is **bold**, *italic* and __strong__ with a [link](https://example.com) and `code`.\nA snake_case name stays ~~as is~~.";
+        assert_eq!(
+            strip_markup(text, MARKDOWN),
+            "The Title\n\nThis is bold, italic and strong with a link and code.\nA snake_case name stays as is."
+        );
+    }
+
+    #[test]
+    fn test_markdown_lists_quotes_and_images() {
+        let text = "- First item\n2. Second item\n> Quoted line\n![A cat](cat.png) sat.\n---";
+        assert_eq!(strip_markup(text, MARKDOWN), "First item\nSecond item\nQuoted line\nA cat sat.\n");
+    }
+
+    #[test]
+    fn test_code_fences_follow_flag() {
+        let text = "Before.\n```rust\nlet x = 1;\n```\nAfter.";
+
+        assert_eq!(strip_markup(text, MARKDOWN), "Before.\nAfter.");
+        assert_eq!(
+            strip_markup(text, InputFormat::Markdown { keep_code_blocks: true }),
+            "Before.\nlet x = 1;\nAfter."
+        );
+    }
+
+    #[test]
+    fn test_html_tags_are_stripped() {
+        let html = "<html><head><title>T</title></head><body><h1>Title</h1><p>Hello <b>world</b> &amp; friends.</p><script>alert(1)</script><p>Second<br>line.</p></body></html>";
+        assert_eq!(strip_markup(html, InputFormat::Html), "Title\n\nHello world & friends.\n\nSecond\n\nline.");
+    }
+}
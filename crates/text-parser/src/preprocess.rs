@@ -0,0 +1,121 @@
+use crate::markup::{strip_markup, InputFormat};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static BLANK_LINES: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*\n[ \t]*\n\s*").unwrap());
+static WHITESPACE_RUN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
+
+/// One cleanup step applied to raw text before it is split into sentences
+pub trait TextPreprocessor: Send + Sync {
+    fn process(&self, text: &str) -> String;
+}
+
+/// Preprocessors applied in the order they were added, each to the previous one's output
+#[derive(Default)]
+pub struct PreprocessPipeline {
+    steps: Vec<Box<dyn TextPreprocessor>>,
+}
+
+impl PreprocessPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a step to run after the existing ones
+    pub fn with(mut self, step: impl TextPreprocessor + 'static) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+impl TextPreprocessor for PreprocessPipeline {
+    fn process(&self, text: &str) -> String {
+        self.steps.iter().fold(text.to_string(), |text, step| step.process(&text))
+    }
+}
+
+/// Removes leading and trailing whitespace
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Trim;
+
+impl TextPreprocessor for Trim {
+    fn process(&self, text: &str) -> String {
+        text.trim().to_string()
+    }
+}
+
+/// Collapses each run of whitespace into one space, except runs containing a
+/// blank line, which become a single paragraph break
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CollapseWhitespace;
+
+impl TextPreprocessor for CollapseWhitespace {
+    fn process(&self, text: &str) -> String {
+        BLANK_LINES
+            .split(text)
+            .map(|paragraph| WHITESPACE_RUN.replace_all(paragraph, " ").into_owned())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Rejoins words hyphenated across line breaks; see `dehyphenate`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Dehyphenate;
+
+impl TextPreprocessor for Dehyphenate {
+    fn process(&self, text: &str) -> String {
+        crate::dehyphenate(text)
+    }
+}
+
+/// Removes Markdown formatting; see `strip_markup`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StripMarkdown {
+    pub keep_code_blocks: bool,
+}
+
+impl TextPreprocessor for StripMarkdown {
+    fn process(&self, text: &str) -> String {
+        strip_markup(text, InputFormat::Markdown { keep_code_blocks: self.keep_code_blocks })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapse_whitespace_keeps_paragraph_breaks() {
+        let text = "One  two\tthree\nfour.\n\n \n  Next   paragraph.";
+        assert_eq!(CollapseWhitespace.process(text), "One two three four.\n\nNext paragraph.");
+    }
+
+    #[test]
+    fn test_pipeline_applies_steps_in_order() {
+        let text = "  An inter-\nnational   **deal**.  ";
+
+        let dehyphenate_first = PreprocessPipeline::new().with(Dehyphenate).with(CollapseWhitespace);
+        assert_eq!(dehyphenate_first.process(text), " An international **deal**. ");
+
+        // Collapsing first turns the line break into a space, so the hyphen stays
+        let collapse_first = PreprocessPipeline::new().with(CollapseWhitespace).with(Dehyphenate);
+        assert_eq!(collapse_first.process(text), " An inter- national **deal**. ");
+
+        let full = PreprocessPipeline::new()
+            .with(Dehyphenate)
+            .with(StripMarkdown::default())
+            .with(CollapseWhitespace)
+            .with(Trim);
+        assert_eq!(full.len(), 4);
+        assert_eq!(full.process(text), "An international deal.");
+    }
+}
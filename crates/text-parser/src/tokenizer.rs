@@ -0,0 +1,114 @@
+/// A run of text that is either a word or the separator between words
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub text: String,
+    pub is_word: bool,
+}
+
+use crate::direction::is_combining_mark;
+
+/// Defines what counts as a word, shared by word extraction and click handling
+pub trait Tokenizer {
+    /// Split text into word and non-word tokens that together cover the whole input
+    fn tokenize(&self, text: &str) -> Vec<Token>;
+
+    /// Lowercased words in the text, in order
+    fn words(&self, text: &str) -> Vec<String> {
+        self.tokenize(text)
+            .into_iter()
+            .filter(|token| token.is_word)
+            .map(|token| token.text.to_lowercase())
+            .collect()
+    }
+
+    /// Whether the whole string is a single word
+    fn is_word(&self, text: &str) -> bool {
+        matches!(self.tokenize(text).as_slice(), [token] if token.is_word)
+    }
+}
+
+/// Words are runs of letters in any script, joined by apostrophes or hyphens
+/// between letters, so "don't", "well-known" and "café" are single words.
+/// Combining marks (Hebrew and Arabic vowel points) stay with their letter, and
+/// Hebrew maqaf and geresh join like hyphens and apostrophes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DefaultTokenizer;
+
+impl DefaultTokenizer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn is_joiner(c: char) -> bool {
+        matches!(c, '\'' | '\u{2019}' | '-' | '\u{05BE}' | '\u{05F3}' | '\u{05F4}')
+    }
+}
+
+impl Tokenizer for DefaultTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut tokens: Vec<Token> = Vec::new();
+
+        for (i, &c) in chars.iter().enumerate() {
+            let continues_word = tokens.last().is_some_and(|last| last.is_word);
+            let is_word = (c.is_alphabetic() && !is_combining_mark(c))
+                || (is_combining_mark(c) && continues_word)
+                || (Self::is_joiner(c)
+                    && continues_word
+                    && chars.get(i + 1).is_some_and(|next| next.is_alphabetic()));
+
+            match tokens.last_mut() {
+                Some(last) if last.is_word == is_word => last.text.push(c),
+                _ => tokens.push(Token { text: c.to_string(), is_word }),
+            }
+        }
+
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(tokens: &[Token]) -> Vec<&str> {
+        tokens.iter().map(|token| token.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_tokens_cover_the_input() {
+        let text = "Hi, it's a well-known café — 'quoted' (yes)!";
+        let tokens = DefaultTokenizer.tokenize(text);
+
+        assert_eq!(tokens.iter().map(|t| t.text.as_str()).collect::<String>(), text);
+        assert!(tokens.windows(2).all(|pair| pair[0].is_word != pair[1].is_word));
+    }
+
+    #[test]
+    fn test_joiners_only_inside_words() {
+        let tokens = DefaultTokenizer.tokenize("'tis rock-n-roll - ok'");
+        assert_eq!(texts(&tokens), vec!["'", "tis", " ", "rock-n-roll", " - ", "ok", "'"]);
+    }
+
+    #[test]
+    fn test_is_word() {
+        assert!(DefaultTokenizer.is_word("don\u{2019}t"));
+        assert!(DefaultTokenizer.is_word("naïve"));
+        assert!(!DefaultTokenizer.is_word("two words"));
+        assert!(!DefaultTokenizer.is_word("-"));
+        assert!(!DefaultTokenizer.is_word(""));
+    }
+
+    #[test]
+    fn test_hebrew_words_with_points_stay_whole() {
+        let tokens = DefaultTokenizer.tokenize("שָׁלוֹם, עוֹלָם! צה״ל");
+        assert_eq!(texts(&tokens), vec!["שָׁלוֹם", ", ", "עוֹלָם", "! ", "צה״ל"]);
+        assert_eq!(DefaultTokenizer.words("בְּרֵאשִׁית בָּרָא").len(), 2);
+    }
+
+    #[test]
+    fn test_arabic_words_with_harakat_stay_whole() {
+        assert!(DefaultTokenizer.is_word("كَتَبَ"));
+        assert_eq!(DefaultTokenizer.words("ذَهَبَ الوَلَدُ"), vec!["ذَهَبَ", "الوَلَدُ"]);
+    }
+}
@@ -1,33 +1,151 @@
+use crate::lemmatizer::{lemmatize, strip_light_inflection};
 use glossia_shared::{WordMeaning, AppError};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tracing::info;
 
 /// Manages known words and filters them from word lists
+#[derive(Default)]
 pub struct KnownWordsFilter {
     known_words: HashSet<String>,
+    /// Multi-word expressions known as a whole, lowercased with single spaces;
+    /// their words are not known on their own
+    known_phrases: HashSet<String>,
+    /// Base forms of the known words, kept only while lemmatization is on
+    known_lemmas: HashSet<String>,
+    lemmatize: bool,
+    /// Known words without a possessive or plural `s`, kept only while light inflection is on
+    known_light_forms: HashSet<String>,
+    light_inflection: bool,
+    /// Most known words kept; past it the least recently matched are demoted
+    max_known_words: Option<usize>,
+    /// Tick of each known word's last add or exact match; updated from `is_known_word`,
+    /// which only borrows the filter
+    last_matched: Mutex<HashMap<String, u64>>,
+    clock: AtomicU64,
 }
 
 impl KnownWordsFilter {
     pub fn new() -> Result<Self, AppError> {
-        Ok(Self {
-            known_words: HashSet::new(),
-        })
+        Ok(Self::default())
+    }
+
+    /// Treat every inflection of a known word as known (`studies` makes `studying` known)
+    pub fn set_lemmatization(&mut self, enabled: bool) {
+        self.lemmatize = enabled;
+        self.rebuild_lemmas();
+    }
+
+    /// Match words differing only by a possessive `'s` or a plural `s` (`cats`
+    /// and `dog's` match known `cat` and `dog`); lighter than lemmatization and
+    /// independent of it
+    pub fn with_light_inflection(mut self, enabled: bool) -> Self {
+        self.set_light_inflection(enabled);
+        self
+    }
+
+    pub fn set_light_inflection(&mut self, enabled: bool) {
+        self.light_inflection = enabled;
+        self.rebuild_lemmas();
+    }
+
+    /// Keep at most `max` known words, demoting the least recently matched ones
+    /// when a new word would exceed it; `None` (the default) means no cap.
+    /// Returns the words demoted to fit an already larger set.
+    pub fn set_max_known_words(&mut self, max: Option<usize>) -> Vec<String> {
+        self.max_known_words = max;
+        self.demote_over_cap()
+    }
+
+    pub fn max_known_words(&self) -> Option<usize> {
+        self.max_known_words
     }
 
     /// Add a word to known words
     pub fn add_known_word(&mut self, word: &str) -> Result<(), AppError> {
-        self.known_words.insert(word.to_lowercase());
+        let word = word.to_lowercase();
+        self.insert_forms(&word);
+        self.touch(&word);
+        self.known_words.insert(word);
+        self.demote_over_cap();
         Ok(())
     }
 
+    /// Add many words at once, applying the known-words cap once at the end;
+    /// returns how many were not already known
+    pub fn add_known_words(&mut self, words: impl IntoIterator<Item = String>) -> usize {
+        let mut added = 0;
+        for word in words {
+            let word = word.to_lowercase();
+            self.insert_forms(&word);
+            self.touch(&word);
+            if self.known_words.insert(word) {
+                added += 1;
+            }
+        }
+        self.demote_over_cap();
+        added
+    }
+
     /// Remove a word from known words
     pub fn remove_known_word(&mut self, word: &str) -> Result<(), AppError> {
-        self.known_words.remove(&word.to_lowercase());
+        let word = word.to_lowercase();
+        self.known_words.remove(&word);
+        self.last_matched.lock().unwrap().remove(&word);
+        self.rebuild_lemmas();
         Ok(())
     }
 
     /// Check if a word is known
+    /// An exact match counts as a use for the known-words cap; inflections matched
+    /// through lemmatization do not
     pub fn is_known_word(&self, word: &str) -> bool {
-        self.known_words.contains(&word.to_lowercase())
+        let word = word.to_lowercase();
+        if self.known_words.contains(&word) {
+            if self.max_known_words.is_some() {
+                self.touch(&word);
+            }
+            return true;
+        }
+        (self.lemmatize && self.known_lemmas.contains(&lemmatize(&word)))
+            || (self.light_inflection && self.known_light_forms.contains(&strip_light_inflection(&word)))
+    }
+
+    /// Add a multi-word expression to known phrases
+    pub fn add_known_phrase(&mut self, phrase: &str) -> Result<(), AppError> {
+        let phrase = normalize_phrase(phrase);
+        if phrase.is_empty() {
+            return Err(AppError::config_error("Known phrase is empty"));
+        }
+        self.known_phrases.insert(phrase);
+        Ok(())
+    }
+
+    pub fn remove_known_phrase(&mut self, phrase: &str) {
+        self.known_phrases.remove(&normalize_phrase(phrase));
+    }
+
+    /// Check if a phrase is known, ignoring case and spacing
+    pub fn is_known_phrase(&self, phrase: &str) -> bool {
+        self.known_phrases.contains(&normalize_phrase(phrase))
+    }
+
+    pub fn get_all_known_phrases(&self) -> Vec<String> {
+        self.known_phrases.iter().cloned().collect()
+    }
+
+    pub fn load_known_phrases(&mut self, phrases: impl IntoIterator<Item = String>) {
+        self.known_phrases.extend(phrases.into_iter().map(|p| normalize_phrase(&p)).filter(|p| !p.is_empty()));
+    }
+
+    /// Whether a word list entry is known: phrases must be known as a whole,
+    /// single words go through `is_known_word`
+    pub fn is_known_entry(&self, word_meaning: &WordMeaning) -> bool {
+        if word_meaning.is_phrase || word_meaning.word.split_whitespace().nth(1).is_some() {
+            return self.is_known_phrase(&word_meaning.word);
+        }
+        self.is_known_word(&word_meaning.word)
     }
 
     /// Get all known words
@@ -43,7 +161,7 @@ impl KnownWordsFilter {
     /// Filter out known words from a word list
     pub fn filter_words(&self, words: &[WordMeaning]) -> Vec<WordMeaning> {
         words.iter()
-            .filter(|word_meaning| !self.is_known_word(&word_meaning.word))
+            .filter(|word_meaning| !self.is_known_entry(word_meaning))
             .cloned()
             .collect()
     }
@@ -51,10 +169,169 @@ impl KnownWordsFilter {
     /// Clear all known words
     pub fn clear(&mut self) {
         self.known_words.clear();
+        self.known_phrases.clear();
+        self.known_lemmas.clear();
+        self.known_light_forms.clear();
+        self.last_matched.lock().unwrap().clear();
     }
 
     /// Load known words from a collection
     pub fn load_known_words(&mut self, words: impl IntoIterator<Item = String>) {
         self.known_words.extend(words.into_iter().map(|w| w.to_lowercase()));
+        self.rebuild_lemmas();
+    }
+
+    fn touch(&self, word: &str) {
+        let tick = self.clock.fetch_add(1, Ordering::Relaxed) + 1;
+        self.last_matched.lock().unwrap().insert(word.to_string(), tick);
+    }
+
+    /// Drop least recently matched words until the cap is met; words never
+    /// matched (e.g. loaded from disk) go first, alphabetically
+    fn demote_over_cap(&mut self) -> Vec<String> {
+        let Some(max) = self.max_known_words else {
+            return Vec::new();
+        };
+        if self.known_words.len() <= max {
+            return Vec::new();
+        }
+
+        let last_matched = self.last_matched.get_mut().unwrap();
+        let mut by_age: Vec<(u64, String)> = self
+            .known_words
+            .iter()
+            .map(|word| (last_matched.get(word).copied().unwrap_or(0), word.clone()))
+            .collect();
+        by_age.sort();
+
+        let excess = self.known_words.len() - max;
+        let demoted: Vec<String> = by_age.into_iter().take(excess).map(|(_, word)| word).collect();
+        for word in &demoted {
+            self.known_words.remove(word);
+            last_matched.remove(word);
+        }
+        info!("Known words cap of {} reached, demoted {:?}", max, demoted);
+        self.rebuild_lemmas();
+        demoted
+    }
+
+    /// Record the lemma and light form of a newly known word, as enabled
+    fn insert_forms(&mut self, word: &str) {
+        if self.lemmatize {
+            self.known_lemmas.insert(lemmatize(word));
+        }
+        if self.light_inflection {
+            self.known_light_forms.insert(strip_light_inflection(word));
+        }
+    }
+
+    fn rebuild_lemmas(&mut self) {
+        self.known_lemmas = if self.lemmatize {
+            self.known_words.iter().map(|word| lemmatize(word)).collect()
+        } else {
+            HashSet::new()
+        };
+        self.known_light_forms = if self.light_inflection {
+            self.known_words.iter().map(|word| strip_light_inflection(word)).collect()
+        } else {
+            HashSet::new()
+        };
+    }
+}
+
+/// Lowercase and collapse runs of whitespace, so "Kick  the Bucket" matches "kick the bucket"
+fn normalize_phrase(phrase: &str) -> String {
+    phrase.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exceeding_cap_demotes_least_recently_matched() {
+        let mut filter = KnownWordsFilter::default();
+        filter.set_max_known_words(Some(2));
+        filter.add_known_word("lucid").unwrap();
+        filter.add_known_word("serene").unwrap();
+
+        // Matching "lucid" makes "serene" the least recently used
+        assert!(filter.is_known_word("Lucid"));
+        filter.add_known_word("ephemeral").unwrap();
+
+        assert_eq!(filter.get_count(), 2);
+        assert!(filter.is_known_word("lucid"));
+        assert!(filter.is_known_word("ephemeral"));
+        assert!(!filter.is_known_word("serene"));
+    }
+
+    #[test]
+    fn test_no_cap_by_default_and_lowering_demotes() {
+        let mut filter = KnownWordsFilter::default();
+        for word in ["alpha", "beta", "gamma"] {
+            filter.add_known_word(word).unwrap();
+        }
+        assert_eq!(filter.get_count(), 3);
+        assert_eq!(filter.max_known_words(), None);
+
+        assert_eq!(filter.set_max_known_words(Some(1)), vec!["alpha".to_string(), "beta".to_string()]);
+        assert!(filter.is_known_word("gamma"));
+    }
+
+    #[test]
+    fn test_light_inflection_matches_plurals_and_possessives() {
+        let mut filter = KnownWordsFilter::default().with_light_inflection(true);
+        filter.add_known_word("cat").unwrap();
+        filter.add_known_word("dog").unwrap();
+        filter.add_known_word("bu").unwrap();
+
+        assert!(filter.is_known_word("cats"));
+        assert!(filter.is_known_word("Dog's"));
+        assert!(!filter.is_known_word("bus"));
+        // Heavier stemming stays off
+        assert!(!filter.is_known_word("catting"));
+
+        let plain = KnownWordsFilter::default();
+        assert!(!plain.is_known_word("cats"));
+    }
+
+    #[test]
+    fn test_light_inflection_applies_to_inserted_forms() {
+        let mut filter = KnownWordsFilter::default();
+        filter.add_known_word("books").unwrap();
+        filter.set_light_inflection(true);
+
+        assert!(filter.is_known_word("book"));
+        assert!(filter.is_known_word("book's"));
+    }
+
+    fn entry(word: &str, is_phrase: bool) -> WordMeaning {
+        WordMeaning {
+            word: word.to_string(),
+            meaning: String::new(),
+            is_phrase,
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn test_known_phrase_is_filtered_as_a_unit() {
+        let mut filter = KnownWordsFilter::default();
+        filter.add_known_phrase("Kick the  bucket").unwrap();
+        filter.add_known_word("ice").unwrap();
+
+        assert!(filter.is_known_phrase("kick the bucket"));
+        assert!(!filter.is_known_word("kick"));
+        assert!(!filter.is_known_phrase("ice"));
+
+        let words = [
+            entry("kick the bucket", true),
+            entry("kick", false),
+            entry("ice cream", true),
+            entry("ice", false),
+        ];
+        let remaining: Vec<String> = filter.filter_words(&words).into_iter().map(|w| w.word).collect();
+        assert_eq!(remaining, vec!["kick", "ice cream"]);
+        assert!(filter.add_known_phrase("   ").is_err());
     }
 }
@@ -0,0 +1,120 @@
+/// Reduce an English word to a shared base form so its inflections count as one word
+///
+/// This is a small suffix-stripping ruleset, not a dictionary lemmatizer,
+/// applied in order so a plural reduces the same way as its singular:
+/// - plural and third-person `-s`/`-es` (`cats` → `cat`, `boxes` → `box`), `-ies` becomes `-y`
+/// - then `-ing` and `-ed`, undoubling a final consonant (`running` → `run`, `stopped` → `stop`),
+///   and `-ied` becomes `-y` (`studied` → `study`); these are kept when the rest would
+///   have no vowel (`string`) or ends in `e` (`speed`)
+/// - a trailing silent `e` is dropped so `make`, `makes` and `making` all give `mak`
+///
+/// Irregular forms (`ran`, `made`, `mice`) are left alone, and the base form
+/// is not always a dictionary word.
+pub fn lemmatize(word: &str) -> String {
+    let word = word.to_lowercase();
+    if word.chars().count() <= 3 || !word.chars().all(char::is_alphabetic) {
+        return word;
+    }
+
+    let singular = if let Some(stem) = word.strip_suffix("ies") {
+        format!("{stem}y")
+    } else if let Some(stem) = word.strip_suffix("es").filter(|stem| ["s", "x", "z", "ch", "sh"].iter().any(|end| stem.ends_with(end))) {
+        stem.to_string()
+    } else if word.ends_with('s') && !["ss", "us", "is"].iter().any(|end| word.ends_with(end)) {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.clone()
+    };
+
+    let stem = if let Some(stem) = singular.strip_suffix("ied") {
+        format!("{stem}y")
+    } else if let Some(stem) = singular.strip_suffix("ing").or_else(|| singular.strip_suffix("ed")) {
+        if stem.chars().count() >= 3 && stem.contains(['a', 'e', 'i', 'o', 'u', 'y']) && !stem.ends_with('e') {
+            undouble(stem)
+        } else {
+            singular.clone()
+        }
+    } else {
+        singular.clone()
+    };
+
+    match stem.strip_suffix('e') {
+        Some(base) if base.chars().count() >= 3 => base.to_string(),
+        _ => stem,
+    }
+}
+
+/// `runn` → `run`, keeping doubled letters that are part of the base (`fall`, `miss`)
+fn undouble(stem: &str) -> String {
+    let mut chars = stem.chars().rev();
+    match (chars.next(), chars.next()) {
+        (Some(last), Some(before)) if last == before && "bdgmnprt".contains(last) => stem[..stem.len() - 1].to_string(),
+        _ => stem.to_string(),
+    }
+}
+
+/// Cheaper alternative to `lemmatize` that only strips a possessive `'s` or
+/// a single plural `s` (`dog's` → `dog`, `cats` → `cat`); words ending in
+/// `ss`, `us` or `is` and words of three letters or fewer keep their `s`
+pub fn strip_light_inflection(word: &str) -> String {
+    let word = word.to_lowercase();
+    if let Some(stem) = word.strip_suffix("'s").or_else(|| word.strip_suffix("\u{2019}s")) {
+        return stem.to_string();
+    }
+    if let Some(stem) = word.strip_suffix('\'').or_else(|| word.strip_suffix('\u{2019}')) {
+        return stem.to_string();
+    }
+    if word.chars().count() > 3 && word.ends_with('s') && !["ss", "us", "is"].iter().any(|end| word.ends_with(end)) {
+        return word[..word.len() - 1].to_string();
+    }
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inflections_share_a_lemma() {
+        for word in ["study", "studies", "studied", "studying", "Studies"] {
+            assert_eq!(lemmatize(word), "study", "word: {word}");
+        }
+        for word in ["run", "runs", "running"] {
+            assert_eq!(lemmatize(word), "run", "word: {word}");
+        }
+        assert_eq!(lemmatize("boxes"), "box");
+        assert_eq!(lemmatize("stopped"), lemmatize("stop"));
+        assert_eq!(lemmatize("making"), lemmatize("makes"));
+    }
+
+    #[test]
+    fn test_short_and_protected_words_are_unchanged() {
+        assert_eq!(lemmatize("bus"), "bus");
+        assert_eq!(lemmatize("glass"), "glass");
+        assert_eq!(lemmatize("falling"), "fall");
+        assert_eq!(lemmatize("thing"), "thing");
+        assert_eq!(lemmatize("don't"), "don't");
+    }
+
+    #[test]
+    fn test_plurals_share_the_lemma_of_their_base() {
+        for (base, plural) in [("speed", "speeds"), ("string", "strings"), ("building", "buildings"), ("morning", "mornings"), ("thing", "things")] {
+            assert_eq!(lemmatize(plural), lemmatize(base), "{base} / {plural}");
+        }
+        assert_eq!(lemmatize("speed"), "speed");
+        assert_eq!(lemmatize("string"), "string");
+        assert_eq!(lemmatize("buildings"), "build");
+        assert_eq!(lemmatize("mornings"), "morn");
+    }
+
+    #[test]
+    fn test_light_inflection_strips_possessives_and_plurals_only() {
+        assert_eq!(strip_light_inflection("Cats"), "cat");
+        assert_eq!(strip_light_inflection("dog's"), "dog");
+        assert_eq!(strip_light_inflection("dog\u{2019}s"), "dog");
+        assert_eq!(strip_light_inflection("dogs'"), "dogs");
+        assert_eq!(strip_light_inflection("bus"), "bus");
+        assert_eq!(strip_light_inflection("glass"), "glass");
+        assert_eq!(strip_light_inflection("studies"), "studie");
+    }
+}
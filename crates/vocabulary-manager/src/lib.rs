@@ -2,31 +2,88 @@ mod word_tracker;
 mod manual_words;
 mod known_words_filter;
 mod vocabulary_trait;
+mod vocabulary_file;
+mod lemmatizer;
+mod promotion_policy;
+mod study_sheet;
 
-pub use word_tracker::WordTracker;
+pub use word_tracker::{WordTracker, DEFAULT_ENGLISH_STOPWORDS};
 pub use manual_words::ManualWordsManager;
 pub use known_words_filter::KnownWordsFilter;
 pub use vocabulary_trait::{VocabularyStore, MemoryVocabularyStore, FileVocabularyStore};
+pub use vocabulary_file::{VOCABULARY_FILE, VOCABULARY_FORMAT_VERSION};
+pub use lemmatizer::{lemmatize, strip_light_inflection};
+pub use promotion_policy::{CountThresholdPolicy, PromotionPolicy, WordStats};
 
 use glossia_shared::{WordMeaning, AppError};
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use tracing::{instrument, info, debug};
+use vocabulary_file::{read_vocabulary_file, write_vocabulary_file, VocabularyData};
+use study_sheet::render_study_sheet_html;
+
+/// Undo token for a removed known word, carrying what is needed to restore it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedWord {
+    pub word: String,
+    pub encounter_count: usize,
+}
+
+/// Known words at a point in time, for `VocabularyManager::new_words_since`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Checkpoint {
+    known_words: HashSet<String>,
+}
+
+impl Checkpoint {
+    /// Number of words known when the checkpoint was taken
+    pub fn known_words_count(&self) -> usize {
+        self.known_words.len()
+    }
+}
 
 /// Centralized vocabulary management system
 /// Combines word tracking, known words filtering, and manual word selection
+#[derive(Default)]
 pub struct VocabularyManager {
     word_tracker: WordTracker,
     manual_words: ManualWordsManager,
     known_words_filter: KnownWordsFilter,
+    pinned_words: HashSet<String>,
+    /// With `require_confirmation`, words added once and awaiting a second add
+    staged_words: HashSet<String>,
+    require_confirmation: bool,
+    dir: Option<PathBuf>,
 }
 
 impl VocabularyManager {
     pub fn new() -> Result<Self, AppError> {
-        Ok(Self {
-            word_tracker: WordTracker::new()?,
-            manual_words: ManualWordsManager::new(),
-            known_words_filter: KnownWordsFilter::new()?,
-        })
+        Ok(Self::default())
+    }
+
+    /// Manager persisted to `VOCABULARY_FILE` inside `dir`, loading any existing file
+    pub fn with_dir(dir: impl Into<PathBuf>) -> Result<Self, AppError> {
+        let mut manager = Self {
+            dir: Some(dir.into()),
+            ..Self::default()
+        };
+        if let Some(path) = manager.file_path() {
+            manager.load_from_file(&path)?;
+        }
+        Ok(manager)
+    }
+
+    /// File used by `save`, when created with `with_dir`
+    pub fn file_path(&self) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(VOCABULARY_FILE))
+    }
+
+    /// Persist to the directory given to `with_dir`; does nothing without one
+    pub fn save(&self) -> Result<(), AppError> {
+        match self.file_path() {
+            Some(path) => self.save_to_file(&path),
+            None => Ok(()),
+        }
     }
 
     /// Add a word encounter (increments count, may promote to known)
@@ -36,6 +93,68 @@ impl VocabularyManager {
         let result = self.word_tracker.add_encounter(word)?;
         if result.1 {
             info!("Word '{}' promoted to known after {} encounters", word, result.0);
+            self.promote_if_staged(word)?;
+        } else {
+            debug!("Word '{}' encounter count: {}", word, result.0);
+        }
+        Ok(result)
+    }
+
+    /// Require encounters in distinct sentences before promotion, so re-reading
+    /// the same sentence doesn't count towards learning a word
+    pub fn with_distinct_context_promotion(mut self, distinct: bool) -> Self {
+        self.word_tracker.set_distinct_contexts(distinct);
+        self
+    }
+
+    /// Ignore these words (case-insensitive) when counting encounters, instead of the English default
+    pub fn with_stopwords(mut self, stopwords: HashSet<String>) -> Self {
+        self.word_tracker = std::mem::take(&mut self.word_tracker).with_stopwords(stopwords);
+        self
+    }
+
+    /// Decide promotions with a custom policy instead of the encounter count threshold
+    pub fn with_promotion_policy(mut self, policy: Box<dyn PromotionPolicy>) -> Self {
+        self.word_tracker = std::mem::take(&mut self.word_tracker).with_policy(policy);
+        self
+    }
+
+    /// Count encounters under a shared base form and treat every inflection of a
+    /// known word as known; see `lemmatize` for the suffix rules applied
+    pub fn with_lemmatization(mut self, enabled: bool) -> Self {
+        self.word_tracker = std::mem::take(&mut self.word_tracker).with_lemmatization(enabled);
+        self.known_words_filter.set_lemmatization(enabled);
+        self
+    }
+
+    /// Treat a known word's plural and possessive (`cats`, `cat's`) as known;
+    /// see `strip_light_inflection`
+    pub fn with_light_inflection(mut self, enabled: bool) -> Self {
+        self.known_words_filter.set_light_inflection(enabled);
+        self
+    }
+
+    /// Cap the number of known words, demoting the least recently matched ones
+    /// when exceeded; uncapped unless set
+    pub fn with_max_known_words(mut self, max: usize) -> Self {
+        self.known_words_filter.set_max_known_words(Some(max));
+        self
+    }
+
+    /// Count stopwords like any other word when `skip` is false
+    pub fn with_skip_stopwords(mut self, skip: bool) -> Self {
+        self.word_tracker.set_skip_stopwords(skip);
+        self
+    }
+
+    /// Add a word encounter seen in `sentence` (increments count, may promote to known)
+    /// With distinct context promotion, repeat encounters in the same sentence are ignored
+    #[instrument(skip(self, sentence), fields(word = %word))]
+    pub fn add_word_encounter_in(&mut self, word: &str, sentence: &str) -> Result<(usize, bool), AppError> {
+        let result = self.word_tracker.add_encounter_in(word, sentence)?;
+        if result.1 {
+            info!("Word '{}' promoted to known after {} encounters", word, result.0);
+            self.promote_if_staged(word)?;
         } else {
             debug!("Word '{}' encounter count: {}", word, result.0);
         }
@@ -43,41 +162,230 @@ impl VocabularyManager {
     }
 
     /// Add a word to known words manually
+    /// With `require_confirmation`, the first add only stages the word; it becomes
+    /// known on a second add, on `confirm_known_word`, or on reaching the encounter threshold
     #[instrument(skip(self), fields(word = %word))]
     pub fn add_known_word(&mut self, word: &str) -> Result<(), AppError> {
+        if self.require_confirmation && !self.is_known_word(word) && self.staged_words.insert(word.to_lowercase()) {
+            info!("Staged word '{}' until the addition is confirmed", word);
+            return Ok(());
+        }
+        self.confirm_known_word(word)
+    }
+
+    /// Add a word to known words without staging, for deliberate actions
+    pub fn confirm_known_word(&mut self, word: &str) -> Result<(), AppError> {
         info!("Manually adding word to known words: '{}'", word);
+        self.staged_words.remove(&word.to_lowercase());
         self.known_words_filter.add_known_word(word)?;
         debug!("Known words count now: {}", self.get_known_words_count());
         Ok(())
     }
 
+    /// Stage words on their first `add_known_word` instead of adding them, so a
+    /// stray double-click doesn't mark a word known
+    pub fn with_require_confirmation(mut self, require: bool) -> Self {
+        self.set_require_confirmation(require);
+        self
+    }
+
+    /// Turning confirmation off drops the staged words without adding them
+    pub fn set_require_confirmation(&mut self, require: bool) {
+        self.require_confirmation = require;
+        if !require {
+            self.staged_words.clear();
+        }
+    }
+
+    pub fn require_confirmation(&self) -> bool {
+        self.require_confirmation
+    }
+
+    /// Whether the word was added once and awaits confirmation
+    pub fn is_staged(&self, word: &str) -> bool {
+        self.staged_words.contains(&word.to_lowercase())
+    }
+
+    /// Discard a staged word without adding it
+    pub fn unstage_word(&mut self, word: &str) {
+        self.staged_words.remove(&word.to_lowercase());
+    }
+
+    fn promote_if_staged(&mut self, word: &str) -> Result<(), AppError> {
+        if self.is_staged(word) {
+            self.confirm_known_word(word)?;
+        }
+        Ok(())
+    }
+
+    /// Mark a multi-word expression (e.g. "kick the bucket") as known as a whole
+    /// Its individual words stay unknown unless added separately
+    pub fn add_known_phrase(&mut self, phrase: &str) -> Result<(), AppError> {
+        info!("Adding known phrase: '{}'", phrase);
+        self.known_words_filter.add_known_phrase(phrase)
+    }
+
+    pub fn remove_known_phrase(&mut self, phrase: &str) {
+        self.known_words_filter.remove_known_phrase(phrase);
+    }
+
+    pub fn is_known_phrase(&self, phrase: &str) -> bool {
+        self.known_words_filter.is_known_phrase(phrase)
+    }
+
+    /// Add every word from a list (e.g. a frequency list) to known words,
+    /// trimmed, lowercased and deduplicated, then save once
+    /// Returns how many words were not already known
+    #[instrument(skip(self, words))]
+    pub fn import_known_words<I: IntoIterator<Item = String>>(&mut self, words: I) -> Result<usize, AppError> {
+        let words: HashSet<String> = words
+            .into_iter()
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let added = self.known_words_filter.add_known_words(words);
+        info!("Imported {} new known words", added);
+        self.save()?;
+        Ok(added)
+    }
+
     /// Remove a word from known words
+    /// Its encounter count is reset so tracking starts over; the returned token
+    /// can be passed to `restore_known_word` to undo the removal
     #[instrument(skip(self), fields(word = %word))]
-    pub fn remove_known_word(&mut self, word: &str) -> Result<(), AppError> {
+    pub fn remove_known_word(&mut self, word: &str) -> Result<RemovedWord, AppError> {
         info!("Removing word from known words: '{}'", word);
         self.known_words_filter.remove_known_word(word)?;
+        let encounter_count = self.word_tracker.remove(word);
         debug!("Known words count now: {}", self.get_known_words_count());
+        Ok(RemovedWord {
+            word: word.to_lowercase(),
+            encounter_count,
+        })
+    }
+
+    /// Undo a removal, re-adding the word and its previous encounter count
+    #[instrument(skip(self), fields(word = %removed.word))]
+    pub fn restore_known_word(&mut self, removed: RemovedWord) -> Result<(), AppError> {
+        info!("Restoring known word: '{}'", removed.word);
+        self.known_words_filter.add_known_word(&removed.word)?;
+        self.word_tracker.set_count(&removed.word, removed.encounter_count);
         Ok(())
     }
 
+    pub fn is_known_word(&self, word: &str) -> bool {
+        self.known_words_filter.is_known_word(word)
+    }
+
+    /// Snapshot the current known words to diff against later
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            known_words: self.known_words_filter.get_all_known_words().unwrap_or_default().into_iter().collect(),
+        }
+    }
+
+    /// Words that became known after `checkpoint` was taken, sorted alphabetically
+    pub fn new_words_since(&self, checkpoint: &Checkpoint) -> Vec<String> {
+        let mut words: Vec<String> = self
+            .known_words_filter
+            .get_all_known_words()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|word| !checkpoint.known_words.contains(word))
+            .collect();
+        words.sort();
+        words
+    }
+
     /// Get all known words
     pub fn get_all_known_words(&self) -> Result<Vec<String>, AppError> {
         self.known_words_filter.get_all_known_words()
     }
 
+    /// Known words with their encounter counts as a printable HTML page, in A–Z sections
+    pub fn export_study_sheet_html(&self) -> Result<String, AppError> {
+        let words: Vec<(String, usize)> = self
+            .get_all_known_words()?
+            .into_iter()
+            .map(|word| {
+                let count = self.get_encounter_count(&word);
+                (word, count)
+            })
+            .collect();
+        Ok(render_study_sheet_html(&words))
+    }
+
     /// Get known words count
     pub fn get_known_words_count(&self) -> usize {
         self.known_words_filter.get_count()
     }
 
     /// Filter out known words from a word list
+    /// Pinned words are kept even when known
     #[instrument(skip(self, words), fields(input_count = words.len()))]
     pub fn filter_known_words(&self, words: &[WordMeaning]) -> Vec<WordMeaning> {
-        let filtered = self.known_words_filter.filter_words(words);
+        let filtered: Vec<WordMeaning> = words.iter()
+            .filter(|word_meaning| {
+                self.is_pinned(&word_meaning.word)
+                    || !self.known_words_filter.is_known_entry(word_meaning)
+            })
+            .cloned()
+            .collect();
         debug!("Filtered {} words from {} (removed {} known words)", filtered.len(), words.len(), words.len() - filtered.len());
         filtered
     }
 
+    /// Number of words with at least one recorded encounter
+    pub fn tracked_words_count(&self) -> usize {
+        self.word_tracker.get_all_counts().len()
+    }
+
+    /// Number of manually selected words
+    pub fn manual_words_count(&self) -> usize {
+        self.manual_words.count()
+    }
+
+    /// Get how many times a word has been encountered
+    pub fn get_encounter_count(&self, word: &str) -> usize {
+        self.word_tracker.get_count(word)
+    }
+
+    /// Order words by ascending encounter count, so the least familiar come first
+    /// Words with equal counts keep their relative order
+    pub fn sort_by_encounter_count(&self, words: &mut [WordMeaning]) {
+        words.sort_by_key(|word_meaning| self.word_tracker.get_count(&word_meaning.word));
+    }
+
+    /// Drop known words (manual selections are always kept) and rank the rest
+    /// by ascending encounter count
+    pub fn rank_by_encounter_count(&self, words: &[WordMeaning]) -> Vec<WordMeaning> {
+        let mut ranked: Vec<WordMeaning> = words.iter()
+            .filter(|word_meaning| {
+                self.manual_words.is_manual_word(&word_meaning.word)
+                    || self.is_pinned(&word_meaning.word)
+                    || !self.known_words_filter.is_known_entry(word_meaning)
+            })
+            .cloned()
+            .collect();
+        self.sort_by_encounter_count(&mut ranked);
+        ranked
+    }
+
+    /// Pin a word so it stays highlighted even once known
+    pub fn pin_word(&mut self, word: &str) {
+        self.pinned_words.insert(word.to_lowercase());
+    }
+
+    /// Remove a word's pin
+    pub fn unpin_word(&mut self, word: &str) {
+        self.pinned_words.remove(&word.to_lowercase());
+    }
+
+    /// Check if a word is pinned
+    pub fn is_pinned(&self, word: &str) -> bool {
+        self.pinned_words.contains(&word.to_lowercase())
+    }
+
     /// Add a manual word selection
     pub fn add_manual_word(&mut self, word: String) {
         self.manual_words.add_word(word);
@@ -103,6 +411,19 @@ impl VocabularyManager {
         self.manual_words.clear();
     }
 
+    /// Manual words that `get_combined_words_with_cache` would skip because they
+    /// do not appear in the sentence, sorted alphabetically
+    pub fn manual_words_missing_from(&self, sentence: &str) -> Vec<String> {
+        let sentence_lower = sentence.to_lowercase();
+        let mut missing: Vec<String> = self.manual_words.get_all_words()
+            .iter()
+            .filter(|word| !sentence_lower.contains(&word.to_lowercase()))
+            .cloned()
+            .collect();
+        missing.sort();
+        missing
+    }
+
     /// Get combined words (from API + manual) for display
     /// Now takes a cache lookup function to get meanings for manual words
     /// Only includes manual words that are present in the current sentence
@@ -110,7 +431,13 @@ impl VocabularyManager {
     where
         F: Fn(&str) -> Option<String>,
     {
-        let mut combined = api_words.to_vec();
+        // Casing variants of one word from the API collapse into the first entry
+        let mut combined: Vec<WordMeaning> = Vec::with_capacity(api_words.len());
+        for api_word in api_words {
+            if !combined.iter().any(|w| w.word.to_lowercase() == api_word.word.to_lowercase()) {
+                combined.push(api_word.clone());
+            }
+        }
         
         // Get manual words with their timestamps
         let manual_words_with_time = self.manual_words.get_words_sorted_by_time();
@@ -129,7 +456,11 @@ impl VocabularyManager {
                 continue; // Skip words not in the current sentence
             }
             
-            if !api_words.iter().any(|w| w.word.to_lowercase() == manual_word.to_lowercase()) {
+            if let Some(existing) = combined.iter_mut().find(|w| w.word.to_lowercase() == manual_word.to_lowercase()) {
+                // Keep the API meaning but remember when the user selected the word
+                debug!("VocabularyManager: Manual word '{}' already in API words, merging", manual_word);
+                existing.timestamp = Some(timestamp);
+            } else {
                 let cached_meaning = cache_lookup(&manual_word);
                 let meaning = cached_meaning.clone().unwrap_or_else(|| "Loading...".to_string());
                 
@@ -145,8 +476,6 @@ impl VocabularyManager {
                     is_phrase: false,
                     timestamp: Some(timestamp),
                 });
-            } else {
-                debug!("VocabularyManager: Manual word '{}' already in API words, skipping", manual_word);
             }
         }
         
@@ -158,10 +487,334 @@ impl VocabularyManager {
     pub fn get_combined_words(&self, api_words: &[WordMeaning], current_sentence: &str) -> Vec<WordMeaning> {
         self.get_combined_words_with_cache(api_words, current_sentence, |_| None)
     }
+
+    /// Persist known words and encounter counts to a JSON file
+    /// Manual words are session-only and are not saved
+    pub fn save_to_file(&self, path: &Path) -> Result<(), AppError> {
+        let data = VocabularyData {
+            known_words: self.get_all_known_words()?.into_iter().collect(),
+            word_counts: self.word_tracker.get_all_counts().clone(),
+            pinned_words: self.pinned_words.clone(),
+            known_phrases: self.known_words_filter.get_all_known_phrases().into_iter().collect(),
+            ..VocabularyData::default()
+        };
+        let known_count = data.known_words.len();
+        write_vocabulary_file(path, data)?;
+
+        info!("Saved {} known words to {:?}", known_count, path);
+        Ok(())
+    }
+
+    /// Load known words and encounter counts previously written by `save_to_file`
+    /// Files in an older format are migrated and re-saved at the current version
+    pub fn load_from_file(&mut self, path: &Path) -> Result<(), AppError> {
+        let Some(loaded) = read_vocabulary_file(path)? else {
+            return Ok(()); // No file to load, start fresh
+        };
+
+        self.known_words_filter.load_known_words(loaded.data.known_words);
+        self.word_tracker.load_counts(loaded.data.word_counts);
+        self.pinned_words.extend(loaded.data.pinned_words);
+        self.known_words_filter.load_known_phrases(loaded.data.known_phrases);
+
+        if loaded.migrated {
+            self.save_to_file(path)?;
+        }
+
+        info!("Loaded vocabulary from {:?}", path);
+        Ok(())
+    }
 }
 
-impl Default for VocabularyManager {
-    fn default() -> Self {
-        Self::new().expect("Failed to create VocabularyManager")
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_reinstates_word_and_count() {
+        let mut manager = VocabularyManager::new().unwrap();
+        manager.add_word_encounter("Ephemeral").unwrap();
+        manager.add_word_encounter("ephemeral").unwrap();
+        manager.add_known_word("ephemeral").unwrap();
+
+        let removed = manager.remove_known_word("Ephemeral").unwrap();
+        assert_eq!(removed, RemovedWord { word: "ephemeral".to_string(), encounter_count: 2 });
+        assert_eq!(manager.get_known_words_count(), 0);
+        assert_eq!(manager.word_tracker.get_count("ephemeral"), 0);
+
+        manager.restore_known_word(removed).unwrap();
+        assert_eq!(manager.get_all_known_words().unwrap(), vec!["ephemeral".to_string()]);
+        assert_eq!(manager.word_tracker.get_count("ephemeral"), 2);
+    }
+
+    #[test]
+    fn test_manual_words_missing_from_ignores_case() {
+        let mut manager = VocabularyManager::new().unwrap();
+        manager.add_manual_word("Ephemeral".to_string());
+
+        assert!(manager.manual_words_missing_from("The EPHEMERAL glow faded.").is_empty());
+    }
+
+    #[test]
+    fn test_manual_words_missing_from_reports_absent_words() {
+        let mut manager = VocabularyManager::new().unwrap();
+        manager.add_manual_word("lucid".to_string());
+        manager.add_manual_word("serene".to_string());
+        manager.add_manual_word("glow".to_string());
+
+        let sentence = "The ephemeral glow faded.";
+        assert_eq!(manager.manual_words_missing_from(sentence), vec!["lucid", "serene"]);
+
+        let combined = manager.get_combined_words(&[], sentence);
+        assert_eq!(combined.len(), 1);
+        assert_eq!(combined[0].word, "glow");
+    }
+
+    fn word(word: &str) -> WordMeaning {
+        WordMeaning {
+            word: word.to_string(),
+            meaning: String::new(),
+            is_phrase: false,
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn test_casing_variants_merge_into_one_entry() {
+        let mut manager = VocabularyManager::new().unwrap();
+        manager.add_manual_word("river".to_string());
+
+        let mut api_river = word("River");
+        api_river.meaning = "a large stream of water".to_string();
+        let mut api_duplicate = word("RIVER");
+        api_duplicate.meaning = "duplicate".to_string();
+
+        let combined = manager.get_combined_words_with_cache(
+            &[api_river, api_duplicate],
+            "The river ran wide.",
+            |_| Some("cached meaning".to_string()),
+        );
+
+        assert_eq!(combined.len(), 1);
+        assert_eq!(combined[0].word, "River");
+        assert_eq!(combined[0].meaning, "a large stream of water");
+        assert!(combined[0].timestamp.is_some());
+    }
+
+    #[test]
+    fn test_pinned_known_word_survives_filtering() {
+        let mut manager = VocabularyManager::new().unwrap();
+        manager.add_known_word("ephemeral").unwrap();
+        manager.add_known_word("lucid").unwrap();
+        manager.pin_word("Ephemeral");
+
+        let filtered = manager.filter_known_words(&[word("ephemeral"), word("lucid"), word("serene")]);
+        let filtered: Vec<&str> = filtered.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(filtered, vec!["ephemeral", "serene"]);
+
+        manager.unpin_word("ephemeral");
+        assert!(!manager.is_pinned("ephemeral"));
+        assert_eq!(manager.filter_known_words(&[word("ephemeral")]).len(), 0);
+    }
+
+    #[test]
+    fn test_pins_are_persisted_with_vocabulary() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("vocabulary.json");
+
+        let mut manager = VocabularyManager::new().unwrap();
+        manager.add_known_word("ephemeral").unwrap();
+        manager.pin_word("ephemeral");
+        manager.save_to_file(&path).unwrap();
+
+        let mut loaded = VocabularyManager::new().unwrap();
+        loaded.load_from_file(&path).unwrap();
+        assert!(loaded.is_pinned("ephemeral"));
+        assert_eq!(loaded.filter_known_words(&[word("ephemeral")]).len(), 1);
+    }
+
+    #[test]
+    fn test_unversioned_file_loads_and_is_resaved_with_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(VOCABULARY_FILE);
+        std::fs::write(&path, r#"{"known_words": ["lucid"], "word_counts": {"serene": 2}}"#).unwrap();
+
+        let mut manager = VocabularyManager::new().unwrap();
+        manager.load_from_file(&path).unwrap();
+        assert!(manager.is_known_word("lucid"));
+        assert_eq!(manager.get_encounter_count("serene"), 2);
+
+        let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["version"], VOCABULARY_FORMAT_VERSION);
+        assert_eq!(saved["known_words"], serde_json::json!(["lucid"]));
+    }
+
+    #[test]
+    fn test_custom_dir_is_honored() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("profile");
+
+        let mut manager = VocabularyManager::with_dir(&dir).unwrap();
+        assert_eq!(manager.file_path(), Some(dir.join(VOCABULARY_FILE)));
+        manager.add_known_word("ephemeral").unwrap();
+        manager.save().unwrap();
+        assert!(dir.join(VOCABULARY_FILE).exists());
+
+        let reloaded = VocabularyManager::with_dir(&dir).unwrap();
+        assert!(reloaded.is_known_word("ephemeral"));
+    }
+
+    #[test]
+    fn test_restore_word_without_encounters() {
+        let mut manager = VocabularyManager::new().unwrap();
+        manager.add_known_word("lucid").unwrap();
+
+        let removed = manager.remove_known_word("lucid").unwrap();
+        assert_eq!(removed.encounter_count, 0);
+
+        manager.restore_known_word(removed).unwrap();
+        assert_eq!(manager.get_known_words_count(), 1);
+        assert_eq!(manager.word_tracker.get_count("lucid"), 0);
+    }
+
+    #[test]
+    fn test_distinct_context_promotion() {
+        let mut manager = VocabularyManager::new().unwrap().with_distinct_context_promotion(true);
+
+        for _ in 0..3 {
+            let (_, promoted) = manager.add_word_encounter_in("lucid", "A lucid dream.").unwrap();
+            assert!(!promoted);
+        }
+        assert_eq!(manager.get_encounter_count("lucid"), 1);
+
+        assert!(!manager.add_word_encounter_in("lucid", "Her notes were lucid.").unwrap().1);
+        assert!(manager.add_word_encounter_in("lucid", "Stay lucid.").unwrap().1);
+    }
+
+    #[test]
+    fn test_lemmatization_promotes_and_filters_inflections() {
+        let mut manager = VocabularyManager::new().unwrap().with_lemmatization(true);
+
+        manager.add_word_encounter("studies").unwrap();
+        manager.add_word_encounter("studied").unwrap();
+        let (count, promoted) = manager.add_word_encounter("studying").unwrap();
+        assert_eq!((count, promoted), (3, true));
+        assert_eq!(manager.get_encounter_count("study"), 3);
+
+        manager.add_known_word("study").unwrap();
+        let filtered = manager.filter_known_words(&[word("studies"), word("Studying"), word("students")]);
+        assert_eq!(filtered.iter().map(|w| w.word.as_str()).collect::<Vec<_>>(), vec!["students"]);
+    }
+
+    #[test]
+    fn test_new_words_since_checkpoint() {
+        let mut manager = VocabularyManager::new().unwrap();
+        manager.add_known_word("lucid").unwrap();
+
+        let checkpoint = manager.checkpoint();
+        assert_eq!(checkpoint.known_words_count(), 1);
+        assert!(manager.new_words_since(&checkpoint).is_empty());
+
+        manager.add_known_word("Serene").unwrap();
+        manager.add_known_word("ephemeral").unwrap();
+        manager.add_known_word("lucid").unwrap();
+        manager.remove_known_word("lucid").unwrap();
+
+        assert_eq!(manager.new_words_since(&checkpoint), vec!["ephemeral", "serene"]);
+    }
+
+    #[test]
+    fn test_import_known_words_counts_only_new_words() {
+        let mut manager = VocabularyManager::new().unwrap();
+        manager.add_known_word("the").unwrap();
+
+        let list = ["the", "of", "And", "and", " of ", "", "to"].map(String::from);
+        assert_eq!(manager.import_known_words(list).unwrap(), 3);
+        assert_eq!(manager.get_known_words_count(), 4);
+        assert!(manager.is_known_word("and"));
+
+        assert_eq!(manager.import_known_words(vec!["TO".to_string()]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_import_known_words_is_saved() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut manager = VocabularyManager::with_dir(temp_dir.path()).unwrap();
+
+        manager.import_known_words(vec!["lucid".to_string(), "serene".to_string()]).unwrap();
+
+        let reloaded = VocabularyManager::with_dir(temp_dir.path()).unwrap();
+        assert!(reloaded.is_known_word("lucid"));
+        assert!(reloaded.is_known_word("serene"));
+    }
+
+    #[test]
+    fn test_study_sheet_lists_known_words_with_counts() {
+        let mut manager = VocabularyManager::default();
+        manager.add_word_encounter("ephemeral").unwrap();
+        manager.add_word_encounter("ephemeral").unwrap();
+        manager.add_known_word("ephemeral").unwrap();
+        manager.add_known_word("zeal").unwrap();
+
+        let html = manager.export_study_sheet_html().unwrap();
+        assert!(html.contains("<h2>E</h2>") && html.contains("<h2>Z</h2>"));
+        assert!(html.contains("ephemeral <span class=\"count\">(2 encounters)</span>"));
+        assert!(html.contains("zeal <span class=\"count\">(0 encounters)</span>"));
+    }
+
+    #[test]
+    fn test_known_phrases_are_filtered_and_persisted() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(VOCABULARY_FILE);
+
+        let mut manager = VocabularyManager::default();
+        manager.add_known_phrase("by and large").unwrap();
+        manager.add_known_word("large").unwrap();
+        manager.save_to_file(&path).unwrap();
+
+        let mut loaded = VocabularyManager::default();
+        loaded.load_from_file(&path).unwrap();
+        assert!(loaded.is_known_phrase("By and large"));
+        assert!(!loaded.is_known_word("by"));
+
+        let mut phrase = word("by and large");
+        phrase.is_phrase = true;
+        let filtered = loaded.filter_known_words(&[phrase, word("large"), word("by")]);
+        let filtered: Vec<&str> = filtered.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(filtered, vec!["by"]);
+    }
+
+    #[test]
+    fn test_confirmation_mode_stages_first_add() {
+        let mut manager = VocabularyManager::default().with_require_confirmation(true);
+
+        manager.add_known_word("Ephemeral").unwrap();
+        assert!(manager.is_staged("ephemeral"));
+        assert!(!manager.is_known_word("ephemeral"));
+
+        manager.add_known_word("ephemeral").unwrap();
+        assert!(manager.is_known_word("ephemeral"));
+        assert!(!manager.is_staged("ephemeral"));
+
+        manager.confirm_known_word("lucid").unwrap();
+        assert!(manager.is_known_word("lucid"));
+    }
+
+    #[test]
+    fn test_staged_word_is_promoted_at_encounter_threshold() {
+        let mut manager = VocabularyManager::default().with_require_confirmation(true);
+        manager.add_known_word("serene").unwrap();
+
+        let mut promoted = false;
+        while !promoted {
+            promoted = manager.add_word_encounter("serene").unwrap().1;
+        }
+        assert!(manager.is_known_word("serene"));
+        assert!(!manager.is_staged("serene"));
+
+        manager.add_known_word("zeal").unwrap();
+        manager.set_require_confirmation(false);
+        assert!(!manager.is_staged("zeal"));
+        assert!(!manager.is_known_word("zeal"));
     }
 }
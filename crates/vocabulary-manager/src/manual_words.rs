@@ -53,7 +53,7 @@ impl ManualWordsManager {
             .collect();
         
         // Sort by timestamp descending (newest first)
-        words_with_time.sort_by(|a, b| b.1.cmp(&a.1));
+        words_with_time.sort_by_key(|&(_, timestamp)| std::cmp::Reverse(timestamp));
         words_with_time
     }
 
@@ -0,0 +1,38 @@
+/// What is known about a tracked word when deciding whether to promote it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordStats {
+    /// Tracking key: lowercased, or the lemma when lemmatization is on
+    pub word: String,
+    pub count: usize,
+    /// Different sentences the word was encountered in via `add_encounter_in`
+    pub distinct_contexts: usize,
+    /// Milliseconds since the Unix epoch
+    pub first_seen: u64,
+    pub last_seen: u64,
+}
+
+/// Decides when a tracked word has been encountered enough to count as known
+///
+/// `WordTracker` reports each word as promoted at most once, the first time
+/// `should_promote` returns true for it.
+pub trait PromotionPolicy: Send + Sync {
+    fn should_promote(&self, word: &WordStats) -> bool;
+}
+
+/// Promote once a word has been encountered `threshold` times
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountThresholdPolicy {
+    pub threshold: usize,
+}
+
+impl CountThresholdPolicy {
+    pub fn new(threshold: usize) -> Self {
+        Self { threshold }
+    }
+}
+
+impl PromotionPolicy for CountThresholdPolicy {
+    fn should_promote(&self, word: &WordStats) -> bool {
+        word.count >= self.threshold
+    }
+}
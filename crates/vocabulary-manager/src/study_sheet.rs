@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+/// Section heading for words that don't start with a letter
+const OTHER_SECTION: &str = "#";
+
+/// Printable HTML page listing `words` with their encounter counts in A–Z sections
+pub(crate) fn render_study_sheet_html(words: &[(String, usize)]) -> String {
+    let mut sections: BTreeMap<String, Vec<&(String, usize)>> = BTreeMap::new();
+    for entry in words {
+        sections.entry(section_heading(&entry.0)).or_default().push(entry);
+    }
+
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Known words</title>\n\
+         <style>\nbody { font-family: serif; }\nsection { break-inside: avoid; }\n\
+         .count { color: #666; font-size: 0.85em; }\n</style>\n</head>\n<body>\n<h1>Known words</h1>\n",
+    );
+    html.push_str(&format!("<p>{} words</p>\n", words.len()));
+
+    // "#" sorts before the letters in a BTreeMap; print it last instead
+    let other = sections.remove(OTHER_SECTION);
+    for (heading, mut entries) in sections.into_iter().chain(other.map(|e| (OTHER_SECTION.to_string(), e))) {
+        entries.sort_by(|a, b| a.0.to_lowercase().cmp(&b.0.to_lowercase()).then_with(|| a.0.cmp(&b.0)));
+        html.push_str(&format!("<section>\n<h2>{}</h2>\n<ul>\n", escape_html(&heading)));
+        for (word, count) in entries {
+            html.push_str(&format!(
+                "<li>{} <span class=\"count\">({} {})</span></li>\n",
+                escape_html(word),
+                count,
+                if *count == 1 { "encounter" } else { "encounters" }
+            ));
+        }
+        html.push_str("</ul>\n</section>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn section_heading(word: &str) -> String {
+    match word.chars().next() {
+        Some(c) if c.is_alphabetic() => c.to_uppercase().collect(),
+        _ => OTHER_SECTION.to_string(),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words_are_grouped_and_sorted() {
+        let words = vec![
+            ("banana".to_string(), 3),
+            ("apple".to_string(), 1),
+            ("Avocado".to_string(), 2),
+            ("'tis".to_string(), 4),
+        ];
+        let html = render_study_sheet_html(&words);
+
+        let a = html.find("<h2>A</h2>").unwrap();
+        let b = html.find("<h2>B</h2>").unwrap();
+        let other = html.find("<h2>#</h2>").unwrap();
+        assert!(a < b && b < other);
+        assert!(html.find("apple").unwrap() < html.find("Avocado").unwrap());
+        assert!(html.contains("apple <span class=\"count\">(1 encounter)</span>"));
+        assert!(html.contains("banana <span class=\"count\">(3 encounters)</span>"));
+    }
+
+    #[test]
+    fn test_special_characters_are_escaped() {
+        let html = render_study_sheet_html(&[("<b>&co\"".to_string(), 0)]);
+        assert!(html.contains("&lt;b&gt;&amp;co&quot;"));
+        assert!(!html.contains("<b>&co"));
+    }
+}
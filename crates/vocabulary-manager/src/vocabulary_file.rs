@@ -0,0 +1,127 @@
+use glossia_shared::AppError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tracing::info;
+
+/// File name used inside a vocabulary directory
+pub const VOCABULARY_FILE: &str = "vocabulary.json";
+
+/// Format version written by `save_to_file`; files without a `version` field are version 0
+pub const VOCABULARY_FORMAT_VERSION: u32 = 1;
+
+/// On-disk vocabulary format, shared by `VocabularyManager` and `FileVocabularyStore`
+#[derive(Serialize, Deserialize, Default)]
+pub(crate) struct VocabularyData {
+    #[serde(default)]
+    pub version: u32,
+    pub known_words: HashSet<String>,
+    pub word_counts: HashMap<String, usize>,
+    #[serde(default)]
+    pub pinned_words: HashSet<String>,
+    #[serde(default)]
+    pub known_phrases: HashSet<String>,
+}
+
+/// A file read by `read_vocabulary_file`, noting whether it was migrated from an older version
+pub(crate) struct LoadedVocabulary {
+    pub data: VocabularyData,
+    pub migrated: bool,
+}
+
+/// Write `data` to `path` at the current format version
+pub(crate) fn write_vocabulary_file(path: &Path, mut data: VocabularyData) -> Result<(), AppError> {
+    data.version = VOCABULARY_FORMAT_VERSION;
+    let json = serde_json::to_string_pretty(&data)
+        .map_err(|e| AppError::config_error(format!("Failed to serialize vocabulary: {e}")))?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| AppError::config_error(format!("Failed to create directory: {e}")))?;
+    }
+
+    std::fs::write(path, json)
+        .map_err(|e| AppError::config_error(format!("Failed to write vocabulary file: {e}")))
+}
+
+/// Read and migrate the file at `path`, or `None` when it does not exist
+pub(crate) fn read_vocabulary_file(path: &Path) -> Result<Option<LoadedVocabulary>, AppError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| AppError::config_error(format!("Failed to read vocabulary file: {e}")))?;
+    let mut value: Value = serde_json::from_str(&json)
+        .map_err(|e| AppError::config_error(format!("Failed to deserialize vocabulary: {e}")))?;
+
+    let found_version = value.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if found_version > VOCABULARY_FORMAT_VERSION {
+        return Err(AppError::config_error(format!(
+            "Vocabulary file version {found_version} is newer than supported version {VOCABULARY_FORMAT_VERSION}"
+        )));
+    }
+
+    let mut version = found_version;
+    while version < VOCABULARY_FORMAT_VERSION {
+        value = migrate(value, version);
+        version += 1;
+    }
+    if found_version < VOCABULARY_FORMAT_VERSION {
+        info!("Migrated vocabulary file {:?} from version {} to {}", path, found_version, VOCABULARY_FORMAT_VERSION);
+    }
+
+    let data: VocabularyData = serde_json::from_value(value)
+        .map_err(|e| AppError::config_error(format!("Failed to deserialize vocabulary: {e}")))?;
+
+    Ok(Some(LoadedVocabulary {
+        data,
+        migrated: found_version < VOCABULARY_FORMAT_VERSION,
+    }))
+}
+
+/// Upgrade a file from `version` to `version + 1`
+fn migrate(mut value: Value, version: u32) -> Value {
+    match version {
+        // Version 0 had the same fields, only without the version marker
+        0 => {
+            value["version"] = Value::from(1);
+            value
+        }
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unversioned_file_is_migrated() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(VOCABULARY_FILE);
+        std::fs::write(&path, r#"{"known_words": ["lucid"], "word_counts": {"serene": 2}}"#).unwrap();
+
+        let loaded = read_vocabulary_file(&path).unwrap().unwrap();
+        assert!(loaded.migrated);
+        assert_eq!(loaded.data.version, VOCABULARY_FORMAT_VERSION);
+        assert!(loaded.data.known_words.contains("lucid"));
+        assert_eq!(loaded.data.word_counts["serene"], 2);
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(VOCABULARY_FILE);
+        std::fs::write(&path, r#"{"version": 99, "known_words": [], "word_counts": {}}"#).unwrap();
+
+        assert!(read_vocabulary_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_missing_file_reads_as_none() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert!(read_vocabulary_file(&temp_dir.path().join(VOCABULARY_FILE)).unwrap().is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use glossia_shared::{AppError, WordMeaning};
 use std::collections::HashSet;
+use crate::vocabulary_file::{read_vocabulary_file, write_vocabulary_file, VocabularyData};
 
 /// Trait for vocabulary storage backends
 /// Enables different storage implementations (memory, file, database, cloud)
@@ -244,54 +245,21 @@ impl VocabularyStore for FileVocabularyStore {
     }
     
     async fn save(&self) -> Result<(), AppError> {
-        use std::collections::HashMap;
-        
-        #[derive(serde::Serialize)]
-        struct VocabularyData {
-            known_words: HashSet<String>,
-            word_counts: HashMap<String, usize>,
-        }
-        
         let data = VocabularyData {
             known_words: self.memory_store.known_words.clone(),
             word_counts: self.memory_store.word_counts.clone(),
+            ..VocabularyData::default()
         };
-        
-        let json = serde_json::to_string_pretty(&data)
-            .map_err(|e| AppError::config_error(format!("Failed to serialize vocabulary: {e}")))?;
-        
-        if let Some(parent) = self.file_path.parent() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| AppError::config_error(format!("Failed to create directory: {e}")))?;
-        }
-        
-        std::fs::write(&self.file_path, json)
-            .map_err(|e| AppError::config_error(format!("Failed to write vocabulary file: {e}")))?;
-        
-        Ok(())
+        write_vocabulary_file(&self.file_path, data)
     }
     
     async fn load(&mut self) -> Result<(), AppError> {
-        use std::collections::HashMap;
-        
-        #[derive(serde::Deserialize)]
-        struct VocabularyData {
-            known_words: HashSet<String>,
-            word_counts: HashMap<String, usize>,
-        }
-        
-        if !self.file_path.exists() {
+        let Some(loaded) = read_vocabulary_file(&self.file_path)? else {
             return Ok(()); // No file to load, start fresh
-        }
-        
-        let json = std::fs::read_to_string(&self.file_path)
-            .map_err(|e| AppError::config_error(format!("Failed to read vocabulary file: {e}")))?;
-        
-        let data: VocabularyData = serde_json::from_str(&json)
-            .map_err(|e| AppError::config_error(format!("Failed to deserialize vocabulary: {e}")))?;
+        };
         
-        self.memory_store.known_words = data.known_words;
-        self.memory_store.word_counts = data.word_counts;
+        self.memory_store.known_words = loaded.data.known_words;
+        self.memory_store.word_counts = loaded.data.word_counts;
         
         Ok(())
     }
@@ -1,33 +1,181 @@
+use crate::lemmatizer::lemmatize;
+use crate::promotion_policy::{PromotionPolicy, WordStats};
 use glossia_shared::AppError;
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Common English function words that are never worth tracking
+pub const DEFAULT_ENGLISH_STOPWORDS: &[&str] = &[
+    "a", "an", "the", "and", "or", "but", "if", "of", "to", "in", "on", "at", "by", "for",
+    "with", "from", "as", "is", "are", "was", "were", "be", "been", "am", "it", "its",
+    "this", "that", "these", "those", "i", "you", "he", "she", "we", "they", "me", "him",
+    "her", "us", "them", "my", "your", "his", "our", "their", "not", "no", "so", "do",
+    "does", "did", "have", "has", "had",
+];
 
 /// Tracks word encounters and handles promotion to known words
 pub struct WordTracker {
     word_counts: HashMap<String, usize>,
     promotion_threshold: usize,
+    distinct_contexts: bool,
+    /// Hashes of the sentences each word was counted in
+    word_contexts: HashMap<String, HashSet<u64>>,
+    /// Words ignored by `add_encounter` while `skip_stopwords` is set
+    stopwords: HashSet<String>,
+    skip_stopwords: bool,
+    /// Count inflections (`studies`, `studying`) under one base form
+    lemmatize: bool,
+    /// First and last encounter per word, in milliseconds since the epoch
+    seen_at: HashMap<String, (u64, u64)>,
+    /// Replaces the count threshold when set
+    policy: Option<Box<dyn PromotionPolicy>>,
+    /// Words already reported as promoted by `policy`
+    promoted: HashSet<String>,
 }
 
-impl WordTracker {
-    pub fn new() -> Result<Self, AppError> {
-        Ok(Self {
+impl Default for WordTracker {
+    fn default() -> Self {
+        Self {
             word_counts: HashMap::new(),
             promotion_threshold: 3, // Promote after 3 encounters
-        })
+            distinct_contexts: false,
+            word_contexts: HashMap::new(),
+            stopwords: DEFAULT_ENGLISH_STOPWORDS.iter().map(|word| word.to_string()).collect(),
+            skip_stopwords: true,
+            lemmatize: false,
+            seen_at: HashMap::new(),
+            policy: None,
+            promoted: HashSet::new(),
+        }
+    }
+}
+
+impl WordTracker {
+    pub fn new() -> Result<Self, AppError> {
+        Ok(Self::default())
+    }
+
+    /// Only count one encounter per word per sentence in `add_encounter_in`
+    pub fn set_distinct_contexts(&mut self, distinct: bool) {
+        self.distinct_contexts = distinct;
+    }
+
+    pub fn distinct_contexts(&self) -> bool {
+        self.distinct_contexts
+    }
+
+    /// Replace the stopword list; words are matched case-insensitively
+    pub fn with_stopwords(mut self, stopwords: HashSet<String>) -> Self {
+        self.stopwords = stopwords.into_iter().map(|word| word.to_lowercase()).collect();
+        self
+    }
+
+    /// Count stopwords like any other word when `skip` is false
+    pub fn set_skip_stopwords(&mut self, skip: bool) {
+        self.skip_stopwords = skip;
+    }
+
+    pub fn skip_stopwords(&self) -> bool {
+        self.skip_stopwords
+    }
+
+    /// Count encounters under the base form from `lemmatize`, so inflections share a count
+    pub fn with_lemmatization(mut self, enabled: bool) -> Self {
+        self.lemmatize = enabled;
+        self
+    }
+
+    /// Decide promotions with `policy` instead of the count threshold
+    pub fn with_policy(mut self, policy: Box<dyn PromotionPolicy>) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    pub fn lemmatization(&self) -> bool {
+        self.lemmatize
+    }
+
+    /// Key a word is counted under: lowercased, and reduced to its base form with lemmatization
+    pub fn tracking_key(&self, word: &str) -> String {
+        if self.lemmatize {
+            lemmatize(word)
+        } else {
+            word.to_lowercase()
+        }
+    }
+
+    pub fn is_stopword(&self, word: &str) -> bool {
+        self.skip_stopwords && self.stopwords.contains(&word.to_lowercase())
+    }
+
+    /// Add an encounter for a word seen in `context`, returns (count, was_promoted)
+    /// With distinct contexts enabled, repeat encounters in the same sentence are ignored
+    pub fn add_encounter_in(&mut self, word: &str, context: &str) -> Result<(usize, bool), AppError> {
+        if self.is_stopword(word) {
+            return Ok((0, false));
+        }
+        let seen = self.word_contexts.entry(self.tracking_key(word)).or_default();
+        if !seen.insert(context_hash(context)) && self.distinct_contexts {
+            return Ok((self.get_count(word), false));
+        }
+        self.add_encounter(word)
     }
 
     /// Add an encounter for a word, returns (count, was_promoted)
+    /// Stopwords aren't counted and report a count of 0
     pub fn add_encounter(&mut self, word: &str) -> Result<(usize, bool), AppError> {
-        let normalized_word = word.to_lowercase();
-        let count = self.word_counts.entry(normalized_word).and_modify(|c| *c += 1).or_insert(1);
-        
-        let was_promoted = *count == self.promotion_threshold;
-        
-        Ok((*count, was_promoted))
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        self.add_encounter_at(word, now)
+    }
+
+    /// Like `add_encounter`, for an encounter at `timestamp` milliseconds since the epoch
+    pub fn add_encounter_at(&mut self, word: &str, timestamp: u64) -> Result<(usize, bool), AppError> {
+        if self.is_stopword(word) {
+            return Ok((0, false));
+        }
+        let normalized_word = self.tracking_key(word);
+        let count = *self.word_counts.entry(normalized_word.clone()).and_modify(|c| *c += 1).or_insert(1);
+        self.seen_at
+            .entry(normalized_word.clone())
+            .and_modify(|(_, last)| *last = timestamp)
+            .or_insert((timestamp, timestamp));
+
+        let was_promoted = match &self.policy {
+            Some(policy) => {
+                let stats = self.stats_for_key(&normalized_word);
+                policy.should_promote(&stats) && self.promoted.insert(normalized_word)
+            }
+            None => count == self.promotion_threshold,
+        };
+
+        Ok((count, was_promoted))
+    }
+
+    /// Counts and timing the promotion policy sees for a word, if it is tracked
+    pub fn stats(&self, word: &str) -> Option<WordStats> {
+        let key = self.tracking_key(word);
+        self.word_counts.contains_key(&key).then(|| self.stats_for_key(&key))
+    }
+
+    fn stats_for_key(&self, key: &str) -> WordStats {
+        let (first_seen, last_seen) = self.seen_at.get(key).copied().unwrap_or_default();
+        WordStats {
+            word: key.to_string(),
+            count: self.word_counts.get(key).copied().unwrap_or(0),
+            distinct_contexts: self.word_contexts.get(key).map_or(0, HashSet::len),
+            first_seen,
+            last_seen,
+        }
     }
 
     /// Get encounter count for a word
     pub fn get_count(&self, word: &str) -> usize {
-        self.word_counts.get(&word.to_lowercase()).copied().unwrap_or(0)
+        self.word_counts.get(&self.tracking_key(word)).copied().unwrap_or(0)
     }
 
     /// Set the promotion threshold
@@ -40,13 +188,186 @@ impl WordTracker {
         self.promotion_threshold
     }
 
+    /// Stop tracking a word, returning its previous count
+    pub fn remove(&mut self, word: &str) -> usize {
+        let key = self.tracking_key(word);
+        self.word_contexts.remove(&key);
+        self.seen_at.remove(&key);
+        self.promoted.remove(&key);
+        self.word_counts.remove(&key).unwrap_or(0)
+    }
+
+    /// Set the encounter count for a word
+    pub fn set_count(&mut self, word: &str, count: usize) {
+        let key = self.tracking_key(word);
+        if count == 0 {
+            self.word_counts.remove(&key);
+        } else {
+            self.word_counts.insert(key, count);
+        }
+    }
+
     /// Clear all word counts
     pub fn clear(&mut self) {
         self.word_counts.clear();
+        self.word_contexts.clear();
+        self.seen_at.clear();
+        self.promoted.clear();
     }
 
     /// Get all tracked words and their counts
     pub fn get_all_counts(&self) -> &HashMap<String, usize> {
         &self.word_counts
     }
+
+    /// Load word counts from a collection, replacing existing counts for those words
+    /// Stopwords counted by earlier versions are dropped
+    pub fn load_counts(&mut self, counts: impl IntoIterator<Item = (String, usize)>) {
+        let counts: Vec<(String, usize)> = counts
+            .into_iter()
+            .map(|(w, c)| (w.to_lowercase(), c))
+            .filter(|(w, _)| !self.is_stopword(w))
+            .collect();
+        self.word_counts.extend(counts);
+    }
+}
+
+/// Hash of a sentence ignoring case and spacing differences
+fn context_hash(context: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for word in context.split_whitespace() {
+        word.to_lowercase().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_sentence_encounters_do_not_promote() {
+        let mut tracker = WordTracker::new().unwrap();
+        tracker.set_distinct_contexts(true);
+
+        for _ in 0..5 {
+            let (count, promoted) = tracker.add_encounter_in("ephemeral", "An ephemeral glow.").unwrap();
+            assert_eq!(count, 1);
+            assert!(!promoted);
+        }
+        // Spacing and case differences are the same sentence
+        assert_eq!(tracker.add_encounter_in("Ephemeral", "an  ephemeral GLOW.").unwrap(), (1, false));
+    }
+
+    #[test]
+    fn test_distinct_sentences_promote() {
+        let mut tracker = WordTracker::new().unwrap();
+        tracker.set_distinct_contexts(true);
+
+        assert_eq!(tracker.add_encounter_in("ephemeral", "An ephemeral glow.").unwrap(), (1, false));
+        assert_eq!(tracker.add_encounter_in("ephemeral", "Fame is ephemeral.").unwrap(), (2, false));
+        assert_eq!(tracker.add_encounter_in("ephemeral", "Ephemeral joys fade.").unwrap(), (3, true));
+    }
+
+    #[test]
+    fn test_same_sentence_counts_without_distinct_contexts() {
+        let mut tracker = WordTracker::new().unwrap();
+
+        tracker.add_encounter_in("ephemeral", "An ephemeral glow.").unwrap();
+        tracker.add_encounter_in("ephemeral", "An ephemeral glow.").unwrap();
+        assert_eq!(tracker.add_encounter_in("ephemeral", "An ephemeral glow.").unwrap(), (3, true));
+    }
+
+    #[test]
+    fn test_stopwords_are_not_counted_or_promoted() {
+        let mut tracker = WordTracker::new().unwrap();
+
+        for _ in 0..5 {
+            assert_eq!(tracker.add_encounter("The").unwrap(), (0, false));
+            assert_eq!(tracker.add_encounter_in("and", "Salt and pepper.").unwrap(), (0, false));
+        }
+        assert_eq!(tracker.get_count("the"), 0);
+        assert!(tracker.get_all_counts().is_empty());
+
+        tracker.add_encounter("lighthouse").unwrap();
+        tracker.add_encounter("lighthouse").unwrap();
+        assert_eq!(tracker.add_encounter("lighthouse").unwrap(), (3, true));
+    }
+
+    #[test]
+    fn test_custom_stopwords_and_disable_flag() {
+        let stopwords = ["Le", "la"].iter().map(|word| word.to_string()).collect();
+        let mut tracker = WordTracker::new().unwrap().with_stopwords(stopwords);
+
+        assert_eq!(tracker.add_encounter("le").unwrap(), (0, false));
+        assert_eq!(tracker.add_encounter("the").unwrap(), (1, false));
+
+        tracker.set_skip_stopwords(false);
+        assert_eq!(tracker.add_encounter("la").unwrap(), (1, false));
+    }
+
+    #[test]
+    fn test_inflections_accumulate_on_the_lemma() {
+        let mut tracker = WordTracker::default().with_lemmatization(true);
+
+        assert_eq!(tracker.add_encounter("studies").unwrap(), (1, false));
+        assert_eq!(tracker.add_encounter("studied").unwrap(), (2, false));
+        assert_eq!(tracker.add_encounter("Studying").unwrap(), (3, true));
+
+        assert_eq!(tracker.get_count("study"), 3);
+        assert_eq!(tracker.get_all_counts().len(), 1);
+    }
+
+    #[test]
+    fn test_inflections_are_separate_without_lemmatization() {
+        let mut tracker = WordTracker::default();
+        tracker.add_encounter("studies").unwrap();
+        tracker.add_encounter("studied").unwrap();
+
+        assert_eq!(tracker.get_count("study"), 0);
+        assert_eq!(tracker.get_count("studies"), 1);
+    }
+
+    /// Promotes only words seen at least `min_span_ms` apart, however often
+    struct SpacedRepetitionPolicy {
+        min_count: usize,
+        min_span_ms: u64,
+    }
+
+    impl PromotionPolicy for SpacedRepetitionPolicy {
+        fn should_promote(&self, word: &WordStats) -> bool {
+            word.count >= self.min_count && word.last_seen - word.first_seen >= self.min_span_ms
+        }
+    }
+
+    #[test]
+    fn test_custom_time_gated_policy() {
+        const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+        let mut tracker = WordTracker::default().with_policy(Box::new(SpacedRepetitionPolicy { min_count: 2, min_span_ms: DAY_MS }));
+
+        // Many encounters within one day don't promote
+        for hour in 0..5 {
+            assert!(!tracker.add_encounter_at("lucid", hour * 60 * 60 * 1000).unwrap().1);
+        }
+
+        // An encounter a day later does, exactly once
+        assert_eq!(tracker.add_encounter_at("lucid", DAY_MS).unwrap(), (6, true));
+        assert_eq!(tracker.add_encounter_at("lucid", 2 * DAY_MS).unwrap(), (7, false));
+
+        let stats = tracker.stats("Lucid").unwrap();
+        assert_eq!((stats.first_seen, stats.last_seen), (0, 2 * DAY_MS));
+        assert!(tracker.stats("serene").is_none());
+    }
+
+    #[test]
+    fn test_stats_count_distinct_contexts() {
+        let mut tracker = WordTracker::default();
+        tracker.add_encounter_in("lucid", "A lucid dream.").unwrap();
+        tracker.add_encounter_in("lucid", "A lucid dream.").unwrap();
+        tracker.add_encounter_in("lucid", "Her lucid prose.").unwrap();
+
+        let stats = tracker.stats("lucid").unwrap();
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.distinct_contexts, 2);
+    }
 }